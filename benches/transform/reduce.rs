@@ -50,6 +50,7 @@ fn reduce(c: &mut Criterion) {
                 ends_when: None,
                 starts_when: None,
                 max_events: None,
+                ..Default::default()
             },
         },
     ] {
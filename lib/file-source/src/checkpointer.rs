@@ -1,8 +1,11 @@
 use std::{
     collections::BTreeSet,
-    fs, io,
+    fs, io, mem,
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use chrono::{DateTime, Utc};
@@ -13,6 +16,7 @@ use tracing::{error, info, warn};
 
 use super::{
     fingerprinter::{FileFingerprint, Fingerprinter},
+    metadata_ext::PortableFileExt,
     FilePosition,
 };
 
@@ -56,6 +60,11 @@ pub struct CheckpointsView {
     checkpoints: DashMap<FileFingerprint, FilePosition>,
     modified_times: DashMap<FileFingerprint, DateTime<Utc>>,
     removed_times: DashMap<FileFingerprint, DateTime<Utc>>,
+    /// Loaded fingerprints that carry a device and inode, keyed by them, so that checkpoints can be
+    /// migrated across strategies without reading the files.
+    loaded_dev_inodes: DashMap<(u64, u64), FileFingerprint>,
+    /// Whether any loaded fingerprint is a `FirstLinesChecksum`.
+    loaded_first_lines_checksums: AtomicBool,
 }
 
 impl CheckpointsView {
@@ -118,12 +127,23 @@ impl CheckpointsView {
     }
 
     fn load(&self, checkpoint: Checkpoint) {
+        self.index_loaded(checkpoint.fingerprint);
         self.checkpoints
             .insert(checkpoint.fingerprint, checkpoint.position);
         self.modified_times
             .insert(checkpoint.fingerprint, checkpoint.modified);
     }
 
+    fn index_loaded(&self, fng: FileFingerprint) {
+        if let Some(dev_inode) = fng.dev_inode() {
+            self.loaded_dev_inodes.insert(dev_inode, fng);
+        }
+        if matches!(fng, FileFingerprint::FirstLinesChecksum(_)) {
+            self.loaded_first_lines_checksums
+                .store(true, Ordering::Relaxed);
+        }
+    }
+
     fn set_state(&self, state: State, ignore_before: Option<DateTime<Utc>>) {
         match state {
             State::V1 { checkpoints } => {
@@ -195,6 +215,115 @@ impl CheckpointsView {
                 }
             }
         }
+
+        if self.checkpoints.get(&fng).is_none() {
+            self.maybe_migrate_strategy(path, fng, fingerprinter, fingerprint_buffer);
+        }
+    }
+
+    /// Carry a checkpoint over from a fingerprint computed with a different
+    /// strategy, so that changing `fingerprint.strategy` does not re-read or
+    /// skip files.
+    ///
+    /// A stored position beyond the current end of the file means the old
+    /// fingerprint refers to some other content (a reused inode or a truncated
+    /// file), so it is left untouched rather than migrated.
+    fn maybe_migrate_strategy(
+        &self,
+        path: &Path,
+        fng: FileFingerprint,
+        fingerprinter: &Fingerprinter,
+        fingerprint_buffer: &mut Vec<u8>,
+    ) {
+        let Some(old) = self.find_migration_source(path, fng, fingerprinter, fingerprint_buffer)
+        else {
+            return;
+        };
+        let Some(pos) = self.get(old) else {
+            return;
+        };
+        let Ok(len) = fs::metadata(path).map(|metadata| metadata.len()) else {
+            return;
+        };
+        if pos > len {
+            warn!(
+                message = "Not migrating checkpoint past the end of file.",
+                ?path,
+                position = pos,
+                file_len = len,
+            );
+            return;
+        }
+        info!(
+            message = "Migrating checkpoint to new fingerprint strategy.",
+            ?path,
+            old_fingerprint = ?old,
+            new_fingerprint = ?fng,
+        );
+        self.update_key(old, fng);
+    }
+
+    /// Find the loaded fingerprint of the file at `path` under another strategy.
+    ///
+    /// Fingerprints with a device and inode are matched against the file's
+    /// actual ones, whatever checksum options they were computed with, and
+    /// `FirstLinesChecksum` ones against the checksum in `fng`. The file
+    /// content is only read when moving from `FirstLinesChecksum` to
+    /// `DevInode`, which has no checksum of its own; the default options (no
+    /// header, one line) are used then.
+    fn find_migration_source(
+        &self,
+        path: &Path,
+        fng: FileFingerprint,
+        fingerprinter: &Fingerprinter,
+        fingerprint_buffer: &mut Vec<u8>,
+    ) -> Option<FileFingerprint> {
+        if !self.loaded_dev_inodes.is_empty() {
+            let dev_inode = fng.dev_inode().or_else(|| {
+                let file = fs::File::open(path).ok()?;
+                Some((file.portable_dev().ok()?, file.portable_ino().ok()?))
+            });
+            let old = dev_inode
+                .and_then(|dev_inode| self.loaded_dev_inodes.get(&dev_inode))
+                .map(|entry| *entry.value())
+                .filter(|old| mem::discriminant(old) != mem::discriminant(&fng));
+            if old.is_some() {
+                return old;
+            }
+        }
+
+        if !self.loaded_first_lines_checksums.load(Ordering::Relaxed) {
+            return None;
+        }
+        match fng {
+            FileFingerprint::DevInodeChecksum(_, _, c) => {
+                Some(FileFingerprint::FirstLinesChecksum(c))
+            }
+            FileFingerprint::DevInode(..) => fingerprinter
+                .get_default_first_lines_checksum(path, fingerprint_buffer)
+                .ok(),
+            _ => None,
+        }
+    }
+
+    /// Find the position of a recently unwatched file whose content checksum
+    /// matches `fng` but whose device or inode differs. Used to recognize the
+    /// copy half of a copy-truncate rotation.
+    pub fn find_rotated_position(&self, fng: FileFingerprint) -> Option<FilePosition> {
+        let FileFingerprint::DevInodeChecksum(dev, ino, checksum) = fng else {
+            return None;
+        };
+
+        self.removed_times
+            .iter()
+            .map(|entry| *entry.key())
+            .find(|old| match old {
+                FileFingerprint::DevInodeChecksum(old_dev, old_ino, old_checksum) => {
+                    *old_checksum == checksum && (*old_dev, *old_ino) != (dev, ino)
+                }
+                _ => false,
+            })
+            .and_then(|old| self.get(old))
     }
 }
 
@@ -232,6 +361,7 @@ impl Checkpointer {
             BytesChecksum(c) => format!("g{:x}.{}", c, pos),
             FirstLinesChecksum(c) => format!("h{:x}.{}", c, pos),
             DevInode(dev, ino) => format!("i{:x}.{:x}.{}", dev, ino, pos),
+            DevInodeChecksum(dev, ino, c) => format!("j{:x}.{:x}.{:x}.{}", dev, ino, c, pos),
             Unknown(x) => format!("{:x}.{}", x, pos),
         };
         self.directory.join(path)
@@ -263,6 +393,18 @@ impl Checkpointer {
                         .unwrap();
                 (DevInode(dev, ino), pos)
             }
+            'j' => {
+                let (dev, ino, c, pos) = scan_fmt!(
+                    file_name,
+                    "j{x}.{x}.{x}.{}",
+                    [hex u64],
+                    [hex u64],
+                    [hex u64],
+                    FilePosition
+                )
+                .unwrap();
+                (DevInodeChecksum(dev, ino, c), pos)
+            }
             _ => {
                 let (c, pos) = scan_fmt!(file_name, "{x}.{}", [hex u64], FilePosition).unwrap();
                 (Unknown(c), pos)
@@ -416,6 +558,7 @@ impl Checkpointer {
                 }
             }
             let (fng, pos) = self.decode(&path);
+            self.checkpoints.index_loaded(fng);
             self.checkpoints.checkpoints.insert(fng, pos);
             if let Some(mtime) = mtime {
                 self.checkpoints.modified_times.insert(fng, mtime);
@@ -442,6 +585,7 @@ mod test {
             FileFingerprint::DevInode(1, 2),
             FileFingerprint::BytesChecksum(3456),
             FileFingerprint::FirstLinesChecksum(78910),
+            FileFingerprint::DevInodeChecksum(1, 2, 3456),
             FileFingerprint::Unknown(1337),
         ];
         for fingerprint in fingerprints {
@@ -513,6 +657,7 @@ mod test {
             FileFingerprint::DevInode(1, 2),
             FileFingerprint::BytesChecksum(3456),
             FileFingerprint::FirstLinesChecksum(78910),
+            FileFingerprint::DevInodeChecksum(1, 2, 3456),
             FileFingerprint::Unknown(1337),
         ];
         for fingerprint in fingerprints {
@@ -652,6 +797,173 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checkpointer_strategy_migration() {
+        let log_dir = tempdir().unwrap();
+        let path = log_dir.path().join("test.log");
+        std::fs::write(&path, "hello\nworld\n").unwrap();
+
+        let strategies = vec![
+            FingerprintStrategy::DevInode,
+            FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            FingerprintStrategy::DevInodeChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+        ];
+        let fingerprinter = |strategy: &FingerprintStrategy| Fingerprinter {
+            strategy: strategy.clone(),
+            max_line_length: 1024,
+            ignore_not_found: false,
+        };
+
+        let mut buf = Vec::new();
+        for old_strategy in &strategies {
+            for new_strategy in strategies
+                .iter()
+                .filter(|s| !std::ptr::eq(*s, old_strategy))
+            {
+                let old_fingerprint = fingerprinter(old_strategy)
+                    .get_fingerprint_of_file(&path, &mut buf)
+                    .unwrap();
+                let new_fingerprinter = fingerprinter(new_strategy);
+                let new_fingerprint = new_fingerprinter
+                    .get_fingerprint_of_file(&path, &mut buf)
+                    .unwrap();
+                let position: FilePosition = 6;
+
+                let data_dir = tempdir().unwrap();
+                {
+                    let mut chkptr = Checkpointer::new(data_dir.path());
+                    chkptr.update_checkpoint(old_fingerprint, position);
+                    chkptr.write_checkpoints().unwrap();
+                }
+                let mut chkptr = Checkpointer::new(data_dir.path());
+                chkptr.read_checkpoints(None);
+
+                chkptr.maybe_upgrade(&path, new_fingerprint, &new_fingerprinter, &mut buf);
+
+                assert_eq!(chkptr.get_checkpoint(new_fingerprint), Some(position));
+                assert_eq!(chkptr.get_checkpoint(old_fingerprint), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_checkpointer_strategy_migration_skips_past_end_of_file() {
+        let log_dir = tempdir().unwrap();
+        let path = log_dir.path().join("test.log");
+        std::fs::write(&path, "hello\n").unwrap();
+
+        let old_fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::DevInode,
+            max_line_length: 1024,
+            ignore_not_found: false,
+        };
+        let new_fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::DevInodeChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: 1024,
+            ignore_not_found: false,
+        };
+
+        let mut buf = Vec::new();
+        let old_fingerprint = old_fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        let new_fingerprint = new_fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+
+        // A position beyond the end of the file means the inode now belongs to different content.
+        let data_dir = tempdir().unwrap();
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.update_checkpoint(old_fingerprint, 1234);
+            chkptr.write_checkpoints().unwrap();
+        }
+        let mut chkptr = Checkpointer::new(data_dir.path());
+        chkptr.read_checkpoints(None);
+
+        chkptr.maybe_upgrade(&path, new_fingerprint, &new_fingerprinter, &mut buf);
+
+        assert_eq!(chkptr.get_checkpoint(new_fingerprint), None);
+        assert_eq!(chkptr.get_checkpoint(old_fingerprint), Some(1234));
+    }
+
+    #[test]
+    fn test_checkpointer_strategy_migration_from_checkpoint_file() {
+        let log_dir = tempdir().unwrap();
+        let path = log_dir.path().join("test.log");
+        std::fs::write(&path, "hello\nworld\n").unwrap();
+
+        // Checksummed over two lines, unlike the defaults, so that only the
+        // device and inode can match the file.
+        let mut buf = Vec::new();
+        let old_fingerprint = Fingerprinter {
+            strategy: FingerprintStrategy::DevInodeChecksum {
+                ignored_header_bytes: 0,
+                lines: 2,
+            },
+            max_line_length: 1024,
+            ignore_not_found: false,
+        }
+        .get_fingerprint_of_file(&path, &mut buf)
+        .unwrap();
+        let FileFingerprint::DevInodeChecksum(dev, ino, checksum) = old_fingerprint else {
+            panic!("unexpected fingerprint {old_fingerprint:?}");
+        };
+
+        let data_dir = tempdir().unwrap();
+        std::fs::write(
+            data_dir.path().join(CHECKPOINT_FILE_NAME),
+            format!(
+                r#"{{"version":"1","checkpoints":[{{"fingerprint":{{"dev_inode_checksum":[{dev},{ino},{checksum}]}},"position":6,"modified":"2021-07-12T18:19:11.769003Z"}}]}}"#
+            ),
+        )
+        .unwrap();
+
+        let new_fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::DevInode,
+            max_line_length: 1024,
+            ignore_not_found: false,
+        };
+        let new_fingerprint = new_fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+
+        let mut chkptr = Checkpointer::new(data_dir.path());
+        chkptr.read_checkpoints(None);
+        chkptr.maybe_upgrade(&path, new_fingerprint, &new_fingerprinter, &mut buf);
+
+        assert_eq!(new_fingerprint, FileFingerprint::DevInode(dev, ino));
+        assert_eq!(chkptr.get_checkpoint(new_fingerprint), Some(6));
+        assert_eq!(chkptr.get_checkpoint(old_fingerprint), None);
+    }
+
+    #[test]
+    fn test_checkpointer_find_rotated_position() {
+        let data_dir = tempdir().unwrap();
+        let mut chkptr = Checkpointer::new(data_dir.path());
+
+        let original = FileFingerprint::DevInodeChecksum(1, 2, 3456);
+        let copy = FileFingerprint::DevInodeChecksum(1, 3, 3456);
+        let unrelated = FileFingerprint::DevInodeChecksum(1, 4, 7890);
+
+        chkptr.update_checkpoint(original, 1234);
+        assert_eq!(chkptr.checkpoints.find_rotated_position(copy), None);
+
+        chkptr.checkpoints.set_dead(original);
+        assert_eq!(chkptr.checkpoints.find_rotated_position(copy), Some(1234));
+        assert_eq!(chkptr.checkpoints.find_rotated_position(original), None);
+        assert_eq!(chkptr.checkpoints.find_rotated_position(unrelated), None);
+    }
+
     #[test]
     fn test_checkpointer_file_upgrades() {
         let fingerprint = FileFingerprint::DevInode(1, 2);
@@ -798,6 +1110,10 @@ mod test {
                 FileFingerprint::FirstLinesChecksum(78910),
                 r#"{"version":"1","checkpoints":[{"fingerprint":{"first_lines_checksum":78910},"position":1234}]}"#,
             ),
+            (
+                FileFingerprint::DevInodeChecksum(1, 2, 3456),
+                r#"{"version":"1","checkpoints":[{"fingerprint":{"dev_inode_checksum":[1,2,3456]},"position":1234}]}"#,
+            ),
             (
                 FileFingerprint::Unknown(1337),
                 r#"{"version":"1","checkpoints":[{"fingerprint":{"unknown":1337},"position":1234}]}"#,
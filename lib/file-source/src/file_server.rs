@@ -2,7 +2,7 @@ use std::{
     cmp,
     collections::{BTreeMap, HashSet},
     fs::{self, remove_file},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{self, Duration},
 };
@@ -22,7 +22,7 @@ use crate::{
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
     paths_provider::PathsProvider,
-    FileSourceInternalEvents, ReadFrom,
+    FilePosition, FileSourceInternalEvents, ReadFrom,
 };
 
 /// `FileServer` is a Source which cooperatively schedules reads over files,
@@ -50,6 +50,7 @@ where
     pub fingerprinter: Fingerprinter,
     pub oldest_first: bool,
     pub remove_after: Option<Duration>,
+    pub stop_at_eof: bool,
     pub emitter: E,
     pub handle: tokio::runtime::Handle,
 }
@@ -226,6 +227,7 @@ where
                         }
                     }
                 }
+                self.reset_truncated_files(&mut fp_map);
                 stats.record("discovery", start.elapsed());
             }
 
@@ -340,21 +342,31 @@ where
                 }
             };
             futures::pin_mut!(sleep);
-            match self.handle.block_on(select(shutdown_data, sleep)) {
-                Either::Left((_, _)) => {
-                    self.handle
-                        .block_on(chans.close())
-                        .expect("error closing file_server data channel.");
-                    let checkpointer = self
-                        .handle
-                        .block_on(checkpoint_task_handle)
-                        .expect("checkpoint task has panicked");
-                    if let Err(error) = checkpointer.write_checkpoints() {
-                        error!(?error, "Error writing checkpoints before shutdown");
-                    }
-                    return Ok(Shutdown);
+
+            // When stopping at the end of the files, a pass that reads nothing from any of the
+            // watched files means that all of them have been read to their end, and the server
+            // stops as if it was shut down.
+            let reached_eof = self.stop_at_eof && global_bytes_read == 0;
+            if reached_eof {
+                info!(message = "Reached the end of all files.");
+            }
+            if reached_eof
+                || matches!(
+                    self.handle.block_on(select(&mut shutdown_data, sleep)),
+                    Either::Left(_)
+                )
+            {
+                self.handle
+                    .block_on(chans.close())
+                    .expect("error closing file_server data channel.");
+                let checkpointer = self
+                    .handle
+                    .block_on(checkpoint_task_handle)
+                    .expect("checkpoint task has panicked");
+                if let Err(error) = checkpointer.write_checkpoints() {
+                    error!(?error, "Error writing checkpoints before shutdown");
                 }
-                Either::Right((_, future)) => shutdown_data = future,
+                return Ok(Shutdown);
             }
             stats.record("sleeping", start.elapsed());
         }
@@ -388,6 +400,13 @@ where
         let read_from = if !self.ignore_checkpoints {
             checkpoints
                 .get(file_id)
+                .or_else(|| {
+                    (!startup)
+                        .then(|| {
+                            self.find_copy_truncate_origin(&path, file_id, fp_map, checkpoints)
+                        })
+                        .flatten()
+                })
                 .map(ReadFrom::Checkpoint)
                 .unwrap_or(fallback)
         } else {
@@ -413,6 +432,73 @@ where
             Err(error) => self.emitter.emit_file_watch_error(&path, error),
         };
     }
+
+    /// When a file is copied away and then truncated in place (copy-truncate
+    /// rotation), the copy shows up as a new inode with the same leading
+    /// content as the original. If the original has since been truncated below
+    /// our read position, or was recently unwatched, the copy is treated as the
+    /// continuation of the original instead of a brand new file.
+    ///
+    /// Only applies to fingerprints that combine device, inode and checksum, as
+    /// the other strategies cannot tell a copy apart from a distinct file.
+    fn find_copy_truncate_origin(
+        &self,
+        path: &Path,
+        file_id: FileFingerprint,
+        fp_map: &IndexMap<FileFingerprint, FileWatcher>,
+        checkpoints: &CheckpointsView,
+    ) -> Option<FilePosition> {
+        let FileFingerprint::DevInodeChecksum(dev, ino, checksum) = file_id else {
+            return None;
+        };
+        let len = fs::metadata(path).ok()?.len();
+
+        let position = fp_map
+            .iter()
+            .find_map(|(origin_id, watcher)| match origin_id {
+                FileFingerprint::DevInodeChecksum(origin_dev, origin_ino, origin_checksum)
+                    if *origin_checksum == checksum && (*origin_dev, *origin_ino) != (dev, ino) =>
+                {
+                    let position = watcher.get_file_position();
+                    let origin_len = fs::metadata(&watcher.path).map(|m| m.len()).unwrap_or(0);
+                    (origin_len < position).then_some(position)
+                }
+                _ => None,
+            })
+            .or_else(|| checkpoints.find_rotated_position(file_id))
+            .filter(|position| *position <= len)?;
+
+        info!(
+            message = "Detected copy-truncate rotation, resuming from the original file's position.",
+            ?path,
+            %position,
+        );
+        Some(position)
+    }
+
+    /// Files fingerprinted by device, inode and checksum keep their fingerprint
+    /// when truncated in place and rewritten with the same leading content, so
+    /// a read position beyond the end of the file is the only sign of it.
+    fn reset_truncated_files(&self, fp_map: &mut IndexMap<FileFingerprint, FileWatcher>) {
+        for (file_id, watcher) in fp_map.iter_mut() {
+            if !matches!(file_id, FileFingerprint::DevInodeChecksum(..)) || !watcher.file_findable()
+            {
+                continue;
+            }
+            let Ok(len) = fs::metadata(&watcher.path).map(|m| m.len()) else {
+                continue;
+            };
+            if len < watcher.get_file_position() {
+                info!(
+                    message = "Watched file has been truncated, reading from the beginning.",
+                    path = ?watcher.path,
+                );
+                if let Err(error) = watcher.reopen_from_start() {
+                    self.emitter.emit_file_watch_error(&watcher.path, error);
+                }
+            }
+        }
+    }
 }
 
 async fn checkpoint_writer(
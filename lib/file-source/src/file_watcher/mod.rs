@@ -174,6 +174,24 @@ impl FileWatcher {
         Ok(())
     }
 
+    /// Reopen the file and start reading from its beginning, discarding any
+    /// partially read line. Used once the file is known to have been truncated.
+    pub fn reopen_from_start(&mut self) -> io::Result<()> {
+        let file_handle = File::open(&self.path)?;
+        let (devno, inode) = (file_handle.portable_dev()?, file_handle.portable_ino()?);
+        let mut reader = io::BufReader::new(file_handle);
+        self.reader = if is_gzipped(&mut reader)? {
+            Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
+        } else {
+            Box::new(reader)
+        };
+        self.devno = devno;
+        self.inode = inode;
+        self.file_position = 0;
+        self.buf.clear();
+        Ok(())
+    }
+
     pub fn set_file_findable(&mut self, f: bool) {
         self.findable = f;
     }
@@ -32,6 +32,10 @@ pub enum FingerprintStrategy {
         lines: usize,
     },
     DevInode,
+    DevInodeChecksum {
+        ignored_header_bytes: usize,
+        lines: usize,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, Ord, PartialOrd)]
@@ -42,6 +46,7 @@ pub enum FileFingerprint {
     #[serde(alias = "first_line_checksum")]
     FirstLinesChecksum(u64),
     DevInode(u64, u64),
+    DevInodeChecksum(u64, u64, u64),
     Unknown(u64),
 }
 
@@ -58,9 +63,35 @@ impl FileFingerprint {
                 buf.write_all(&ino.to_be_bytes()).expect("writing to array");
                 FINGERPRINT_CRC.checksum(&buf[..])
             }
+            DevInodeChecksum(dev, ino, c) => {
+                let mut buf = Vec::with_capacity(std::mem::size_of_val(dev) * 3);
+                buf.write_all(&dev.to_be_bytes()).expect("writing to array");
+                buf.write_all(&ino.to_be_bytes()).expect("writing to array");
+                buf.write_all(&c.to_be_bytes()).expect("writing to array");
+                FINGERPRINT_CRC.checksum(&buf[..])
+            }
             Unknown(c) => *c,
         }
     }
+
+    /// Returns the device and inode pair this fingerprint was derived from, if any.
+    pub const fn dev_inode(&self) -> Option<(u64, u64)> {
+        match self {
+            FileFingerprint::DevInode(dev, ino)
+            | FileFingerprint::DevInodeChecksum(dev, ino, _) => Some((*dev, *ino)),
+            _ => None,
+        }
+    }
+
+    /// Returns the checksum of the leading file content for fingerprints that include one.
+    pub const fn content_checksum(&self) -> Option<u64> {
+        match self {
+            FileFingerprint::FirstLinesChecksum(c) | FileFingerprint::DevInodeChecksum(_, _, c) => {
+                Some(*c)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<u64> for FileFingerprint {
@@ -93,16 +124,50 @@ impl Fingerprinter {
                 ignored_header_bytes,
                 lines,
             } => {
-                buffer.resize(self.max_line_length, 0u8);
-                let mut fp = fs::File::open(path)?;
-                fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
-                let bytes_read = fingerprinter_read_until(fp, b'\n', lines, buffer)?;
-                let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..bytes_read]);
+                let fp = fs::File::open(path)?;
+                let fingerprint =
+                    self.first_lines_checksum(fp, ignored_header_bytes, lines, buffer)?;
                 Ok(FirstLinesChecksum(fingerprint))
             }
+            FingerprintStrategy::DevInodeChecksum {
+                ignored_header_bytes,
+                lines,
+            } => {
+                let fp = fs::File::open(path)?;
+                let dev = fp.portable_dev()?;
+                let ino = fp.portable_ino()?;
+                let fingerprint =
+                    self.first_lines_checksum(fp, ignored_header_bytes, lines, buffer)?;
+                Ok(DevInodeChecksum(dev, ino, fingerprint))
+            }
         }
     }
 
+    fn first_lines_checksum(
+        &self,
+        mut fp: File,
+        ignored_header_bytes: usize,
+        lines: usize,
+        buffer: &mut Vec<u8>,
+    ) -> Result<u64, io::Error> {
+        buffer.resize(self.max_line_length, 0u8);
+        fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
+        let bytes_read = fingerprinter_read_until(fp, b'\n', lines, buffer)?;
+        Ok(FINGERPRINT_CRC.checksum(&buffer[..bytes_read]))
+    }
+
+    /// Calculates the `FirstLinesChecksum` fingerprint of a file with the
+    /// default options, no ignored header and a single line.
+    pub fn get_default_first_lines_checksum(
+        &self,
+        path: &Path,
+        buffer: &mut Vec<u8>,
+    ) -> Result<FileFingerprint, io::Error> {
+        let fp = File::open(path)?;
+        let checksum = self.first_lines_checksum(fp, 0, 1, buffer)?;
+        Ok(FileFingerprint::FirstLinesChecksum(checksum))
+    }
+
     pub fn get_fingerprint_or_log_error(
         &self,
         path: &Path,
@@ -497,6 +562,46 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_inode_checksum_fingerprint() {
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::DevInodeChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: 1024,
+            ignore_not_found: false,
+        };
+
+        let target_dir = tempdir().unwrap();
+        let incomplete_path = target_dir.path().join("incomplete.log");
+        let banner_path = target_dir.path().join("banner.log");
+        let duplicate_banner_path = target_dir.path().join("duplicate_banner.log");
+        fs::write(&incomplete_path, b"missing newline char").unwrap();
+        fs::write(&banner_path, b"banner\nfirst file\n").unwrap();
+        fs::write(&duplicate_banner_path, b"banner\nsecond file\n").unwrap();
+
+        let mut buf = Vec::new();
+        let mut run = move |path| fingerprinter.get_fingerprint_of_file(path, &mut buf);
+
+        assert!(run(&incomplete_path).is_err());
+
+        // Same leading content, different files.
+        let banner = run(&banner_path).unwrap();
+        let duplicate_banner = run(&duplicate_banner_path).unwrap();
+        assert_ne!(banner, duplicate_banner);
+        assert_eq!(
+            banner.content_checksum(),
+            duplicate_banner.content_checksum()
+        );
+
+        // Same file, rewritten in place with different leading content.
+        fs::write(&banner_path, b"new banner\n").unwrap();
+        let rewritten = run(&banner_path).unwrap();
+        assert_ne!(banner, rewritten);
+        assert_eq!(banner.dev_inode(), rewritten.dev_inode());
+    }
+
     #[test]
     fn no_error_on_dir() {
         let target_dir = tempdir().unwrap();
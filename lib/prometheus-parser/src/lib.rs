@@ -3,11 +3,12 @@
 use std::{collections::BTreeMap, convert::TryFrom};
 
 use indexmap::IndexMap;
+use prost::Message;
 use snafu::ResultExt;
 
 mod line;
 
-pub use line::ErrorKind;
+pub use line::{ErrorKind, Exemplar};
 use line::{Line, Metric, MetricKind};
 
 pub const METRIC_NAME_LABEL: &str = "__name__";
@@ -34,6 +35,21 @@ pub mod proto {
     }
 }
 
+/// The messages of the Remote-Write 2.0 protocol, which reference the strings
+/// of a request by their index in its symbol table.
+#[allow(warnings)] // Ignore some clippy warnings
+pub mod proto_v2 {
+    include!(concat!(env!("OUT_DIR"), "/io.prometheus.write.v2.rs"));
+
+    pub use metadata::MetricType;
+}
+
+/// The protobuf exposition format, as served by Prometheus client libraries.
+#[allow(warnings)] // Ignore some clippy warnings
+pub mod exposition {
+    include!(concat!(env!("OUT_DIR"), "/io.prometheus.client.rs"));
+}
+
 #[derive(Debug, snafu::Snafu, PartialEq)]
 pub enum ParserError {
     #[snafu(display("{}, line: `{}`", kind, line))]
@@ -60,6 +76,16 @@ pub enum ParserError {
     MultipleMetricKinds { name: String },
     #[snafu(display("request is missing metric name label"))]
     RequestNoNameLabel,
+    #[snafu(display("request references unknown symbol {}", reference))]
+    InvalidSymbolReference { reference: u32 },
+    #[snafu(display("request has a label name reference without a value reference"))]
+    UnpairedLabelReference,
+
+    #[snafu(display("error decoding metric family: {}", error))]
+    DecodeMetricFamily {
+        #[snafu(source)]
+        error: prost::DecodeError,
+    },
 }
 
 vector_common::impl_event_data_eq!(ParserError);
@@ -81,12 +107,15 @@ pub struct SummaryMetric {
     pub quantiles: Vec<SummaryQuantile>,
     pub sum: f64,
     pub count: u64,
+    /// Creation timestamp of the series in milliseconds.
+    pub created: Option<i64>,
 }
 
 #[derive(Debug, Default, PartialEq, PartialOrd)]
 pub struct HistogramBucket {
     pub bucket: f64,
     pub count: u64,
+    pub exemplar: Option<Exemplar>,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -94,11 +123,42 @@ pub struct HistogramMetric {
     pub buckets: Vec<HistogramBucket>,
     pub sum: f64,
     pub count: u64,
+    /// Creation timestamp of the series in milliseconds.
+    pub created: Option<i64>,
+    /// The exponential buckets of native histograms, which have no explicit
+    /// buckets.
+    pub native: Option<NativeHistogram>,
+}
+
+/// The sparse exponential buckets of a native histogram.
+///
+/// The positive bucket with index `i` covers `(2^((i - 1) * 2^-schema), 2^(i * 2^-schema)]`,
+/// and the negative buckets mirror the positive ones. Values whose absolute
+/// value is at most the zero threshold are counted in the zero bucket.
+#[derive(Debug, Default, PartialEq)]
+pub struct NativeHistogram {
+    pub schema: i32,
+    pub zero_threshold: f64,
+    pub zero_count: f64,
+    /// The index and count of each positive bucket, ordered by index.
+    pub positive: Vec<(i32, f64)>,
+    /// The index and count of each negative bucket, ordered by index.
+    pub negative: Vec<(i32, f64)>,
+}
+
+impl NativeHistogram {
+    /// The upper bound of the positive bucket with the given index.
+    pub fn bucket_upper_bound(&self, index: i32) -> f64 {
+        native_bucket_upper_bound(self.schema, index)
+    }
 }
 
 #[derive(Debug, Default, PartialEq)]
 pub struct SimpleMetric {
     pub value: f64,
+    /// Creation timestamp of the series in milliseconds, only known for counters.
+    pub created: Option<i64>,
+    pub exemplar: Option<Exemplar>,
 }
 
 type MetricMap<T> = IndexMap<GroupKey, T>;
@@ -125,7 +185,13 @@ impl GroupKind {
 
     fn new_untyped(key: GroupKey, value: f64) -> Self {
         let mut metrics = IndexMap::default();
-        metrics.insert(key, SimpleMetric { value });
+        metrics.insert(
+            key,
+            SimpleMetric {
+                value,
+                ..Default::default()
+            },
+        );
         Self::Untyped(metrics)
     }
 
@@ -142,6 +208,9 @@ impl GroupKind {
     /// Err(_) if there are irrecoverable error.
     /// Ok(Some(metric)) if this metric belongs to another group.
     /// Ok(None) pushed successfully.
+    ///
+    /// The OpenMetrics `_total`, `_created`, `_gsum` and `_gcount` suffixes are accepted in both
+    /// text formats.
     fn try_push(
         &mut self,
         prefix_len: usize,
@@ -153,20 +222,46 @@ impl GroupKind {
             labels: metric.labels,
         };
         let value = metric.value;
+        let exemplar = metric.exemplar;
 
         match self {
-            Self::Counter(ref mut metrics)
-            | Self::Gauge(ref mut metrics)
-            | Self::Untyped(ref mut metrics) => {
+            Self::Counter(ref mut metrics) => match suffix {
+                "" | "_total" => {
+                    let counter = matching_group(metrics, key);
+                    counter.value = value;
+                    counter.exemplar = exemplar;
+                }
+                "_created" => {
+                    created_group(metrics, key).created = Some(seconds_to_millis(value));
+                }
+                _ => {
+                    return Ok(Some(Metric {
+                        name: metric.name,
+                        timestamp: key.timestamp,
+                        labels: key.labels,
+                        value,
+                        exemplar,
+                    }))
+                }
+            },
+            Self::Gauge(ref mut metrics) | Self::Untyped(ref mut metrics) => {
                 if !suffix.is_empty() {
                     return Ok(Some(Metric {
                         name: metric.name,
                         timestamp: key.timestamp,
                         labels: key.labels,
                         value,
+                        exemplar,
                     }));
                 }
-                metrics.insert(key, SimpleMetric { value });
+                metrics.insert(
+                    key,
+                    SimpleMetric {
+                        value,
+                        created: None,
+                        exemplar,
+                    },
+                );
             }
             Self::Histogram(ref mut metrics) => match suffix {
                 "_bucket" => {
@@ -175,24 +270,30 @@ impl GroupKind {
                         .map_err(Into::into)
                         .context(ParseLabelValueSnafu)?;
                     let count = try_f64_to_u64(metric.value)?;
-                    matching_group(metrics, key)
-                        .buckets
-                        .push(HistogramBucket { bucket, count });
+                    matching_group(metrics, key).buckets.push(HistogramBucket {
+                        bucket,
+                        count,
+                        exemplar,
+                    });
                 }
-                "_sum" => {
+                "_sum" | "_gsum" => {
                     let sum = metric.value;
                     matching_group(metrics, key).sum = sum;
                 }
-                "_count" => {
+                "_count" | "_gcount" => {
                     let count = try_f64_to_u64(metric.value)?;
                     matching_group(metrics, key).count = count;
                 }
+                "_created" => {
+                    created_group(metrics, key).created = Some(seconds_to_millis(value));
+                }
                 _ => {
                     return Ok(Some(Metric {
                         name: metric.name,
                         timestamp: key.timestamp,
                         labels: key.labels,
                         value,
+                        exemplar,
                     }))
                 }
             },
@@ -218,12 +319,16 @@ impl GroupKind {
                     let count = try_f64_to_u64(metric.value)?;
                     matching_group(metrics, key).count = count;
                 }
+                "_created" => {
+                    created_group(metrics, key).created = Some(seconds_to_millis(value));
+                }
                 _ => {
                     return Ok(Some(Metric {
                         name: metric.name,
                         timestamp: key.timestamp,
                         labels: key.labels,
                         value,
+                        exemplar,
                     }))
                 }
             },
@@ -236,6 +341,19 @@ impl GroupKind {
 pub struct MetricGroup {
     pub name: String,
     pub metrics: GroupKind,
+    /// The help text of the group, when it is known.
+    pub help: Option<String>,
+    /// The unit of the group, when it is known.
+    pub unit: Option<String>,
+}
+
+/// Converts the seconds used by OpenMetrics `_created` samples to milliseconds.
+fn seconds_to_millis(seconds: f64) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
+
+fn timestamp_millis(timestamp: prost_types::Timestamp) -> i64 {
+    timestamp.seconds * 1000 + i64::from(timestamp.nanos) / 1_000_000
 }
 
 fn try_f64_to_u64(f: f64) -> Result<u64, ParserError> {
@@ -252,7 +370,12 @@ fn try_f64_to_u64(f: f64) -> Result<u64, ParserError> {
 impl MetricGroup {
     fn new(name: String, kind: MetricKind) -> Self {
         let metrics = GroupKind::new(kind);
-        MetricGroup { name, metrics }
+        MetricGroup {
+            name,
+            metrics,
+            help: None,
+            unit: None,
+        }
     }
 
     // For cases where a metric group was not defined with `# TYPE ...`.
@@ -262,11 +385,14 @@ impl MetricGroup {
             labels,
             value,
             timestamp,
+            ..
         } = metric;
         let key = GroupKey { timestamp, labels };
         MetricGroup {
             name,
             metrics: GroupKind::new_untyped(key, value),
+            help: None,
+            unit: None,
         }
     }
 
@@ -285,13 +411,44 @@ fn matching_group<T: Default>(values: &mut MetricMap<T>, group: GroupKey) -> &mu
     values.entry(group).or_default()
 }
 
+/// `_created` samples usually have no timestamp, even when the samples of
+/// their series have one, so they are matched on their labels only.
+fn created_group<T: Default>(values: &mut MetricMap<T>, group: GroupKey) -> &mut T {
+    match values.keys().rposition(|key| key.labels == group.labels) {
+        Some(index) => &mut values[index],
+        None => matching_group(values, group),
+    }
+}
+
 /// Parse the given text input, and group the result into higher-level
 /// metric types based on the declared types in the text.
 pub fn parse_text(input: &str) -> Result<Vec<MetricGroup>, ParserError> {
+    parse_lines(input, Line::parse)
+}
+
+/// Parse the given OpenMetrics text input, and group the result into
+/// higher-level metric types based on the declared types in the text.
+///
+/// Counters are named after their `_total` samples, like the Prometheus
+/// text format names them.
+pub fn parse_openmetrics_text(input: &str) -> Result<Vec<MetricGroup>, ParserError> {
+    let mut groups = parse_lines(input, Line::parse_openmetrics)?;
+    for group in &mut groups {
+        if matches!(group.metrics, GroupKind::Counter(_)) && !group.name.ends_with("_total") {
+            group.name.push_str("_total");
+        }
+    }
+    Ok(groups)
+}
+
+fn parse_lines(
+    input: &str,
+    parse_line: fn(&str) -> Result<Option<Line>, ErrorKind>,
+) -> Result<Vec<MetricGroup>, ParserError> {
     let mut groups = Vec::new();
 
     for line in input.lines() {
-        let line = Line::parse(line).with_context(|_| WithLineSnafu {
+        let line = parse_line(line).with_context(|_| WithLineSnafu {
             line: line.to_owned(),
         })?;
         if let Some(line) = line {
@@ -316,7 +473,7 @@ pub fn parse_text(input: &str) -> Result<Vec<MetricGroup>, ParserError> {
 }
 
 #[derive(Default)]
-struct MetricGroupSet(IndexMap<String, GroupKind>);
+struct MetricGroupSet(IndexMap<String, MetricGroup>);
 
 impl MetricGroupSet {
     fn get_group<'a>(&'a mut self, name: &str) -> (usize, &'a String, &'a mut GroupKind) {
@@ -330,21 +487,38 @@ impl MetricGroupSet {
         } else if name.ends_with("_count") && self.0.contains_key(&name[..len - 6]) {
             &name[..len - 6]
         } else {
-            self.0
-                .insert(name.into(), GroupKind::new(MetricKind::Untyped));
+            self.0.insert(
+                name.into(),
+                MetricGroup::new(name.into(), MetricKind::Untyped),
+            );
             name
         };
-        self.0.get_full_mut(name).unwrap()
+        let (index, name, group) = self.0.get_full_mut(name).unwrap();
+        (index, name, &mut group.metrics)
     }
 
-    fn insert_metadata(&mut self, name: String, kind: MetricKind) -> Result<(), ParserError> {
-        match self.0.get(&name) {
-            Some(group) if !group.matches_kind(kind) => {
+    fn insert_metadata(
+        &mut self,
+        name: String,
+        kind: MetricKind,
+        help: Option<String>,
+        unit: Option<String>,
+    ) -> Result<(), ParserError> {
+        match self.0.get_mut(&name) {
+            Some(group) if !group.metrics.matches_kind(kind) => {
                 Err(ParserError::MultipleMetricKinds { name })
             }
-            Some(_) => Ok(()), // metadata already exists and is the right type
+            // metadata already exists and is the right type
+            Some(group) => {
+                group.help = group.help.take().or(help);
+                group.unit = group.unit.take().or(unit);
+                Ok(())
+            }
             None => {
-                self.0.insert(name, GroupKind::new(kind));
+                let mut group = MetricGroup::new(name.clone(), kind);
+                group.help = help;
+                group.unit = unit;
+                self.0.insert(name, group);
                 Ok(())
             }
         }
@@ -364,23 +538,44 @@ impl MetricGroupSet {
                 labels: labels.clone(),
                 value: sample.value,
                 timestamp: Some(sample.timestamp),
+                exemplar: None,
             },
         )? {
-            let key = GroupKey {
-                timestamp: metric.timestamp,
-                labels: metric.labels,
-            };
-            let group = GroupKind::new_untyped(key, metric.value);
-            self.0.insert(metric.name, group);
+            self.0
+                .insert(metric.name.clone(), MetricGroup::new_untyped(metric));
+        }
+        Ok(())
+    }
+
+    /// Native histograms are sent as a single series named after their group,
+    /// which is a histogram even without metadata.
+    fn insert_native_histogram(
+        &mut self,
+        name: &str,
+        labels: &BTreeMap<String, String>,
+        timestamp: i64,
+        histogram: HistogramMetric,
+    ) -> Result<(), ParserError> {
+        let group = self
+            .0
+            .entry(name.into())
+            .or_insert_with(|| MetricGroup::new(name.into(), MetricKind::Histogram));
+        if matches!(&group.metrics, GroupKind::Untyped(metrics) if metrics.is_empty()) {
+            group.metrics = GroupKind::new(MetricKind::Histogram);
         }
+        let GroupKind::Histogram(metrics) = &mut group.metrics else {
+            return Err(ParserError::MultipleMetricKinds { name: name.into() });
+        };
+        let key = GroupKey {
+            timestamp: Some(timestamp),
+            labels: labels.clone(),
+        };
+        metrics.insert(key, histogram);
         Ok(())
     }
 
     fn finish(self) -> Vec<MetricGroup> {
-        self.0
-            .into_iter()
-            .map(|(name, metrics)| MetricGroup { name, metrics })
-            .collect()
+        self.0.into_values().collect()
     }
 }
 
@@ -394,7 +589,12 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
         let kind = proto::MetricType::try_from(metadata.r#type)
             .unwrap_or(proto::MetricType::Unknown)
             .into();
-        groups.insert_metadata(name, kind)?;
+        groups.insert_metadata(
+            name,
+            kind,
+            non_empty(metadata.help),
+            non_empty(metadata.unit),
+        )?;
     }
 
     for timeseries in request.timeseries {
@@ -411,11 +611,385 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
         for sample in timeseries.samples {
             groups.insert_sample(&name, &labels, sample)?;
         }
+        for histogram in timeseries.histograms {
+            let timestamp = histogram.timestamp;
+            groups.insert_native_histogram(&name, &labels, timestamp, histogram.into())?;
+        }
     }
 
     Ok(groups.finish())
 }
 
+/// Parse the given Remote-Write 2.0 request, grouping the metrics into
+/// higher-level metric types based on the metadata of each series.
+pub fn parse_request_v2(request: proto_v2::Request) -> Result<Vec<MetricGroup>, ParserError> {
+    let symbols = request.symbols;
+    let symbol = |reference: u32| {
+        symbols
+            .get(reference as usize)
+            .cloned()
+            .ok_or(ParserError::InvalidSymbolReference { reference })
+    };
+    let mut groups = MetricGroupSet::default();
+
+    for timeseries in request.timeseries {
+        if timeseries.labels_refs.len() % 2 != 0 {
+            return Err(ParserError::UnpairedLabelReference);
+        }
+        let mut labels = BTreeMap::new();
+        for pair in timeseries.labels_refs.chunks_exact(2) {
+            labels.insert(symbol(pair[0])?, symbol(pair[1])?);
+        }
+        let name = match labels.remove(METRIC_NAME_LABEL) {
+            Some(name) => name,
+            None => return Err(ParserError::RequestNoNameLabel),
+        };
+
+        if let Some(metadata) = timeseries.metadata {
+            let kind = proto_v2::MetricType::try_from(metadata.r#type)
+                .unwrap_or(proto_v2::MetricType::Unspecified)
+                .into();
+            groups.insert_metadata(
+                family_name(&name, kind).into(),
+                kind,
+                non_empty(symbol(metadata.help_ref)?),
+                non_empty(symbol(metadata.unit_ref)?),
+            )?;
+        }
+
+        for sample in timeseries.samples {
+            let sample = proto::Sample {
+                value: sample.value,
+                timestamp: sample.timestamp,
+            };
+            groups.insert_sample(&name, &labels, sample)?;
+        }
+        for histogram in timeseries.histograms {
+            let timestamp = histogram.timestamp;
+            groups.insert_native_histogram(&name, &labels, timestamp, histogram.into())?;
+        }
+    }
+
+    Ok(groups.finish())
+}
+
+/// Remote-Write 2.0 attaches the metadata to every series, including the
+/// suffixed series of histograms and summaries.
+fn family_name(name: &str, kind: MetricKind) -> &str {
+    let suffixes: &[&str] = match kind {
+        MetricKind::Histogram => &["_bucket", "_sum", "_count"],
+        MetricKind::Summary => &["_sum", "_count"],
+        _ => &[],
+    };
+    suffixes
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .unwrap_or(name)
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+/// Parse the given length-delimited metric families of the protobuf
+/// exposition format.
+///
+/// Native histograms are converted to histograms with explicit buckets, whose
+/// bounds are the exact bounds of the exponential buckets.
+pub fn parse_protobuf(mut input: &[u8]) -> Result<Vec<MetricGroup>, ParserError> {
+    let mut groups = Vec::new();
+
+    while !input.is_empty() {
+        let family = exposition::MetricFamily::decode_length_delimited(&mut input)
+            .context(DecodeMetricFamilySnafu)?;
+        groups.push(MetricGroup::from_family(family));
+    }
+
+    Ok(groups)
+}
+
+impl MetricGroup {
+    fn from_family(family: exposition::MetricFamily) -> Self {
+        let mut metrics = GroupKind::new(family.r#type().into());
+
+        for metric in family.metric {
+            let key = GroupKey {
+                timestamp: metric.timestamp_ms,
+                labels: labels_from_pairs(metric.label),
+            };
+            match &mut metrics {
+                GroupKind::Counter(metrics) => {
+                    let counter = metric.counter.unwrap_or_default();
+                    let value = counter.value();
+                    metrics.insert(
+                        key,
+                        SimpleMetric {
+                            value,
+                            created: counter.created_timestamp.map(timestamp_millis),
+                            exemplar: counter.exemplar.map(Into::into),
+                        },
+                    );
+                }
+                GroupKind::Gauge(metrics) => {
+                    let value = metric.gauge.unwrap_or_default().value();
+                    metrics.insert(
+                        key,
+                        SimpleMetric {
+                            value,
+                            ..Default::default()
+                        },
+                    );
+                }
+                GroupKind::Untyped(metrics) => {
+                    let value = metric.untyped.unwrap_or_default().value();
+                    metrics.insert(
+                        key,
+                        SimpleMetric {
+                            value,
+                            ..Default::default()
+                        },
+                    );
+                }
+                GroupKind::Summary(metrics) => {
+                    let summary = metric.summary.unwrap_or_default();
+                    metrics.insert(
+                        key,
+                        SummaryMetric {
+                            quantiles: summary
+                                .quantile
+                                .iter()
+                                .map(|quantile| SummaryQuantile {
+                                    quantile: quantile.quantile(),
+                                    value: quantile.value(),
+                                })
+                                .collect(),
+                            sum: summary.sample_sum(),
+                            count: summary.sample_count(),
+                            created: summary.created_timestamp.map(timestamp_millis),
+                        },
+                    );
+                }
+                GroupKind::Histogram(metrics) => {
+                    let histogram = metric.histogram.unwrap_or_default();
+                    metrics.insert(key, histogram.into());
+                }
+            }
+        }
+
+        MetricGroup {
+            name: family.name.unwrap_or_default(),
+            metrics,
+            help: family.help,
+            unit: None,
+        }
+    }
+}
+
+fn labels_from_pairs(pairs: Vec<exposition::LabelPair>) -> BTreeMap<String, String> {
+    pairs
+        .into_iter()
+        .map(|pair| {
+            (
+                pair.name.unwrap_or_default(),
+                pair.value.unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+impl From<exposition::Exemplar> for Exemplar {
+    fn from(exemplar: exposition::Exemplar) -> Self {
+        Self {
+            value: exemplar.value(),
+            labels: labels_from_pairs(exemplar.label),
+            timestamp: exemplar.timestamp.map(timestamp_millis),
+        }
+    }
+}
+
+impl From<exposition::Histogram> for HistogramMetric {
+    fn from(mut histogram: exposition::Histogram) -> Self {
+        let count = if histogram.sample_count_float() > 0.0 {
+            histogram.sample_count_float() as u64
+        } else {
+            histogram.sample_count()
+        };
+        let sum = histogram.sample_sum();
+        let created = histogram.created_timestamp.take().map(timestamp_millis);
+        if is_native_histogram(&histogram) {
+            return Self {
+                buckets: Vec::new(),
+                sum,
+                count,
+                created,
+                native: Some(NativeHistogram::from(&histogram)),
+            };
+        }
+
+        let buckets = histogram
+            .bucket
+            .into_iter()
+            .map(|bucket| HistogramBucket {
+                bucket: bucket.upper_bound(),
+                count: if bucket.cumulative_count_float() > 0.0 {
+                    bucket.cumulative_count_float() as u64
+                } else {
+                    bucket.cumulative_count()
+                },
+                exemplar: bucket.exemplar.map(Into::into),
+            })
+            .collect();
+
+        Self {
+            buckets,
+            sum,
+            count,
+            created,
+            native: None,
+        }
+    }
+}
+
+impl From<&exposition::Histogram> for NativeHistogram {
+    fn from(histogram: &exposition::Histogram) -> Self {
+        let spans = |spans: &[exposition::BucketSpan]| {
+            spans
+                .iter()
+                .map(|span| (span.offset(), span.length()))
+                .collect::<Vec<_>>()
+        };
+        Self {
+            schema: histogram.schema(),
+            zero_threshold: histogram.zero_threshold(),
+            zero_count: if histogram.zero_count_float() > 0.0 {
+                histogram.zero_count_float()
+            } else {
+                histogram.zero_count() as f64
+            },
+            positive: sparse_bucket_counts(
+                &spans(&histogram.positive_span),
+                &histogram.positive_delta,
+                &histogram.positive_count,
+            ),
+            negative: sparse_bucket_counts(
+                &spans(&histogram.negative_span),
+                &histogram.negative_delta,
+                &histogram.negative_count,
+            ),
+        }
+    }
+}
+
+/// Converts the native histograms of remote write requests, whose messages
+/// are the same in both versions of the protocol.
+macro_rules! impl_from_remote_histogram {
+    ($proto:ident) => {
+        impl From<$proto::Histogram> for HistogramMetric {
+            fn from(histogram: $proto::Histogram) -> Self {
+                use $proto::histogram::{Count, ZeroCount};
+
+                let spans = |spans: &[$proto::BucketSpan]| {
+                    spans
+                        .iter()
+                        .map(|span| (span.offset, span.length))
+                        .collect::<Vec<_>>()
+                };
+                let count = match histogram.count {
+                    Some(Count::CountInt(count)) => count,
+                    Some(Count::CountFloat(count)) => count as u64,
+                    None => 0,
+                };
+                let zero_count = match histogram.zero_count {
+                    Some(ZeroCount::ZeroCountInt(count)) => count as f64,
+                    Some(ZeroCount::ZeroCountFloat(count)) => count,
+                    None => 0.0,
+                };
+
+                Self {
+                    buckets: Vec::new(),
+                    sum: histogram.sum,
+                    count,
+                    created: None,
+                    native: Some(NativeHistogram {
+                        schema: histogram.schema,
+                        zero_threshold: histogram.zero_threshold,
+                        zero_count,
+                        positive: sparse_bucket_counts(
+                            &spans(&histogram.positive_spans),
+                            &histogram.positive_deltas,
+                            &histogram.positive_counts,
+                        ),
+                        negative: sparse_bucket_counts(
+                            &spans(&histogram.negative_spans),
+                            &histogram.negative_deltas,
+                            &histogram.negative_counts,
+                        ),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_from_remote_histogram!(proto);
+impl_from_remote_histogram!(proto_v2);
+
+/// Histograms can carry both conventional and native buckets, in which case
+/// the native buckets are used.
+fn is_native_histogram(histogram: &exposition::Histogram) -> bool {
+    !histogram.positive_span.is_empty()
+        || !histogram.negative_span.is_empty()
+        || histogram.zero_threshold() > 0.0
+        || histogram.zero_count() > 0
+        || histogram.zero_count_float() > 0.0
+}
+
+/// The upper bound of the positive native histogram bucket with the given
+/// index, which is `2^(index * 2^-schema)`.
+fn native_bucket_upper_bound(schema: i32, index: i32) -> f64 {
+    2f64.powf(f64::from(index) * 2f64.powi(-schema))
+}
+
+/// Returns the index and count of each bucket described by the spans, given as
+/// an offset and a length. Counts are either delta-encoded integers or absolute
+/// floats.
+fn sparse_bucket_counts(spans: &[(i32, u32)], deltas: &[i64], counts: &[f64]) -> Vec<(i32, f64)> {
+    let mut buckets = Vec::new();
+    let mut index = 0;
+    let mut position = 0;
+    let mut count = 0i64;
+
+    for &(offset, length) in spans {
+        index += offset;
+        for _ in 0..length {
+            let value = if counts.is_empty() {
+                count += deltas.get(position).copied().unwrap_or_default();
+                count as f64
+            } else {
+                counts.get(position).copied().unwrap_or_default()
+            };
+            buckets.push((index, value));
+            index += 1;
+            position += 1;
+        }
+    }
+
+    buckets
+}
+
+impl From<exposition::MetricType> for MetricKind {
+    fn from(kind: exposition::MetricType) -> Self {
+        use exposition::MetricType::*;
+        match kind {
+            Counter => MetricKind::Counter,
+            Gauge => MetricKind::Gauge,
+            Histogram | GaugeHistogram => MetricKind::Histogram,
+            Summary => MetricKind::Summary,
+            Untyped => MetricKind::Untyped,
+        }
+    }
+}
+
 impl From<proto::MetricType> for MetricKind {
     fn from(kind: proto::MetricType) -> Self {
         use proto::MetricType::*;
@@ -430,6 +1004,19 @@ impl From<proto::MetricType> for MetricKind {
     }
 }
 
+impl From<proto_v2::MetricType> for MetricKind {
+    fn from(kind: proto_v2::MetricType) -> Self {
+        use proto_v2::MetricType::*;
+        match kind {
+            Counter => MetricKind::Counter,
+            Gauge => MetricKind::Gauge,
+            Histogram | Gaugehistogram => MetricKind::Histogram,
+            Summary => MetricKind::Summary,
+            _ => MetricKind::Untyped,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -461,7 +1048,10 @@ mod test {
                     timestamp: $timestamp,
                     labels: $labels,
                 },
-                &SimpleMetric { value: $value },
+                &SimpleMetric {
+                    value: $value,
+                    ..Default::default()
+                },
             )
         };
     }
@@ -554,15 +1144,17 @@ mod test {
                 },
                 &HistogramMetric {
                     buckets: vec![
-                        HistogramBucket { bucket: 0.05, count: 24054 },
-                        HistogramBucket { bucket: 0.1, count: 33444 },
-                        HistogramBucket { bucket: 0.2, count: 100392 },
-                        HistogramBucket { bucket: 0.5, count: 129389 },
-                        HistogramBucket { bucket: 1.0, count: 133988 },
-                        HistogramBucket { bucket: f64::INFINITY, count: 144320 },
+                        HistogramBucket { bucket: 0.05, count: 24054, exemplar: None },
+                        HistogramBucket { bucket: 0.1, count: 33444, exemplar: None },
+                        HistogramBucket { bucket: 0.2, count: 100392, exemplar: None },
+                        HistogramBucket { bucket: 0.5, count: 129389, exemplar: None },
+                        HistogramBucket { bucket: 1.0, count: 133988, exemplar: None },
+                        HistogramBucket { bucket: f64::INFINITY, count: 144320, exemplar: None },
                     ],
                     count: 144320,
                     sum: 53423.0,
+                    created: None,
+                    native: None,
                 },
             ));
         });
@@ -575,10 +1167,12 @@ mod test {
                 },
                 &HistogramMetric {
                     buckets: vec![
-                        HistogramBucket { bucket: 24.999999999999996, count: 18_939_392_877},
+                        HistogramBucket { bucket: 24.999999999999996, count: 18_939_392_877, exemplar: None },
                     ],
                     count: 10,
                     sum: 5.0,
+                    created: None,
+                    native: None,
                 },
             ));
         });
@@ -599,11 +1193,170 @@ mod test {
                     ],
                     count: 4588206224,
                     sum: 1.7560473e+07,
+                    created: None,
                 },
             ));
         });
     }
 
+    #[test]
+    fn test_parse_openmetrics_text() {
+        let input = r#"
+            # TYPE foo counter
+            # HELP foo A counter with an exemplar.
+            foo_total{a="b"} 17.0 1520879607.789 # {trace_id="KOO5S4vxi0o"} 0.67
+            foo_created{a="b"} 1520430000.123
+            # TYPE bar gaugehistogram
+            bar_bucket{le="1"} 2
+            bar_bucket{le="+Inf"} 3
+            bar_gcount 3
+            bar_gsum 4.5
+            # EOF
+            "#;
+        let output = parse_openmetrics_text(input).unwrap();
+        assert_eq!(output.len(), 2);
+        match_group!(output[0], "foo_total", Counter => |metrics: &MetricMap<SimpleMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics.get_index(0).unwrap(), (
+                &GroupKey {
+                    timestamp: Some(1520879607789),
+                    labels: labels!(a => "b"),
+                },
+                &SimpleMetric {
+                    value: 17.0,
+                    created: Some(1520430000123),
+                    exemplar: Some(Exemplar {
+                        labels: labels!(trace_id => "KOO5S4vxi0o"),
+                        value: 0.67,
+                        timestamp: None,
+                    }),
+                },
+            ));
+        });
+        match_group!(output[1], "bar", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics.get_index(0).unwrap().1, &HistogramMetric {
+                buckets: vec![
+                    HistogramBucket { bucket: 1.0, count: 2, exemplar: None },
+                    HistogramBucket { bucket: f64::INFINITY, count: 3, exemplar: None },
+                ],
+                count: 3,
+                sum: 4.5,
+                created: None,
+                native: None,
+            });
+        });
+    }
+
+    #[test]
+    fn test_parse_protobuf() {
+        use exposition::{
+            BucketSpan, Counter, Histogram, LabelPair, Metric, MetricFamily, MetricType,
+        };
+
+        fn span(offset: i32, length: u32) -> BucketSpan {
+            BucketSpan {
+                offset: Some(offset),
+                length: Some(length),
+            }
+        }
+
+        let families = [
+            MetricFamily {
+                name: Some("requests_total".into()),
+                help: None,
+                r#type: Some(MetricType::Counter as i32),
+                metric: vec![Metric {
+                    label: vec![LabelPair {
+                        name: Some("code".into()),
+                        value: Some("200".into()),
+                    }],
+                    counter: Some(Counter {
+                        value: Some(5.0),
+                        exemplar: None,
+                        created_timestamp: Some(prost_types::Timestamp {
+                            seconds: 10,
+                            nanos: 500_000_000,
+                        }),
+                    }),
+                    timestamp_ms: Some(20000),
+                    ..Default::default()
+                }],
+            },
+            MetricFamily {
+                name: Some("latency".into()),
+                help: None,
+                r#type: Some(MetricType::Histogram as i32),
+                metric: vec![Metric {
+                    histogram: Some(Histogram {
+                        sample_count: Some(7),
+                        sample_sum: Some(10.0),
+                        schema: Some(0),
+                        zero_threshold: Some(0.5),
+                        zero_count: Some(1),
+                        negative_span: vec![span(1, 1)],
+                        negative_delta: vec![1],
+                        positive_span: vec![span(0, 2), span(1, 1)],
+                        positive_delta: vec![2, -1, 1],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }],
+            },
+        ];
+        let mut input = Vec::new();
+        for family in &families {
+            family.encode_length_delimited(&mut input).unwrap();
+        }
+
+        let output = parse_protobuf(&input).unwrap();
+        assert_eq!(output.len(), 2);
+        match_group!(output[0], "requests_total", Counter => |metrics: &MetricMap<SimpleMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics.get_index(0).unwrap(), (
+                &GroupKey {
+                    timestamp: Some(20000),
+                    labels: labels!(code => "200"),
+                },
+                &SimpleMetric {
+                    value: 5.0,
+                    created: Some(10500),
+                    exemplar: None,
+                },
+            ));
+        });
+        match_group!(output[1], "latency", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics.get_index(0).unwrap().1, &HistogramMetric {
+                buckets: vec![],
+                count: 7,
+                sum: 10.0,
+                created: None,
+                native: Some(NativeHistogram {
+                    schema: 0,
+                    zero_threshold: 0.5,
+                    zero_count: 1.0,
+                    positive: vec![(0, 2.0), (1, 1.0), (3, 2.0)],
+                    negative: vec![(1, 1.0)],
+                }),
+            });
+        });
+
+        assert!(matches!(
+            parse_protobuf(b"\x05ab"),
+            Err(ParserError::DecodeMetricFamily { .. })
+        ));
+    }
+
+    #[test]
+    fn native_bucket_bounds() {
+        assert_eq!(native_bucket_upper_bound(0, 3), 8.0);
+        assert_eq!(native_bucket_upper_bound(-1, 1), 4.0);
+        assert_eq!(native_bucket_upper_bound(3, 8), 2.0);
+        assert_eq!(native_bucket_upper_bound(3, -16), 0.25);
+        assert!((native_bucket_upper_bound(3, 1) - 2f64.powf(0.125)).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_f64_to_u64() {
         let value = -1.0;
@@ -736,6 +1489,7 @@ mod test {
                     samples: vec![
                         $( proto::Sample { value: $sample as f64, timestamp: $timestamp as i64 }, )*
                     ],
+                    ..Default::default()
                 }, )* ],
             }
         };
@@ -834,11 +1588,13 @@ mod test {
                     },
                     &HistogramMetric {
                         buckets: vec![
-                            HistogramBucket { bucket: 1.0, count: 15 },
-                            HistogramBucket { bucket: f64::INFINITY, count: 19 },
+                            HistogramBucket { bucket: 1.0, count: 15, exemplar: None },
+                            HistogramBucket { bucket: f64::INFINITY, count: 19, exemplar: None },
                         ],
                         count: 19,
                         sum: 12.0,
+                        created: None,
+                        native: None,
                     })
             );
         });
@@ -878,6 +1634,7 @@ mod test {
                         ],
                         count: 21,
                         sum: 12.0,
+                        created: None,
                     })
             );
         });
@@ -886,4 +1643,181 @@ mod test {
             assert_eq!(metrics.get_index(0).unwrap(), simple_metric!(Some(1395066367700), labels!(), 24.0));
         });
     }
+
+    #[test]
+    fn parse_request_native_histogram() {
+        let mut request = write_request!(["one" = Histogram], []);
+        request.metadata[0].help = "Latency of requests.".into();
+        request.metadata[0].unit = "seconds".into();
+        request.timeseries.push(proto::TimeSeries {
+            labels: vec![proto::Label {
+                name: METRIC_NAME_LABEL.into(),
+                value: "one".into(),
+            }],
+            histograms: vec![proto::Histogram {
+                count: Some(proto::histogram::Count::CountInt(6)),
+                sum: 10.0,
+                schema: 0,
+                zero_threshold: 0.5,
+                zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                negative_spans: vec![proto::BucketSpan {
+                    offset: 1,
+                    length: 1,
+                }],
+                negative_deltas: vec![1],
+                positive_spans: vec![
+                    proto::BucketSpan {
+                        offset: 0,
+                        length: 2,
+                    },
+                    proto::BucketSpan {
+                        offset: 1,
+                        length: 1,
+                    },
+                ],
+                positive_deltas: vec![2, -1, 0],
+                timestamp: 1395066367700,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let parsed = parse_request(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].help.as_deref(), Some("Latency of requests."));
+        assert_eq!(parsed[0].unit.as_deref(), Some("seconds"));
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(
+                metrics.get_index(0).unwrap(), (
+                    &GroupKey {
+                        timestamp: Some(1395066367700),
+                        labels: labels!(),
+                    },
+                    &HistogramMetric {
+                        buckets: vec![],
+                        count: 6,
+                        sum: 10.0,
+                        created: None,
+                        native: Some(NativeHistogram {
+                            schema: 0,
+                            zero_threshold: 0.5,
+                            zero_count: 1.0,
+                            positive: vec![(0, 2.0), (1, 1.0), (3, 1.0)],
+                            negative: vec![(1, 1.0)],
+                        }),
+                    })
+            );
+        });
+    }
+
+    #[test]
+    fn parse_request_v2() {
+        let request = proto_v2::Request {
+            symbols: [
+                "",
+                "__name__",
+                "one_bucket",
+                "le",
+                "1",
+                "one_count",
+                "one",
+                "Help.",
+                "two",
+            ]
+            .map(Into::into)
+            .to_vec(),
+            timeseries: vec![
+                proto_v2::TimeSeries {
+                    labels_refs: vec![1, 2, 3, 4],
+                    samples: vec![proto_v2::Sample {
+                        value: 3.0,
+                        timestamp: 1395066367700,
+                    }],
+                    metadata: Some(proto_v2::Metadata {
+                        r#type: proto_v2::MetricType::Histogram as i32,
+                        help_ref: 7,
+                        unit_ref: 0,
+                    }),
+                    ..Default::default()
+                },
+                proto_v2::TimeSeries {
+                    labels_refs: vec![1, 5],
+                    samples: vec![proto_v2::Sample {
+                        value: 4.0,
+                        timestamp: 1395066367700,
+                    }],
+                    metadata: Some(proto_v2::Metadata {
+                        r#type: proto_v2::MetricType::Histogram as i32,
+                        help_ref: 7,
+                        unit_ref: 0,
+                    }),
+                    ..Default::default()
+                },
+                proto_v2::TimeSeries {
+                    labels_refs: vec![1, 8],
+                    histograms: vec![proto_v2::Histogram {
+                        count: Some(proto_v2::histogram::Count::CountFloat(2.0)),
+                        sum: 3.0,
+                        positive_spans: vec![proto_v2::BucketSpan {
+                            offset: 1,
+                            length: 1,
+                        }],
+                        positive_counts: vec![2.0],
+                        timestamp: 1395066367800,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let parsed = parse_request_v2(request).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].help.as_deref(), Some("Help."));
+        assert_eq!(parsed[0].unit, None);
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics.get_index(0).unwrap().1, &HistogramMetric {
+                buckets: vec![HistogramBucket { bucket: 1.0, count: 3, exemplar: None }],
+                count: 4,
+                sum: 0.0,
+                created: None,
+                native: None,
+            });
+        });
+        match_group!(parsed[1], "two", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert_eq!(metrics.len(), 1);
+            let histogram = metrics.get_index(0).unwrap().1;
+            assert_eq!(histogram.count, 2);
+            assert_eq!(
+                histogram.native.as_ref().unwrap().positive,
+                vec![(1, 2.0)]
+            );
+        });
+    }
+
+    #[test]
+    fn parse_request_v2_errors() {
+        let request = |labels_refs: Vec<u32>| proto_v2::Request {
+            symbols: vec!["".into(), "__name__".into(), "one".into()],
+            timeseries: vec![proto_v2::TimeSeries {
+                labels_refs,
+                ..Default::default()
+            }],
+        };
+
+        assert_eq!(
+            parse_request_v2(request(vec![1, 3])).unwrap_err(),
+            ParserError::InvalidSymbolReference { reference: 3 }
+        );
+        assert_eq!(
+            parse_request_v2(request(vec![1, 2, 1])).unwrap_err(),
+            ParserError::UnpairedLabelReference
+        );
+        assert_eq!(
+            parse_request_v2(request(vec![2, 2])).unwrap_err(),
+            ParserError::RequestNoNameLabel
+        );
+    }
 }
@@ -1,4 +1,4 @@
-//! Parse a single line of Prometheus text format.
+//! Parse a single line of Prometheus or OpenMetrics text format.
 
 use std::collections::BTreeMap;
 
@@ -100,6 +100,16 @@ pub struct Metric {
     pub labels: BTreeMap<String, String>,
     pub value: f64,
     pub timestamp: Option<i64>,
+    pub exemplar: Option<Exemplar>,
+}
+
+/// An OpenMetrics exemplar, which references a single observation of a sample.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct Exemplar {
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+    /// Timestamp in milliseconds.
+    pub timestamp: Option<i64>,
 }
 
 impl Metric {
@@ -125,6 +135,35 @@ impl Metric {
                 labels,
                 value,
                 timestamp,
+                exemplar: None,
+            },
+        ))
+    }
+
+    /// Parse a single line with the OpenMetrics format
+    ///
+    /// ``` text
+    /// metric_name [
+    ///   "{" label_name "=" `"` label_value `"` { "," label_name "=" `"` label_value `"` } [ "," ] "}"
+    /// ] value [ timestamp ] [ "#" "{" labels "}" value [ timestamp ] ]
+    /// ```
+    ///
+    /// Unlike the Prometheus text format, timestamps are in seconds and can have a fractional part.
+    fn parse_openmetrics(input: &str) -> IResult<Self> {
+        let input = trim_space(input);
+        let (input, name) = parse_name(input)?;
+        let (input, labels) = Self::parse_labels(input)?;
+        let (input, value) = Self::parse_value(input)?;
+        let (input, timestamp) = Self::parse_openmetrics_timestamp(input)?;
+        let (input, exemplar) = Self::parse_exemplar(input)?;
+        Ok((
+            input,
+            Metric {
+                name,
+                labels,
+                value,
+                timestamp,
+                exemplar,
             },
         ))
     }
@@ -156,6 +195,33 @@ impl Metric {
         }))(input)
     }
 
+    /// Seconds with an optional fractional part, converted to milliseconds.
+    fn parse_openmetrics_timestamp(input: &str) -> IResult<Option<i64>> {
+        let input = trim_space(input);
+        opt(map(double, |seconds| (seconds * 1000.0).round() as i64))(input)
+    }
+
+    /// Parse ` # {label_name="value",...} value [ timestamp ]`
+    fn parse_exemplar(input: &str) -> IResult<Option<Exemplar>> {
+        let input = trim_space(input);
+        let input = match char::<_, NomErrorType>('#')(input) {
+            Ok((input, _)) => input,
+            Err(_) => return Ok((input, None)),
+        };
+        let (input, _) = match_char('{')(input)?;
+        let (input, labels) = Self::parse_labels_inner(input)?;
+        let (input, value) = Self::parse_value(input)?;
+        let (input, timestamp) = Self::parse_openmetrics_timestamp(input)?;
+        Ok((
+            input,
+            Some(Exemplar {
+                labels,
+                value,
+                timestamp,
+            }),
+        ))
+    }
+
     fn parse_name_value(input: &str) -> IResult<(String, String)> {
         map(
             tuple((parse_name, match_char('='), Self::parse_escaped_string)),
@@ -290,6 +356,8 @@ impl Header {
     }
 
     /// `# TYPE <metric_name> <metric_type>`
+    ///
+    /// The OpenMetrics types are mapped to the closest Prometheus type.
     fn parse(input: &str) -> IResult<Self> {
         let input = trim_space(input);
         let (input, _) = char('#')(input).map_err(|_: NomError| ErrorKind::ExpectedChar {
@@ -306,10 +374,15 @@ impl Header {
         let (input, _) = Self::space1(input)?;
         let (input, kind) = alt((
             value(MetricKind::Counter, tag("counter")),
+            // Must be tried before `gauge`, which is a prefix of it.
+            value(MetricKind::Histogram, tag("gaugehistogram")),
             value(MetricKind::Gauge, tag("gauge")),
             value(MetricKind::Summary, tag("summary")),
             value(MetricKind::Histogram, tag("histogram")),
             value(MetricKind::Untyped, tag("untyped")),
+            value(MetricKind::Untyped, tag("unknown")),
+            value(MetricKind::Untyped, tag("info")),
+            value(MetricKind::Gauge, tag("stateset")),
         ))(input)
         .map_err(|_: NomError| ErrorKind::InvalidMetricKind {
             input: input.to_owned(),
@@ -329,12 +402,25 @@ pub enum Line {
 impl Line {
     /// Parse a single line. Return `None` if it is a comment or an empty line.
     pub(crate) fn parse(input: &str) -> Result<Option<Self>, ErrorKind> {
+        Self::parse_with(input, Metric::parse)
+    }
+
+    /// Parse a single line of the OpenMetrics format. Return `None` if it is a comment, such as
+    /// `# EOF`, or an empty line.
+    pub(crate) fn parse_openmetrics(input: &str) -> Result<Option<Self>, ErrorKind> {
+        Self::parse_with(input, Metric::parse_openmetrics)
+    }
+
+    fn parse_with(
+        input: &str,
+        parse_metric: fn(&str) -> IResult<Metric>,
+    ) -> Result<Option<Self>, ErrorKind> {
         let input = input.trim();
         if input.is_empty() {
             return Ok(None);
         }
 
-        let metric_error = match Metric::parse(input) {
+        let metric_error = match parse_metric(input) {
             Ok((_, metric)) => {
                 return Ok(Some(Line::Metric(metric)));
             }
@@ -536,6 +622,25 @@ mod test {
                 kind: MetricKind::Untyped,
             }
         );
+
+        let tests = [
+            ("unknown", MetricKind::Untyped),
+            ("info", MetricKind::Untyped),
+            ("stateset", MetricKind::Gauge),
+            ("gaugehistogram", MetricKind::Histogram),
+        ];
+        for (kind_name, kind) in tests {
+            let input = wrap(&format!("# TYPE abc_def {kind_name}"));
+            let (left, r) = Header::parse(&input).unwrap();
+            assert_eq!(left, tail);
+            assert_eq!(
+                r,
+                Header {
+                    metric_name: "abc_def".into(),
+                    kind,
+                }
+            );
+        }
     }
 
     #[test]
@@ -669,6 +774,51 @@ mod test {
         assert_eq!(Metric::parse_timestamp(" -23"), Ok(("", Some(-23))));
     }
 
+    #[test]
+    fn test_parse_openmetrics_timestamp() {
+        assert_eq!(Metric::parse_openmetrics_timestamp(""), Ok(("", None)));
+        assert_eq!(
+            Metric::parse_openmetrics_timestamp("1520879607.789"),
+            Ok(("", Some(1520879607789)))
+        );
+        assert_eq!(
+            Metric::parse_openmetrics_timestamp(" 12 # {}"),
+            Ok((" # {}", Some(12000)))
+        );
+    }
+
+    #[test]
+    fn test_parse_exemplar() {
+        let input = r#"foo_bucket{le="0.5"} 3 # {trace_id="KOO5S4vxi0o"} 0.67 1520879607.789"#;
+        let (_, metric) = Metric::parse_openmetrics(input).unwrap();
+        assert_eq!(metric.name, "foo_bucket");
+        assert_eq!(metric.value, 3.0);
+        assert_eq!(metric.timestamp, None);
+        assert_eq!(
+            metric.exemplar,
+            Some(Exemplar {
+                labels: btreemap! {"trace_id" => "KOO5S4vxi0o"},
+                value: 0.67,
+                timestamp: Some(1520879607789),
+            })
+        );
+
+        let input = r#"foo_total 17.0 1520879607.789 # {} 1.0"#;
+        let (_, metric) = Metric::parse_openmetrics(input).unwrap();
+        assert_eq!(metric.timestamp, Some(1520879607789));
+        assert_eq!(
+            metric.exemplar,
+            Some(Exemplar {
+                labels: BTreeMap::new(),
+                value: 1.0,
+                timestamp: None,
+            })
+        );
+
+        let input = r#"foo_total 17.0 # {trace_id="abc" 1.0"#;
+        assert!(Metric::parse_openmetrics(input).is_err());
+    }
+
     #[test]
     fn test_parse_line() {
         let input = r#"
@@ -711,4 +861,28 @@ mod test {
             "#;
         assert!(input.lines().map(Line::parse).all(|r| r.is_ok()));
     }
+
+    #[test]
+    fn test_parse_openmetrics_line() {
+        let input = r#"
+            # TYPE acme_http_router_request_seconds summary
+            # UNIT acme_http_router_request_seconds seconds
+            # HELP acme_http_router_request_seconds Latency though all of ACME's HTTP request router.
+            acme_http_router_request_seconds_sum{path="/api/v1",method="GET"} 9036.32
+            acme_http_router_request_seconds_count{path="/api/v1",method="GET"} 807283.0
+            acme_http_router_request_seconds_created{path="/api/v1",method="GET"} 1605281325.0
+            # TYPE foo histogram
+            foo_bucket{le="0.01"} 0
+            foo_bucket{le="+Inf"} 17 # {trace_id="oHg5SJYRHA0"} 9.8 1520879607.789
+            foo_count 17
+            foo_sum 324789.3
+            foo_created 1520430000.123
+            # EOF
+            "#;
+        assert!(input
+            .lines()
+            .map(Line::parse_openmetrics)
+            .all(|r| r.is_ok()));
+        assert_eq!(Line::parse_openmetrics("# EOF"), Ok(None));
+    }
 }
@@ -1,8 +1,9 @@
 use anyhow::Context;
 use graphql_client::GraphQLQuery;
+use rustls::ClientConfig;
 use url::Url;
 
-use crate::gql::HealthQueryExt;
+use crate::{gql::HealthQueryExt, TlsConfig};
 
 /// Wrapped `Result` type, that returns deserialized GraphQL response data.
 pub type QueryResult<T> =
@@ -12,12 +13,32 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    authorization: Option<String>,
+    tls: Option<TlsConfig>,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL.
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self {
+            url,
+            authorization: None,
+            tls: None,
+        }
+    }
+
+    /// Sets the value of the `Authorization` header sent with each query, for API servers that
+    /// require credentials.
+    pub fn with_authorization(mut self, authorization: Option<String>) -> Self {
+        self.authorization = authorization;
+        self
+    }
+
+    /// Sets the TLS configuration used for HTTPS URLs, in place of one that only trusts the webpki
+    /// roots.
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+        self
     }
 
     /// Send a health query
@@ -31,11 +52,20 @@ impl Client {
         &self,
         request_body: &graphql_client::QueryBody<T::Variables>,
     ) -> QueryResult<T> {
-        let client = reqwest::Client::new();
+        let client = match &self.tls {
+            Some(tls) => reqwest::Client::builder()
+                .use_preconfigured_tls(ClientConfig::clone(&tls.client_config()))
+                .build()
+                .context("Couldn't build the HTTP client")?,
+            None => reqwest::Client::new(),
+        };
+
+        let mut request = client.post(self.url.clone()).json(request_body);
+        if let Some(authorization) = &self.authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        request
             .send()
             .await
             .with_context(|| {
@@ -24,6 +24,8 @@ pub enum TapEncodingFormat {
     Json,
     Yaml,
     Logfmt,
+    #[value(name = "native_json")]
+    NativeJson,
 }
 
 /// String -> TapEncodingFormat, typically for parsing user input.
@@ -35,6 +37,7 @@ impl std::str::FromStr for TapEncodingFormat {
             "json" => Ok(Self::Json),
             "yaml" => Ok(Self::Yaml),
             "logfmt" => Ok(Self::Logfmt),
+            "native_json" => Ok(Self::NativeJson),
             _ => Err("Invalid encoding format".to_string()),
         }
     }
@@ -49,6 +52,7 @@ impl From<TapEncodingFormat>
             TapEncodingFormat::Json => Self::JSON,
             TapEncodingFormat::Yaml => Self::YAML,
             TapEncodingFormat::Logfmt => Self::LOGFMT,
+            TapEncodingFormat::NativeJson => Self::NATIVE_JSON,
         }
     }
 }
@@ -59,6 +63,7 @@ pub trait TapSubscriptionExt {
         &self,
         outputs_patterns: Vec<String>,
         inputs_patterns: Vec<String>,
+        filter: Option<String>,
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
@@ -71,6 +76,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
         &self,
         outputs_patterns: Vec<String>,
         inputs_patterns: Vec<String>,
+        filter: Option<String>,
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
@@ -79,6 +85,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
             output_events_by_component_id_patterns_subscription::Variables {
                 outputs_patterns,
                 inputs_patterns: Some(inputs_patterns),
+                filter,
                 limit,
                 interval,
                 encoding: encoding.into(),
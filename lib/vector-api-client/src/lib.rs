@@ -17,6 +17,8 @@ mod client;
 pub mod gql;
 mod subscription;
 pub mod test;
+mod tls;
 
 pub use client::*;
 pub use subscription::*;
+pub use tls::TlsConfig;
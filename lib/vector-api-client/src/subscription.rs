@@ -13,10 +13,20 @@ use tokio::sync::{
     mpsc, oneshot,
 };
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{
+    connect_async_tls_with_config,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header::AUTHORIZATION, HeaderValue},
+        Error, Message,
+    },
+    Connector,
+};
 use url::Url;
 use uuid::Uuid;
 
+use crate::TlsConfig;
+
 /// Subscription GraphQL response, returned from an active stream.
 pub type BoxedSubscription<T> = Pin<
     Box<
@@ -173,10 +183,24 @@ impl SubscriptionClient {
 /// Connect to a new WebSocket GraphQL server endpoint, and return a `SubscriptionClient`.
 /// This method will a) connect to a ws(s):// endpoint, and perform the initial handshake, and b)
 /// set up channel forwarding to expose just the returned `Payload`s to the client.
+///
+/// The `authorization` value is sent in the `Authorization` header of the handshake request, for
+/// API servers that require credentials. `wss://` endpoints are connected to with `tls`, or with a
+/// configuration that only trusts the webpki roots if it is unset.
 pub async fn connect_subscription_client(
     url: Url,
-) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
-    let (ws, _) = connect_async(url).await?;
+    authorization: Option<&str>,
+    tls: Option<&TlsConfig>,
+) -> Result<SubscriptionClient, Error> {
+    let mut request = url.into_client_request()?;
+    if let Some(authorization) = authorization {
+        let value = HeaderValue::from_str(authorization)
+            .map_err(|error| Error::HttpFormat(error.into()))?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+
+    let connector = tls.map(|tls| Connector::Rustls(tls.client_config()));
+    let (ws, _) = connect_async_tls_with_config(request, None, false, connector).await?;
     let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
 
     let (send_tx, mut send_rx) = mpsc::unbounded_channel::<Payload>();
@@ -0,0 +1,48 @@
+use std::{fs::File, io::BufReader, path::Path, sync::Arc};
+
+use anyhow::Context;
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore};
+
+/// TLS configuration for connecting to API servers over HTTPS, which trusts additional certificate
+/// authorities on top of the webpki roots.
+#[derive(Clone, Debug)]
+pub struct TlsConfig(Arc<ClientConfig>);
+
+impl TlsConfig {
+    /// Trusts the PEM-encoded certificates in `ca_file`, on top of the webpki roots.
+    pub fn with_ca_file(ca_file: &Path) -> anyhow::Result<Self> {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                anchor.subject,
+                anchor.spki,
+                anchor.name_constraints,
+            )
+        }));
+
+        let file = File::open(ca_file)
+            .with_context(|| format!("Couldn't open CA file {}", ca_file.display()))?;
+        let certificates = rustls_pemfile::certs(&mut BufReader::new(file))
+            .with_context(|| format!("Couldn't read CA file {}", ca_file.display()))?;
+        anyhow::ensure!(
+            !certificates.is_empty(),
+            "No certificates found in CA file {}",
+            ca_file.display()
+        );
+        for certificate in certificates {
+            roots
+                .add(&Certificate(certificate))
+                .with_context(|| format!("Invalid certificate in CA file {}", ca_file.display()))?;
+        }
+
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(Self(Arc::new(config)))
+    }
+
+    pub(crate) fn client_config(&self) -> Arc<ClientConfig> {
+        Arc::clone(&self.0)
+    }
+}
@@ -3,7 +3,14 @@
 //! as it flows through transforms, being duplicated and merged, and
 //! then report its status when the last copy is delivered or dropped.
 
-use std::{cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
+use std::{
+    cmp,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    task::Poll,
+};
 
 use crossbeam_utils::atomic::AtomicCell;
 use futures::future::FutureExt;
@@ -86,6 +93,14 @@ impl EventFinalizers {
         }
     }
 
+    /// Marks all event finalizers in the collection as rejected, recording the details of the
+    /// rejection in their underlying batches.
+    pub fn reject(&self, rejection: &Rejection) {
+        for finalizer in &self.0 {
+            finalizer.reject(rejection);
+        }
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -139,6 +154,13 @@ impl EventFinalizer {
             .unwrap_or_else(|_| unreachable!());
     }
 
+    /// Marks the event finalizer as rejected, recording the details of the rejection in the
+    /// underlying batch.
+    pub fn reject(&self, rejection: &Rejection) {
+        self.update_status(EventStatus::Rejected);
+        self.batch.record_rejection(rejection);
+    }
+
     /// Updates the underlying batch status with the status of the event finalizer.
     ///
     /// In doing so, the event finalizer is marked as "recorded", which prevents any further updates to it.
@@ -160,14 +182,14 @@ impl Drop for EventFinalizer {
 /// A convenience newtype wrapper for the one-shot receiver for an
 /// individual batch status.
 #[pin_project::pin_project]
-pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+pub struct BatchStatusReceiver(oneshot::Receiver<(BatchStatus, Option<Rejection>)>);
 
 impl Future for BatchStatusReceiver {
     type Output = BatchStatus;
     fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
         match self.0.poll_unpin(ctx) {
             Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(status)) => Poll::Ready(status),
+            Poll::Ready(Ok((status, _))) => Poll::Ready(status),
             Poll::Ready(Err(error)) => {
                 error!(%error, "Batch status receiver dropped before sending.");
                 Poll::Ready(BatchStatus::Errored)
@@ -184,7 +206,19 @@ impl BatchStatusReceiver {
     /// - `TryRecvError::Empty` if no value has been sent yet.
     /// - `TryRecvError::Closed` if the sender has dropped without sending a value.
     pub fn try_recv(&mut self) -> Result<BatchStatus, oneshot::error::TryRecvError> {
-        self.0.try_recv()
+        self.0.try_recv().map(|(status, _)| status)
+    }
+
+    /// Waits for the batch status, along with the details of the first rejection recorded for
+    /// the batch, if any.
+    pub async fn with_rejection(self) -> (BatchStatus, Option<Rejection>) {
+        match self.0.await {
+            Ok(result) => result,
+            Err(error) => {
+                error!(%error, "Batch status receiver dropped before sending.");
+                (BatchStatus::Errored, None)
+            }
+        }
     }
 }
 
@@ -201,6 +235,7 @@ impl BatchNotifier {
         let (sender, receiver) = oneshot::channel();
         let notifier = OwnedBatchNotifier {
             status: AtomicCell::new(BatchStatus::Delivered),
+            rejection: OnceLock::new(),
             notifier: Some(sender),
         };
         (Self(Arc::new(notifier)), BatchStatusReceiver(receiver))
@@ -250,13 +285,19 @@ impl BatchNotifier {
                 .unwrap_or_else(|_| unreachable!());
         }
     }
+
+    /// Records the details of a rejection. Only the first rejection of a batch is kept.
+    fn record_rejection(&self, rejection: &Rejection) {
+        self.0.rejection.get_or_init(|| rejection.clone());
+    }
 }
 
 /// The non-shared data underlying the shared `BatchNotifier`
 #[derive(Debug)]
 pub struct OwnedBatchNotifier {
     status: AtomicCell<BatchStatus>,
-    notifier: Option<oneshot::Sender<BatchStatus>>,
+    rejection: OnceLock<Rejection>,
+    notifier: Option<oneshot::Sender<(BatchStatus, Option<Rejection>)>>,
 }
 
 impl OwnedBatchNotifier {
@@ -266,9 +307,36 @@ impl OwnedBatchNotifier {
             let status = self.status.load();
             // Ignore the error case, as it will happen during normal
             // source shutdown and we can't detect that here.
-            _ = notifier.send(status);
+            _ = notifier.send((status, self.rejection.take()));
+        }
+    }
+}
+
+/// The details of why a sink permanently rejected an event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rejection {
+    /// A description of the error that caused the rejection.
+    pub reason: String,
+    /// The HTTP status code returned by the downstream service, if any.
+    pub http_status: Option<u16>,
+}
+
+impl Rejection {
+    /// Creates a new `Rejection` with the given reason.
+    #[must_use]
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            http_status: None,
         }
     }
+
+    /// Sets the HTTP status code returned by the downstream service.
+    #[must_use]
+    pub fn with_http_status(mut self, http_status: u16) -> Self {
+        self.http_status = Some(http_status);
+        self
+    }
 }
 
 impl Drop for OwnedBatchNotifier {
@@ -507,6 +575,37 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[tokio::test]
+    async fn records_first_rejection() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event1 = EventFinalizers::new(EventFinalizer::new(batch.clone()));
+        let event2 = EventFinalizers::new(EventFinalizer::new(batch));
+
+        event1.reject(&Rejection::new("bad request").with_http_status(400));
+        event2.reject(&Rejection::new("conflict").with_http_status(409));
+        drop(event1);
+        drop(event2);
+
+        assert_eq!(
+            receiver.with_rejection().await,
+            (
+                BatchStatus::Rejected,
+                Some(Rejection::new("bad request").with_http_status(400))
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn no_rejection_when_delivered() {
+        let (fin, receiver) = make_finalizer();
+        fin.update_status(EventStatus::Delivered);
+        drop(fin);
+        assert_eq!(
+            receiver.with_rejection().await,
+            (BatchStatus::Delivered, None)
+        );
+    }
+
     fn make_finalizer() -> (EventFinalizers, BatchStatusReceiver) {
         let (batch, receiver) = BatchNotifier::new_with_receiver();
         let finalizer = EventFinalizers::new(EventFinalizer::new(batch));
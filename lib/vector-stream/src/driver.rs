@@ -10,7 +10,7 @@ use vector_common::internal_event::{
     RegisteredEventCache, SharedString, TaggedEventsSent,
 };
 use vector_common::request_metadata::{GroupedCountByteSize, MetaDescriptive};
-use vector_core::event::{EventFinalizers, EventStatus, Finalizable};
+use vector_core::event::{EventFinalizers, EventStatus, Finalizable, Rejection};
 
 use super::FuturesUnorderedCount;
 
@@ -24,6 +24,13 @@ pub trait DriverResponse {
     fn bytes_sent(&self) -> Option<usize> {
         None
     }
+
+    /// Return the details of why the request was rejected, when the event status is `Rejected`.
+    ///
+    /// These are attached to the rejected events so they can be routed to a dead-letter output.
+    fn rejection(&self) -> Option<Rejection> {
+        None
+    }
 }
 
 /// Drives the interaction between a stream of items and a service which processes them
@@ -205,8 +212,8 @@ where
     ) {
         match result {
             Err(error) => {
+                finalizers.reject(&Rejection::new(format!("{error:?}")));
                 Self::emit_call_error(Some(error), request_id, event_count);
-                finalizers.update_status(EventStatus::Rejected);
             }
             Ok(response) => {
                 trace!(message = "Service call succeeded.", request_id);
@@ -223,7 +230,9 @@ where
                 // This condition occurs specifically when the `HttpBatchService::call()` is called *within* the `Service::call()`
                 } else if response.event_status() == EventStatus::Rejected {
                     Self::emit_call_error(None, request_id, event_count);
-                    finalizers.update_status(EventStatus::Rejected);
+                    finalizers.reject(&response.rejection().unwrap_or_else(|| {
+                        Rejection::new("Request was rejected by the downstream service.")
+                    }));
                 }
             }
         };
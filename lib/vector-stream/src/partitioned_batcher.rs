@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     hash::{BuildHasherDefault, Hash},
     num::NonZeroUsize,
     pin::Pin,
@@ -194,6 +194,11 @@ where
     /// preferentially flushed prior to consuming any new items from the
     /// underlying stream.
     closed_batches: Vec<(Prt::Key, B)>,
+    /// The maximum number of live batches, if limited.
+    max_partitions: Option<NonZeroUsize>,
+    /// The keys of the live batches, oldest first. Only tracked when the number of live batches is
+    /// limited.
+    partitions: VecDeque<Prt::Key>,
     /// The queue of pending batch expirations
     timer: KT,
     /// The partitioner for this `Batcher`
@@ -218,6 +223,8 @@ where
             state: settings,
             batches: HashMap::default(),
             closed_batches: Vec::default(),
+            max_partitions: None,
+            partitions: VecDeque::default(),
             timer: ExpirationQueue::new(timeout),
             partitioner,
             stream: stream.fuse(),
@@ -225,6 +232,21 @@ where
     }
 }
 
+impl<St, Prt, KT, C, F, B> PartitionedBatcher<St, Prt, KT, C, F, B>
+where
+    Prt: Partitioner,
+{
+    /// Limits the number of partitions with a live batch.
+    ///
+    /// When an item for a new partition is received while this limit is reached, the batch of the
+    /// oldest partition is flushed to make room for the new one.
+    #[must_use]
+    pub fn with_max_partitions(mut self, max_partitions: NonZeroUsize) -> Self {
+        self.max_partitions = Some(max_partitions);
+        self
+    }
+}
+
 #[cfg(test)]
 impl<St, Prt, KT, C, F, B> PartitionedBatcher<St, Prt, KT, C, F, B>
 where
@@ -240,6 +262,8 @@ where
             state: settings,
             batches: HashMap::default(),
             closed_batches: Vec::default(),
+            max_partitions: None,
+            partitions: VecDeque::default(),
             timer,
             partitioner,
             stream: stream.fuse(),
@@ -279,6 +303,7 @@ where
                             .batches
                             .remove(&item_key)
                             .expect("batch should exist if it is set to expire");
+                        this.partitions.retain(|key| key != &item_key);
                         this.closed_batches.push((item_key, batch.take_batch()));
                     }
                 },
@@ -290,6 +315,7 @@ where
                     // we finish.
                     if !this.batches.is_empty() {
                         this.timer.clear();
+                        this.partitions.clear();
                         this.closed_batches.extend(
                             this.batches
                                 .drain()
@@ -306,6 +332,22 @@ where
                     let batch = if let Some(batch) = this.batches.get_mut(&item_key) {
                         batch
                     } else {
+                        if let Some(max_partitions) = this.max_partitions {
+                            if this.batches.len() >= max_partitions.get() {
+                                // Flush the batch of the oldest partition to make room for
+                                // this one.
+                                if let Some(oldest_key) = this.partitions.pop_front() {
+                                    let mut batch = this
+                                        .batches
+                                        .remove(&oldest_key)
+                                        .expect("batch should exist if its partition is tracked");
+                                    this.timer.remove(&oldest_key);
+                                    this.closed_batches.push((oldest_key, batch.take_batch()));
+                                }
+                            }
+                            this.partitions.push_back(item_key.clone());
+                        }
+
                         let batch = (this.state)();
                         this.batches.insert(item_key.clone(), batch);
                         this.timer.insert(item_key.clone());
@@ -337,6 +379,7 @@ where
                             .push((item_key.clone(), batch.take_batch()));
                         this.batches.remove(&item_key);
                         this.timer.remove(&item_key);
+                        this.partitions.retain(|key| key != &item_key);
                     }
                 }
             }
@@ -639,6 +682,37 @@ mod test {
         }
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn max_partitions_flushes_oldest_partition() {
+        let partitioner = TestPartitioner {
+            key_space: NonZeroU8::new(3).unwrap(),
+        };
+        let batch_settings = BatcherSettings::new(
+            Duration::from_secs(60),
+            NonZeroUsize::new(1024).unwrap(),
+            NonZeroUsize::new(16).unwrap(),
+        );
+        let mut stream = stream::iter([0_u64, 1, 3, 2, 6]);
+        let mut batcher = PartitionedBatcher::new(&mut stream, partitioner, move || {
+            batch_settings.as_byte_size_config()
+        })
+        .with_max_partitions(NonZeroUsize::new(2).unwrap());
+        pin!(batcher);
+
+        // The third partition flushes the batch of the first one, then the batch of the second
+        // one is flushed when the first partition is seen again.
+        let mut batches = Vec::new();
+        while let Poll::Ready(Some(batch)) = single_poll(|cx| batcher.as_mut().poll_next(cx)) {
+            batches.push(batch);
+        }
+
+        assert_eq!(batches[0], (0, vec![0, 3]));
+        assert_eq!(batches[1], (1, vec![1]));
+        assert_eq!(batches.len(), 4);
+        assert!(batches[2..].contains(&(2, vec![2])));
+        assert!(batches[2..].contains(&(0, vec![6])));
+    }
+
     #[tokio::test(start_paused = true)]
     #[allow(clippy::semicolon_if_nothing_returned)] // https://github.com/rust-lang/rust-clippy/issues/7438
     async fn expiration_queue_impl_keyed_timer() {
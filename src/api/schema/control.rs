@@ -0,0 +1,89 @@
+use async_graphql::{Context, Object, SimpleObject};
+use tokio::sync::oneshot;
+
+use crate::{
+    config::ComponentKey,
+    topology::{ControlCommand, ControlRequest, ControlTx},
+};
+
+/// Marks a request as authorized to run mutations, by way of the configured mutation token.
+#[derive(Clone, Copy, Debug)]
+pub struct ControlAccess;
+
+#[derive(SimpleObject)]
+pub struct ControlResult {
+    /// Whether the command was applied
+    success: bool,
+    /// The errors that made the command fail, such as configuration validation errors
+    errors: Vec<String>,
+}
+
+impl From<Result<(), Vec<String>>> for ControlResult {
+    fn from(result: Result<(), Vec<String>>) -> Self {
+        match result {
+            Ok(()) => Self {
+                success: true,
+                errors: Vec::new(),
+            },
+            Err(errors) => Self {
+                success: false,
+                errors,
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ControlMutation;
+
+#[Object]
+impl ControlMutation {
+    /// Reloads the configuration from disk. If the new configuration fails to load, the previous
+    /// one is kept
+    async fn reload_config(&self, ctx: &Context<'_>) -> async_graphql::Result<ControlResult> {
+        send(ctx, ControlCommand::Reload).await
+    }
+
+    /// Pauses a source, which stops pulling new events until it is resumed
+    async fn pause_source(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<ControlResult> {
+        send(ctx, ControlCommand::PauseSource(ComponentKey::from(id))).await
+    }
+
+    /// Resumes a paused source
+    async fn resume_source(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<ControlResult> {
+        send(ctx, ControlCommand::ResumeSource(ComponentKey::from(id))).await
+    }
+
+    /// Removes a sink once it has processed the events in its buffer. The sink comes back on the
+    /// next reload, unless it is also removed from the configuration
+    async fn drain_sink(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<ControlResult> {
+        send(ctx, ControlCommand::DrainSink(ComponentKey::from(id))).await
+    }
+}
+
+async fn send(ctx: &Context<'_>, command: ControlCommand) -> async_graphql::Result<ControlResult> {
+    if ctx.data_opt::<ControlAccess>().is_none() {
+        return Err("Unauthorized: mutations require the `api.mutation_token` bearer token".into());
+    }
+
+    let control_tx = ctx.data::<ControlTx>()?;
+    let (reply, result) = oneshot::channel();
+    control_tx
+        .send(ControlRequest { command, reply })
+        .map_err(|_| "Vector is shutting down")?;
+
+    let result = result.await.map_err(|_| "Vector is shutting down")?;
+    Ok(result.into())
+}
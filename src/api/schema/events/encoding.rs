@@ -6,4 +6,5 @@ pub(crate) enum EventEncodingType {
     Json,
     Yaml,
     Logfmt,
+    NativeJson,
 }
@@ -65,6 +65,10 @@ impl Log {
                 .expect("YAML serialization of log event failed. Please report."),
             EventEncodingType::Logfmt => encode_logfmt::encode_value(self.event.value())
                 .expect("logfmt serialization of log event failed. Please report."),
+            EventEncodingType::NativeJson => {
+                serde_json::to_string(&event::Event::from(self.event.clone()))
+                    .expect("native JSON serialization of log event failed. Please report.")
+            }
         }
     }
 
@@ -134,6 +134,10 @@ impl Metric {
                     _ => panic!("logfmt serialization of metric event failed: metric converted to unexpected serde Value. Please report."),
                 }
             }
+            EventEncodingType::NativeJson => {
+                serde_json::to_string(&event::Event::from(self.event.clone()))
+                    .expect("native JSON serialization of metric event failed. Please report.")
+            }
         }
     }
 }
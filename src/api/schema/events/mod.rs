@@ -15,14 +15,20 @@ use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::tap::TapController,
+    conditions::{AnyCondition, Condition},
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
-/// flowing into (for_inputs) or out of (for_outputs) specified components
+/// flowing into (for_inputs) or out of (for_outputs) specified components.
+/// An optional filter condition restricts the tapped events to those matching it
 #[derive(Debug)]
 pub struct TapPatterns {
     pub for_outputs: HashSet<String>,
     pub for_inputs: HashSet<String>,
+    pub filter: Option<Condition>,
 }
 
 impl TapPatterns {
@@ -30,9 +36,16 @@ impl TapPatterns {
         Self {
             for_outputs,
             for_inputs,
+            filter: None,
         }
     }
 
+    /// Only tap events matching the provided condition
+    pub fn with_filter(mut self, filter: Condition) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// Get all user-specified patterns
     pub fn all_patterns(&self) -> HashSet<String> {
         self.for_outputs
@@ -48,23 +61,39 @@ pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
-    /// A stream of events emitted from matched component ID patterns
+    /// A stream of events emitted from matched component ID patterns. When a VRL `filter`
+    /// condition is provided, only events for which it evaluates to `true` are returned
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
         outputs_patterns: Vec<String>,
         inputs_patterns: Option<Vec<String>>,
+        filter: Option<String>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
-        let patterns = TapPatterns {
-            for_outputs: outputs_patterns.into_iter().collect(),
-            for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
-        };
+        let mut patterns = TapPatterns::new(
+            outputs_patterns.into_iter().collect(),
+            inputs_patterns.unwrap_or_default().into_iter().collect(),
+        );
+        if let Some(filter) = filter {
+            // The filter is compiled once, up front, so that invalid programs are reported to
+            // the client instead of silently dropping every event.
+            let condition = AnyCondition::String(filter)
+                .build(&Default::default())
+                .map_err(|error| format!("Invalid tap filter: {}", error))?;
+            patterns = patterns.with_filter(condition);
+        }
+
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            interval as u64,
+            limit as usize,
+        ))
     }
 }
 
@@ -44,6 +44,10 @@ impl Trace {
                 .expect("YAML serialization of log event failed. Please report."),
             EventEncodingType::Logfmt => encode_logfmt::encode_map(self.event.as_map())
                 .expect("logfmt serialization of log event failed. Please report."),
+            EventEncodingType::NativeJson => {
+                serde_json::to_string(&event::Event::from(self.event.clone()))
+                    .expect("native JSON serialization of trace event failed. Please report.")
+            }
         }
     }
 
@@ -9,19 +9,28 @@ use async_graphql::{
     Data, Request, Schema,
 };
 use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
-use hyper::{server::conn::AddrIncoming, service::make_service_fn, Server as HyperServer};
+use hyper::{service::make_service_fn, Server as HyperServer};
+use subtle::ConstantTimeEq;
+use tokio::net::TcpStream;
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 use tower::ServiceBuilder;
 use tracing::Span;
-use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
+use warp::{
+    filters::BoxedFilter,
+    http::{Response, StatusCode},
+    reject::Reject,
+    ws::Ws,
+    Filter, Rejection, Reply,
+};
 
-use super::{handler, schema, ShutdownTx};
+use super::{handler, schema, schema::control::ControlAccess, ShutdownTx};
 use crate::{
     config::{self, api},
-    http::build_http_trace_layer,
+    http::{build_http_trace_layer, Auth},
     internal_events::{SocketBindError, SocketMode},
-    topology,
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings},
+    topology::{self, ControlTx},
 };
 
 pub struct Server {
@@ -36,25 +45,41 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        control_tx: ControlTx,
         handle: &Handle,
     ) -> crate::Result<Self> {
-        let routes = make_routes(config.api, watch_rx, running);
+        let credentials = Credentials::new(&config.api)?;
+        let tls = MaybeTlsSettings::from_config(&config.api.tls, true)?;
+        let routes = make_routes(
+            config.api.clone(),
+            watch_rx,
+            running,
+            control_tx,
+            credentials,
+        );
 
         let (_shutdown, rx) = oneshot::channel();
         // warp uses `tokio::spawn` and so needs us to enter the runtime context.
         let _guard = handle.enter();
 
+        // The listener is bound right away, so that binding errors are returned to the caller.
         let addr = config.api.address.expect("No socket address");
-        let incoming = AddrIncoming::bind(&addr).map_err(|error| {
-            emit!(SocketBindError {
-                mode: SocketMode::Tcp,
-                error: &error,
-            });
-            error
-        })?;
+        let listener = std::net::TcpListener::bind(addr)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                tokio::net::TcpListener::from_std(listener)
+            })
+            .map_err(|error| {
+                emit!(SocketBindError {
+                    mode: SocketMode::Tcp,
+                    error: &error,
+                });
+                error
+            })?;
+        let listener = tls.wrap_listener(listener)?;
 
         let span = Span::current();
-        let make_svc = make_service_fn(move |_conn| {
+        let make_svc = make_service_fn(move |_conn: &MaybeTlsIncomingStream<TcpStream>| {
             let svc = ServiceBuilder::new()
                 .layer(build_http_trace_layer(span.clone()))
                 .service(warp::service(routes.clone()));
@@ -62,7 +87,7 @@ impl Server {
         });
 
         let server = async move {
-            HyperServer::builder(incoming)
+            HyperServer::builder(hyper::server::accept::from_stream(listener.accept_stream()))
                 .serve(make_svc)
                 .with_graceful_shutdown(async {
                     rx.await.ok();
@@ -99,6 +124,8 @@ fn make_routes(
     api: api::Options,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    control_tx: ControlTx,
+    credentials: Credentials,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
@@ -107,6 +134,22 @@ fn make_routes(
         .and(with_shared(running))
         .and_then(handler::health);
 
+    // Rejects GraphQL requests without the required credentials, and extracts whether the
+    // request is allowed to run mutations.
+    let challenge = credentials.challenge;
+    let authorized = warp::header::optional::<String>("authorization").and_then(
+        move |authorization: Option<String>| {
+            let access = credentials.access(authorization.as_deref());
+            async move {
+                match access {
+                    Access::Denied => Err(warp::reject::custom(Unauthorized)),
+                    Access::Query => Ok(false),
+                    Access::Control => Ok(true),
+                }
+            }
+        },
+    );
+
     // 404.
     let not_found_graphql = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
@@ -115,16 +158,23 @@ fn make_routes(
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
     // topology changes.
-    let graphql_subscription_handler =
-        warp::ws()
-            .and(graphql_protocol())
-            .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+    let subscription_control_tx = control_tx.clone();
+    let graphql_subscription_handler = warp::ws()
+        .and(graphql_protocol())
+        .and(authorized.clone())
+        .map(
+            move |ws: Ws, protocol: WebSocketProtocols, authorized: bool| {
+                let schema = schema::build_schema()
+                    .data(subscription_control_tx.clone())
+                    .finish();
                 let watch_tx = watch_tx.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
                     let mut data = Data::default();
                     data.insert(watch_tx);
+                    if authorized {
+                        data.insert(ControlAccess);
+                    }
 
                     GraphQLWebSocket::new(socket, schema, protocol)
                         .with_data(data)
@@ -136,20 +186,30 @@ fn make_routes(
                     "Sec-WebSocket-Protocol",
                     protocol.sec_websocket_protocol(),
                 )
-            });
+            },
+        );
 
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
     // All other queries will fall back to the default HTTP handler.
     let graphql_handler = if api.graphql {
         warp::path("graphql")
-            .and(graphql_subscription_handler.or(
-                async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-                    |(schema, request): (Schema<_, _, _>, Request)| async move {
+            .and(
+                graphql_subscription_handler.or(async_graphql_warp::graphql(
+                    schema::build_schema().data(control_tx).finish(),
+                )
+                .and(authorized)
+                .and_then(
+                    |(schema, request): (Schema<_, _, _>, Request), authorized: bool| async move {
+                        let request = if authorized {
+                            request.data(ControlAccess)
+                        } else {
+                            request
+                        };
                         Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
                     },
-                ),
-            ))
+                )),
+            )
             .boxed()
     } else {
         not_found_graphql.boxed()
@@ -176,6 +236,17 @@ fn make_routes(
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
+        .recover(move |rejection: Rejection| async move {
+            if rejection.find::<Unauthorized>().is_some() {
+                Ok(warp::reply::with_header(
+                    warp::reply::with_status("Unauthorized", StatusCode::UNAUTHORIZED),
+                    "WWW-Authenticate",
+                    challenge,
+                ))
+            } else {
+                Err(rejection)
+            }
+        })
         .with(
             warp::cors()
                 .allow_any_origin()
@@ -188,6 +259,7 @@ fn make_routes(
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
                     "Content-Type",
+                    "Authorization",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
                     "Host",
@@ -199,8 +271,125 @@ fn make_routes(
         .boxed()
 }
 
+#[derive(Debug)]
+struct Unauthorized;
+
+impl Reject for Unauthorized {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Access {
+    Denied,
+    Query,
+    Control,
+}
+
+/// The `Authorization` header values accepted by the GraphQL endpoint.
+#[derive(Clone, Debug)]
+struct Credentials {
+    /// Allows queries and subscriptions. When unset, they don't require credentials.
+    query: Option<String>,
+    /// Allows mutations as well. When unset, mutations are disabled.
+    control: Option<String>,
+    /// The `WWW-Authenticate` challenge sent along with rejected requests.
+    challenge: &'static str,
+}
+
+impl Credentials {
+    fn new(api: &api::Options) -> crate::Result<Self> {
+        let query = api
+            .auth
+            .as_ref()
+            .map(|auth| auth.header_value().ok_or("Invalid `api.auth` credentials."))
+            .transpose()?;
+        let control = api
+            .mutation_token
+            .as_ref()
+            .map(|token| {
+                let auth = Auth::Bearer {
+                    token: token.clone(),
+                };
+                auth.header_value().ok_or("Invalid `api.mutation_token`.")
+            })
+            .transpose()?;
+        let challenge = match api.auth {
+            Some(Auth::Basic { .. }) => r#"Basic realm="Vector API""#,
+            _ => "Bearer",
+        };
+
+        Ok(Self {
+            query,
+            control,
+            challenge,
+        })
+    }
+
+    /// Checks the `Authorization` header of a request. The mutation token also gives access to
+    /// queries and subscriptions.
+    fn access(&self, authorization: Option<&str>) -> Access {
+        // Compared in constant time, so that the timing of rejections doesn't leak the secrets.
+        let matches = |expected: &Option<String>| match (expected, authorization) {
+            (Some(expected), Some(authorization)) => {
+                bool::from(expected.as_bytes().ct_eq(authorization.as_bytes()))
+            }
+            _ => false,
+        };
+
+        if matches(&self.control) {
+            Access::Control
+        } else if self.query.is_none() || matches(&self.query) {
+            Access::Query
+        } else {
+            Access::Denied
+        }
+    }
+}
+
 fn with_shared(
     shared: Arc<AtomicBool>,
 ) -> impl Filter<Extract = (Arc<AtomicBool>,), Error = Infallible> + Clone {
     warp::any().map(move || Arc::<AtomicBool>::clone(&shared))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials(auth: Option<Auth>, mutation_token: Option<&str>) -> Credentials {
+        Credentials::new(&api::Options {
+            auth,
+            mutation_token: mutation_token.map(|token| token.to_string().into()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn authorizes_bearer_token() {
+        let credentials = credentials(None, Some("secret"));
+
+        assert_eq!(credentials.access(Some("Bearer secret")), Access::Control);
+        assert_eq!(credentials.access(Some("Bearer other")), Access::Query);
+        assert_eq!(credentials.access(Some("secret")), Access::Query);
+        assert_eq!(credentials.access(None), Access::Query);
+
+        let credentials = self::credentials(None, None);
+        assert_eq!(credentials.access(Some("Bearer secret")), Access::Query);
+    }
+
+    #[test]
+    fn authenticates_requests() {
+        let auth = Auth::Basic {
+            user: "user".to_string(),
+            password: "password".to_string().into(),
+        };
+        let credentials = credentials(Some(auth), Some("secret"));
+
+        assert_eq!(
+            credentials.access(Some("Basic dXNlcjpwYXNzd29yZA==")),
+            Access::Query
+        );
+        assert_eq!(credentials.access(Some("Bearer secret")), Access::Control);
+        assert_eq!(credentials.access(Some("Basic b3RoZXI=")), Access::Denied);
+        assert_eq!(credentials.access(None), Access::Denied);
+    }
+}
@@ -21,8 +21,9 @@ use super::{
     ShutdownRx, ShutdownTx,
 };
 use crate::{
+    conditions::Condition,
     config::ComponentKey,
-    event::{EventArray, LogArray, MetricArray, TraceArray},
+    event::{Event, EventArray, EventContainer, LogArray, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
 };
 
@@ -127,18 +128,67 @@ impl TapPayload {
 }
 
 /// A `TapTransformer` transforms raw events and ships them to the global tap receiver.
+///
+/// Events that don't match the optional filter condition are dropped here, before they
+/// reach the client.
 #[derive(Clone)]
 pub struct TapTransformer {
     tap_tx: TapSender,
     output: TapOutput,
+    filter: Option<Condition>,
 }
 
 impl TapTransformer {
-    pub const fn new(tap_tx: TapSender, output: TapOutput) -> Self {
-        Self { tap_tx, output }
+    pub const fn new(tap_tx: TapSender, output: TapOutput, filter: Option<Condition>) -> Self {
+        Self {
+            tap_tx,
+            output,
+            filter,
+        }
+    }
+
+    /// Retains only the events matching the filter condition, if one was provided.
+    fn filter(&self, events: EventArray) -> EventArray {
+        let Some(filter) = &self.filter else {
+            return events;
+        };
+
+        match events {
+            EventArray::Logs(logs) => EventArray::Logs(
+                logs.into_iter()
+                    .filter_map(|log| {
+                        let (matched, event) = filter.check(Event::Log(log));
+                        matched.then(|| event.into_log())
+                    })
+                    .collect(),
+            ),
+            EventArray::Metrics(metrics) => EventArray::Metrics(
+                metrics
+                    .into_iter()
+                    .filter_map(|metric| {
+                        let (matched, event) = filter.check(Event::Metric(metric));
+                        matched.then(|| event.into_metric())
+                    })
+                    .collect(),
+            ),
+            EventArray::Traces(traces) => EventArray::Traces(
+                traces
+                    .into_iter()
+                    .filter_map(|trace| {
+                        let (matched, event) = filter.check(Event::Trace(trace));
+                        matched.then(|| event.into_trace())
+                    })
+                    .collect(),
+            ),
+        }
     }
 
     pub fn try_send(&mut self, events: EventArray) {
+        let events = self.filter(events);
+        if events.is_empty() {
+            return;
+        }
+
         let payload = match events {
             EventArray::Logs(logs) => TapPayload::Log(self.output.clone(), logs),
             EventArray::Metrics(metrics) => TapPayload::Metric(self.output.clone(), metrics),
@@ -249,7 +299,7 @@ async fn tap_handler(
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
 ) {
-    debug!(message = "Started tap.", outputs_patterns = ?patterns.for_outputs, inputs_patterns = ?patterns.for_inputs);
+    debug!(message = "Started tap.", outputs_patterns = ?patterns.for_outputs, inputs_patterns = ?patterns.for_inputs, filter = ?patterns.filter);
 
     // Sinks register for the current tap. Contains the id of the matched component, and
     // a shutdown trigger for sending a remove control message when matching sinks change.
@@ -329,7 +379,7 @@ async fn tap_handler(
                             // wrap each event payload with the necessary metadata before forwarding
                             // it to our global tap receiver.
                             let (tap_buffer_tx, mut tap_buffer_rx) = TopologyBuilder::standalone_memory(TAP_BUFFER_SIZE, WhenFull::DropNewest, &Span::current()).await;
-                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone());
+                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone(), patterns.filter.clone());
 
                             tokio::spawn(async move {
                                 while let Some(events) = tap_buffer_rx.next().await {
@@ -469,6 +519,43 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    /// Events not matching the tap filter shouldn't be sent to the client.
+    async fn filters_events() {
+        let (tap_tx, mut tap_rx) = tokio_mpsc::channel(10);
+        let output = TapOutput {
+            output_id: OutputId::from(&ComponentKey::from("in")),
+            component_kind: "source",
+            component_type: "demo".to_string(),
+        };
+        let filter = crate::conditions::AnyCondition::String(".status >= 500".to_string())
+            .build(&Default::default())
+            .unwrap();
+        let mut transformer = TapTransformer::new(tap_tx, output, Some(filter));
+
+        let log = |status: i64| {
+            let mut log = LogEvent::default();
+            log.insert("status", status);
+            log
+        };
+
+        // Arrays with no matching events are dropped entirely.
+        transformer.try_send(vec![log(200), log(404)].into());
+        transformer.try_send(vec![log(200), log(503), log(500)].into());
+
+        match tap_rx.recv().await {
+            Some(TapPayload::Log(_, logs)) => {
+                let statuses = logs
+                    .iter()
+                    .map(|log| log.get("status").unwrap().as_integer().unwrap())
+                    .collect::<Vec<_>>();
+                assert_eq!(statuses, vec![503, 500]);
+            }
+            _ => panic!("unexpected payload"),
+        }
+        assert!(tap_rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     /// A tap sink should match a pattern, receive the correct notifications,
     /// and receive events
@@ -0,0 +1,67 @@
+//! Options shared by the subcommands that connect to the GraphQL API server.
+
+use std::path::Path;
+
+use clap::Args;
+use vector_lib::api_client::TlsConfig;
+
+use crate::http::Auth;
+
+/// Options for connecting to the GraphQL API server
+#[derive(Args, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+pub struct ConnectionOpts {
+    /// Bearer token for a GraphQL API server that requires authentication
+    #[arg(
+        long,
+        env = "VECTOR_API_TOKEN",
+        hide_env_values = true,
+        conflicts_with = "user"
+    )]
+    token: Option<String>,
+
+    /// Username for a GraphQL API server that requires basic authentication
+    #[arg(long, env = "VECTOR_API_USER", requires = "password")]
+    user: Option<String>,
+
+    /// Password for a GraphQL API server that requires basic authentication
+    #[arg(
+        long,
+        env = "VECTOR_API_PASSWORD",
+        hide_env_values = true,
+        requires = "user"
+    )]
+    password: Option<String>,
+
+    /// PEM file with the certificate authorities to trust for an HTTPS GraphQL API server, on top
+    /// of the webpki roots
+    #[arg(long = "ca-file", value_name = "PATH", value_parser = load_ca_file)]
+    tls: Option<TlsConfig>,
+}
+
+impl ConnectionOpts {
+    /// Value of the `Authorization` header sent to the GraphQL API server, if credentials
+    /// were provided.
+    pub fn authorization(&self) -> Option<String> {
+        let auth = match (&self.token, &self.user) {
+            (Some(token), _) => Auth::Bearer {
+                token: token.clone().into(),
+            },
+            (None, Some(user)) => Auth::Basic {
+                user: user.clone(),
+                password: self.password.clone().unwrap_or_default().into(),
+            },
+            (None, None) => return None,
+        };
+        auth.header_value()
+    }
+
+    /// TLS configuration for an HTTPS GraphQL API server, if a CA file was provided.
+    pub const fn tls(&self) -> Option<&TlsConfig> {
+        self.tls.as_ref()
+    }
+}
+
+fn load_ca_file(path: &str) -> crate::Result<TlsConfig> {
+    TlsConfig::with_ca_file(Path::new(path)).map_err(|error| format!("{error:#}").into())
+}
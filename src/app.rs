@@ -7,7 +7,7 @@ use futures::StreamExt;
 use futures_util::future::BoxFuture;
 use once_cell::race::OnceNonZeroUsize;
 use tokio::runtime::{self, Runtime};
-use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast::error::RecvError, mpsc};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 #[cfg(feature = "enterprise")]
@@ -19,14 +19,15 @@ use crate::extra_context::ExtraContext;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
+    batch_mode::Deliveries,
     cli::{handle_config_errors, LogFormat, Opts, RootOpts},
     config::{self, Config, ConfigPath},
     heartbeat,
     internal_events::{VectorQuit, VectorStarted, VectorStopped},
     signal::{SignalHandler, SignalPair, SignalRx, SignalTo},
     topology::{
-        ReloadOutcome, RunningTopology, SharedTopologyController, ShutdownErrorReceiver,
-        TopologyController,
+        ControlCommand, ControlRequest, ControlRx, ReloadOutcome, RunningTopology,
+        SharedTopologyController, ShutdownErrorReceiver, TopologyController,
     },
     trace,
 };
@@ -65,7 +66,8 @@ impl ApplicationConfig {
     ) -> Result<Self, ExitCode> {
         let config_paths = opts.config_paths_with_formats();
 
-        let graceful_shutdown_duration = (!opts.no_graceful_shutdown_limit)
+        // In batch mode, the topology is always drained entirely.
+        let graceful_shutdown_duration = (!opts.no_graceful_shutdown_limit && !opts.batch)
             .then(|| Duration::from_secs(u64::from(opts.graceful_shutdown_limit_secs)));
 
         let config = load_configs(
@@ -74,6 +76,7 @@ impl ApplicationConfig {
             opts.require_healthy,
             opts.allow_empty_config,
             graceful_shutdown_duration,
+            opts.batch,
             signal_handler,
         )
         .await?;
@@ -95,7 +98,7 @@ impl ApplicationConfig {
         let enterprise = build_enterprise(&mut config, config_paths.clone())?;
 
         #[cfg(feature = "api")]
-        let api = config.api;
+        let api = config.api.clone();
 
         let (topology, graceful_crash_receiver) =
             RunningTopology::start_init_validated(config, extra_context.clone())
@@ -131,12 +134,17 @@ impl ApplicationConfig {
 
     /// Configure the API server, if applicable
     #[cfg(feature = "api")]
-    pub fn setup_api(&self, handle: &Handle) -> Option<api::Server> {
+    pub fn setup_api(
+        &self,
+        handle: &Handle,
+        control_tx: crate::topology::ControlTx,
+    ) -> Option<api::Server> {
         if self.api.enabled {
             match api::Server::start(
                 self.topology.config(),
                 self.topology.watch(),
                 std::sync::Arc::clone(&self.topology.running),
+                control_tx,
                 handle,
             ) {
                 Ok(api_server) => {
@@ -249,9 +257,13 @@ impl Application {
             signals,
         } = self;
 
+        // Commands sent through the API to change the running topology are handled by the main loop.
+        let (control_tx, control_rx) = mpsc::unbounded_channel();
+
         let topology_controller = SharedTopologyController::new(TopologyController {
             #[cfg(feature = "api")]
-            api_server: config.setup_api(handle),
+            api_server: config.setup_api(handle, control_tx.clone()),
+            control_tx,
             topology: config.topology,
             config_paths: config.config_paths.clone(),
             require_healthy: root_opts.require_healthy,
@@ -266,7 +278,9 @@ impl Application {
             graceful_crash_receiver: config.graceful_crash_receiver,
             signals,
             topology_controller,
+            control_rx,
             allow_empty_config: root_opts.allow_empty_config,
+            batch: root_opts.batch,
         })
     }
 }
@@ -277,7 +291,9 @@ pub struct StartedApplication {
     pub graceful_crash_receiver: ShutdownErrorReceiver,
     pub signals: SignalPair,
     pub topology_controller: SharedTopologyController,
+    pub control_rx: ControlRx,
     pub allow_empty_config: bool,
+    pub batch: bool,
 }
 
 impl StartedApplication {
@@ -292,7 +308,9 @@ impl StartedApplication {
             signals,
             topology_controller,
             internal_topologies,
+            mut control_rx,
             allow_empty_config,
+            batch,
         } = self;
 
         let mut graceful_crash = UnboundedReceiverStream::new(graceful_crash_receiver);
@@ -312,6 +330,15 @@ impl StartedApplication {
                 ).await {
                     break signal;
                 },
+                Some(request) = control_rx.recv() => if let Some(signal) = handle_control(
+                    request,
+                    &topology_controller,
+                    &config_paths,
+                    &mut signal_handler,
+                    allow_empty_config,
+                ).await {
+                    break signal;
+                },
                 // Trigger graceful shutdown if a component crashed, or all sources have ended.
                 error = graceful_crash.next() => break SignalTo::Shutdown(error),
                 _ = TopologyController::sources_finished(topology_controller.clone()), if has_sources => {
@@ -327,6 +354,7 @@ impl StartedApplication {
             signal_rx,
             topology_controller,
             internal_topologies,
+            batch,
         }
     }
 }
@@ -349,15 +377,9 @@ async fn handle_signal(
         }
         Ok(SignalTo::ReloadFromDisk) => {
             let mut topology_controller = topology_controller.lock().await;
-
-            // Reload paths
-            if let Some(paths) = config::process_paths(config_paths) {
-                topology_controller.config_paths = paths;
-            }
-
-            // Reload config
-            let new_config = config::load_from_paths_with_provider_and_secrets(
-                &topology_controller.config_paths,
+            let new_config = load_from_disk(
+                &mut topology_controller,
+                config_paths,
                 signal_handler,
                 allow_empty_config,
             )
@@ -379,11 +401,100 @@ async fn handle_signal(
     }
 }
 
+async fn handle_control(
+    request: ControlRequest,
+    topology_controller: &SharedTopologyController,
+    config_paths: &[ConfigPath],
+    signal_handler: &mut SignalHandler,
+    allow_empty_config: bool,
+) -> Option<SignalTo> {
+    let ControlRequest { command, reply } = request;
+    let mut topology_controller = topology_controller.lock().await;
+
+    let (result, outcome) = match command {
+        ControlCommand::Reload => {
+            let new_config = load_from_disk(
+                &mut topology_controller,
+                config_paths,
+                signal_handler,
+                allow_empty_config,
+            )
+            .await;
+            let errors = new_config.as_ref().err().cloned().unwrap_or_default();
+            let outcome = topology_controller
+                .reload(new_config.map_err(handle_config_errors).ok())
+                .await;
+            (outcome_result(&outcome, errors), Some(outcome))
+        }
+        ControlCommand::PauseSource(key) => (
+            topology_controller
+                .topology
+                .set_source_paused(&key, true)
+                .map_err(|error| vec![error]),
+            None,
+        ),
+        ControlCommand::ResumeSource(key) => (
+            topology_controller
+                .topology
+                .set_source_paused(&key, false)
+                .map_err(|error| vec![error]),
+            None,
+        ),
+        ControlCommand::DrainSink(key) => match topology_controller.drain_sink(&key).await {
+            Ok(outcome) => (outcome_result(&outcome, Vec::new()), Some(outcome)),
+            Err(error) => (Err(vec![error]), None),
+        },
+    };
+
+    // The client may have gone away in the meantime.
+    _ = reply.send(result);
+
+    match outcome {
+        Some(ReloadOutcome::FatalError(error)) => Some(SignalTo::Shutdown(Some(error))),
+        _ => None,
+    }
+}
+
+/// Reloads the config paths, and loads the configuration from them.
+async fn load_from_disk(
+    topology_controller: &mut TopologyController,
+    config_paths: &[ConfigPath],
+    signal_handler: &mut SignalHandler,
+    allow_empty_config: bool,
+) -> Result<Config, Vec<String>> {
+    if let Some(paths) = config::process_paths(config_paths) {
+        topology_controller.config_paths = paths;
+    }
+
+    config::load_from_paths_with_provider_and_secrets(
+        &topology_controller.config_paths,
+        signal_handler,
+        allow_empty_config,
+    )
+    .await
+}
+
+/// Converts the outcome of a reload into the result of a control command, using the given errors
+/// if the configuration couldn't be loaded.
+fn outcome_result(outcome: &ReloadOutcome, errors: Vec<String>) -> Result<(), Vec<String>> {
+    match outcome {
+        ReloadOutcome::Success => Ok(()),
+        ReloadOutcome::NoConfig => Err(errors),
+        ReloadOutcome::MissingApiKey => Err(vec!["Missing the Datadog API key.".to_string()]),
+        ReloadOutcome::RolledBack => Err(vec![
+            "Failed to load the new configuration, the previous one was restored. See the logs for details."
+                .to_string(),
+        ]),
+        ReloadOutcome::FatalError(error) => Err(vec![error.to_string()]),
+    }
+}
+
 pub struct FinishedApplication {
     pub signal: SignalTo,
     pub signal_rx: SignalRx,
     pub topology_controller: SharedTopologyController,
     pub internal_topologies: Vec<RunningTopology>,
+    pub batch: bool,
 }
 
 impl FinishedApplication {
@@ -393,6 +504,7 @@ impl FinishedApplication {
             signal_rx,
             topology_controller,
             internal_topologies,
+            batch,
         } = self;
 
         // At this point, we'll have the only reference to the shared topology controller and can
@@ -403,7 +515,9 @@ impl FinishedApplication {
             .into_inner();
 
         let status = match signal {
-            SignalTo::Shutdown(_) => Self::stop(topology_controller, signal_rx).await,
+            SignalTo::Shutdown(error) => {
+                Self::stop(topology_controller, signal_rx, batch, error.is_some()).await
+            }
             SignalTo::Quit => Self::quit(),
             _ => unreachable!(),
         };
@@ -415,21 +529,41 @@ impl FinishedApplication {
         status
     }
 
-    async fn stop(topology_controller: TopologyController, mut signal_rx: SignalRx) -> ExitStatus {
+    async fn stop(
+        topology_controller: TopologyController,
+        mut signal_rx: SignalRx,
+        batch: bool,
+        component_failed: bool,
+    ) -> ExitStatus {
         emit!(VectorStopped);
+        let deliveries = topology_controller.topology.deliveries().clone();
         tokio::select! {
-            _ = topology_controller.stop() => ExitStatus::from_raw({
-                #[cfg(windows)]
-                {
-                    exitcode::OK as u32
-                }
-                #[cfg(unix)]
-                exitcode::OK
-            }), // Graceful shutdown finished
+            _ = topology_controller.stop() => if batch {
+                Self::batch_status(&deliveries, component_failed).await
+            } else {
+                exit_status(exitcode::OK)
+            }, // Graceful shutdown finished
             _ = signal_rx.recv() => Self::quit(),
         }
     }
 
+    /// Determines the exit status of a batch run, once the topology is drained.
+    async fn batch_status(deliveries: &Deliveries, component_failed: bool) -> ExitStatus {
+        let undelivered = deliveries.undelivered_events().await;
+        if undelivered > 0 {
+            error!(
+                message = "Sinks rejected or failed to deliver events.",
+                count = undelivered
+            );
+            exit_status(exitcode::DATAERR)
+        } else if component_failed {
+            exit_status(exitcode::SOFTWARE)
+        } else {
+            info!("All events have been delivered.");
+            exit_status(exitcode::OK)
+        }
+    }
+
     fn quit() -> ExitStatus {
         // It is highly unlikely that this event will exit from topology.
         emit!(VectorQuit);
@@ -444,6 +578,19 @@ impl FinishedApplication {
     }
 }
 
+/// Converts an exit code into the exit status of the process.
+fn exit_status(code: ExitCode) -> ExitStatus {
+    #[cfg(windows)]
+    {
+        ExitStatus::from_raw(code as u32)
+    }
+    #[cfg(unix)]
+    {
+        // The raw status of a process that exited normally holds its exit code in the second byte.
+        ExitStatus::from_raw(code << 8)
+    }
+}
+
 fn get_log_levels(default: &str) -> String {
     std::env::var("VECTOR_LOG")
         .or_else(|_| {
@@ -483,6 +630,7 @@ pub async fn load_configs(
     require_healthy: Option<bool>,
     allow_empty_config: bool,
     graceful_shutdown_duration: Option<Duration>,
+    batch: bool,
     signal_handler: &mut SignalHandler,
 ) -> Result<Config, ExitCode> {
     let config_paths = config::process_paths(config_paths).ok_or(exitcode::CONFIG)?;
@@ -521,6 +669,7 @@ pub async fn load_configs(
     }
     config.healthchecks.set_require_healthy(require_healthy);
     config.graceful_shutdown_duration = graceful_shutdown_duration;
+    config.batch = batch;
 
     Ok(config)
 }
@@ -574,3 +723,54 @@ pub fn init_logging(color: bool, format: LogFormat, log_level: &str, rate: u64)
     );
     info!(message = "Log level is enabled.", level = ?level);
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::{broadcast, oneshot};
+
+    use super::*;
+    use crate::{
+        event::{Event, EventContainer, EventStatus, LogEvent},
+        test_util::{
+            mock::{basic_source, oneshot_sink},
+            start_topology, trace_init,
+        },
+    };
+
+    #[tokio::test]
+    async fn batch_mode_fails_with_rejected_events() {
+        trace_init();
+
+        let (mut tx, source) = basic_source();
+        let (sink_tx, sink_rx) = oneshot::channel();
+        let mut config = Config::builder();
+        config.batch = true;
+        config.add_source("in", source);
+        config.add_sink("out", &["in"], oneshot_sink(sink_tx));
+
+        let (topology, _) = start_topology(config.build().unwrap(), false).await;
+        tx.send_event(Event::Log(LogEvent::from("rejected")))
+            .await
+            .unwrap();
+        for mut event in sink_rx.await.unwrap().into_events() {
+            event.take_finalizers().update_status(EventStatus::Rejected);
+        }
+
+        let (control_tx, _control_rx) = mpsc::unbounded_channel();
+        let topology_controller = TopologyController {
+            topology,
+            config_paths: vec![],
+            require_healthy: None,
+            #[cfg(feature = "enterprise")]
+            enterprise_reporter: None,
+            #[cfg(feature = "api")]
+            api_server: None,
+            control_tx,
+            extra_context: Default::default(),
+        };
+        let (_signal_tx, signal_rx) = broadcast::channel(1);
+
+        let status = FinishedApplication::stop(topology_controller, signal_rx, true, false).await;
+        assert_eq!(status.code(), Some(exitcode::DATAERR));
+    }
+}
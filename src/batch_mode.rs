@@ -0,0 +1,114 @@
+//! Tracking of the delivery of events in batch mode.
+//!
+//! In batch mode, Vector runs its topology to completion: once all sources have reached the end of
+//! their input, the topology is drained and Vector exits, with a non-zero status if sinks failed to
+//! deliver some of the events. To count these events, each event flowing into a sink is given a
+//! dedicated batch notifier, whose status is recorded once the sink finalizes the event.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use futures::future::join_all;
+use tokio::sync::Notify;
+
+use crate::event::{BatchNotifier, BatchStatus, EventArray, EventFinalizer};
+
+/// Counts the events that sinks rejected or failed to deliver in batch mode.
+///
+/// It is shared by all the sinks built for a running topology, including across reloads.
+#[derive(Clone, Debug, Default)]
+pub struct Deliveries(Arc<Inner>);
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The number of tracked event arrays whose events aren't all finalized yet.
+    pending: AtomicUsize,
+    /// The number of events that sinks rejected or failed to deliver.
+    undelivered: AtomicUsize,
+    /// Notified when the last pending event array is finalized.
+    settled: Notify,
+}
+
+impl Deliveries {
+    /// Tracks the delivery of the events flowing into a sink.
+    pub(crate) fn track(&self, mut events: EventArray) -> EventArray {
+        let receivers = events
+            .iter_events_mut()
+            .map(|mut event| {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                event
+                    .metadata_mut()
+                    .add_finalizer(EventFinalizer::new(batch));
+                receiver
+            })
+            .collect::<Vec<_>>();
+
+        let inner = Arc::clone(&self.0);
+        inner.pending.fetch_add(1, Ordering::AcqRel);
+        tokio::spawn(async move {
+            let undelivered = join_all(receivers)
+                .await
+                .into_iter()
+                .filter(|status| matches!(status, BatchStatus::Errored | BatchStatus::Rejected))
+                .count();
+            inner.undelivered.fetch_add(undelivered, Ordering::AcqRel);
+            if inner.pending.fetch_sub(1, Ordering::AcqRel) == 1 {
+                inner.settled.notify_waiters();
+            }
+        });
+
+        events
+    }
+
+    /// Waits for all the tracked events to be finalized, and returns the number of events that
+    /// sinks rejected or failed to deliver.
+    pub async fn undelivered_events(&self) -> usize {
+        loop {
+            // The notification is registered before checking for pending events, so that it can't
+            // be missed in between.
+            let settled = self.0.settled.notified();
+            if self.0.pending.load(Ordering::Acquire) == 0 {
+                return self.0.undelivered.load(Ordering::Acquire);
+            }
+            settled.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::event::{Event, EventStatus, LogEvent};
+
+    #[tokio::test]
+    async fn counts_undelivered_events() {
+        let events = ["delivered", "rejected", "errored", "dropped"]
+            .into_iter()
+            .map(LogEvent::from)
+            .collect::<Vec<_>>();
+
+        let deliveries = Deliveries::default();
+        let EventArray::Logs(logs) = deliveries.track(events.into()) else {
+            panic!("expected logs");
+        };
+        for (log, status) in logs.into_iter().zip([
+            EventStatus::Delivered,
+            EventStatus::Rejected,
+            EventStatus::Errored,
+            EventStatus::Dropped,
+        ]) {
+            Event::from(log).take_finalizers().update_status(status);
+        }
+
+        let undelivered = timeout(Duration::from_secs(5), deliveries.undelivered_events())
+            .await
+            .expect("tracked events weren't finalized");
+        assert_eq!(undelivered, 2);
+    }
+}
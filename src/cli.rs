@@ -206,6 +206,15 @@ pub struct RootOpts {
     #[arg(long, env = "VECTOR_OPENSSL_NO_PROBE", default_value = "false")]
     pub openssl_no_probe: bool,
 
+    /// Run the topology to completion, as a one-shot batch job.
+    ///
+    /// Sources with a finite input, such as `file`, `stdin`, `exec` and `replay`, stop once they
+    /// reach its end. When all sources have stopped, Vector waits for all the events to be
+    /// delivered, without time limit, and exits. The exit status is non-zero if sinks rejected or
+    /// failed to deliver some of the events, or if a component failed.
+    #[arg(long, env = "VECTOR_BATCH", default_value = "false")]
+    pub batch: bool,
+
     /// Allow the configuration to run without any components. This is useful for loading in an
     /// empty stub config that will later be replaced with actual components. Note that this is
     /// likely not useful without also watching for config file changes as described in
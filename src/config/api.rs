@@ -1,11 +1,16 @@
 use std::net::{Ipv4Addr, SocketAddr};
 
 use url::Url;
-use vector_lib::configurable::configurable_component;
+use vector_lib::{
+    configurable::configurable_component, sensitive_string::SensitiveString,
+    tls::TlsEnableableConfig,
+};
+
+use crate::http::Auth;
 
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -23,6 +28,24 @@ pub struct Options {
     /// Whether or not the GraphQL endpoint is enabled
     #[serde(default = "default_graphql", skip_serializing_if = "is_true")]
     pub graphql: bool,
+
+    /// The token required to run GraphQL mutations, such as reloading the configuration or
+    /// pausing a source.
+    ///
+    /// Clients send it in the `Authorization` header as a bearer token. When unset, mutations are
+    /// disabled.
+    #[configurable(metadata(docs::examples = "${VECTOR_API_MUTATION_TOKEN}"))]
+    pub mutation_token: Option<SensitiveString>,
+
+    /// The credentials required to use the GraphQL endpoint, including its WebSocket subscriptions.
+    ///
+    /// Requests carrying the `mutation_token` are accepted as well. The `/health` endpoint doesn't
+    /// require credentials.
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
 }
 
 impl Default for Options {
@@ -32,6 +55,9 @@ impl Default for Options {
             playground: default_playground(),
             address: default_address(),
             graphql: default_graphql(),
+            mutation_token: None,
+            auth: None,
+            tls: None,
         }
     }
 }
@@ -89,11 +115,35 @@ impl Options {
             }
         };
 
+        let mutation_token = match (self.mutation_token.take(), other.mutation_token) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api` mutation token.".to_string())
+            }
+            (a, b) => a.or(b),
+        };
+
+        let auth = match (self.auth.take(), other.auth) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api` authentication.".to_string())
+            }
+            (a, b) => a.or(b),
+        };
+
+        let tls = match (self.tls.take(), other.tls) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api` TLS options.".to_string())
+            }
+            (a, b) => a.or(b),
+        };
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
             graphql: self.graphql & other.graphql,
+            mutation_token,
+            auth,
+            tls,
         };
 
         *self = options;
@@ -108,6 +158,9 @@ fn bool_merge() {
         address: None,
         playground: false,
         graphql: false,
+        mutation_token: None,
+        auth: None,
+        tls: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -118,7 +171,10 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
-            graphql: false
+            graphql: false,
+            mutation_token: None,
+            auth: None,
+            tls: None,
         }
     );
 }
@@ -131,6 +187,9 @@ fn bind_merge() {
         address: Some(address),
         playground: true,
         graphql: true,
+        mutation_token: None,
+        auth: None,
+        tls: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -142,6 +201,9 @@ fn bind_merge() {
             address: Some(address),
             playground: true,
             graphql: true,
+            mutation_token: None,
+            auth: None,
+            tls: None,
         }
     );
 }
@@ -160,3 +222,45 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn mutation_token_merge() {
+    let mut a = Options {
+        mutation_token: Some("token".to_string().into()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.mutation_token, Some("token".to_string().into()));
+
+    let b = Options {
+        mutation_token: Some("other".to_string().into()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn auth_merge() {
+    let auth = Auth::Bearer {
+        token: "token".to_string().into(),
+    };
+    let mut a = Options {
+        auth: Some(auth.clone()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.auth, Some(auth));
+
+    let b = Options {
+        auth: Some(Auth::Basic {
+            user: "user".to_string(),
+            password: "password".to_string().into(),
+        }),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
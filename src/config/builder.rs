@@ -84,6 +84,12 @@ pub struct ConfigBuilder {
     #[doc(hidden)]
     pub graceful_shutdown_duration: Option<Duration>,
 
+    /// Whether to run the topology to completion, stopping once all sources have reached the end of
+    /// their input. This value can be set using a [cli arg](crate::cli::RootOpts::batch).
+    #[serde(default, skip)]
+    #[doc(hidden)]
+    pub batch: bool,
+
     /// Allow the configuration to be empty, resulting in a topology with no components.
     #[serde(default, skip)]
     #[doc(hidden)]
@@ -206,6 +212,7 @@ impl From<Config> for ConfigBuilder {
             tests,
             secret,
             graceful_shutdown_duration,
+            batch,
             hash: _,
         } = config;
 
@@ -237,6 +244,7 @@ impl From<Config> for ConfigBuilder {
             tests,
             secret,
             graceful_shutdown_duration,
+            batch,
             allow_empty: false,
         }
     }
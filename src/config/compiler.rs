@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use super::{
     builder::ConfigBuilder, graph::Graph, id::Inputs, transform::get_transform_output_ids,
     validation, Config, OutputId,
@@ -57,9 +59,17 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         provider: _,
         secret,
         graceful_shutdown_duration,
+        batch,
         allow_empty: _,
     } = builder;
 
+    // Sinks that encode their payloads can be used as unit test outputs.
+    let test_sink_targets = sinks
+        .iter()
+        .filter(|(_, sink)| sink.inner.encoding_config().is_some())
+        .map(|(key, _)| key.clone())
+        .collect::<HashSet<_>>();
+
     let graph = match Graph::new(&sources, &transforms, &sinks, schema) {
         Ok(graph) => graph,
         Err(graph_errors) => {
@@ -94,7 +104,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         .collect();
     let tests = tests
         .into_iter()
-        .map(|test| test.resolve_outputs(&graph))
+        .map(|test| test.resolve_outputs(&graph, &test_sink_targets))
         .collect::<Result<Vec<_>, Vec<_>>>()?;
 
     if errors.is_empty() {
@@ -114,6 +124,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             tests,
             secret,
             graceful_shutdown_duration,
+            batch,
         };
 
         config.propagate_acknowledgements()?;
@@ -173,8 +173,16 @@ pub(super) mod process {
             format: Format,
         ) -> Result<Option<(String, Table, Vec<String>)>, Vec<String>> {
             if let (Ok(name), Some(file)) = (component_name(path), open_file(path)) {
-                self.load(file, format)
-                    .map(|(value, warnings)| Some((name, value, warnings)))
+                self.load(file, format).map(|(mut value, warnings)| {
+                    if let (Some(Value::Array(tests)), Some(dir)) =
+                        (value.get_mut("tests"), path.parent())
+                    {
+                        for test in tests.iter_mut().filter_map(Value::as_table_mut) {
+                            resolve_golden_files(test, dir);
+                        }
+                    }
+                    Some((name, value, warnings))
+                })
             } else {
                 Ok(None)
             }
@@ -277,8 +285,13 @@ where
             if path.exists() && path.is_dir() {
                 // Transforms are treated differently from other component types; they can be
                 // arbitrarily nested.
-                let (table, warns) =
+                let (mut table, warns) =
                     self.load_dir(&path, matches!(hint, ComponentHint::Transform))?;
+                if matches!(hint, ComponentHint::Test) {
+                    for test in table.values_mut().filter_map(Value::as_table_mut) {
+                        resolve_golden_files(test, &path);
+                    }
+                }
 
                 self.merge(table, Some(hint))?;
 
@@ -290,6 +303,24 @@ where
     }
 }
 
+/// Makes the relative `golden_file` paths of a test relative to the directory of the file declaring
+/// it, rather than to the working directory.
+fn resolve_golden_files(test: &mut Table, dir: &Path) {
+    let Some(Value::Array(outputs)) = test.get_mut("outputs") else {
+        return;
+    };
+    for output in outputs.iter_mut().filter_map(Value::as_table_mut) {
+        if let Some(Value::String(golden_file)) = output.get_mut("golden_file") {
+            let path = Path::new(golden_file.as_str());
+            if path.is_relative() {
+                if let Some(resolved) = dir.join(path).to_str() {
+                    *golden_file = resolved.to_string();
+                }
+            }
+        }
+    }
+}
+
 /// Merge two TOML `Value`s, returning a new `Value`.
 fn merge_values(value: toml::Value, other: toml::Value) -> Result<toml::Value, Vec<String>> {
     serde_toml_merge::merge(value, other).map_err(|e| vec![e.to_string()])
@@ -353,6 +353,8 @@ fn default_config_paths() -> Vec<ConfigPath> {
 mod tests {
     use std::path::PathBuf;
 
+    use indoc::indoc;
+
     use super::load_builder_from_paths;
     use crate::config::{ComponentKey, ConfigPath};
 
@@ -420,4 +422,48 @@ mod tests {
         let (_, warnings) = load_builder_from_paths(&configs).unwrap();
         assert!(warnings.is_empty());
     }
+
+    #[test]
+    fn load_golden_files_relative_to_config() {
+        let dir = crate::test_util::temp_dir();
+        std::fs::create_dir_all(dir.join("tests")).unwrap();
+        std::fs::write(
+            dir.join("vector.toml"),
+            indoc! {r#"
+                [[tests]]
+                  name = "top-level"
+
+                  [[tests.outputs]]
+                    extract_from = "out"
+                    golden_file = "expected.txt"
+            "#},
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("tests").join("nested.toml"),
+            indoc! {r#"
+                name = "nested"
+
+                [[outputs]]
+                  extract_from = "out"
+                  golden_file = "expected.txt"
+            "#},
+        )
+        .unwrap();
+
+        let configs = vec![ConfigPath::Dir(dir.clone())];
+        let (builder, _) = load_builder_from_paths(&configs).unwrap();
+        let golden_files = builder
+            .tests
+            .iter()
+            .map(|test| (test.name.as_str(), test.outputs[0].golden_file.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            golden_files,
+            vec![
+                ("top-level", Some(dir.join("expected.txt"))),
+                ("nested", Some(dir.join("tests").join("expected.txt"))),
+            ]
+        );
+    }
 }
@@ -57,12 +57,16 @@ pub use loading::{
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
-pub use sink::{BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
+pub use sink::{
+    BoxedSink, SinkConfig, SinkContext, SinkDeadLetterOptions, SinkHealthcheckOptions, SinkOuter,
+};
 pub use source::{BoxedSource, SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
     get_transform_output_ids, BoxedTransform, TransformConfig, TransformContext, TransformOuter,
 };
-pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
+pub use unit_test::{
+    build_unit_tests, build_unit_tests_main, SnapshotMode, Snapshots, UnitTestResult,
+};
 pub use validation::warnings;
 pub use vars::{interpolate, ENVIRONMENT_VARIABLE_INTERPOLATION_REGEX};
 pub use vector_lib::config::{
@@ -120,6 +124,7 @@ pub struct Config {
     tests: Vec<TestDefinition>,
     secret: IndexMap<ComponentKey, SecretBackends>,
     pub graceful_shutdown_duration: Option<Duration>,
+    pub batch: bool,
 }
 
 impl Config {
@@ -155,6 +160,34 @@ impl Config {
         self.sinks.get(id)
     }
 
+    /// Returns a copy of this configuration without the given sink.
+    ///
+    /// Sinks are never inputs of other components, so the result stays valid. The tests and
+    /// secret backends are only needed while loading a configuration, and are left out.
+    pub fn without_sink(&self, id: &ComponentKey) -> Self {
+        let mut sinks = self.sinks.clone();
+        sinks.shift_remove(id);
+
+        Self {
+            #[cfg(feature = "api")]
+            api: self.api.clone(),
+            schema: self.schema,
+            hash: self.hash.clone(),
+            #[cfg(feature = "enterprise")]
+            enterprise: self.enterprise.clone(),
+            global: self.global.clone(),
+            healthchecks: self.healthchecks,
+            sources: self.sources.clone(),
+            sinks,
+            transforms: self.transforms.clone(),
+            enrichment_tables: self.enrichment_tables.clone(),
+            tests: Vec::new(),
+            secret: IndexMap::new(),
+            graceful_shutdown_duration: self.graceful_shutdown_duration,
+            batch: self.batch,
+        }
+    }
+
     pub fn inputs_for_node(&self, id: &ComponentKey) -> Option<&[OutputId]> {
         self.transforms
             .get(id)
@@ -361,9 +394,14 @@ pub struct TestDefinition<T: 'static = OutputId> {
 }
 
 impl TestDefinition<String> {
+    /// Resolves the `extract_from` and `no_outputs_from` targets into output IDs.
+    ///
+    /// Besides the outputs known to the graph, `extract_from` may name any of the given sinks whose
+    /// encoded payloads can be captured.
     fn resolve_outputs(
         self,
         graph: &graph::Graph,
+        sink_targets: &HashSet<ComponentKey>,
     ) -> Result<TestDefinition<OutputId>, Vec<String>> {
         let TestDefinition {
             name,
@@ -382,15 +420,20 @@ impl TestDefinition<String> {
                 let TestOutput {
                     extract_from,
                     conditions,
+                    golden_file,
                 } = old;
 
-                (extract_from.to_vec(), conditions)
+                (extract_from.to_vec(), conditions, golden_file)
             })
-            .filter_map(|(extract_from, conditions)| {
+            .filter_map(|(extract_from, conditions, golden_file)| {
                 let mut outputs = Vec::new();
                 for from in extract_from {
                     if let Some(output_id) = output_map.get(&from) {
                         outputs.push(output_id.clone());
+                    } else if let Some(sink_key) =
+                        sink_targets.get(&ComponentKey::from(from.as_str()))
+                    {
+                        outputs.push(OutputId::from(sink_key.clone()));
                     } else {
                         errors.push(format!(
                             r#"Invalid extract_from target in test '{}': '{}' does not exist"#,
@@ -404,6 +447,7 @@ impl TestDefinition<String> {
                     Some(TestOutput {
                         extract_from: outputs.into(),
                         conditions,
+                        golden_file,
                     })
                 }
             })
@@ -459,6 +503,7 @@ impl TestDefinition<OutputId> {
                     .collect::<Vec<_>>()
                     .into(),
                 conditions: old.conditions,
+                golden_file: old.golden_file,
             })
             .collect();
 
@@ -501,6 +546,9 @@ pub enum TestInputValue {
 #[serde(deny_unknown_fields)]
 pub struct TestInput {
     /// The name of the transform to insert the input event to.
+    ///
+    /// This can also be the name of a source that decodes its input, such as `socket` or `stdin`,
+    /// in which case the `raw` value is run through the source's framing and decoding first.
     pub insert_at: ComponentKey,
 
     /// The type of the input event.
@@ -512,7 +560,8 @@ pub struct TestInput {
     /// The raw string value to use as the input event.
     ///
     /// Use this only when the input event should be a raw event (i.e. unprocessed/undecoded log
-    /// event) and when the input type is set to `raw`.
+    /// event) and when the input type is set to `raw`. When inserting into a source, this is the
+    /// raw payload the source receives, and it can decode into any number of events.
     pub value: Option<String>,
 
     /// The vrl expression to generate the input event.
@@ -544,10 +593,23 @@ fn default_test_input_type() -> String {
 #[serde(deny_unknown_fields)]
 pub struct TestOutput<T: 'static = OutputId> {
     /// The transform outputs to extract events from.
+    ///
+    /// This can also be the name of a sink that encodes its output, such as `console`, `file`, or
+    /// `socket`, in which case the events are captured as the payload bytes the sink would send.
     pub extract_from: OneOrMany<T>,
 
     /// The conditions to run against the output to validate that they were transformed as expected.
+    ///
+    /// When extracting from a sink, the conditions run against log events whose `message` field
+    /// holds the payload bytes that the sink encoded for each event.
     pub conditions: Option<Vec<conditions::AnyCondition>>,
+
+    /// The path to a file containing the payload bytes the sink is expected to produce.
+    ///
+    /// Only relevant when `extract_from` is a sink. The framed payloads of all events received by
+    /// the sink are concatenated and compared against the contents of this file. Relative paths
+    /// are resolved against the directory of the configuration file declaring the test.
+    pub golden_file: Option<PathBuf>,
 }
 
 #[cfg(all(test, feature = "sources-file", feature = "sinks-console"))]
@@ -566,8 +628,15 @@ mod tests {
                 let c2 = config::load_from_str(config, format).unwrap();
                 match (
                     config::warnings(&c2),
-                    topology::TopologyPieces::build(&c, &diff, HashMap::new(), Default::default())
-                        .await,
+                    topology::TopologyPieces::build(
+                        &c,
+                        &diff,
+                        HashMap::new(),
+                        Default::default(),
+                        Default::default(),
+                        Default::default(),
+                    )
+                    .await,
                 ) {
                     (warnings, Ok(_pieces)) => Ok(warnings),
                     (_, Err(errors)) => Err(errors),
@@ -15,6 +15,7 @@ use vector_lib::{
 };
 
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
+use crate::codecs::{EncodingConfigWithFraming, SinkType};
 use crate::extra_context::ExtraContext;
 use crate::sinks::{util::UriSerde, Healthcheck};
 
@@ -74,6 +75,10 @@ where
     #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
     proxy: ProxyConfig,
 
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
+    pub dead_letter: SinkDeadLetterOptions,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: BoxedSink,
@@ -95,6 +100,7 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            dead_letter: Default::default(),
         }
     }
 
@@ -151,6 +157,7 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            dead_letter: self.dead_letter,
         }
     }
 }
@@ -195,6 +202,22 @@ impl From<UriSerde> for SinkHealthcheckOptions {
     }
 }
 
+/// Dead-letter configuration.
+///
+/// Events that the sink permanently rejects are sent to the `dead_letter` sources, instead of
+/// being dropped, so that another sink can consume them.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct SinkDeadLetterOptions {
+    /// Whether or not to send events rejected by the sink to `dead_letter` sources.
+    ///
+    /// Each rejected event is annotated with the reason of the rejection, the HTTP status
+    /// returned by the downstream service if any, and the ID of the sink, in the
+    /// `%vector.dead_letter` metadata field.
+    pub enabled: bool,
+}
+
 /// Generalized interface for describing and building sink components.
 #[async_trait]
 #[typetag::serde(tag = "type")]
@@ -224,6 +247,15 @@ pub trait SinkConfig: DynClone + NamedComponent + core::fmt::Debug + Send + Sync
         Vec::new()
     }
 
+    /// Gets the encoding configuration, if any, used by this sink to turn events into payload
+    /// bytes, along with the way the sink sends those payloads.
+    ///
+    /// Unit tests use this to capture the encoded payload that the sink would send, without having
+    /// to build the sink or open a network connection.
+    fn encoding_config(&self) -> Option<(EncodingConfigWithFraming, SinkType)> {
+        None
+    }
+
     /// Gets the acknowledgements configuration for this sink.
     fn acknowledgements(&self) -> &AcknowledgementsConfig;
 }
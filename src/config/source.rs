@@ -17,7 +17,9 @@ use vector_lib::{
 };
 
 use super::{schema, ComponentKey, ProxyConfig, Resource};
-use crate::{shutdown::ShutdownSignal, SourceSender};
+use crate::{
+    codecs::DecodingConfig, dead_letter::DeadLetters, shutdown::ShutdownSignal, SourceSender,
+};
 
 pub type BoxedSource = Box<dyn SourceConfig>;
 
@@ -100,6 +102,15 @@ pub trait SourceConfig: DynClone + NamedComponent + core::fmt::Debug + Send + Sy
         Vec::new()
     }
 
+    /// Gets the framing and decoding configuration, if any, used by this source to turn raw bytes
+    /// into events.
+    ///
+    /// Unit tests use this to feed raw input through the same framing and decoder that the source
+    /// would apply at runtime, without having to build the source itself.
+    fn decoding_config(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        None
+    }
+
     /// Whether or not this source can acknowledge the events it emits.
     ///
     /// Generally, Vector uses acknowledgements to track when an event has finally been processed,
@@ -126,6 +137,13 @@ pub struct SourceContext {
     pub acknowledgements: bool,
     pub schema: schema::Options,
 
+    /// Whether Vector runs in batch mode, in which sources with a finite input stop once they reach
+    /// its end, instead of waiting for more data.
+    pub batch: bool,
+
+    /// The channel carrying the events rejected by the sinks of the topology.
+    pub dead_letters: DeadLetters,
+
     /// Tracks the schema IDs assigned to schemas exposed by the source.
     ///
     /// Given a source can expose multiple [`SourceOutput`] channels, the ID is tied to the identifier of
@@ -151,6 +169,8 @@ impl SourceContext {
                 acknowledgements: false,
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
+                batch: false,
+                dead_letters: Default::default(),
             },
             shutdown,
         )
@@ -170,6 +190,8 @@ impl SourceContext {
             acknowledgements: false,
             schema_definitions: schema_definitions.unwrap_or_default(),
             schema: Default::default(),
+            batch: false,
+            dead_letters: Default::default(),
         }
     }
 
@@ -1,12 +1,15 @@
+mod snapshot;
 #[cfg(all(test, feature = "vector-unit-test-tests"))]
 mod tests;
 mod unit_test_components;
 
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
     sync::Arc,
 };
 
+use bytes::BytesMut;
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use indexmap::IndexMap;
 use ordered_float::NotNan;
@@ -14,19 +17,23 @@ use tokio::sync::{
     oneshot::{self, Receiver},
     Mutex,
 };
+use tokio_util::codec::Decoder as _;
 use uuid::Uuid;
+use vector_lib::codecs::encoding::Framer;
 use vrl::{
     compiler::{state::RuntimeState, Context, TargetValue, TimeZone},
     diagnostic::Formatter,
     value,
 };
 
+pub use self::snapshot::{SnapshotMode, Snapshots, SNAPSHOT_DIR_NAME};
 pub use self::unit_test_components::{
-    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkResult, UnitTestSourceConfig,
-    UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
+    UnitTestSinkCheck, UnitTestSinkConfig, UnitTestSinkEncoding, UnitTestSinkResult,
+    UnitTestSourceConfig, UnitTestStreamSinkConfig, UnitTestStreamSourceConfig,
 };
 use super::{compiler::expand_globs, graph::Graph, transform::get_transform_output_ids, OutputId};
 use crate::{
+    codecs::{DecodingConfig, Encoder},
     conditions::Condition,
     config::{
         self, loading, ComponentKey, Config, ConfigBuilder, ConfigPath, SinkOuter, SourceOuter,
@@ -46,6 +53,8 @@ pub struct UnitTest {
 
 pub struct UnitTestResult {
     pub errors: Vec<String>,
+    /// The events received at each `extract_from` target, keyed by target.
+    pub outputs: BTreeMap<String, Vec<Event>>,
 }
 
 impl UnitTest {
@@ -63,14 +72,16 @@ impl UnitTest {
             .collect::<FuturesUnordered<_>>();
 
         let mut errors = Vec::new();
+        let mut outputs = BTreeMap::new();
         while let Some(partial_result) = in_flight.next().await {
             let partial_result = partial_result.expect(
                 "An unexpected error occurred while executing unit tests. Please try again.",
             );
             errors.extend(partial_result.test_errors);
+            outputs.extend(partial_result.outputs);
         }
 
-        UnitTestResult { errors }
+        UnitTestResult { errors, outputs }
     }
 }
 
@@ -95,14 +106,15 @@ pub async fn build_unit_tests_main(
 pub async fn build_unit_tests(
     mut config_builder: ConfigBuilder,
 ) -> Result<Vec<UnitTest>, Vec<String>> {
-    // Sanitize config by removing existing sources and sinks
-    config_builder.sources = Default::default();
-    config_builder.sinks = Default::default();
+    // Sanitize config by removing existing sources and sinks. They are kept aside so that tests
+    // can feed raw input through a source's decoding and capture a sink's encoded payloads.
+    let sources = std::mem::take(&mut config_builder.sources);
+    let sinks = std::mem::take(&mut config_builder.sinks);
 
     let test_definitions = std::mem::take(&mut config_builder.tests);
     let mut tests = Vec::new();
     let mut build_errors = Vec::new();
-    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder)?;
+    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder, &sources, &sinks)?;
 
     for mut test_definition in test_definitions {
         let test_name = test_definition.name.clone();
@@ -140,20 +152,35 @@ pub struct UnitTestBuildMetadata {
     template_sources: IndexMap<ComponentKey, UnitTestSourceConfig>,
     // A mapping from transform name to unit test sink name.
     sink_ids: HashMap<OutputId, String>,
+    // A mapping from source name to the decoding used to turn raw test inputs into events.
+    source_decoders: HashMap<ComponentKey, DecodingConfig>,
+    // A mapping from sink name to the inputs and encoding of that sink, used to capture the
+    // payloads it would send.
+    sink_targets: HashMap<ComponentKey, UnitTestSinkTarget>,
+}
+
+/// A sink whose encoded payloads can be extracted from in a unit test.
+struct UnitTestSinkTarget {
+    inputs: Vec<String>,
+    encoding: Result<UnitTestSinkEncoding, String>,
 }
 
 impl UnitTestBuildMetadata {
-    pub fn initialize(config_builder: &mut ConfigBuilder) -> Result<Self, Vec<String>> {
+    pub fn initialize(
+        config_builder: &mut ConfigBuilder,
+        sources: &IndexMap<ComponentKey, SourceOuter>,
+        sinks: &IndexMap<ComponentKey, SinkOuter<String>>,
+    ) -> Result<Self, Vec<String>> {
         // A unique id used to name test sources and sinks to avoid name clashes
         let random_id = Uuid::new_v4().to_string();
 
-        let available_insert_targets = config_builder
+        let mut available_insert_targets = config_builder
             .transforms
             .keys()
             .cloned()
             .collect::<HashSet<_>>();
 
-        let source_ids = available_insert_targets
+        let mut source_ids = available_insert_targets
             .iter()
             .map(|key| (key.clone(), format!("{}-{}-{}", key, "source", random_id)))
             .collect::<HashMap<_, _>>();
@@ -170,6 +197,24 @@ impl UnitTestBuildMetadata {
             template_sources.insert(key.clone(), UnitTestSourceConfig::default());
         }
 
+        // Sources that decode raw bytes are replaced by a test source with the same name, so that
+        // any transforms consuming them keep their inputs
+        let global_log_namespace = config_builder.schema.log_namespace();
+        let source_decoders = sources
+            .iter()
+            .filter_map(|(key, source)| {
+                source
+                    .inner
+                    .decoding_config(global_log_namespace)
+                    .map(|decoding| (key.clone(), decoding))
+            })
+            .collect::<HashMap<_, _>>();
+        for key in source_decoders.keys() {
+            available_insert_targets.insert(key.clone());
+            source_ids.insert(key.clone(), key.to_string());
+            template_sources.insert(key.clone(), UnitTestSourceConfig::default());
+        }
+
         let builder = config_builder.clone();
         let available_extract_targets = builder
             .transforms
@@ -183,8 +228,35 @@ impl UnitTestBuildMetadata {
             })
             .collect::<HashSet<_>>();
 
+        // Sinks that encode their payloads are stood in for by a test sink with the same inputs
+        let sink_targets = sinks
+            .iter()
+            .filter_map(|(key, sink)| {
+                let (config, sink_type) = sink.inner.encoding_config()?;
+                let encoding = config
+                    .build(sink_type)
+                    .map(|(framer, serializer)| UnitTestSinkEncoding {
+                        transformer: config.transformer(),
+                        encoder: Encoder::<Framer>::new(framer, serializer),
+                    })
+                    .map_err(|error| {
+                        format!("failed to build encoder for sink '{}': {}", key, error)
+                    });
+                let target = UnitTestSinkTarget {
+                    inputs: sink.inputs.to_vec(),
+                    encoding,
+                };
+                Some((key.clone(), target))
+            })
+            .collect::<HashMap<_, _>>();
+        let sink_target_ids = sink_targets
+            .keys()
+            .map(|key| OutputId::from(key.clone()))
+            .collect::<Vec<_>>();
+
         let sink_ids = available_extract_targets
             .iter()
+            .chain(sink_target_ids.iter())
             .map(|key| {
                 (
                     key.clone(),
@@ -203,15 +275,55 @@ impl UnitTestBuildMetadata {
             source_ids,
             template_sources,
             sink_ids,
+            source_decoders,
+            sink_targets,
         })
     }
 
+    /// The names of the sinks that can be used as `extract_from` targets.
+    fn sink_target_keys(&self) -> HashSet<ComponentKey> {
+        self.sink_targets.keys().cloned().collect()
+    }
+
+    /// Whether the given test sink stands in for a sink under test.
+    fn is_sink_under_test(&self, key: &ComponentKey) -> bool {
+        self.sink_targets.keys().any(|sink_key| {
+            self.sink_ids
+                .get(&OutputId::from(sink_key.clone()))
+                .is_some_and(|id| id == key.id())
+        })
+    }
+
+    /// Gets the encoding of the sink under test, if the given outputs extract from one.
+    fn sink_encoding(
+        &self,
+        extract_from: &[OutputId],
+    ) -> Result<Option<UnitTestSinkEncoding>, String> {
+        let Some(target) = extract_from
+            .iter()
+            .find_map(|id| self.sink_targets.get(&id.component))
+        else {
+            return Ok(None);
+        };
+
+        if extract_from.len() > 1 {
+            return Err(
+                "a sink must be the only `extract_from` target of a test output".to_string(),
+            );
+        }
+        target.encoding.clone().map(Some)
+    }
+
     /// Convert test inputs into sources for use in a unit testing topology
     pub fn hydrate_into_sources(
         &self,
         inputs: &[TestInput],
     ) -> Result<IndexMap<ComponentKey, SourceOuter>, Vec<String>> {
-        let inputs = build_and_validate_inputs(inputs, &self.available_insert_targets)?;
+        let inputs = build_and_validate_inputs(
+            inputs,
+            &self.available_insert_targets,
+            &self.source_decoders,
+        )?;
         let mut template_sources = self.template_sources.clone();
         Ok(inputs
             .into_iter()
@@ -259,8 +371,24 @@ impl UnitTestBuildMetadata {
 
         let mut template_sinks = IndexMap::new();
         let mut test_result_rxs = Vec::new();
+        let mut errors = Vec::new();
         // Add sinks with checks
-        for (ids, checks) in outputs {
+        for (ids, (checks, golden_files)) in outputs {
+            let encoding = match self.sink_encoding(&ids) {
+                Ok(encoding) => encoding,
+                Err(error) => {
+                    errors.push(error);
+                    continue;
+                }
+            };
+            if encoding.is_none() && !golden_files.is_empty() {
+                errors.push(format!(
+                    "`golden_file` is only supported when extracting from a sink, not from {:?}",
+                    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>()
+                ));
+                continue;
+            }
+
             let (tx, rx) = oneshot::channel();
             let sink_ids = ids.clone();
             let sink_config = UnitTestSinkConfig {
@@ -268,6 +396,8 @@ impl UnitTestBuildMetadata {
                 transform_ids: ids.iter().map(|id| id.to_string()).collect(),
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::Checks(checks),
+                encoding,
+                golden_files,
             };
 
             test_result_rxs.push(rx);
@@ -282,19 +412,32 @@ impl UnitTestBuildMetadata {
                 transform_ids: vec![id.to_string()],
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::NoOutputs,
+                encoding: None,
+                golden_files: Vec::new(),
             };
 
             test_result_rxs.push(rx);
             template_sinks.insert(vec![id.clone()], sink_config);
         }
 
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
         let sinks = template_sinks
             .into_iter()
             .map(|(transform_ids, sink_config)| {
-                let transform_ids_str = transform_ids
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<_>>();
+                // A test sink standing in for a sink under test consumes that sink's inputs
+                let transform_ids_str = match transform_ids
+                    .first()
+                    .and_then(|id| self.sink_targets.get(&id.component))
+                {
+                    Some(target) => target.inputs.clone(),
+                    None => transform_ids
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<_>>(),
+                };
                 let sink_ids = transform_ids
                     .iter()
                     .map(|transform_id| {
@@ -332,7 +475,7 @@ fn get_relevant_test_components(
                 source
                     .to_string()
                     .rsplit_once("-source-")
-                    .unwrap_or(("", ""))
+                    .unwrap_or((source.id(), ""))
                     .0
             ));
         } else {
@@ -361,7 +504,7 @@ async fn build_unit_test(
         &transform_only_config.sinks,
         transform_only_config.schema,
     );
-    let test = test.resolve_outputs(&transform_only_graph)?;
+    let test = test.resolve_outputs(&transform_only_graph, &metadata.sink_target_keys())?;
 
     let sources = metadata.hydrate_into_sources(&test.inputs)?;
     let (test_result_rxs, sinks) =
@@ -416,6 +559,16 @@ async fn build_unit_test(
             .filter(|input| valid_inputs.contains_key(input))
             .collect();
     }
+    // Likewise for sinks under test, which keep all of the original sink's inputs
+    for (key, sink) in config_builder.sinks.iter_mut() {
+        if metadata.is_sink_under_test(key) {
+            let inputs = std::mem::take(&mut sink.inputs);
+            sink.inputs = inputs
+                .into_iter()
+                .filter(|input| valid_inputs.contains_key(input))
+                .collect();
+        }
+    }
 
     if let Some(sink) = get_loose_end_outputs_sink(&config_builder) {
         config_builder
@@ -424,7 +577,15 @@ async fn build_unit_test(
     }
     let config = config_builder.build()?;
     let diff = config::ConfigDiff::initial(&config);
-    let pieces = TopologyPieces::build(&config, &diff, HashMap::new(), Default::default()).await?;
+    let pieces = TopologyPieces::build(
+        &config,
+        &diff,
+        HashMap::new(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    )
+    .await?;
 
     Ok(UnitTest {
         name: test.name,
@@ -475,6 +636,8 @@ fn get_loose_end_outputs_sink(config: &ConfigBuilder) -> Option<SinkOuter<String
             transform_ids: vec![],
             result_tx: Arc::new(Mutex::new(None)),
             check: UnitTestSinkCheck::NoOp,
+            encoding: None,
+            golden_files: Vec::new(),
         };
         Some(SinkOuter::new(loose_end_outputs, noop_sink))
     }
@@ -483,6 +646,7 @@ fn get_loose_end_outputs_sink(config: &ConfigBuilder) -> Option<SinkOuter<String
 fn build_and_validate_inputs(
     test_inputs: &[TestInput],
     available_insert_targets: &HashSet<ComponentKey>,
+    source_decoders: &HashMap<ComponentKey, DecodingConfig>,
 ) -> Result<HashMap<ComponentKey, Vec<Event>>, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut errors = Vec::new();
@@ -493,14 +657,16 @@ fn build_and_validate_inputs(
 
     for (index, input) in test_inputs.iter().enumerate() {
         if available_insert_targets.contains(&input.insert_at) {
-            match build_input_event(input) {
-                Ok(input_event) => {
+            let input_events = match source_decoders.get(&input.insert_at) {
+                Some(decoding) => build_decoded_input_events(input, decoding),
+                None => build_input_event(input).map(|input_event| vec![input_event]),
+            };
+            match input_events {
+                Ok(input_events) => {
                     inputs
                         .entry(input.insert_at.clone())
-                        .and_modify(|events: &mut Vec<Event>| {
-                            events.push(input_event.clone());
-                        })
-                        .or_insert_with(|| vec![input_event]);
+                        .or_insert_with(Vec::new)
+                        .extend(input_events);
                 }
                 Err(error) => errors.push(error),
             }
@@ -519,10 +685,12 @@ fn build_and_validate_inputs(
     }
 }
 
+type TestOutputChecks = (Vec<Vec<Condition>>, Vec<PathBuf>);
+
 fn build_outputs(
     test_outputs: &[TestOutput],
-) -> Result<IndexMap<Vec<OutputId>, Vec<Vec<Condition>>>, Vec<String>> {
-    let mut outputs: IndexMap<Vec<OutputId>, Vec<Vec<Condition>>> = IndexMap::new();
+) -> Result<IndexMap<Vec<OutputId>, TestOutputChecks>, Vec<String>> {
+    let mut outputs: IndexMap<Vec<OutputId>, TestOutputChecks> = IndexMap::new();
     let mut errors = Vec::new();
 
     for output in test_outputs {
@@ -543,10 +711,11 @@ fn build_outputs(
             }
         }
 
-        outputs
+        let (existing_conditions, golden_files) = outputs
             .entry(output.extract_from.clone().to_vec())
-            .and_modify(|existing_conditions| existing_conditions.push(conditions.clone()))
-            .or_insert(vec![conditions.clone()]);
+            .or_default();
+        existing_conditions.push(conditions);
+        golden_files.extend(output.golden_file.clone());
     }
 
     if errors.is_empty() {
@@ -556,6 +725,50 @@ fn build_outputs(
     }
 }
 
+/// Runs a raw test input through the framing and decoding of the source it is inserted at.
+///
+/// Only the decoded events are produced; source metadata such as the host or the ingest timestamp
+/// is not added, so that test outputs stay deterministic.
+fn build_decoded_input_events(
+    input: &TestInput,
+    decoding: &DecodingConfig,
+) -> Result<Vec<Event>, String> {
+    if input.type_str != "raw" {
+        return Err(format!(
+            "input type '{}' is not supported when inserting at source '{}', expected 'raw'",
+            input.type_str, input.insert_at
+        ));
+    }
+    let value = input
+        .value
+        .as_ref()
+        .ok_or_else(|| "input type 'raw' requires the field 'value'".to_string())?;
+
+    let mut decoder = decoding.build().map_err(|error| {
+        format!(
+            "failed to build decoder for source '{}': {}",
+            input.insert_at, error
+        )
+    })?;
+
+    let mut buffer = BytesMut::from(value.as_bytes());
+    let mut events = Vec::new();
+    loop {
+        match decoder.decode_eof(&mut buffer) {
+            Ok(Some((decoded, _))) => events.extend(decoded),
+            Ok(None) => break,
+            Err(error) => {
+                return Err(format!(
+                    "failed to decode input for source '{}': {}",
+                    input.insert_at, error
+                ))
+            }
+        }
+    }
+
+    Ok(events)
+}
+
 fn build_input_event(input: &TestInput) -> Result<Event, String> {
     match input.type_str.as_ref() {
         "raw" => match input.value.as_ref() {
@@ -0,0 +1,459 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use vector_lib::lookup::lookup_v2::{parse_value_path, OwnedSegment, OwnedValuePath};
+
+use crate::event::Event;
+
+/// The name of the directory, next to the configuration, in which snapshots are stored.
+pub const SNAPSHOT_DIR_NAME: &str = "__snapshots__";
+
+/// Whether snapshots are recorded or compared against.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SnapshotMode {
+    /// Compare the events against the snapshot files, failing tests that have none.
+    Compare,
+    /// Record the events into the snapshot files, replacing previous recordings.
+    Update,
+}
+
+/// Records the events produced at each `extract_from` target of a unit test, or compares them
+/// against a previous recording.
+#[derive(Clone, Debug)]
+pub struct Snapshots {
+    dir: PathBuf,
+    mode: SnapshotMode,
+    ignore_paths: Vec<String>,
+}
+
+/// The contents of a snapshot file, holding the recorded events of a single test.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Snapshot {
+    /// Paths removed from every event before recording or comparing it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ignore_paths: Vec<String>,
+
+    /// The events recorded for each `extract_from` target.
+    outputs: BTreeMap<String, Vec<JsonValue>>,
+}
+
+impl Snapshots {
+    /// Creates a new set of snapshots stored in `dir`.
+    ///
+    /// The `ignore_paths` are removed from every event, and are stored in the snapshot so that later
+    /// runs ignore them too.
+    pub fn new(
+        dir: PathBuf,
+        mode: SnapshotMode,
+        ignore_paths: Vec<String>,
+    ) -> Result<Self, String> {
+        parse_ignore_paths(&ignore_paths)?;
+        Ok(Self {
+            dir,
+            mode,
+            ignore_paths,
+        })
+    }
+
+    /// The path of the snapshot file for the given test.
+    ///
+    /// Characters that aren't safe in file names are replaced, in which case a hash of the test
+    /// name is appended so that names differing only in those characters don't share a file.
+    pub fn path(&self, test_name: &str) -> PathBuf {
+        let mut file_name = test_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        if file_name != test_name {
+            file_name = format!(
+                "{}-{:08x}",
+                file_name,
+                seahash::hash(test_name.as_bytes()) as u32
+            );
+        }
+        self.dir.join(format!("{}.json", file_name))
+    }
+
+    /// Records or compares the outputs of a test, returning any errors.
+    pub fn check(&self, test_name: &str, outputs: &BTreeMap<String, Vec<Event>>) -> Vec<String> {
+        let path = self.path(test_name);
+        let result = match self.mode {
+            SnapshotMode::Update => self.record(&path, outputs),
+            SnapshotMode::Compare => self.compare(&path, outputs),
+        };
+        result.unwrap_or_else(|error| vec![error])
+    }
+
+    fn record(
+        &self,
+        path: &Path,
+        outputs: &BTreeMap<String, Vec<Event>>,
+    ) -> Result<Vec<String>, String> {
+        let ignore_paths = parse_ignore_paths(&self.ignore_paths)?;
+        let snapshot = Snapshot {
+            ignore_paths: self.ignore_paths.clone(),
+            outputs: outputs
+                .iter()
+                .map(|(target, events)| (target.clone(), to_json(events, &ignore_paths)))
+                .collect(),
+        };
+
+        let contents = serde_json::to_string_pretty(&snapshot)
+            .map_err(|error| format!("unable to serialize snapshot: {}", error))?;
+        fs::create_dir_all(&self.dir).map_err(|error| {
+            format!(
+                "unable to create snapshot directory {:?}: {}",
+                self.dir, error
+            )
+        })?;
+        fs::write(path, contents + "\n")
+            .map_err(|error| format!("unable to write snapshot {:?}: {}", path, error))?;
+
+        Ok(Vec::new())
+    }
+
+    fn compare(
+        &self,
+        path: &Path,
+        outputs: &BTreeMap<String, Vec<Event>>,
+    ) -> Result<Vec<String>, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Err(format!(
+                    "no snapshot found at {:?}, run with `--update-snapshots` to record one",
+                    path
+                ))
+            }
+            Err(error) => return Err(format!("unable to read snapshot {:?}: {}", path, error)),
+        };
+        let snapshot: Snapshot = serde_json::from_str(&contents)
+            .map_err(|error| format!("unable to parse snapshot {:?}: {}", path, error))?;
+
+        let mut ignore_paths = snapshot.ignore_paths.clone();
+        ignore_paths.extend(self.ignore_paths.iter().cloned());
+        let ignore_paths = parse_ignore_paths(&ignore_paths)?;
+
+        let mut errors = Vec::new();
+        for (target, expected) in snapshot.outputs.iter() {
+            let Some(actual) = outputs.get(target) else {
+                errors.push(format!(
+                    "snapshot for {:?} failed: target is no longer extracted from",
+                    target
+                ));
+                continue;
+            };
+
+            let mut expected = expected.clone();
+            for event in expected.iter_mut() {
+                for path in ignore_paths.iter() {
+                    remove_path(event, path);
+                }
+            }
+            let actual = to_json(actual, &ignore_paths);
+
+            let diff = diff_events(&expected, &actual);
+            if !diff.is_empty() {
+                errors.push(format!(
+                    "snapshot for {:?} failed, run with `--update-snapshots` if this change is expected:\n  {}",
+                    target,
+                    diff.join("\n  ")
+                ));
+            }
+        }
+        for target in outputs.keys() {
+            if !snapshot.outputs.contains_key(target) {
+                errors.push(format!(
+                    "snapshot for {:?} failed: no events were recorded for this target",
+                    target
+                ));
+            }
+        }
+
+        Ok(errors)
+    }
+}
+
+fn parse_ignore_paths(ignore_paths: &[String]) -> Result<Vec<OwnedValuePath>, String> {
+    ignore_paths
+        .iter()
+        .map(|path| {
+            parse_value_path(path)
+                .map_err(|error| format!("invalid snapshot ignore path {:?}: {}", path, error))
+        })
+        .collect()
+}
+
+fn to_json(events: &[Event], ignore_paths: &[OwnedValuePath]) -> Vec<JsonValue> {
+    events
+        .iter()
+        .map(|event| {
+            let mut value = match event {
+                Event::Log(log) => serde_json::to_value(log),
+                Event::Metric(metric) => serde_json::to_value(metric),
+                Event::Trace(trace) => serde_json::to_value(trace),
+            }
+            .unwrap_or_else(|_| JsonValue::Object(Default::default()));
+            for path in ignore_paths {
+                remove_path(&mut value, path);
+            }
+            value
+        })
+        .collect()
+}
+
+fn remove_path(value: &mut JsonValue, path: &OwnedValuePath) {
+    let Some((last, parents)) = path.segments.split_last() else {
+        return;
+    };
+
+    let mut current = value;
+    for segment in parents {
+        match step(current, segment) {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+
+    match (current, last) {
+        (JsonValue::Object(map), OwnedSegment::Field(field)) => {
+            map.remove(field.as_str());
+        }
+        (JsonValue::Object(map), OwnedSegment::Coalesce(fields)) => {
+            if let Some(field) = fields.iter().find(|field| map.contains_key(field.as_str())) {
+                map.remove(field.as_str());
+            }
+        }
+        (JsonValue::Array(items), OwnedSegment::Index(index)) => {
+            if let Some(index) = array_index(items.len(), *index) {
+                items.remove(index);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn step<'a>(value: &'a mut JsonValue, segment: &OwnedSegment) -> Option<&'a mut JsonValue> {
+    match (value, segment) {
+        (JsonValue::Object(map), OwnedSegment::Field(field)) => map.get_mut(field.as_str()),
+        (JsonValue::Object(map), OwnedSegment::Coalesce(fields)) => {
+            let field = fields
+                .iter()
+                .find(|field| map.contains_key(field.as_str()))?;
+            map.get_mut(field.as_str())
+        }
+        (JsonValue::Array(items), OwnedSegment::Index(index)) => {
+            let index = array_index(items.len(), *index)?;
+            items.get_mut(index)
+        }
+        _ => None,
+    }
+}
+
+fn array_index(len: usize, index: isize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index as usize).filter(|index| *index < len)
+    }
+}
+
+/// Produces a field-level diff between the expected and actual events.
+fn diff_events(expected: &[JsonValue], actual: &[JsonValue]) -> Vec<String> {
+    let mut diff = Vec::new();
+    if expected.len() != actual.len() {
+        diff.push(format!(
+            "expected {} events, got {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) => {
+                let expected_fields = flatten(expected);
+                let actual_fields = flatten(actual);
+                let mut field_diff = Vec::new();
+                for (path, expected_value) in expected_fields.iter() {
+                    match actual_fields.get(path) {
+                        Some(actual_value) if actual_value == expected_value => {}
+                        Some(actual_value) => field_diff.push(format!(
+                            "  {}: expected {}, got {}",
+                            path, expected_value, actual_value
+                        )),
+                        None => field_diff.push(format!(
+                            "  {}: expected {}, got nothing",
+                            path, expected_value
+                        )),
+                    }
+                }
+                for (path, actual_value) in actual_fields.iter() {
+                    if !expected_fields.contains_key(path) {
+                        field_diff.push(format!("  {}: unexpected {}", path, actual_value));
+                    }
+                }
+                if !field_diff.is_empty() {
+                    diff.push(format!("event[{}]:", index));
+                    diff.extend(field_diff);
+                }
+            }
+            (Some(expected), None) => diff.push(format!("event[{}]: missing {}", index, expected)),
+            (None, Some(actual)) => diff.push(format!("event[{}]: unexpected {}", index, actual)),
+            (None, None) => {}
+        }
+    }
+
+    diff
+}
+
+/// Flattens a JSON value into a map from each leaf path to its value.
+fn flatten(value: &JsonValue) -> BTreeMap<String, JsonValue> {
+    fn flatten_into(path: String, value: &JsonValue, fields: &mut BTreeMap<String, JsonValue>) {
+        match value {
+            JsonValue::Object(map) if !map.is_empty() => {
+                for (key, value) in map {
+                    flatten_into(format!("{}.{}", path, quote_field(key)), value, fields);
+                }
+            }
+            JsonValue::Array(items) if !items.is_empty() => {
+                for (index, value) in items.iter().enumerate() {
+                    flatten_into(format!("{}[{}]", path, index), value, fields);
+                }
+            }
+            _ => {
+                let path = if path.is_empty() {
+                    ".".to_string()
+                } else {
+                    path
+                };
+                fields.insert(path, value.clone());
+            }
+        }
+    }
+
+    let mut fields = BTreeMap::new();
+    flatten_into(String::new(), value, &mut fields);
+    fields
+}
+
+fn quote_field(field: &str) -> String {
+    if !field.is_empty()
+        && field
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '@')
+    {
+        field.to_string()
+    } else {
+        format!("{:?}", field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn outputs(message: &str) -> BTreeMap<String, Vec<Event>> {
+        let mut event = LogEvent::from(message);
+        event.insert("host", "localhost");
+        BTreeMap::from([("foo".to_string(), vec![Event::Log(event)])])
+    }
+
+    #[test]
+    fn records_and_compares_snapshots() {
+        let dir = crate::test_util::temp_dir();
+        let snapshots =
+            Snapshots::new(dir.clone(), SnapshotMode::Update, vec![".host".to_string()]).unwrap();
+        assert!(snapshots.check("my_test", &outputs("hello")).is_empty());
+
+        let recorded: JsonValue =
+            serde_json::from_str(&fs::read_to_string(dir.join("my_test.json")).unwrap()).unwrap();
+        assert_eq!(recorded["ignore_paths"], json!([".host"]));
+        assert!(recorded["outputs"]["foo"][0].get("host").is_none());
+
+        let snapshots = Snapshots::new(dir, SnapshotMode::Compare, Vec::new()).unwrap();
+        assert!(snapshots.check("my_test", &outputs("hello")).is_empty());
+
+        let errors = snapshots.check("my_test", &outputs("goodbye"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains(r#".message: expected "hello", got "goodbye""#));
+    }
+
+    #[test]
+    fn fails_tests_without_snapshots() {
+        let snapshots = Snapshots::new(
+            crate::test_util::temp_dir(),
+            SnapshotMode::Compare,
+            Vec::new(),
+        )
+        .unwrap();
+        let errors = snapshots.check("my_test", &outputs("hello"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("no snapshot found at"));
+    }
+
+    #[test]
+    fn sanitized_names_do_not_collide() {
+        let snapshots = Snapshots::new(
+            crate::test_util::temp_dir(),
+            SnapshotMode::Update,
+            Vec::new(),
+        )
+        .unwrap();
+        let paths = ["my test", "my/test", "my_test"].map(|name| snapshots.path(name));
+        assert_ne!(paths[0], paths[1]);
+        assert_ne!(paths[0], paths[2]);
+        assert_ne!(paths[1], paths[2]);
+        assert!(paths[2].ends_with("my_test.json"));
+    }
+
+    #[test]
+    fn diffs_fields() {
+        let diff = diff_events(
+            &[json!({"a": {"b": 1, "c": [1, 2]}, "d": "x"})],
+            &[json!({"a": {"b": 2, "c": [1]}, "e": true}), json!({})],
+        );
+        assert_eq!(
+            diff,
+            vec![
+                "expected 1 events, got 2",
+                "event[0]:",
+                "  .a.b: expected 1, got 2",
+                "  .a.c[1]: expected 2, got nothing",
+                "  .d: expected \"x\", got nothing",
+                "  .e: unexpected true",
+                "event[1]: unexpected {}",
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_ignored_paths() {
+        let mut value = json!({"a": {"b": 1, "c": [1, 2]}, "timestamp": "now"});
+        for path in parse_ignore_paths(&[
+            "timestamp".to_string(),
+            ".a.c[-1]".to_string(),
+            ".missing.field".to_string(),
+        ])
+        .unwrap()
+        {
+            remove_path(&mut value, &path);
+        }
+        assert_eq!(value, json!({"a": {"b": 1, "c": [1]}}));
+    }
+}
@@ -1235,3 +1235,202 @@ async fn test_glob_input() {
     let mut tests = build_unit_tests(config).await.unwrap();
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
+
+#[tokio::test]
+async fn test_source_decoding() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [sources.in]
+          type = "socket"
+          mode = "tcp"
+          address = "0.0.0.0:9000"
+          decoding.codec = "json"
+
+        [transforms.foo]
+          type = "remap"
+          inputs = [ "in" ]
+          source = '.env = "prod"'
+
+        [[tests]]
+          name = "source decoding test"
+
+          [[tests.inputs]]
+            insert_at = "in"
+            value = "{\"message\": \"first\"}\n{\"message\": \"second\"}"
+
+          [[tests.outputs]]
+            extract_from = "foo"
+
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = """
+                assert_eq!(.message, "first", "incorrect message")
+                assert_eq!(.env, "prod", "incorrect env")
+              """
+
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = """
+                assert_eq!(.message, "second", "incorrect message")
+              """
+    "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+}
+
+#[tokio::test]
+async fn test_source_decoding_rejects_non_raw_input() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [sources.in]
+          type = "socket"
+          mode = "tcp"
+          address = "0.0.0.0:9000"
+
+        [transforms.foo]
+          type = "remap"
+          inputs = [ "in" ]
+          source = '.env = "prod"'
+
+        [[tests]]
+          name = "broken test"
+
+          [[tests.inputs]]
+            type = "log"
+            insert_at = "in"
+
+            [tests.inputs.log_fields]
+              message = "test"
+
+          [[tests.outputs]]
+            extract_from = "foo"
+    "#})
+    .unwrap();
+
+    let errs = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errs,
+        vec![indoc! {r#"
+            Failed to build test 'broken test':
+              input type 'log' is not supported when inserting at source 'in', expected 'raw'"#}
+        .to_owned(),]
+    );
+}
+
+#[tokio::test]
+async fn test_sink_encoding() {
+    let golden_file = crate::test_util::temp_file();
+    std::fs::write(
+        &golden_file,
+        "{\"env\":\"prod\",\"message\":\"first\"}\n{\"env\":\"prod\",\"message\":\"second\"}\n",
+    )
+    .unwrap();
+
+    let config: ConfigBuilder = toml::from_str(&format!(
+        indoc! {r#"
+            [sources.in]
+              type = "socket"
+              mode = "tcp"
+              address = "0.0.0.0:9000"
+              decoding.codec = "json"
+
+            [transforms.foo]
+              type = "remap"
+              inputs = [ "in" ]
+              source = '.env = "prod"'
+
+            [sinks.out]
+              type = "console"
+              inputs = [ "foo" ]
+              encoding.codec = "json"
+              encoding.except_fields = [ "timestamp" ]
+
+            [[tests]]
+              name = "sink encoding test"
+
+              [[tests.inputs]]
+                insert_at = "in"
+                value = "{{\"message\": \"first\"}}\n{{\"message\": \"second\"}}"
+
+              [[tests.outputs]]
+                extract_from = "out"
+                golden_file = "{}"
+
+                [[tests.outputs.conditions]]
+                  type = "vrl"
+                  source = """
+                    assert_eq!(.message, s'{{"env":"prod","message":"first"}}', "incorrect payload")
+                  """
+        "#},
+        golden_file.display()
+    ))
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.remove(0).run().await.errors, Vec::<String>::new());
+
+    std::fs::write(&golden_file, "{\"message\":\"first\"}\n").unwrap();
+    let config: ConfigBuilder = toml::from_str(&format!(
+        indoc! {r#"
+            [transforms.foo]
+              type = "remap"
+              inputs = [ "in" ]
+              source = '.env = "prod"'
+
+            [sinks.out]
+              type = "console"
+              inputs = [ "foo" ]
+              encoding.codec = "json"
+
+            [[tests]]
+              name = "sink encoding test"
+
+              [[tests.inputs]]
+                type = "log"
+                insert_at = "foo"
+
+                [tests.inputs.log_fields]
+                  message = "first"
+
+              [[tests.outputs]]
+                extract_from = "out"
+                golden_file = "{}"
+        "#},
+        golden_file.display()
+    ))
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert!(!tests.remove(0).run().await.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_golden_file_requires_sink() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.foo]
+          type = "remap"
+          inputs = [ "ignored" ]
+          source = '.env = "prod"'
+
+        [[tests]]
+          name = "broken test"
+
+          [[tests.inputs]]
+            insert_at = "foo"
+            value = "test"
+
+          [[tests.outputs]]
+            extract_from = "foo"
+            golden_file = "expected.txt"
+    "#})
+    .unwrap();
+
+    let errs = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errs,
+        vec![indoc! {r#"
+            Failed to build test 'broken test':
+              `golden_file` is only supported when extracting from a sink, not from ["foo"]"#}
+        .to_owned(),]
+    );
+}
@@ -1,17 +1,24 @@
-use std::sync::Arc;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
+use bytes::{Bytes, BytesMut};
 use futures::{stream, Sink, Stream};
 use futures_util::{future, stream::BoxStream, FutureExt, StreamExt};
 use tokio::sync::{oneshot, Mutex};
+use tokio_util::codec::Encoder as _;
+use vector_lib::codecs::encoding::Framer;
 use vector_lib::configurable::configurable_component;
 use vector_lib::{
     config::{DataType, Input, LogNamespace},
-    event::Event,
+    event::{Event, LogEvent},
     schema,
     sink::{StreamSink, VectorSink},
 };
 
 use crate::{
+    codecs::{Encoder, Transformer},
     conditions::Condition,
     config::{
         AcknowledgementsConfig, SinkConfig, SinkContext, SourceConfig, SourceContext, SourceOutput,
@@ -127,10 +134,48 @@ pub enum UnitTestSinkCheck {
     NoOp,
 }
 
+/// The encoding of a sink under test, used to capture the payloads it would send.
+#[derive(Clone, Debug)]
+pub struct UnitTestSinkEncoding {
+    pub transformer: Transformer,
+    pub encoder: Encoder<Framer>,
+}
+
+impl UnitTestSinkEncoding {
+    /// Encodes the events the way the sink under test would.
+    ///
+    /// Returns a log event per input event whose `message` holds that event's serialized bytes,
+    /// along with the framed payload of all events.
+    fn encode(&mut self, events: Vec<Event>) -> Result<(Vec<Event>, Bytes), String> {
+        let mut payload = BytesMut::new();
+        let mut encoded_events = Vec::with_capacity(events.len());
+
+        for mut event in events {
+            self.transformer.transform(&mut event);
+
+            let mut serialized = BytesMut::new();
+            self.encoder
+                .serialize(event.clone(), &mut serialized)
+                .map_err(|error| format!("failed to encode event: {}", error))?;
+            encoded_events.push(Event::Log(LogEvent::from_bytes_legacy(
+                &serialized.freeze(),
+            )));
+
+            self.encoder
+                .encode(event, &mut payload)
+                .map_err(|error| format!("failed to encode event: {}", error))?;
+        }
+
+        Ok((encoded_events, payload.freeze()))
+    }
+}
+
 #[derive(Debug)]
 pub struct UnitTestSinkResult {
     pub test_name: String,
     pub test_errors: Vec<String>,
+    // The targets this sink extracted from and the events it received, for sinks checking outputs
+    pub outputs: Option<(String, Vec<Event>)>,
 }
 
 /// Configuration for the `unit_test` sink.
@@ -152,6 +197,14 @@ pub struct UnitTestSinkConfig {
     #[serde(skip)]
     #[derivative(Debug = "ignore")]
     pub check: UnitTestSinkCheck,
+
+    /// Encoding of the sink under test, if this sink stands in for one.
+    #[serde(skip)]
+    pub encoding: Option<UnitTestSinkEncoding>,
+
+    /// Files holding the payload expected from the sink under test.
+    #[serde(skip)]
+    pub golden_files: Vec<PathBuf>,
 }
 
 impl_generate_config_from_default!(UnitTestSinkConfig);
@@ -166,6 +219,8 @@ impl SinkConfig for UnitTestSinkConfig {
             transform_ids: self.transform_ids.clone(),
             result_tx: tx,
             check: self.check.clone(),
+            encoding: self.encoding.clone(),
+            golden_files: self.golden_files.clone(),
         };
         let healthcheck = future::ok(()).boxed();
 
@@ -187,6 +242,8 @@ pub struct UnitTestSink {
     // None for NoOp test sinks
     pub result_tx: Option<oneshot::Sender<UnitTestSinkResult>>,
     pub check: UnitTestSinkCheck,
+    pub encoding: Option<UnitTestSinkEncoding>,
+    pub golden_files: Vec<PathBuf>,
 }
 
 #[async_trait::async_trait]
@@ -196,14 +253,35 @@ impl StreamSink<Event> for UnitTestSink {
         let mut result = UnitTestSinkResult {
             test_name: self.test_name,
             test_errors: Vec::new(),
+            outputs: None,
         };
 
         while let Some(event) = input.next().await {
             output_events.push(event);
         }
 
+        // When standing in for a sink under test, check its encoded payloads rather than the
+        // events themselves.
+        let mut payload = None;
+        if let Some(encoding) = self.encoding.as_mut() {
+            match encoding.encode(output_events) {
+                Ok((encoded_events, encoded_payload)) => {
+                    output_events = encoded_events;
+                    payload = Some(encoded_payload);
+                }
+                Err(error) => {
+                    output_events = Vec::new();
+                    result.test_errors.push(format!(
+                        "checks for sink {:?} failed: {}",
+                        self.transform_ids, error
+                    ));
+                }
+            }
+        }
+
         match self.check {
             UnitTestSinkCheck::Checks(checks) => {
+                result.outputs = Some((self.transform_ids.join(","), output_events.clone()));
                 if output_events.is_empty() {
                     result
                         .test_errors
@@ -241,6 +319,17 @@ impl StreamSink<Event> for UnitTestSink {
                         result.test_errors.extend(check_errors);
                     }
 
+                    if let Some(payload) = payload.as_ref() {
+                        for golden_file in self.golden_files.iter() {
+                            if let Err(error) = check_golden_file(golden_file, payload) {
+                                result.test_errors.push(format!(
+                                    "check for sink {:?} failed: {}",
+                                    self.transform_ids, error
+                                ));
+                            }
+                        }
+                    }
+
                     // If there are errors, add a summary of events received
                     if !result.test_errors.is_empty() {
                         result.test_errors.push(format!(
@@ -316,6 +405,22 @@ impl SinkConfig for UnitTestStreamSinkConfig {
     }
 }
 
+fn check_golden_file(golden_file: &Path, payload: &Bytes) -> Result<(), String> {
+    let expected = std::fs::read(golden_file)
+        .map_err(|error| format!("unable to read golden file {:?}: {}", golden_file, error))?;
+
+    if expected == payload.as_ref() {
+        Ok(())
+    } else {
+        Err(format!(
+            "payload does not match golden file {:?}:\n  expected: {:?}\n  actual: {:?}",
+            golden_file,
+            String::from_utf8_lossy(&expected),
+            String::from_utf8_lossy(payload)
+        ))
+    }
+}
+
 fn events_to_string(events: &[Event]) -> String {
     events
         .iter()
@@ -0,0 +1,205 @@
+//! Routing of the events that sinks permanently reject to `dead_letter` sources.
+//!
+//! Sinks with `dead_letter.enabled` hold on to a copy of each event they receive, along with a
+//! dedicated batch notifier. When the sink rejects the event, the copy is annotated with the
+//! details of the rejection and broadcast to the `dead_letter` sources of the same topology.
+
+use tokio::sync::broadcast;
+use vector_lib::lookup::path;
+
+use crate::{
+    config::ComponentKey,
+    event::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventArray, EventContainer,
+        EventFinalizer, EventMetadata, Finalizable, Rejection,
+    },
+};
+
+/// The number of rejected events buffered for each `dead_letter` source. Events are dropped
+/// when a source falls further behind than this.
+const DEAD_LETTER_BUFFER_SIZE: usize = 1000;
+
+/// An event rejected by a sink.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    /// The sink that rejected the event.
+    pub sink: ComponentKey,
+    /// The rejected event, annotated with the details of the rejection.
+    pub event: Event,
+}
+
+/// The channel carrying the events rejected by the sinks of a topology to its `dead_letter`
+/// sources.
+///
+/// It is shared by all the components built for a running topology, including across reloads.
+#[derive(Clone, Debug)]
+pub struct DeadLetters {
+    sender: broadcast::Sender<DeadLetter>,
+}
+
+impl Default for DeadLetters {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(DEAD_LETTER_BUFFER_SIZE).0,
+        }
+    }
+}
+
+impl DeadLetters {
+    /// Subscribes to the events rejected by sinks with dead-lettering enabled.
+    pub fn subscribe(&self) -> broadcast::Receiver<DeadLetter> {
+        self.sender.subscribe()
+    }
+
+    /// Tracks the events flowing into a sink, so that the ones it rejects are sent to the
+    /// `dead_letter` sources.
+    ///
+    /// As this holds a copy of each event until the sink finalizes it, events are only tracked
+    /// while at least one `dead_letter` source is running.
+    pub(crate) fn track(&self, sink: &ComponentKey, mut events: EventArray) -> EventArray {
+        if self.sender.receiver_count() == 0 {
+            return events;
+        }
+
+        let mut pending = Vec::with_capacity(events.len());
+        match &mut events {
+            EventArray::Logs(logs) => {
+                for log in logs {
+                    pending.push(hold(log.clone().into(), log.metadata_mut()));
+                }
+            }
+            EventArray::Metrics(metrics) => {
+                for metric in metrics {
+                    pending.push(hold(metric.clone().into(), metric.metadata_mut()));
+                }
+            }
+            EventArray::Traces(traces) => {
+                for trace in traces {
+                    pending.push(hold(trace.clone().into(), trace.metadata_mut()));
+                }
+            }
+        }
+
+        let sink = sink.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            for (mut event, receiver) in pending {
+                if let (BatchStatus::Rejected, rejection) = receiver.with_rejection().await {
+                    annotate(&mut event, &sink, rejection);
+                    // An error means that all `dead_letter` sources have since been stopped.
+                    _ = sender.send(DeadLetter {
+                        sink: sink.clone(),
+                        event,
+                    });
+                }
+            }
+        });
+
+        events
+    }
+}
+
+/// Attaches a new batch notifier to an event, returning the copy of the event to send when it
+/// is rejected along with the receiver for its status.
+fn hold(mut copy: Event, metadata: &mut EventMetadata) -> (Event, BatchStatusReceiver) {
+    // The copy must not keep the original finalizers alive, otherwise the source would only be
+    // acknowledged once the copy is dropped.
+    drop(copy.take_finalizers());
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    metadata.add_finalizer(EventFinalizer::new(batch));
+    (copy, receiver)
+}
+
+/// Annotates a rejected event with the details of the rejection in its metadata.
+fn annotate(event: &mut Event, sink: &ComponentKey, rejection: Option<Rejection>) {
+    let rejection = rejection.unwrap_or_else(|| Rejection::new("Event was rejected by the sink."));
+    let metadata = event.metadata_mut().value_mut();
+    metadata.insert(path!("vector", "dead_letter", "reason"), rejection.reason);
+    if let Some(http_status) = rejection.http_status {
+        metadata.insert(
+            path!("vector", "dead_letter", "http_status"),
+            i64::from(http_status),
+        );
+    }
+    metadata.insert(path!("vector", "dead_letter", "component_id"), sink.id());
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::value::Value;
+
+    use super::*;
+    use crate::event::{EventStatus, LogEvent};
+
+    fn dead_letter_field<'a>(event: &'a Event, field: &str) -> Option<&'a Value> {
+        event
+            .metadata()
+            .value()
+            .get(path!("vector", "dead_letter", field))
+    }
+
+    #[test]
+    fn skips_events_without_subscribers() {
+        let dead_letters = DeadLetters::default();
+        let events = dead_letters.track(
+            &ComponentKey::from("out"),
+            vec![LogEvent::from("rejected")].into(),
+        );
+
+        let EventArray::Logs(logs) = events else {
+            panic!("expected logs");
+        };
+        assert!(logs[0].metadata().finalizers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sends_rejected_events() {
+        let dead_letters = DeadLetters::default();
+        let mut subscriber = dead_letters.subscribe();
+        let sink = ComponentKey::from("out");
+
+        let (source_batch, source_receiver) = BatchNotifier::new_with_receiver();
+        let events = ["rejected", "delivered"]
+            .into_iter()
+            .map(|message| LogEvent::from(message).with_batch_notifier(&source_batch))
+            .collect::<Vec<_>>();
+        drop(source_batch);
+
+        let events = dead_letters.track(&sink, events.into());
+        let EventArray::Logs(mut logs) = events else {
+            panic!("expected logs");
+        };
+        let delivered = logs.pop().unwrap();
+        let rejected = logs.pop().unwrap();
+        delivered.metadata().update_status(EventStatus::Delivered);
+        let mut rejected = Event::from(rejected);
+        rejected
+            .take_finalizers()
+            .reject(&Rejection::new("mapping conflict").with_http_status(400));
+        drop(delivered);
+        drop(rejected);
+
+        // The source is acknowledged without waiting for the dead-letter copy.
+        assert_eq!(source_receiver.await, BatchStatus::Rejected);
+
+        let dead_letter = subscriber.recv().await.unwrap();
+        assert_eq!(dead_letter.sink, sink);
+        let event = dead_letter.event;
+        assert_eq!(event.as_log()["message"], "rejected".into());
+        assert!(event.metadata().finalizers().is_empty());
+        assert_eq!(
+            dead_letter_field(&event, "reason"),
+            Some(&Value::from("mapping conflict"))
+        );
+        assert_eq!(
+            dead_letter_field(&event, "http_status"),
+            Some(&Value::from(400_i64))
+        );
+        assert_eq!(
+            dead_letter_field(&event, "component_id"),
+            Some(&Value::from("out"))
+        );
+        assert!(subscriber.try_recv().is_err());
+    }
+}
@@ -9,7 +9,10 @@ use std::{
 use futures::future::BoxFuture;
 use headers::{Authorization, HeaderMapExt};
 use http::{
-    header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Response, Uri,
+    header::{HeaderValue, AUTHORIZATION},
+    request::Builder,
+    uri::InvalidUri,
+    HeaderMap, Request, Response, Uri,
 };
 use hyper::{
     body::{Body, HttpBody},
@@ -327,6 +330,16 @@ impl Auth {
         builder
     }
 
+    /// Returns the value of the `Authorization` header sent with these credentials, if it is
+    /// valid.
+    pub fn header_value(&self) -> Option<String> {
+        let mut map = HeaderMap::new();
+        self.apply_headers_map(&mut map);
+        map.get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+    }
+
     pub fn apply_headers_map(&self, map: &mut HeaderMap) {
         match &self {
             Auth::Basic { user, password } => {
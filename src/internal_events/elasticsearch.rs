@@ -0,0 +1,96 @@
+use http::StatusCode;
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+/// Items of a bulk request that failed with the same status.
+#[derive(Debug)]
+pub struct ElasticsearchBulkItemsFailed<'a> {
+    pub status: StatusCode,
+    pub count: usize,
+    pub reason: &'a str,
+    pub retriable: bool,
+}
+
+impl<'a> InternalEvent for ElasticsearchBulkItemsFailed<'a> {
+    fn emit(self) {
+        if self.retriable {
+            warn!(
+                message = "Bulk items failed; retrying them.",
+                status = %self.status,
+                count = self.count,
+                reason = self.reason,
+                internal_log_rate_limit = true,
+            );
+        } else {
+            error!(
+                message = "Bulk items rejected.",
+                status = %self.status,
+                count = self.count,
+                reason = self.reason,
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::SENDING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::SENDING,
+            );
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: self.count,
+                reason: self.reason,
+            });
+        }
+        counter!(
+            "elasticsearch_bulk_item_errors_total", self.count as u64,
+            "status" => self.status.as_u16().to_string(),
+        );
+    }
+}
+
+/// An event was dropped because its bulk action requires a document ID, which it lacks.
+#[derive(Debug)]
+pub struct ElasticsearchMissingDocumentId {
+    pub action: &'static str,
+}
+
+impl InternalEvent for ElasticsearchMissingDocumentId {
+    fn emit(self) {
+        let reason = "Event has no document ID, which its bulk action requires.";
+        error!(
+            message = reason,
+            action = self.action,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchUpsertWithoutScript;
+
+impl InternalEvent for ElasticsearchUpsertWithoutScript {
+    fn emit(self) {
+        let reason = "Event has the `upsert` bulk action, which requires `bulk.script`.";
+        error!(
+            message = reason,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -4,7 +4,7 @@ use http::Response;
 use metrics::{counter, histogram};
 use vector_lib::internal_event::InternalEvent;
 use vector_lib::{
-    internal_event::{error_stage, error_type},
+    internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL},
     json_size::JsonSize,
 };
 
@@ -179,3 +179,33 @@ impl<'a> InternalEvent for HttpInternalError<'a> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct HttpInvalidUriError<'a> {
+    pub uri: &'a str,
+    pub error: &'a dyn Error,
+}
+
+impl<'a> InternalEvent for HttpInvalidUriError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Rendered URI is invalid.",
+            uri = %self.uri,
+            error = %self.error,
+            error_code = "invalid_uri",
+            error_type = error_type::TEMPLATE_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_uri",
+            "error_type" => error_type::TEMPLATE_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Rendered URI is invalid.",
+        });
+    }
+}
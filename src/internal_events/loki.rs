@@ -71,3 +71,21 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
         counter!("rewritten_timestamp_events_total", self.count as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct LokiLabelCardinalityLimitReached<'a> {
+    pub label: &'a str,
+    pub action: &'static str,
+}
+
+impl InternalEvent for LokiLabelCardinalityLimitReached<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Label cardinality limit reached, removing label.",
+            label = %self.label,
+            action = self.action,
+            internal_log_rate_limit = true,
+        );
+        counter!("loki_labels_removed_total", 1, "action" => self.action);
+    }
+}
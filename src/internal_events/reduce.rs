@@ -1,4 +1,4 @@
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_lib::internal_event::InternalEvent;
 
 #[derive(Debug)]
@@ -9,3 +9,25 @@ impl InternalEvent for ReduceStaleEventFlushed {
         counter!("stale_events_flushed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct ReduceIncompleteEventFlushed;
+
+impl InternalEvent for ReduceIncompleteEventFlushed {
+    fn emit(self) {
+        counter!("incomplete_events_flushed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ReduceGroups {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ReduceGroups {
+    fn emit(self) {
+        gauge!("reduce_groups", self.count as f64);
+        gauge!("reduce_groups_bytes", self.byte_size as f64);
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ReplayFileOpenError<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
+impl<'a> InternalEvent for ReplayFileOpenError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to open file for replay; skipping it.",
+            path = %self.path.display(),
+            error = %self.error,
+            error_code = "opening_file",
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "opening_file",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayFileFinished<'a> {
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for ReplayFileFinished<'a> {
+    fn emit(self) {
+        info!(message = "Finished replaying file.", path = %self.path.display());
+    }
+}
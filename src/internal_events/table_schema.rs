@@ -0,0 +1,57 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+/// An event could not be converted to a row of its table.
+#[derive(Debug)]
+pub struct TableRowConversionError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for TableRowConversionError<E> {
+    fn emit(self) {
+        let reason = "Event could not be converted to the column types of the table.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+/// The columns of a table could not be introspected, so its events were dropped.
+#[derive(Debug)]
+pub struct TableSchemaError<E> {
+    pub error: E,
+    pub count: usize,
+}
+
+impl<E: std::fmt::Display> InternalEvent for TableSchemaError<E> {
+    fn emit(self) {
+        let reason = "Failed to introspect the columns of the table.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
@@ -62,14 +62,18 @@ pub mod amqp;
 #[cfg(feature = "api")]
 #[allow(unreachable_pub)]
 pub mod api;
+#[cfg(feature = "api-client")]
+pub mod api_client;
 pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub mod batch_mode;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub mod common;
 mod convert_config;
+pub mod dead_letter;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
 pub mod extra_context;
@@ -125,6 +125,10 @@ pub struct Logic<K, C> {
 
     /// A queue of key timeouts.
     timeouts: DelayQueue<K>,
+
+    /// Merges the contexts of the lines between the first and the last
+    /// lines of an aggregate into the initial context, when set.
+    merge_context: Option<fn(&mut C, C)>,
 }
 
 impl<K, C> Logic<K, C> {
@@ -134,8 +138,17 @@ impl<K, C> Logic<K, C> {
             config,
             buffers: HashMap::new(),
             timeouts: DelayQueue::new(),
+            merge_context: None,
         }
     }
+
+    /// Merge the contexts of the lines between the first and the last lines
+    /// of an aggregate into the initial context with `merge_context`,
+    /// instead of dropping them.
+    pub fn with_context_merge(mut self, merge_context: fn(&mut C, C)) -> Self {
+        self.merge_context = Some(merge_context);
+        self
+    }
 }
 
 impl<T, K, C> LineAgg<T, K, C>
@@ -331,13 +344,13 @@ where
                     Decision::Continue => {
                         let buffered = entry.get_mut();
                         self.timeouts.reset(&buffered.0, self.config.timeout);
-                        buffered.1.add_next_line(line, context);
+                        buffered.1.add_next_line(line, context, self.merge_context);
                         None
                     }
                     Decision::EndInclude => {
                         let (src, (key, mut buffered)) = entry.remove_entry();
                         self.timeouts.remove(&key);
-                        buffered.add_next_line(line, context);
+                        buffered.add_next_line(line, context, self.merge_context);
                         Some((src, Emit::One(buffered.merge())))
                     }
                     Decision::EndExclude => {
@@ -381,8 +394,11 @@ impl<C> Aggregate<C> {
         }
     }
 
-    fn add_next_line(&mut self, line: Bytes, context: C) {
-        self.last_context = Some(context);
+    fn add_next_line(&mut self, line: Bytes, context: C, merge_context: Option<fn(&mut C, C)>) {
+        let previous_context = self.last_context.replace(context);
+        if let (Some(merge_context), Some(previous_context)) = (merge_context, previous_context) {
+            merge_context(&mut self.initial_context, previous_context);
+        }
         self.lines.push(line);
     }
 
@@ -592,6 +608,44 @@ mod tests {
         run_and_assert(&lines, config, &expected).await;
     }
 
+    #[tokio::test]
+    async fn merge_context() {
+        let lines = vec![
+            "java.lang.Exception",
+            "    at com.foo.bar(bar.java:123)",
+            "    at com.foo.baz(baz.java:456)",
+            "    at com.foo.qux(qux.java:789)",
+            "some usual line",
+        ];
+        let config = Config {
+            start_pattern: Regex::new("^[^\\s]").unwrap(),
+            condition_pattern: Regex::new("^[\\s]+at").unwrap(),
+            mode: Mode::ContinueThrough,
+            timeout: Duration::from_millis(10),
+        };
+        let logic = Logic::new(config)
+            .with_context_merge(|initial: &mut usize, context| *initial += context);
+        let results = LineAgg::new(stream_from_lines(&lines), logic)
+            .collect()
+            .await;
+        // The contexts of the two lines between the first and the last ones are added to the
+        // initial one.
+        let expected = vec![
+            (
+                concat!(
+                    "java.lang.Exception\n",
+                    "    at com.foo.bar(bar.java:123)\n",
+                    "    at com.foo.baz(baz.java:456)\n",
+                    "    at com.foo.qux(qux.java:789)",
+                ),
+                3,
+                Some(3),
+            ),
+            ("some usual line", 4, None),
+        ];
+        assert_results(results, &expected);
+    }
+
     #[tokio::test]
     async fn use_case_ruby_exception() {
         let lines = vec![
@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use http::{Request, StatusCode, Uri};
 use hyper::Body;
 
 use super::{
+    schema::{SchemaCache, SchemaFetcher},
     service::{ClickhouseRetryLogic, ClickhouseService},
     sink::ClickhouseSink,
 };
@@ -13,6 +16,36 @@ use crate::{
     },
 };
 
+/// The format used to insert data into ClickHouse.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// Events are sent as newline-delimited JSON, and ClickHouse converts their fields to the
+    /// column types.
+    #[default]
+    JsonEachRow,
+
+    /// Event fields are converted to the column types of the table and sent row by row.
+    ///
+    /// Rows are sent in the `RowBinaryWithDefaults` format, so that missing fields are left to the
+    /// column's `DEFAULT` expression.
+    RowBinary,
+
+    /// Event fields are converted to the column types of the table and sent column by column.
+    ///
+    /// Missing fields are written as the zero value of the column type, as the format can't leave
+    /// them to the column's `DEFAULT` expression.
+    Native,
+}
+
+impl Format {
+    /// Whether events are converted to the table's column types before being sent.
+    pub const fn is_binary(self) -> bool {
+        !matches!(self, Self::JsonEachRow)
+    }
+}
+
 /// Configuration for the `clickhouse` sink.
 #[configurable_component(sink("clickhouse", "Deliver log data to a ClickHouse database."))]
 #[derive(Clone, Debug, Default)]
@@ -31,11 +64,24 @@ pub struct ClickhouseConfig {
     #[configurable(metadata(docs::examples = "mydatabase"))]
     pub database: Option<Template>,
 
+    /// The format used to insert events.
+    ///
+    /// With `row_binary` and `native`, the columns of the table are introspected, and each event is
+    /// converted to the column types before being sent. Fields that are not columns of the table are
+    /// ignored, and missing or `null` fields are set to the default value of their column type. Events
+    /// that cannot be converted are rejected individually.
+    #[serde(default)]
+    pub format: Format,
+
     /// Sets `input_format_skip_unknown_fields`, allowing ClickHouse to discard fields not present in the table schema.
+    ///
+    /// Only applies to the `json_each_row` format.
     #[serde(default)]
     pub skip_unknown_fields: bool,
 
     /// Sets `date_time_input_format` to `best_effort`, allowing ClickHouse to properly parse RFC3339/ISO 8601.
+    ///
+    /// Only applies to the `json_each_row` format.
     #[serde(default)]
     pub date_time_best_effort: bool,
 
@@ -84,15 +130,23 @@ impl SinkConfig for ClickhouseConfig {
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls_settings, &cx.proxy)?;
 
+        let request_limits = self.request.into_settings();
+        let schemas = self.format.is_binary().then(|| {
+            Arc::new(SchemaCache::new(
+                SchemaFetcher::new(client.clone(), endpoint.clone(), auth.clone()),
+                &request_limits,
+            ))
+        });
+
         let service = ClickhouseService::new(
             client.clone(),
             auth.clone(),
             endpoint.clone(),
+            self.format,
             self.skip_unknown_fields,
             self.date_time_best_effort,
+            schemas.clone(),
         );
-
-        let request_limits = self.request.into_settings();
         let service = ServiceBuilder::new()
             .settings(request_limits, ClickhouseRetryLogic::default())
             .service(service);
@@ -103,6 +157,20 @@ impl SinkConfig for ClickhouseConfig {
                 .try_into()
                 .expect("'default' should be a valid template")
         });
+
+        // With a templated database or table, which columns to check isn't known until events
+        // arrive.
+        let healthcheck_table = match &schemas {
+            Some(schemas) if !database.is_dynamic() && !self.table.is_dynamic() => Some((
+                Arc::clone(schemas),
+                (
+                    database.get_ref().to_string(),
+                    self.table.get_ref().to_string(),
+                ),
+            )),
+            _ => None,
+        };
+
         let sink = ClickhouseSink::new(
             batch_settings,
             self.compression,
@@ -111,9 +179,11 @@ impl SinkConfig for ClickhouseConfig {
             protocol,
             database,
             self.table.clone(),
+            self.format,
+            schemas,
         );
 
-        let healthcheck = Box::pin(healthcheck(client, endpoint, auth));
+        let healthcheck = Box::pin(healthcheck(client, endpoint, auth, healthcheck_table));
 
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
     }
@@ -136,7 +206,12 @@ fn get_healthcheck_uri(endpoint: &Uri) -> String {
     uri
 }
 
-async fn healthcheck(client: HttpClient, endpoint: Uri, auth: Option<Auth>) -> crate::Result<()> {
+async fn healthcheck(
+    client: HttpClient,
+    endpoint: Uri,
+    auth: Option<Auth>,
+    table: Option<(Arc<SchemaCache>, (String, String))>,
+) -> crate::Result<()> {
     let uri = get_healthcheck_uri(&endpoint);
     let mut request = Request::get(uri).body(Body::empty()).unwrap();
 
@@ -146,10 +221,20 @@ async fn healthcheck(client: HttpClient, endpoint: Uri, auth: Option<Auth>) -> c
 
     let response = client.send(request).await?;
 
-    match response.status() {
-        StatusCode::OK => Ok(()),
-        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    if response.status() != StatusCode::OK {
+        return Err(HealthcheckError::UnexpectedStatus {
+            status: response.status(),
+        }
+        .into());
+    }
+
+    // A missing table, or a column of a type that events can't be converted to, would otherwise
+    // only show up as every batch being rejected.
+    if let Some((schemas, table)) = table {
+        schemas.get(&table).await?;
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
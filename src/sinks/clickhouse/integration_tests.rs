@@ -19,7 +19,7 @@ use vector_lib::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event,
 use vector_lib::lookup::PathPrefix;
 use warp::Filter;
 
-use super::*;
+use super::{config::Format, *};
 use crate::{
     codecs::{TimestampFormat, Transformer},
     config::{log_schema, SinkConfig, SinkContext},
@@ -391,6 +391,103 @@ async fn templated_table() {
     }
 }
 
+#[tokio::test]
+async fn insert_events_binary_formats() {
+    trace_init();
+
+    for format in [Format::RowBinary, Format::Native] {
+        let table = gen_table();
+        let host = clickhouse_address();
+
+        let config = ClickhouseConfig {
+            endpoint: host.parse().unwrap(),
+            table: table.clone().try_into().unwrap(),
+            format,
+            compression: Compression::None,
+            request: TowerRequestConfig {
+                retry_attempts: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let client = ClickhouseClient::new(host);
+        client
+            .create_table(
+                &table,
+                "host LowCardinality(String), timestamp DateTime64(3, 'UTC'), message String, \
+                 count Nullable(UInt8), items Array(String), labels Map(String, String)",
+            )
+            .await;
+
+        let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+        healthcheck.await.unwrap();
+
+        let (mut valid_event, mut valid_receiver) = make_event();
+        valid_event.as_mut_log().insert("count", 3);
+        valid_event
+            .as_mut_log()
+            .insert("items", vec!["item1", "item2"]);
+        valid_event.as_mut_log().insert("labels.env", "prod");
+        let (mut invalid_event, mut invalid_receiver) = make_event();
+        invalid_event.as_mut_log().insert("count", 300);
+
+        run_and_assert_sink_compliance(
+            sink,
+            stream::iter(vec![valid_event, invalid_event]),
+            &SINK_TAGS,
+        )
+        .await;
+
+        let output = client.select_all(&table).await;
+        assert_eq!(1, output.rows);
+        assert_eq!(output.data[0]["host"], "example.com");
+        assert_eq!(output.data[0]["count"], 3);
+        assert_eq!(
+            output.data[0]["items"],
+            serde_json::json!(["item1", "item2"])
+        );
+        assert_eq!(output.data[0]["labels"], serde_json::json!({"env": "prod"}));
+
+        assert_eq!(valid_receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(invalid_receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+}
+
+#[tokio::test]
+async fn row_binary_uses_column_defaults() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone().try_into().unwrap(),
+        format: Format::RowBinary,
+        compression: Compression::None,
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, message String, status String DEFAULT 'unknown'",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::default()).await.unwrap();
+
+    let (event, mut receiver) = make_event();
+    run_and_assert_sink_compliance(sink, stream::once(ready(event)), &SINK_TAGS).await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+    assert_eq!(output.data[0]["status"], "unknown");
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
 fn make_event() -> (Event, BatchStatusReceiver) {
     let (batch, receiver) = BatchNotifier::new_with_receiver();
     let mut event = LogEvent::from("raw log line").with_batch_notifier(&batch);
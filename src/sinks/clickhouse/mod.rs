@@ -7,11 +7,16 @@
 //! `INSERT INTO my_db.my_table FORMAT JSONEachRow`. The event payload is encoded as new-line
 //! delimited JSON.
 //!
+//! Alternatively, the columns of the table can be introspected so that events are converted to the
+//! column types and sent with `FORMAT RowBinary` or `FORMAT Native`.
+//!
 //! This sink only supports logs for now but could support metrics and traces as well in the future.
 
 mod config;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
+mod schema;
 mod service;
 mod sink;
+mod types;
 pub use self::config::ClickhouseConfig;
@@ -0,0 +1,401 @@
+//! Introspection of the tables that events are inserted into with a binary format.
+
+use async_trait::async_trait;
+use bytes::{BufMut, BytesMut};
+use http::{Request, StatusCode, Uri};
+use hyper::{body, Body};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use vector_lib::lookup::{event_path, OwnedTargetPath};
+use vrl::path::parse_target_path;
+
+use super::{
+    config::Format,
+    types::{put_var_uint, Cell, ColumnType, TypeParseError, ValueError},
+};
+use crate::{
+    event::LogEvent,
+    http::{Auth, HttpClient, HttpError},
+    sinks::util::schema_cache::{self, FetchSchema},
+};
+
+/// Only the columns that can be written to are listed, in the order used by `RowBinary`.
+const COLUMNS_QUERY: &str = "SELECT name, type FROM system.columns \
+    WHERE database = {database:String} AND table = {table:String} \
+    AND default_kind NOT IN ('MATERIALIZED', 'ALIAS') \
+    ORDER BY position FORMAT JSONEachRow";
+
+#[derive(Debug, Snafu)]
+pub(super) enum SchemaError {
+    #[snafu(display("Failed to query the columns of {}.{}: {}", database, table, source))]
+    Request {
+        database: String,
+        table: String,
+        source: HttpError,
+    },
+    #[snafu(display("Failed to read the columns of {}.{}: {}", database, table, source))]
+    ReadBody {
+        database: String,
+        table: String,
+        source: hyper::Error,
+    },
+    #[snafu(display(
+        "Unexpected status {} querying the columns of {}.{}: {}",
+        status,
+        database,
+        table,
+        body
+    ))]
+    UnexpectedStatus {
+        database: String,
+        table: String,
+        status: StatusCode,
+        body: String,
+    },
+    #[snafu(display("Invalid column description for {}.{}: {}", database, table, source))]
+    InvalidColumn {
+        database: String,
+        table: String,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Table {}.{} does not exist or has no columns", database, table))]
+    NoColumns { database: String, table: String },
+    #[snafu(display("Column {:?} of {}.{}: {}", column, database, table, source))]
+    UnsupportedColumn {
+        database: String,
+        table: String,
+        column: String,
+        source: TypeParseError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Failed to convert field {:?}: {}", column, source))]
+pub(super) struct ConversionError {
+    column: String,
+    source: ValueError,
+}
+
+#[derive(Deserialize)]
+struct ColumnDescription {
+    name: String,
+    #[serde(rename = "type")]
+    column_type: String,
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    path: Option<OwnedTargetPath>,
+    column_type: ColumnType,
+}
+
+impl Column {
+    fn new(name: String, column_type: ColumnType) -> Self {
+        Self {
+            path: parse_target_path(&name).ok(),
+            name,
+            column_type,
+        }
+    }
+
+    /// The columns of `Nested` structures are named like `a.b`, which is matched against the
+    /// nested `b` field of `a` first, and then against a field named `a.b`.
+    fn lookup<'a>(&self, log: &'a LogEvent) -> Option<&'a vrl::value::Value> {
+        self.path
+            .as_ref()
+            .and_then(|path| log.get(path))
+            .or_else(|| log.get(event_path!(self.name.as_str())))
+    }
+}
+
+/// The columns of a table, in insertion order.
+#[derive(Debug)]
+pub(super) struct TableSchema {
+    columns: Vec<Column>,
+}
+
+impl TableSchema {
+    fn from_descriptions(
+        database: &str,
+        table: &str,
+        descriptions: Vec<ColumnDescription>,
+    ) -> Result<Self, SchemaError> {
+        if descriptions.is_empty() {
+            return Err(SchemaError::NoColumns {
+                database: database.to_string(),
+                table: table.to_string(),
+            });
+        }
+        let columns = descriptions
+            .into_iter()
+            .map(|description| {
+                let column_type = ColumnType::parse(&description.column_type).context(
+                    UnsupportedColumnSnafu {
+                        database,
+                        table,
+                        column: &description.name,
+                    },
+                )?;
+                Ok(Column::new(description.name, column_type))
+            })
+            .collect::<Result<_, SchemaError>>()?;
+        Ok(Self { columns })
+    }
+
+    /// Converts the fields of an event into a row of this table.
+    pub(super) fn convert(&self, log: &LogEvent) -> Result<Vec<Cell>, ConversionError> {
+        self.columns
+            .iter()
+            .map(|column| {
+                column
+                    .column_type
+                    .convert(column.lookup(log))
+                    .context(ConversionSnafu {
+                        column: &column.name,
+                    })
+            })
+            .collect()
+    }
+
+    /// Encodes converted rows in the given binary format.
+    pub(super) fn encode(&self, format: Format, rows: &[Vec<Cell>], buffer: &mut BytesMut) {
+        match format {
+            Format::JsonEachRow => unreachable!("rows are only converted for binary formats"),
+            // Sent as `RowBinaryWithDefaults`, where each value is preceded by whether to use the
+            // column's default instead.
+            Format::RowBinary => {
+                for row in rows {
+                    for (column, cell) in self.columns.iter().zip(row) {
+                        if let Cell::Default = cell {
+                            buffer.put_u8(1);
+                        } else {
+                            buffer.put_u8(0);
+                            column.column_type.write_row_binary(cell, buffer);
+                        }
+                    }
+                }
+            }
+            Format::Native => {
+                put_var_uint(buffer, self.columns.len() as u64);
+                put_var_uint(buffer, rows.len() as u64);
+                for (index, column) in self.columns.iter().enumerate() {
+                    put_string(buffer, column.name.as_bytes());
+                    put_string(buffer, column.column_type.native_name().as_bytes());
+                    // Blocks can't leave single values to the column's default.
+                    let default = column.column_type.default_cell();
+                    let cells = rows
+                        .iter()
+                        .map(|row| match &row[index] {
+                            Cell::Default => &default,
+                            cell => cell,
+                        })
+                        .collect::<Vec<_>>();
+                    column.column_type.write_native(&cells, buffer);
+                }
+            }
+        }
+    }
+}
+
+fn put_string(buffer: &mut BytesMut, string: &[u8]) {
+    put_var_uint(buffer, string.len() as u64);
+    buffer.put_slice(string);
+}
+
+/// The schemas of the tables, keyed by database and table name.
+pub(super) type SchemaCache = schema_cache::SchemaCache<SchemaFetcher>;
+
+/// Fetches the columns of a table from `system.columns`.
+pub(super) struct SchemaFetcher {
+    client: HttpClient,
+    endpoint: Uri,
+    auth: Option<Auth>,
+}
+
+impl SchemaFetcher {
+    pub(super) const fn new(client: HttpClient, endpoint: Uri, auth: Option<Auth>) -> Self {
+        Self {
+            client,
+            endpoint,
+            auth,
+        }
+    }
+}
+
+#[async_trait]
+impl FetchSchema for SchemaFetcher {
+    type Key = (String, String);
+    type Schema = TableSchema;
+    type Error = SchemaError;
+
+    async fn fetch(&self, (database, table): &Self::Key) -> Result<TableSchema, SchemaError> {
+        let mut request = Request::get(columns_query_uri(&self.endpoint, database, table))
+            .body(Body::empty())
+            .unwrap();
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context(RequestSnafu { database, table })?;
+        let status = response.status();
+        let body = body::to_bytes(response.into_body())
+            .await
+            .context(ReadBodySnafu { database, table })?;
+        if status != StatusCode::OK {
+            return Err(SchemaError::UnexpectedStatus {
+                database: database.to_string(),
+                table: table.to_string(),
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+
+        let descriptions = serde_json::Deserializer::from_slice(&body)
+            .into_iter::<ColumnDescription>()
+            .collect::<Result<_, _>>()
+            .context(InvalidColumnSnafu { database, table })?;
+        let schema = TableSchema::from_descriptions(database, table, descriptions)?;
+        debug!(
+            message = "Introspected table columns.",
+            database,
+            table,
+            columns = schema.columns.len(),
+        );
+        Ok(schema)
+    }
+
+    fn is_retriable_error(&self, error: &SchemaError) -> bool {
+        match error {
+            SchemaError::Request { .. } | SchemaError::ReadBody { .. } => true,
+            SchemaError::UnexpectedStatus { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            _ => false,
+        }
+    }
+}
+
+fn columns_query_uri(endpoint: &Uri, database: &str, table: &str) -> String {
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("query", COLUMNS_QUERY)
+        .append_pair("param_database", database)
+        .append_pair("param_table", table)
+        .finish();
+
+    let mut uri = endpoint.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push('?');
+    uri.push_str(&query);
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::value::Value;
+
+    use super::*;
+
+    fn schema(columns: &[(&str, &str)]) -> TableSchema {
+        TableSchema::from_descriptions(
+            "db",
+            "table",
+            columns
+                .iter()
+                .map(|(name, column_type)| ColumnDescription {
+                    name: name.to_string(),
+                    column_type: column_type.to_string(),
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rejects_unsupported_columns() {
+        let error = TableSchema::from_descriptions(
+            "db",
+            "table",
+            vec![ColumnDescription {
+                name: "amount".into(),
+                column_type: "Decimal(10, 2)".into(),
+            }],
+        )
+        .unwrap_err();
+        assert!(matches!(error, SchemaError::UnsupportedColumn { .. }));
+    }
+
+    #[test]
+    fn converts_nested_and_dotted_fields() {
+        let schema = schema(&[("a.b", "String"), ("c.d", "UInt8")]);
+        let mut log = LogEvent::default();
+        log.insert("a.b", "nested");
+        log.insert(event_path!("c.d"), 7);
+
+        let mut buffer = BytesMut::new();
+        let row = schema.convert(&log).unwrap();
+        schema.encode(Format::RowBinary, &[row], &mut buffer);
+        assert_eq!(buffer.to_vec(), b"\x00\x06nested\x00\x07".to_vec());
+    }
+
+    #[test]
+    fn leaves_missing_fields_to_column_defaults() {
+        let schema = schema(&[("message", "String"), ("count", "UInt8")]);
+        let rows = vec![schema.convert(&LogEvent::from("a")).unwrap()];
+
+        let mut buffer = BytesMut::new();
+        schema.encode(Format::RowBinary, &rows, &mut buffer);
+        assert_eq!(buffer.to_vec(), b"\x00\x01a\x01".to_vec());
+
+        let mut buffer = BytesMut::new();
+        schema.encode(Format::Native, &rows, &mut buffer);
+        assert_eq!(
+            buffer.to_vec(),
+            b"\x02\x01\x07message\x06String\x01a\x05count\x05UInt8\x00".to_vec()
+        );
+    }
+
+    #[test]
+    fn reports_the_failing_column() {
+        let schema = schema(&[("count", "UInt8")]);
+        let mut log = LogEvent::default();
+        log.insert("count", Value::from(-1));
+
+        let error = schema.convert(&log).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Failed to convert field \"count\": value -1 is out of range for UInt8"
+        );
+    }
+
+    #[test]
+    fn encodes_native_blocks() {
+        let schema = schema(&[("message", "LowCardinality(String)")]);
+        let rows = ["a", "bc"]
+            .into_iter()
+            .map(|message| schema.convert(&LogEvent::from(message)).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut buffer = BytesMut::new();
+        schema.encode(Format::Native, &rows, &mut buffer);
+        assert_eq!(
+            buffer.to_vec(),
+            b"\x01\x02\x07message\x06String\x01a\x02bc".to_vec()
+        );
+    }
+
+    #[test]
+    fn columns_query_uses_parameters() {
+        let uri = columns_query_uri(&"http://localhost:8123".parse().unwrap(), "db", "my\"table");
+        assert!(
+            uri.starts_with("http://localhost:8123/?query=SELECT+name%2C+type+FROM+system.columns")
+        );
+        assert!(uri.ends_with("&param_database=db&param_table=my%22table"));
+    }
+}
@@ -5,9 +5,12 @@ use http::{
 };
 use hyper::{body, Body};
 use snafu::ResultExt;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 use tracing::Instrument;
 
+use super::{config::Format, schema::SchemaCache};
 use crate::{
     http::{Auth, HttpClient, HttpError},
     sinks::{
@@ -103,6 +106,10 @@ impl RetryLogic for ClickhouseRetryLogic {
             _ => self.inner.should_retry_response(&response.http_response),
         }
     }
+
+    fn retry_after_response(&self, response: &Self::Response) -> Option<Duration> {
+        self.inner.retry_after_response(&response.http_response)
+    }
 }
 
 /// `ClickhouseService` is a `Tower` service used to send logs to Clickhouse.
@@ -111,8 +118,11 @@ pub struct ClickhouseService {
     client: HttpClient,
     auth: Option<Auth>,
     endpoint: Uri,
+    format: Format,
     skip_unknown_fields: bool,
     date_time_best_effort: bool,
+    /// The schemas the rows of binary formats are encoded against.
+    schemas: Option<Arc<SchemaCache>>,
 }
 
 impl ClickhouseService {
@@ -121,15 +131,19 @@ impl ClickhouseService {
         client: HttpClient,
         auth: Option<Auth>,
         endpoint: Uri,
+        format: Format,
         skip_unknown_fields: bool,
         date_time_best_effort: bool,
+        schemas: Option<Arc<SchemaCache>>,
     ) -> Self {
         Self {
             client,
             auth,
             endpoint,
+            format,
             skip_unknown_fields,
             date_time_best_effort,
+            schemas,
         }
     }
 }
@@ -154,13 +168,22 @@ impl Service<ClickhouseRequest> for ClickhouseService {
             &self.endpoint,
             &request.database,
             &request.table,
+            self.format,
             self.skip_unknown_fields,
             self.date_time_best_effort,
         );
+        let content_type = match self.format {
+            Format::JsonEachRow => "application/x-ndjson",
+            Format::RowBinary | Format::Native => "application/octet-stream",
+        };
+        let schema = self.schemas.clone().map(|schemas| {
+            let table = (request.database.clone(), request.table.clone());
+            (schemas, table)
+        });
 
         Box::pin(async move {
             let mut builder = Request::post(&uri?)
-                .header(CONTENT_TYPE, "application/x-ndjson")
+                .header(CONTENT_TYPE, content_type)
                 .header(CONTENT_LENGTH, request.body.len());
             if let Some(ce) = request.compression.content_encoding() {
                 builder = builder.header(CONTENT_ENCODING, ce);
@@ -176,6 +199,11 @@ impl Service<ClickhouseRequest> for ClickhouseService {
             let response = client.call(http_request).in_current_span().await?;
             let (parts, body) = response.into_parts();
             let body = body::to_bytes(body).await?;
+            // The rows may have been encoded against an outdated schema, such as after an
+            // `ALTER TABLE`, so it is fetched again for the next requests.
+            if let Some((schemas, table)) = schema.filter(|_| !parts.status.is_success()) {
+                schemas.invalidate(&table);
+            }
             Ok(ClickhouseResponse {
                 http_response: hyper::Response::from_parts(parts, body),
                 raw_byte_size: request.metadata.request_encoded_size(),
@@ -191,16 +219,23 @@ fn set_uri_query(
     uri: &Uri,
     database: &str,
     table: &str,
+    format: Format,
     skip_unknown: bool,
     date_time_best_effort: bool,
 ) -> crate::Result<Uri> {
+    let format_name = match format {
+        Format::JsonEachRow => "JSONEachRow",
+        Format::RowBinary => "RowBinaryWithDefaults",
+        Format::Native => "Native",
+    };
     let query = url::form_urlencoded::Serializer::new(String::new())
         .append_pair(
             "query",
             format!(
-                "INSERT INTO \"{}\".\"{}\" FORMAT JSONEachRow",
+                "INSERT INTO \"{}\".\"{}\" FORMAT {}",
                 database,
-                table.replace('\"', "\\\"")
+                table.replace('\"', "\\\""),
+                format_name
             )
             .as_str(),
         )
@@ -211,12 +246,19 @@ fn set_uri_query(
         uri.push('/');
     }
 
-    uri.push_str("?input_format_import_nested_json=1&");
-    if skip_unknown {
-        uri.push_str("input_format_skip_unknown_fields=1&");
-    }
-    if date_time_best_effort {
-        uri.push_str("date_time_input_format=best_effort&")
+    match format {
+        Format::JsonEachRow => {
+            uri.push_str("?input_format_import_nested_json=1&");
+            if skip_unknown {
+                uri.push_str("input_format_skip_unknown_fields=1&");
+            }
+            if date_time_best_effort {
+                uri.push_str("date_time_input_format=best_effort&")
+            }
+        }
+        Format::RowBinary => uri.push('?'),
+        // `LowCardinality` columns are sent as their inner type.
+        Format::Native => uri.push_str("?low_cardinality_allow_in_native_format=0&"),
     }
     uri.push_str(query.as_str());
 
@@ -235,6 +277,7 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
+            Format::JsonEachRow,
             false,
             true,
         )
@@ -245,6 +288,7 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_\"table\"",
+            Format::JsonEachRow,
             false,
             false,
         )
@@ -258,9 +302,35 @@ mod tests {
             &"localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
+            Format::JsonEachRow,
             false,
             false,
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn encode_binary_formats() {
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            Format::RowBinary,
+            true,
+            true,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+RowBinaryWithDefaults");
+
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            Format::Native,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?low_cardinality_allow_in_native_format=0&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+Native");
+    }
 }
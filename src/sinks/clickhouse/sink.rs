@@ -1,8 +1,19 @@
-use bytes::Bytes;
+use std::{io, sync::Arc};
+
+use bytes::{Bytes, BytesMut};
 use vector_lib::codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+use vector_lib::config::telemetry;
 
-use super::service::{ClickhouseRequest, ClickhouseRetryLogic, ClickhouseService};
-use crate::sinks::prelude::*;
+use super::{
+    config::Format,
+    schema::{SchemaCache, TableSchema},
+    service::{ClickhouseRequest, ClickhouseRetryLogic, ClickhouseService},
+    types::Cell,
+};
+use crate::{
+    internal_events::{TableRowConversionError, TableSchemaError},
+    sinks::prelude::*,
+};
 
 pub struct ClickhouseSink {
     batch_settings: BatcherSettings,
@@ -12,6 +23,8 @@ pub struct ClickhouseSink {
     protocol: &'static str,
     database: Template,
     table: Template,
+    format: Format,
+    schemas: Option<Arc<SchemaCache>>,
 }
 
 impl ClickhouseSink {
@@ -23,6 +36,8 @@ impl ClickhouseSink {
         protocol: &'static str,
         database: Template,
         table: Template,
+        format: Format,
+        schemas: Option<Arc<SchemaCache>>,
     ) -> Self {
         Self {
             batch_settings,
@@ -38,22 +53,48 @@ impl ClickhouseSink {
             protocol,
             database,
             table,
+            format,
+            schemas,
         }
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let batch_settings = self.batch_settings;
+        let schemas = self.schemas;
 
         input
             .batched_partitioned(KeyPartitioner::new(self.database, self.table), || {
                 batch_settings.as_byte_size_config()
             })
             .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
+            .filter_map(move |(key, mut batch)| {
+                let schemas = schemas.clone();
+                async move {
+                    let Some(schemas) = schemas else {
+                        return Some((key, None, batch));
+                    };
+                    let table = (key.database.clone(), key.table.clone());
+                    match schemas.get(&table).await {
+                        Ok(schema) => Some((key, Some(schema), batch)),
+                        Err(error) => {
+                            emit!(TableSchemaError {
+                                error: &error,
+                                count: batch.len(),
+                            });
+                            batch.take_finalizers().update_status(EventStatus::Errored);
+                            None
+                        }
+                    }
+                }
+            })
             .request_builder(
                 default_request_builder_concurrency_limit(),
                 ClickhouseRequestBuilder {
                     compression: self.compression,
-                    encoding: self.encoding,
+                    encoder: ClickhouseEncoder {
+                        format: self.format,
+                        json: self.encoding,
+                    },
                 },
             )
             .filter_map(|request| async {
@@ -62,6 +103,8 @@ impl ClickhouseSink {
                         emit!(SinkRequestBuildError { error });
                         None
                     }
+                    // Every event of the batch was rejected while converting it to a row.
+                    Ok(req) if req.metadata.event_count() == 0 => None,
                     Ok(req) => Some(req),
                 }
             })
@@ -82,15 +125,94 @@ impl StreamSink<Event> for ClickhouseSink {
     }
 }
 
+/// The events of a batch, either as-is or converted to the rows of the table.
+enum ClickhouseEvents {
+    Json(Vec<Event>),
+    Rows {
+        schema: Arc<TableSchema>,
+        rows: Vec<Vec<Cell>>,
+        byte_size: GroupedCountByteSize,
+    },
+}
+
+struct ClickhouseEncoder {
+    format: Format,
+    json: (Transformer, Encoder<Framer>),
+}
+
+impl encoding::Encoder<ClickhouseEvents> for ClickhouseEncoder {
+    fn encode_input(
+        &self,
+        input: ClickhouseEvents,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        match input {
+            ClickhouseEvents::Json(events) => {
+                encoding::Encoder::encode_input(&self.json, events, writer)
+            }
+            ClickhouseEvents::Rows {
+                schema,
+                rows,
+                byte_size,
+            } => {
+                let mut buffer = BytesMut::new();
+                schema.encode(self.format, &rows, &mut buffer);
+                writer.write_all(&buffer)?;
+                Ok((buffer.len(), byte_size))
+            }
+        }
+    }
+}
+
 struct ClickhouseRequestBuilder {
     compression: Compression,
-    encoding: (Transformer, Encoder<Framer>),
+    encoder: ClickhouseEncoder,
+}
+
+impl ClickhouseRequestBuilder {
+    /// Converts each event to a row of the table, rejecting the events that cannot be converted.
+    fn convert(
+        &self,
+        schema: Arc<TableSchema>,
+        events: Vec<Event>,
+    ) -> (EventFinalizers, RequestMetadataBuilder, ClickhouseEvents) {
+        let mut finalizers = EventFinalizers::default();
+        let mut rows = Vec::with_capacity(events.len());
+        let mut events_byte_size = 0;
+        let mut byte_size = telemetry().create_request_count_byte_size();
+
+        for mut event in events {
+            self.encoder.json.0.transform(&mut event);
+            match schema.convert(event.as_log()) {
+                Ok(row) => {
+                    events_byte_size += event.size_of();
+                    byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+                    finalizers.merge(event.take_finalizers());
+                    rows.push(row);
+                }
+                Err(error) => {
+                    emit!(TableRowConversionError { error: &error });
+                    event.take_finalizers().update_status(EventStatus::Rejected);
+                }
+            }
+        }
+
+        let builder = RequestMetadataBuilder::new(rows.len(), events_byte_size, byte_size.clone());
+        let events = ClickhouseEvents::Rows {
+            schema,
+            rows,
+            byte_size,
+        };
+        (finalizers, builder, events)
+    }
 }
 
-impl RequestBuilder<(PartitionKey, Vec<Event>)> for ClickhouseRequestBuilder {
+impl RequestBuilder<(PartitionKey, Option<Arc<TableSchema>>, Vec<Event>)>
+    for ClickhouseRequestBuilder
+{
     type Metadata = (PartitionKey, EventFinalizers);
-    type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Events = ClickhouseEvents;
+    type Encoder = ClickhouseEncoder;
     type Payload = Bytes;
     type Request = ClickhouseRequest;
     type Error = std::io::Error;
@@ -100,18 +222,26 @@ impl RequestBuilder<(PartitionKey, Vec<Event>)> for ClickhouseRequestBuilder {
     }
 
     fn encoder(&self) -> &Self::Encoder {
-        &self.encoding
+        &self.encoder
     }
 
     fn split_input(
         &self,
-        input: (PartitionKey, Vec<Event>),
+        input: (PartitionKey, Option<Arc<TableSchema>>, Vec<Event>),
     ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
-        let (key, mut events) = input;
+        let (key, schema, mut events) = input;
 
-        let finalizers = events.take_finalizers();
-        let builder = RequestMetadataBuilder::from_events(&events);
-        ((key, finalizers), builder, events)
+        match schema {
+            Some(schema) => {
+                let (finalizers, builder, events) = self.convert(schema, events);
+                ((key, finalizers), builder, events)
+            }
+            None => {
+                let finalizers = events.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&events);
+                ((key, finalizers), builder, ClickhouseEvents::Json(events))
+            }
+        }
     }
 
     fn build_request(
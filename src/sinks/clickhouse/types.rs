@@ -0,0 +1,794 @@
+//! ClickHouse column types and the conversion of event fields into them.
+//!
+//! Values are first converted into [`Cell`]s, which hold the binary representation of scalar
+//! values, and are then laid out either row by row (`RowBinary`) or column by column (`Native`).
+
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDate, SecondsFormat, TimeZone, Utc};
+use snafu::Snafu;
+use vrl::value::Value;
+
+/// A column type that events can be converted into.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum ColumnType {
+    Bool,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    String,
+    FixedString(usize),
+    Uuid,
+    Date,
+    Date32,
+    DateTime(Option<String>),
+    DateTime64(u32, Option<String>),
+    IPv4,
+    IPv6,
+    Nullable(Box<ColumnType>),
+    LowCardinality(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+}
+
+/// A converted value, ready to be written out.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Cell {
+    /// A missing field, left to the column's `DEFAULT` expression.
+    Default,
+    Null,
+    Scalar(Vec<u8>),
+    Array(Vec<Cell>),
+    Map(Vec<(Cell, Cell)>),
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum TypeParseError {
+    #[snafu(display("unsupported column type {:?}", type_name))]
+    Unsupported { type_name: String },
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub(super) enum ValueError {
+    #[snafu(display("cannot convert a value of type {} to {}", kind, column_type))]
+    Mismatch {
+        kind: &'static str,
+        column_type: ColumnType,
+    },
+    #[snafu(display("value {} is out of range for {}", value, column_type))]
+    OutOfRange {
+        value: String,
+        column_type: ColumnType,
+    },
+    #[snafu(display("cannot parse {:?} as {}", value, column_type))]
+    Unparsable {
+        value: String,
+        column_type: ColumnType,
+    },
+}
+
+impl ColumnType {
+    /// Parses a type name as reported by `system.columns`.
+    pub(super) fn parse(type_name: &str) -> Result<Self, TypeParseError> {
+        let unsupported = || TypeParseError::Unsupported {
+            type_name: type_name.to_string(),
+        };
+        let type_name = type_name.trim();
+        let (name, args) = match type_name.find('(') {
+            Some(open) if type_name.ends_with(')') => (
+                &type_name[..open],
+                split_arguments(&type_name[open + 1..type_name.len() - 1]),
+            ),
+            Some(_) => return Err(unsupported()),
+            None => (type_name, Vec::new()),
+        };
+        let nested = |arg: &str| Self::parse(arg).map(Box::new);
+
+        Ok(match (name, args.as_slice()) {
+            ("Bool", []) => Self::Bool,
+            ("UInt8", []) => Self::UInt8,
+            ("UInt16", []) => Self::UInt16,
+            ("UInt32", []) => Self::UInt32,
+            ("UInt64", []) => Self::UInt64,
+            ("Int8", []) => Self::Int8,
+            ("Int16", []) => Self::Int16,
+            ("Int32", []) => Self::Int32,
+            ("Int64", []) => Self::Int64,
+            ("Float32", []) => Self::Float32,
+            ("Float64", []) => Self::Float64,
+            ("String", []) => Self::String,
+            ("FixedString", [length]) => {
+                Self::FixedString(length.parse().map_err(|_| unsupported())?)
+            }
+            ("UUID", []) => Self::Uuid,
+            ("Date", []) => Self::Date,
+            ("Date32", []) => Self::Date32,
+            ("DateTime", []) => Self::DateTime(None),
+            ("DateTime", [timezone]) => Self::DateTime(Some(timezone.to_string())),
+            ("DateTime64", [precision, timezone @ ..]) if timezone.len() <= 1 => {
+                let precision = precision
+                    .parse()
+                    .ok()
+                    .filter(|precision| *precision <= 9)
+                    .ok_or_else(unsupported)?;
+                Self::DateTime64(precision, timezone.first().map(ToString::to_string))
+            }
+            ("IPv4", []) => Self::IPv4,
+            ("IPv6", []) => Self::IPv6,
+            ("Nullable", [inner]) => Self::Nullable(nested(inner)?),
+            ("LowCardinality", [inner]) => Self::LowCardinality(nested(inner)?),
+            ("Array", [inner]) => Self::Array(nested(inner)?),
+            ("Map", [key, value]) => Self::Map(nested(key)?, nested(value)?),
+            _ => return Err(unsupported()),
+        })
+    }
+
+    /// The type name to announce in a `Native` block.
+    ///
+    /// `LowCardinality` columns are sent as their inner type, which the server converts back when
+    /// `low_cardinality_allow_in_native_format` is disabled.
+    pub(super) fn native_name(&self) -> String {
+        let mut name = String::new();
+        self.write_name(&mut name, false);
+        name
+    }
+
+    fn write_name(&self, out: &mut String, low_cardinality: bool) {
+        let simple = match self {
+            Self::Bool => "Bool",
+            Self::UInt8 => "UInt8",
+            Self::UInt16 => "UInt16",
+            Self::UInt32 => "UInt32",
+            Self::UInt64 => "UInt64",
+            Self::Int8 => "Int8",
+            Self::Int16 => "Int16",
+            Self::Int32 => "Int32",
+            Self::Int64 => "Int64",
+            Self::Float32 => "Float32",
+            Self::Float64 => "Float64",
+            Self::String => "String",
+            Self::Uuid => "UUID",
+            Self::Date => "Date",
+            Self::Date32 => "Date32",
+            Self::IPv4 => "IPv4",
+            Self::IPv6 => "IPv6",
+            Self::FixedString(length) => {
+                out.push_str(&format!("FixedString({})", length));
+                return;
+            }
+            Self::DateTime(timezone) => {
+                out.push_str("DateTime");
+                if let Some(timezone) = timezone {
+                    out.push_str(&format!("({})", timezone));
+                }
+                return;
+            }
+            Self::DateTime64(precision, timezone) => {
+                out.push_str(&format!("DateTime64({}", precision));
+                if let Some(timezone) = timezone {
+                    out.push_str(&format!(", {}", timezone));
+                }
+                out.push(')');
+                return;
+            }
+            Self::Nullable(inner) => {
+                out.push_str("Nullable(");
+                inner.write_name(out, low_cardinality);
+                out.push(')');
+                return;
+            }
+            Self::LowCardinality(inner) => {
+                if low_cardinality {
+                    out.push_str("LowCardinality(");
+                    inner.write_name(out, low_cardinality);
+                    out.push(')');
+                } else {
+                    inner.write_name(out, low_cardinality);
+                }
+                return;
+            }
+            Self::Array(inner) => {
+                out.push_str("Array(");
+                inner.write_name(out, low_cardinality);
+                out.push(')');
+                return;
+            }
+            Self::Map(key, value) => {
+                out.push_str("Map(");
+                key.write_name(out, low_cardinality);
+                out.push_str(", ");
+                value.write_name(out, low_cardinality);
+                out.push(')');
+                return;
+            }
+        };
+        out.push_str(simple);
+    }
+
+    /// The zero value of this type, written for `null` items of arrays and maps, and for missing
+    /// fields in formats that can't leave them to the column's `DEFAULT` expression.
+    pub(super) fn default_cell(&self) -> Cell {
+        match self {
+            Self::Bool | Self::UInt8 | Self::Int8 => Cell::Scalar(vec![0; 1]),
+            Self::UInt16 | Self::Int16 | Self::Date => Cell::Scalar(vec![0; 2]),
+            Self::UInt32 | Self::Int32 | Self::Float32 | Self::Date32 | Self::DateTime(_) => {
+                Cell::Scalar(vec![0; 4])
+            }
+            Self::IPv4 => Cell::Scalar(vec![0; 4]),
+            Self::UInt64 | Self::Int64 | Self::Float64 | Self::DateTime64(..) => {
+                Cell::Scalar(vec![0; 8])
+            }
+            Self::Uuid | Self::IPv6 => Cell::Scalar(vec![0; 16]),
+            // An empty string is its zero length prefix.
+            Self::String => Cell::Scalar(vec![0; 1]),
+            Self::FixedString(length) => Cell::Scalar(vec![0; *length]),
+            Self::Nullable(_) => Cell::Null,
+            Self::LowCardinality(inner) => inner.default_cell(),
+            Self::Array(_) => Cell::Array(Vec::new()),
+            Self::Map(..) => Cell::Map(Vec::new()),
+        }
+    }
+
+    /// Converts a field of an event, or its absence, into a cell of this type.
+    ///
+    /// Like in `JSONEachRow`, missing fields and the `null` values of columns that aren't
+    /// nullable are left to the column's `DEFAULT` expression.
+    pub(super) fn convert(&self, value: Option<&Value>) -> Result<Cell, ValueError> {
+        match value {
+            None => Ok(Cell::Default),
+            Some(Value::Null) if !self.is_nullable() => Ok(Cell::Default),
+            Some(value) => self.convert_value(value),
+        }
+    }
+
+    fn is_nullable(&self) -> bool {
+        match self {
+            Self::Nullable(_) => true,
+            Self::LowCardinality(inner) => inner.is_nullable(),
+            _ => false,
+        }
+    }
+
+    fn convert_value(&self, value: &Value) -> Result<Cell, ValueError> {
+        if let Value::Null = value {
+            return Ok(self.default_cell());
+        }
+
+        match self {
+            Self::Nullable(inner) | Self::LowCardinality(inner) => inner.convert_value(value),
+            Self::Array(inner) => match value {
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| inner.convert_value(item))
+                    .collect::<Result<_, _>>()
+                    .map(Cell::Array),
+                _ => Err(self.mismatch(value)),
+            },
+            Self::Map(key_type, value_type) => match value {
+                Value::Object(fields) => fields
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            key_type.convert_value(&Value::from(key.as_str()))?,
+                            value_type.convert_value(value)?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(Cell::Map),
+                _ => Err(self.mismatch(value)),
+            },
+            _ => self.convert_scalar(value).map(Cell::Scalar),
+        }
+    }
+
+    fn convert_scalar(&self, value: &Value) -> Result<Vec<u8>, ValueError> {
+        match self {
+            Self::Bool => match value {
+                Value::Boolean(b) => Ok(vec![u8::from(*b)]),
+                Value::Integer(0) => Ok(vec![0]),
+                Value::Integer(1) => Ok(vec![1]),
+                Value::Bytes(bytes) => match bytes.as_ref() {
+                    b"true" | b"1" => Ok(vec![1]),
+                    b"false" | b"0" => Ok(vec![0]),
+                    _ => Err(self.unparsable(value)),
+                },
+                _ => Err(self.mismatch(value)),
+            },
+            Self::UInt8 | Self::UInt16 | Self::UInt32 | Self::UInt64 => {
+                let integer = self.integer(value)?;
+                let bytes = match self {
+                    Self::UInt8 => u8::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                    Self::UInt16 => u16::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                    Self::UInt32 => u32::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                    _ => u64::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                };
+                bytes.map_err(|_| self.out_of_range(integer))
+            }
+            Self::Int8 | Self::Int16 | Self::Int32 | Self::Int64 => {
+                let integer = self.integer(value)?;
+                let bytes = match self {
+                    Self::Int8 => i8::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                    Self::Int16 => i16::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                    Self::Int32 => i32::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                    _ => i64::try_from(integer).map(|i| i.to_le_bytes().to_vec()),
+                };
+                bytes.map_err(|_| self.out_of_range(integer))
+            }
+            Self::Float32 => self.float(value).map(|f| (f as f32).to_le_bytes().to_vec()),
+            Self::Float64 => self.float(value).map(|f| f.to_le_bytes().to_vec()),
+            Self::String => {
+                let string = self.string(value)?;
+                let mut bytes = Vec::with_capacity(string.len() + 2);
+                put_var_uint(&mut bytes, string.len() as u64);
+                bytes.extend_from_slice(&string);
+                Ok(bytes)
+            }
+            Self::FixedString(length) => {
+                let mut string = self.string(value)?;
+                if string.len() > *length {
+                    return Err(self.out_of_range(String::from_utf8_lossy(&string)));
+                }
+                string.resize(*length, 0);
+                Ok(string)
+            }
+            Self::Uuid => {
+                let uuid = value
+                    .as_bytes()
+                    .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                    .and_then(|uuid| uuid::Uuid::parse_str(uuid).ok())
+                    .ok_or_else(|| self.unparsable(value))?
+                    .as_u128();
+                // Serialized as two little endian halves, the high one first.
+                let mut bytes = ((uuid >> 64) as u64).to_le_bytes().to_vec();
+                bytes.extend_from_slice(&(uuid as u64).to_le_bytes());
+                Ok(bytes)
+            }
+            // Numbers are seconds since the Unix epoch, like for the other time types.
+            Self::Date | Self::Date32 => {
+                let date = match value {
+                    Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                        .ok()
+                        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()),
+                    _ => None,
+                };
+                let date = match date {
+                    Some(date) => date,
+                    None => self.timestamp(value)?.date_naive(),
+                };
+                let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+                let days = i128::from(date.signed_duration_since(epoch).num_days());
+                match self {
+                    Self::Date => u16::try_from(days).map(|d| d.to_le_bytes().to_vec()),
+                    _ => i32::try_from(days).map(|d| d.to_le_bytes().to_vec()),
+                }
+                .map_err(|_| self.out_of_range(days))
+            }
+            Self::DateTime(_) => {
+                let seconds = self.timestamp(value)?.timestamp();
+                u32::try_from(seconds)
+                    .map(|seconds| seconds.to_le_bytes().to_vec())
+                    .map_err(|_| self.out_of_range(seconds))
+            }
+            Self::DateTime64(precision, _) => {
+                let timestamp = self.timestamp(value)?;
+                let ticks = i128::from(timestamp.timestamp()) * 10i128.pow(*precision)
+                    + i128::from(timestamp.timestamp_subsec_nanos()) / 10i128.pow(9 - *precision);
+                i64::try_from(ticks)
+                    .map(|ticks| ticks.to_le_bytes().to_vec())
+                    .map_err(|_| self.out_of_range(ticks))
+            }
+            Self::IPv4 => match self.ip(value)? {
+                IpAddr::V4(ip) => Ok(u32::from(ip).to_le_bytes().to_vec()),
+                IpAddr::V6(_) => Err(self.unparsable(value)),
+            },
+            Self::IPv6 => match self.ip(value)? {
+                IpAddr::V4(ip) => Ok(ip.to_ipv6_mapped().octets().to_vec()),
+                IpAddr::V6(ip) => Ok(ip.octets().to_vec()),
+            },
+            Self::Nullable(_) | Self::LowCardinality(_) | Self::Array(_) | Self::Map(..) => {
+                Err(self.mismatch(value))
+            }
+        }
+    }
+
+    fn integer(&self, value: &Value) -> Result<i128, ValueError> {
+        match value {
+            Value::Integer(integer) => Ok(i128::from(*integer)),
+            Value::Boolean(b) => Ok(i128::from(*b)),
+            Value::Float(float) if float.fract() == 0.0 && float.abs() < 2f64.powi(127) => {
+                Ok(float.into_inner() as i128)
+            }
+            Value::Float(float) => Err(self.out_of_range(float)),
+            Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|integer| integer.trim().parse().ok())
+                .ok_or_else(|| self.unparsable(value)),
+            _ => Err(self.mismatch(value)),
+        }
+    }
+
+    fn float(&self, value: &Value) -> Result<f64, ValueError> {
+        match value {
+            Value::Float(float) => Ok(float.into_inner()),
+            Value::Integer(integer) => Ok(*integer as f64),
+            Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|float| float.trim().parse().ok())
+                .ok_or_else(|| self.unparsable(value)),
+            _ => Err(self.mismatch(value)),
+        }
+    }
+
+    fn string(&self, value: &Value) -> Result<Vec<u8>, ValueError> {
+        match value {
+            Value::Bytes(bytes) => Ok(bytes.to_vec()),
+            Value::Timestamp(timestamp) => Ok(timestamp
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into_bytes()),
+            Value::Object(_) | Value::Array(_) => {
+                serde_json::to_vec(value).map_err(|_| self.mismatch(value))
+            }
+            _ => Ok(value.to_string_lossy().into_owned().into_bytes()),
+        }
+    }
+
+    /// Timestamps are taken as-is, numbers as seconds since the Unix epoch and strings as RFC 3339.
+    fn timestamp(&self, value: &Value) -> Result<DateTime<Utc>, ValueError> {
+        match value {
+            Value::Timestamp(timestamp) => Ok(*timestamp),
+            Value::Integer(seconds) => Utc
+                .timestamp_opt(*seconds, 0)
+                .single()
+                .ok_or_else(|| self.out_of_range(seconds)),
+            Value::Float(seconds) => {
+                let nanos = (seconds.fract() * 1e9) as u32;
+                Utc.timestamp_opt(seconds.floor() as i64, nanos)
+                    .single()
+                    .ok_or_else(|| self.out_of_range(seconds))
+            }
+            Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .ok_or_else(|| self.unparsable(value)),
+            _ => Err(self.mismatch(value)),
+        }
+    }
+
+    fn ip(&self, value: &Value) -> Result<IpAddr, ValueError> {
+        match value {
+            Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|ip| {
+                    ip.parse::<Ipv4Addr>()
+                        .map(IpAddr::V4)
+                        .or_else(|_| ip.parse::<Ipv6Addr>().map(IpAddr::V6))
+                        .ok()
+                })
+                .ok_or_else(|| self.unparsable(value)),
+            _ => Err(self.mismatch(value)),
+        }
+    }
+
+    fn mismatch(&self, value: &Value) -> ValueError {
+        ValueError::Mismatch {
+            kind: value.kind_str(),
+            column_type: self.clone(),
+        }
+    }
+
+    fn out_of_range(&self, value: impl fmt::Display) -> ValueError {
+        ValueError::OutOfRange {
+            value: value.to_string(),
+            column_type: self.clone(),
+        }
+    }
+
+    fn unparsable(&self, value: &Value) -> ValueError {
+        ValueError::Unparsable {
+            value: value.to_string_lossy().into_owned(),
+            column_type: self.clone(),
+        }
+    }
+
+    /// Writes a cell as part of a `RowBinary` row.
+    pub(super) fn write_row_binary(&self, cell: &Cell, buffer: &mut BytesMut) {
+        match (self, cell) {
+            (Self::Nullable(_), Cell::Null) => buffer.put_u8(1),
+            (Self::Nullable(inner), cell) => {
+                buffer.put_u8(0);
+                inner.write_row_binary(cell, buffer);
+            }
+            (Self::LowCardinality(inner), cell) => inner.write_row_binary(cell, buffer),
+            (Self::Array(inner), Cell::Array(items)) => {
+                put_var_uint(buffer, items.len() as u64);
+                for item in items {
+                    inner.write_row_binary(item, buffer);
+                }
+            }
+            (Self::Map(key_type, value_type), Cell::Map(entries)) => {
+                put_var_uint(buffer, entries.len() as u64);
+                for (key, value) in entries {
+                    key_type.write_row_binary(key, buffer);
+                    value_type.write_row_binary(value, buffer);
+                }
+            }
+            (_, Cell::Scalar(bytes)) => buffer.put_slice(bytes),
+            _ => unreachable!("cells are converted from their column type"),
+        }
+    }
+
+    /// Writes the cells of one column as part of a `Native` block.
+    pub(super) fn write_native(&self, cells: &[&Cell], buffer: &mut BytesMut) {
+        match self {
+            Self::Nullable(inner) => {
+                let default = inner.default_cell();
+                for cell in cells {
+                    buffer.put_u8(u8::from(matches!(cell, Cell::Null)));
+                }
+                let values = cells
+                    .iter()
+                    .map(|cell| match cell {
+                        Cell::Null => &default,
+                        cell => *cell,
+                    })
+                    .collect::<Vec<_>>();
+                inner.write_native(&values, buffer);
+            }
+            Self::LowCardinality(inner) => inner.write_native(cells, buffer),
+            Self::Array(inner) => {
+                let mut offset = 0;
+                let mut items = Vec::new();
+                for cell in cells {
+                    if let Cell::Array(cell_items) = cell {
+                        offset += cell_items.len() as u64;
+                        items.extend(cell_items);
+                    }
+                    buffer.put_u64_le(offset);
+                }
+                inner.write_native(&items, buffer);
+            }
+            Self::Map(key_type, value_type) => {
+                let mut offset = 0;
+                let mut keys = Vec::new();
+                let mut values = Vec::new();
+                for cell in cells {
+                    if let Cell::Map(entries) = cell {
+                        offset += entries.len() as u64;
+                        for (key, value) in entries {
+                            keys.push(key);
+                            values.push(value);
+                        }
+                    }
+                    buffer.put_u64_le(offset);
+                }
+                key_type.write_native(&keys, buffer);
+                value_type.write_native(&values, buffer);
+            }
+            _ => {
+                for cell in cells {
+                    if let Cell::Scalar(bytes) = cell {
+                        buffer.put_slice(bytes);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut name = String::new();
+        self.write_name(&mut name, true);
+        f.write_str(&name)
+    }
+}
+
+/// Writes an unsigned LEB128 integer, which prefixes strings and collections.
+pub(super) fn put_var_uint(buffer: &mut impl BufMut, mut value: u64) {
+    while value >= 0x80 {
+        buffer.put_u8((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.put_u8(value as u8);
+}
+
+/// Splits the arguments of a parametrized type at the top-level commas.
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in arguments.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                parts.push(arguments[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(arguments[start..].trim());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::btreemap;
+
+    use super::*;
+
+    fn parse(type_name: &str) -> ColumnType {
+        ColumnType::parse(type_name).unwrap()
+    }
+
+    fn row_binary(column_type: &ColumnType, value: Value) -> Vec<u8> {
+        let cell = column_type.convert(Some(&value)).unwrap();
+        let mut buffer = BytesMut::new();
+        column_type.write_row_binary(&cell, &mut buffer);
+        buffer.to_vec()
+    }
+
+    #[test]
+    fn parses_nested_types() {
+        assert_eq!(
+            parse("Map(LowCardinality(String), Array(Nullable(DateTime64(3, 'Europe/Paris'))))"),
+            ColumnType::Map(
+                Box::new(ColumnType::LowCardinality(Box::new(ColumnType::String))),
+                Box::new(ColumnType::Array(Box::new(ColumnType::Nullable(Box::new(
+                    ColumnType::DateTime64(3, Some("'Europe/Paris'".into()))
+                ))))),
+            )
+        );
+        assert_eq!(parse("FixedString(16)"), ColumnType::FixedString(16));
+        assert_eq!(
+            parse("DateTime('UTC')"),
+            ColumnType::DateTime(Some("'UTC'".into()))
+        );
+        assert!(ColumnType::parse("Decimal(10, 2)").is_err());
+        assert!(ColumnType::parse("DateTime64(12)").is_err());
+    }
+
+    #[test]
+    fn native_name_drops_low_cardinality() {
+        let column_type = parse("Array(LowCardinality(Nullable(String)))");
+        assert_eq!(column_type.native_name(), "Array(Nullable(String))");
+        assert_eq!(
+            column_type.to_string(),
+            "Array(LowCardinality(Nullable(String)))"
+        );
+    }
+
+    #[test]
+    fn encodes_scalars_as_row_binary() {
+        assert_eq!(row_binary(&parse("UInt16"), Value::from(258)), vec![2, 1]);
+        assert_eq!(row_binary(&parse("Int8"), Value::from("-1")), vec![0xff]);
+        assert_eq!(
+            row_binary(&parse("String"), Value::from("abc")),
+            vec![3, b'a', b'b', b'c']
+        );
+        assert_eq!(
+            row_binary(&parse("FixedString(4)"), Value::from("ab")),
+            vec![b'a', b'b', 0, 0]
+        );
+        assert_eq!(
+            row_binary(&parse("IPv4"), Value::from("1.2.3.4")),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(
+            row_binary(&parse("Date"), Value::from("1970-01-03")),
+            vec![2, 0]
+        );
+    }
+
+    #[test]
+    fn encodes_date_time64() {
+        let timestamp = Utc.timestamp_opt(1, 500_000_000).unwrap();
+        assert_eq!(
+            row_binary(&parse("DateTime64(3)"), Value::from(timestamp)),
+            1500i64.to_le_bytes().to_vec()
+        );
+        assert_eq!(
+            row_binary(
+                &parse("DateTime64(6, 'UTC')"),
+                Value::from("1970-01-01T00:00:01.5Z")
+            ),
+            1_500_000i64.to_le_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn encodes_nested_row_binary() {
+        let column_type = parse("Map(LowCardinality(String), Array(Nullable(UInt8)))");
+        let value = Value::from(btreemap! {
+            "a" => vec![Value::from(1), Value::Null],
+        });
+        assert_eq!(
+            row_binary(&column_type, value),
+            vec![1, 1, b'a', 2, 0, 1, 1]
+        );
+    }
+
+    #[test]
+    fn missing_values_use_defaults() {
+        assert_eq!(parse("UInt32").convert(None), Ok(Cell::Default));
+        assert_eq!(
+            parse("UInt32").convert(Some(&Value::Null)),
+            Ok(Cell::Default)
+        );
+        assert_eq!(parse("Nullable(String)").convert(None), Ok(Cell::Default));
+        assert_eq!(
+            parse("LowCardinality(Nullable(String))").convert(Some(&Value::Null)),
+            Ok(Cell::Null)
+        );
+        assert_eq!(
+            parse("Array(UInt8)").convert(Some(&Value::from(vec![Value::Null]))),
+            Ok(Cell::Array(vec![Cell::Scalar(vec![0])]))
+        );
+    }
+
+    #[test]
+    fn encodes_dates_from_seconds() {
+        assert_eq!(
+            row_binary(&parse("Date"), Value::from(2 * 86_400 + 1)),
+            vec![2, 0]
+        );
+        assert_eq!(
+            row_binary(&parse("Date32"), Value::from(-86_400)),
+            (-1i32).to_le_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn reports_conversion_failures() {
+        assert!(matches!(
+            parse("UInt8").convert(Some(&Value::from(256))),
+            Err(ValueError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            parse("UUID").convert(Some(&Value::from("nope"))),
+            Err(ValueError::Unparsable { .. })
+        ));
+        assert!(matches!(
+            parse("Array(String)").convert(Some(&Value::from("nope"))),
+            Err(ValueError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn encodes_native_columns() {
+        let column_type = parse("Array(Nullable(UInt8))");
+        let first = column_type
+            .convert(Some(&Value::from(vec![Value::from(1), Value::Null])))
+            .unwrap();
+        let second = column_type
+            .convert(Some(&Value::from(vec![Value::from(2)])))
+            .unwrap();
+        let mut buffer = BytesMut::new();
+        column_type.write_native(&[&first, &second], &mut buffer);
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&2u64.to_le_bytes());
+        expected.extend_from_slice(&3u64.to_le_bytes());
+        expected.extend_from_slice(&[0, 1, 0]);
+        expected.extend_from_slice(&[1, 0, 2]);
+        assert_eq!(buffer.to_vec(), expected);
+    }
+}
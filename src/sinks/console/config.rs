@@ -99,6 +99,10 @@ impl SinkConfig for ConsoleSinkConfig {
         Input::new(self.encoding.config().1.input_type())
     }
 
+    fn encoding_config(&self) -> Option<(EncodingConfigWithFraming, SinkType)> {
+        Some((self.encoding.clone(), SinkType::StreamBased))
+    }
+
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
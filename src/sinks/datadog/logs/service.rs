@@ -1,6 +1,7 @@
 use std::{
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use bytes::Bytes;
@@ -34,6 +35,10 @@ impl RetryLogic for LogApiRetry {
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
         error.is_retriable()
     }
+
+    fn retry_after_error(&self, error: &Self::Error) -> Option<Duration> {
+        error.retry_after()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1,6 +1,6 @@
 #![allow(clippy::print_stdout)] // tests
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use bytes::Bytes;
 use chrono::Utc;
@@ -500,6 +500,23 @@ async fn error_is_retriable() {
             source: TlsError::MissingKey
         }
     }));
+    assert!(retry.is_retriable_error(&DatadogApiError::TooManyRequests { retry_after: None }));
+
+    // retry-after
+    assert_eq!(
+        retry.retry_after_error(&DatadogApiError::TooManyRequests {
+            retry_after: Some(Duration::from_secs(30))
+        }),
+        Some(Duration::from_secs(30))
+    );
+    assert_eq!(
+        retry.retry_after_error(&DatadogApiError::ServiceUnavailable {
+            retry_after: Some(Duration::from_secs(5))
+        }),
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(retry.retry_after_error(&DatadogApiError::ServerError), None);
+
     // note: HttpError::CallRequest and HttpError::MakeHttpsConnector are all retry-able,
     //       but are not straightforward to instantiate due to the design of
     //       the crates they originate from.
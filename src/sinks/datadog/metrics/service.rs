@@ -1,5 +1,6 @@
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
@@ -31,6 +32,10 @@ impl RetryLogic for DatadogMetricsRetryLogic {
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
         error.is_retriable()
     }
+
+    fn retry_after_error(&self, error: &Self::Error) -> Option<Duration> {
+        error.retry_after()
+    }
 }
 
 /// Generalized request for sending metrics to the Datadog metrics endpoints.
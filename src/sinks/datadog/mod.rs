@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use futures_util::FutureExt;
 use http::{Request, StatusCode, Uri};
 use hyper::body::Body;
@@ -10,7 +12,7 @@ use vector_lib::{
 use crate::{
     common::datadog::{self, get_api_base_endpoint},
     http::{HttpClient, HttpError},
-    sinks::HealthcheckError,
+    sinks::{util::http::parse_retry_after, HealthcheckError},
 };
 
 use super::Healthcheck;
@@ -189,9 +191,11 @@ pub enum DatadogApiError {
     #[snafu(display("Client sent a payload that is too large."))]
     PayloadTooLarge,
     #[snafu(display("Client sent too many requests (rate limiting)."))]
-    TooManyRequests,
+    TooManyRequests { retry_after: Option<Duration> },
     #[snafu(display("Client request was invalid."))]
     ClientError,
+    #[snafu(display("Server is not ready to handle the request."))]
+    ServiceUnavailable { retry_after: Option<Duration> },
     #[snafu(display("Server responded with an error."))]
     ServerError,
 }
@@ -228,8 +232,13 @@ impl DatadogApiError {
                     StatusCode::FORBIDDEN => Err(DatadogApiError::Forbidden),
                     StatusCode::REQUEST_TIMEOUT => Err(DatadogApiError::RequestTimeout),
                     StatusCode::PAYLOAD_TOO_LARGE => Err(DatadogApiError::PayloadTooLarge),
-                    StatusCode::TOO_MANY_REQUESTS => Err(DatadogApiError::TooManyRequests),
+                    StatusCode::TOO_MANY_REQUESTS => Err(DatadogApiError::TooManyRequests {
+                        retry_after: parse_retry_after(response.headers()),
+                    }),
                     s if s.is_client_error() => Err(DatadogApiError::ClientError),
+                    StatusCode::SERVICE_UNAVAILABLE => Err(DatadogApiError::ServiceUnavailable {
+                        retry_after: parse_retry_after(response.headers()),
+                    }),
                     _ => Err(DatadogApiError::ServerError),
                 }
             }
@@ -248,11 +257,21 @@ impl DatadogApiError {
             DatadogApiError::HttpError { error } => error.is_retriable(),
             DatadogApiError::BadRequest | DatadogApiError::PayloadTooLarge => false,
             DatadogApiError::ServerError
+            | DatadogApiError::ServiceUnavailable { .. }
             | DatadogApiError::ClientError
             | DatadogApiError::Unauthorized
             | DatadogApiError::Forbidden
             | DatadogApiError::RequestTimeout
-            | DatadogApiError::TooManyRequests => true,
+            | DatadogApiError::TooManyRequests { .. } => true,
+        }
+    }
+
+    /// The delay the API asked to wait for before retrying the request, if any.
+    pub const fn retry_after(&self) -> Option<Duration> {
+        match self {
+            DatadogApiError::TooManyRequests { retry_after }
+            | DatadogApiError::ServiceUnavailable { retry_after } => *retry_after,
+            _ => None,
         }
     }
 }
@@ -1,6 +1,7 @@
 use std::{
     collections::BTreeMap,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use bytes::{Buf, Bytes};
@@ -15,7 +16,10 @@ use vector_lib::stream::DriverResponse;
 
 use crate::{
     http::{BuildRequestSnafu, CallRequestSnafu, HttpClient, HttpError},
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        http::parse_retry_after,
+        retries::{RetryAction, RetryLogic},
+    },
 };
 
 #[derive(Debug, Default, Clone)]
@@ -49,6 +53,10 @@ impl RetryLogic for TraceApiRetry {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after_response(&self, response: &Self::Response) -> Option<Duration> {
+        response.retry_after
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -93,6 +101,7 @@ pub struct TraceApiResponse {
     body: Bytes,
     byte_size: GroupedCountByteSize,
     uncompressed_size: usize,
+    retry_after: Option<Duration>,
 }
 
 impl DriverResponse for TraceApiResponse {
@@ -163,6 +172,7 @@ impl Service<TraceApiRequest> for TraceApiService {
                 body,
                 byte_size,
                 uncompressed_size,
+                retry_after: parse_retry_after(&parts.headers),
             })
         })
     }
@@ -180,7 +180,9 @@ impl ElasticsearchCommon {
                 transformer: config.encoding.clone(),
                 doc_type,
                 suppress_type_name,
+                script: config.bulk.script.clone(),
             },
+            retry_partial: config.request_retry_partial,
         };
 
         Ok(Self {
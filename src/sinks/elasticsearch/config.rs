@@ -19,7 +19,7 @@ use crate::{
             service::{ElasticsearchService, HttpRequestBuilder},
             sink::ElasticsearchSink,
             ElasticsearchApiVersion, ElasticsearchAuthConfig, ElasticsearchCommon,
-            ElasticsearchCommonMode, ElasticsearchMode,
+            ElasticsearchCommonMode, ElasticsearchMode, ParseError,
         },
         util::{
             http::RequestConfig, service::HealthConfig, BatchConfig, Compression,
@@ -90,9 +90,10 @@ pub struct ElasticsearchConfig {
     )]
     pub suppress_type_name: bool,
 
-    /// Whether or not to retry successful requests containing partial failures.
+    /// Whether or not to retry the items of successful requests that failed with a retriable status.
     ///
-    /// To avoid duplicates in Elasticsearch, please use option `id_key`.
+    /// Only the items that failed with a `429` or server error status are sent again, while the
+    /// items rejected with any other status are marked as such.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
     pub request_retry_partial: bool,
@@ -227,10 +228,20 @@ impl Default for ElasticsearchConfig {
 impl ElasticsearchConfig {
     pub fn common_mode(&self) -> crate::Result<ElasticsearchCommonMode> {
         match self.mode {
-            ElasticsearchMode::Bulk => Ok(ElasticsearchCommonMode::Bulk {
-                index: self.bulk.index.clone(),
-                action: self.bulk.action.clone(),
-            }),
+            ElasticsearchMode::Bulk => {
+                let scripted = self.bulk.script.is_some();
+                if !scripted
+                    && !self.bulk.action.is_dynamic()
+                    && self.bulk.action.get_ref() == "upsert"
+                {
+                    return Err(ParseError::UpsertScriptRequired.into());
+                }
+                Ok(ElasticsearchCommonMode::Bulk {
+                    index: self.bulk.index.clone(),
+                    action: self.bulk.action.clone(),
+                    scripted,
+                })
+            }
             ElasticsearchMode::DataStream => Ok(ElasticsearchCommonMode::DataStream(
                 self.data_stream.clone().unwrap_or_default(),
             )),
@@ -245,7 +256,14 @@ impl ElasticsearchConfig {
 pub struct BulkConfig {
     /// Action to use when making requests to the [Elasticsearch Bulk API][es_bulk].
     ///
-    /// Only `index` and `create` actions are supported.
+    /// The `index`, `create`, `update`, `upsert` and `delete` actions are supported. The `update`,
+    /// `upsert` and `delete` actions require the document ID, set through `id_key`, and the
+    /// events without one are dropped.
+    ///
+    /// The `update` action sends the event as a partial document, with `doc_as_upsert` so that
+    /// the document is created from the event if it does not exist. The `upsert` action updates
+    /// the document with `bulk.script` instead, and requires it: the events rendering `upsert`
+    /// without a script are dropped.
     ///
     /// [es_bulk]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
     #[serde(default = "default_bulk_action")]
@@ -258,6 +276,18 @@ pub struct BulkConfig {
     #[configurable(metadata(docs::examples = "application-{{ application_id }}-%Y-%m-%d"))]
     #[configurable(metadata(docs::examples = "{{ index }}"))]
     pub index: Template,
+
+    /// The [script][es_script] used to update the documents with the `upsert` action.
+    ///
+    /// The event is available to the script as `params.event`, and is used as the document
+    /// when it does not exist yet. Required by the `upsert` action.
+    ///
+    /// [es_script]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update.html#update-api-example
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(
+        docs::examples = "ctx._source.counter = (ctx._source.counter ?: 0) + params.event.count"
+    ))]
+    pub script: Option<String>,
 }
 
 fn default_bulk_action() -> Template {
@@ -273,6 +303,7 @@ impl Default for BulkConfig {
         Self {
             action: default_bulk_action(),
             index: default_index(),
+            script: None,
         }
     }
 }
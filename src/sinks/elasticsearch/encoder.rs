@@ -18,7 +18,7 @@ use crate::{
     },
 };
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ProcessedEvent {
     pub index: String,
     pub bulk_action: BulkAction,
@@ -62,6 +62,7 @@ pub struct ElasticsearchEncoder {
     pub transformer: Transformer,
     pub doc_type: String,
     pub suppress_type_name: bool,
+    pub script: Option<String>,
 }
 
 impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
@@ -88,19 +89,48 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
                 self.suppress_type_name,
                 &event.id,
             )?;
-            written_bytes +=
-                as_tracked_write::<_, _, io::Error>(writer, &log, |mut writer, log| {
+            written_bytes += as_tracked_write::<_, _, io::Error>(writer, &log, |writer, log| {
+                writer.write_all(&[b'\n'])?;
+                if write_bulk_source(writer, event.bulk_action, log, self.script.as_deref())? {
                     writer.write_all(&[b'\n'])?;
-                    serde_json::to_writer(&mut writer, log)?;
-                    writer.write_all(&[b'\n'])?;
-                    Ok(())
-                })?;
+                }
+                Ok(())
+            })?;
         }
 
         Ok((written_bytes, byte_size))
     }
 }
 
+/// Writes the source line following the action line of a bulk request, if the action has one.
+fn write_bulk_source(
+    writer: &mut dyn Write,
+    bulk_action: BulkAction,
+    log: &LogEvent,
+    script: Option<&str>,
+) -> std::io::Result<bool> {
+    match (bulk_action, script) {
+        (BulkAction::Index | BulkAction::Create, _) => serde_json::to_writer(&mut *writer, log)?,
+        (BulkAction::Update, _) => {
+            writer.write_all(br#"{"doc":"#)?;
+            serde_json::to_writer(&mut *writer, log)?;
+            writer.write_all(br#","doc_as_upsert":true}"#)?;
+        }
+        (BulkAction::Upsert, Some(script)) => {
+            writer.write_all(br#"{"script":{"source":"#)?;
+            serde_json::to_writer(&mut *writer, script)?;
+            writer.write_all(br#","params":{"event":"#)?;
+            serde_json::to_writer(&mut *writer, log)?;
+            writer.write_all(br#"}},"upsert":"#)?;
+            serde_json::to_writer(&mut *writer, log)?;
+            writer.write_all(b"}")?;
+        }
+        // The events of `upsert` actions without a script are dropped beforehand.
+        (BulkAction::Upsert, None) | (BulkAction::Delete, _) => return Ok(false),
+    }
+    Ok(true)
+}
+
 fn write_bulk_action(
     writer: &mut dyn Write,
     bulk_action: &str,
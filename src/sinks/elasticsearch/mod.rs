@@ -26,7 +26,7 @@ use vector_lib::sensitive_string::SensitiveString;
 
 use crate::{
     event::{EventRef, LogEvent},
-    internal_events::TemplateRenderingError,
+    internal_events::{ElasticsearchUpsertWithoutScript, TemplateRenderingError},
     template::{Template, TemplateParseError},
 };
 
@@ -85,14 +85,28 @@ pub enum BulkAction {
 
     /// The `create` action.
     Create,
+
+    /// The `update` action, with the event as the partial document, which creates the document
+    /// if it does not exist.
+    Update,
+
+    /// The `update` action with `bulk.script`, creating the document from the event if it does
+    /// not exist.
+    Upsert,
+
+    /// The `delete` action.
+    Delete,
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
 impl BulkAction {
+    /// The name of the action in the Bulk API.
     pub const fn as_str(&self) -> &'static str {
         match self {
             BulkAction::Index => "index",
             BulkAction::Create => "create",
+            BulkAction::Update | BulkAction::Upsert => "update",
+            BulkAction::Delete => "delete",
         }
     }
 
@@ -100,8 +114,18 @@ impl BulkAction {
         match self {
             BulkAction::Index => "/index",
             BulkAction::Create => "/create",
+            BulkAction::Update | BulkAction::Upsert => "/update",
+            BulkAction::Delete => "/delete",
         }
     }
+
+    /// Whether the action targets an existing document, and thus requires its ID.
+    pub const fn requires_id(&self) -> bool {
+        matches!(
+            self,
+            BulkAction::Update | BulkAction::Upsert | BulkAction::Delete
+        )
+    }
 }
 
 impl TryFrom<&str> for BulkAction {
@@ -111,6 +135,9 @@ impl TryFrom<&str> for BulkAction {
         match input {
             "index" => Ok(BulkAction::Index),
             "create" => Ok(BulkAction::Create),
+            "update" => Ok(BulkAction::Update),
+            "upsert" => Ok(BulkAction::Upsert),
+            "delete" => Ok(BulkAction::Delete),
             _ => Err(format!("Invalid bulk action: {}", input)),
         }
     }
@@ -120,7 +147,11 @@ impl_generate_config_from_default!(ElasticsearchConfig);
 
 #[derive(Debug, Clone)]
 pub enum ElasticsearchCommonMode {
-    Bulk { index: Template, action: Template },
+    Bulk {
+        index: Template,
+        action: Template,
+        scripted: bool,
+    },
     DataStream(DataStreamConfig),
 }
 
@@ -145,6 +176,7 @@ impl ElasticsearchCommonMode {
         match self {
             ElasticsearchCommonMode::Bulk {
                 action: bulk_action_template,
+                scripted,
                 ..
             } => bulk_action_template
                 .render_string(event)
@@ -156,7 +188,14 @@ impl ElasticsearchCommonMode {
                     });
                 })
                 .ok()
-                .and_then(|value| BulkAction::try_from(value.as_str()).ok()),
+                .and_then(|value| BulkAction::try_from(value.as_str()).ok())
+                .filter(|action| {
+                    let missing_script = *action == BulkAction::Upsert && !scripted;
+                    if missing_script {
+                        emit!(ElasticsearchUpsertWithoutScript);
+                    }
+                    !missing_script
+                }),
             // avoid the interpolation
             ElasticsearchCommonMode::DataStream(_) => Some(BulkAction::Create),
         }
@@ -210,6 +249,8 @@ pub enum ParseError {
     IndexTemplate { source: TemplateParseError },
     #[snafu(display("Batch action template parse error: {}", source))]
     BatchActionTemplate { source: TemplateParseError },
+    #[snafu(display("The `upsert` bulk action requires `bulk.script`"))]
+    UpsertScriptRequired,
     #[cfg(feature = "aws-core")]
     #[snafu(display("aws.region required when AWS authentication is in use"))]
     RegionRequired,
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bytes::Bytes;
 use vector_lib::EstimatedJsonEncodedSizeOf;
 use vector_lib::{json_size::JsonSize, request_metadata::RequestMetadata};
@@ -7,7 +9,7 @@ use crate::{
     sinks::{
         elasticsearch::{
             encoder::{ElasticsearchEncoder, ProcessedEvent},
-            service::ElasticsearchRequest,
+            service::{ElasticsearchRequest, PartialRetry},
         },
         util::{
             metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
@@ -20,12 +22,14 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: ElasticsearchEncoder,
+    pub retry_partial: bool,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
     events_byte_size: JsonSize,
+    partial_retry: Option<Arc<PartialRetry>>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -56,10 +60,25 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
 
+        // Retrying partial failures requires the events to encode again, along with their own
+        // finalizers to mark the ones rejected by Elasticsearch.
+        let (finalizers, partial_retry) = if self.retry_partial {
+            let item_finalizers = events
+                .iter_mut()
+                .map(Finalizable::take_finalizers)
+                .collect::<Vec<_>>();
+            let finalizers = item_finalizers.iter().cloned().collect();
+            let partial_retry = PartialRetry::new(events.clone(), item_finalizers);
+            (finalizers, Some(Arc::new(partial_retry)))
+        } else {
+            (events.take_finalizers(), None)
+        };
+
         let es_metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            finalizers,
             batch_size: events.len(),
             events_byte_size,
+            partial_retry,
         };
         (es_metadata, metadata_builder, events)
     }
@@ -76,6 +95,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
+            partial_retry: es_metadata.partial_retry,
         }
     }
 }
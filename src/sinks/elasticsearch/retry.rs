@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use http::StatusCode;
 use serde::Deserialize;
 
@@ -5,17 +7,20 @@ use crate::{
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
-        util::retries::{RetryAction, RetryLogic},
+        util::{
+            http::parse_retry_after,
+            retries::{RetryAction, RetryLogic},
+        },
     },
 };
 
 #[derive(Deserialize, Debug)]
-struct EsResultResponse {
-    items: Vec<EsResultItem>,
+pub(super) struct EsResultResponse {
+    pub(super) items: Vec<EsResultItem>,
 }
 
 impl EsResultResponse {
-    fn parse(body: &str) -> Result<Self, String> {
+    pub(super) fn parse(body: &str) -> Result<Self, String> {
         serde_json::from_str::<EsResultResponse>(body).map_err(|json_error| {
             format!(
                 "some messages failed, could not parse response, error: {}",
@@ -28,8 +33,7 @@ impl EsResultResponse {
     fn iter_status(&self) -> impl Iterator<Item = (StatusCode, Option<&EsErrorDetails>)> {
         self.items.iter().filter_map(|item| {
             item.result()
-                .status
-                .and_then(|status| StatusCode::from_u16(status).ok())
+                .status_code()
                 .map(|status| (status, item.result().error.as_ref()))
         })
     }
@@ -51,34 +55,54 @@ impl EsResultResponse {
 }
 
 #[derive(Deserialize, Debug)]
-enum EsResultItem {
+pub(super) enum EsResultItem {
     #[serde(rename = "index")]
     Index(EsIndexResult),
     #[serde(rename = "create")]
     Create(EsIndexResult),
+    #[serde(rename = "update")]
+    Update(EsIndexResult),
+    #[serde(rename = "delete")]
+    Delete(EsIndexResult),
 }
 
 impl EsResultItem {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    fn result(&self) -> &EsIndexResult {
+    pub(super) fn result(&self) -> &EsIndexResult {
         match self {
             EsResultItem::Index(r) => r,
             EsResultItem::Create(r) => r,
+            EsResultItem::Update(r) => r,
+            EsResultItem::Delete(r) => r,
         }
     }
 }
 
 #[derive(Deserialize, Debug)]
-struct EsIndexResult {
-    status: Option<u16>,
-    error: Option<EsErrorDetails>,
+pub(super) struct EsIndexResult {
+    pub(super) status: Option<u16>,
+    pub(super) error: Option<EsErrorDetails>,
+}
+
+impl EsIndexResult {
+    /// The status of the item, if it is a valid status code.
+    pub(super) fn status_code(&self) -> Option<StatusCode> {
+        self.status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+    }
 }
 
 #[derive(Deserialize, Debug)]
-struct EsErrorDetails {
-    reason: String,
+pub(super) struct EsErrorDetails {
+    pub(super) reason: String,
     #[serde(rename = "type")]
-    err_type: String,
+    pub(super) err_type: String,
+}
+
+/// Whether an item of a bulk request failed with a status worth retrying, which are
+/// backpressure and server errors.
+pub(super) fn is_retriable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
 }
 
 #[derive(Clone)]
@@ -124,11 +148,9 @@ impl RetryLogic for ElasticsearchRetryLogic {
                                 // We will retry if there exists at least one item that
                                 // failed with a retriable error.
                                 // Those are backpressure and server errors.
-                                if let Some((status, error)) =
-                                    resp.iter_status().find(|(status, _)| {
-                                        *status == StatusCode::TOO_MANY_REQUESTS
-                                            || status.is_server_error()
-                                    })
+                                if let Some((status, error)) = resp
+                                    .iter_status()
+                                    .find(|(status, _)| is_retriable_status(*status))
                                 {
                                     let msg = if let Some(error) = error {
                                         format!(
@@ -140,6 +162,10 @@ impl RetryLogic for ElasticsearchRetryLogic {
                                     };
                                     return RetryAction::Retry(msg.into());
                                 }
+
+                                // The items rejected by Elasticsearch have already been marked
+                                // as such by the service, and the others were delivered.
+                                return RetryAction::Successful;
                             }
 
                             RetryAction::DontRetry(resp.get_error_reason(&body).into())
@@ -153,6 +179,10 @@ impl RetryLogic for ElasticsearchRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after_response(&self, response: &ElasticsearchResponse) -> Option<Duration> {
+        parse_retry_after(response.http_response.headers())
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +237,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn handles_partial_rejection_response() {
+        let json = "{\"took\":34,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-asjkf1234\",\"_type\":\"log_lines\",\"_id\":\"4Z3QLYEBT52RtoOEKz2H\",\"status\":201}},{\"index\":{\"_index\":\"test-asjkf1234\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(json))
+            .unwrap();
+        let logic = ElasticsearchRetryLogic {
+            retry_partial: true,
+        };
+        assert!(matches!(
+            logic.should_retry_response(&ElasticsearchResponse {
+                http_response: response,
+                event_status: EventStatus::Delivered,
+                batch_size: 2,
+                events_byte_size: CountByteSize(2, JsonSize::new(2)).into(),
+            }),
+            RetryAction::Successful
+        ));
+    }
+
     #[test]
     fn get_index_error_reason() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
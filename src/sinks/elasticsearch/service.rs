@@ -1,12 +1,13 @@
 use std::{
-    collections::HashMap,
-    sync::Arc,
+    collections::{BTreeMap, HashMap},
+    io,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use http::{Response, Uri};
+use http::{Response, StatusCode, Uri};
 use hyper::{service::Service, Body, Request};
 use tower::ServiceExt;
 use vector_lib::stream::DriverResponse;
@@ -16,14 +17,21 @@ use vector_lib::{
     request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata},
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+use super::{
+    encoder::ProcessedEvent,
+    request_builder::ElasticsearchRequestBuilder,
+    retry::{is_retriable_status, EsResultResponse},
+    ElasticsearchCommon, ElasticsearchConfig,
+};
 use crate::{
-    event::{EventFinalizers, EventStatus, Finalizable},
+    event::{EventFinalizers, EventStatus, Finalizable, Rejection},
     http::HttpClient,
+    internal_events::ElasticsearchBulkItemsFailed,
     sinks::util::{
         auth::Auth,
-        http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+        http::{http_rejection, HttpBatchService, RequestConfig},
+        request_builder::EncodeResult,
+        Compression, ElementCount, RequestBuilder,
     },
 };
 
@@ -34,6 +42,103 @@ pub struct ElasticsearchRequest {
     pub batch_size: usize,
     pub events_byte_size: JsonSize,
     pub metadata: RequestMetadata,
+    /// Set when `request_retry_partial` is enabled, shared by all the attempts of the request.
+    pub partial_retry: Option<Arc<PartialRetry>>,
+}
+
+/// The items of a request whose partial failures are retried.
+///
+/// Each attempt of the request only sends the items that failed with a retriable status in the
+/// previous one, while the items rejected by Elasticsearch are finalized on their own.
+#[derive(Debug)]
+pub struct PartialRetry {
+    events: Vec<ProcessedEvent>,
+    finalizers: Vec<EventFinalizers>,
+    pending: Mutex<Option<Vec<usize>>>,
+}
+
+impl PartialRetry {
+    pub fn new(events: Vec<ProcessedEvent>, finalizers: Vec<EventFinalizers>) -> Self {
+        Self {
+            events,
+            finalizers,
+            pending: Mutex::new(None),
+        }
+    }
+
+    /// The indices of the items to send in the next attempt.
+    fn pending(&self) -> Vec<usize> {
+        self.pending
+            .lock()
+            .expect("mutex poisoned")
+            .clone()
+            .unwrap_or_else(|| (0..self.events.len()).collect())
+    }
+
+    fn encode(
+        &self,
+        items: &[usize],
+        request_builder: &ElasticsearchRequestBuilder,
+    ) -> Result<Bytes, io::Error> {
+        let events = items.iter().map(|&i| self.events[i].clone()).collect();
+        request_builder
+            .encode_events(events)
+            .map(EncodeResult::into_payload)
+    }
+
+    /// Records the outcome of the items sent by an attempt, and returns the status of the request.
+    fn update(&self, items: &[usize], response: &Response<Bytes>) -> EventStatus {
+        let status = get_event_status(response);
+        if status != EventStatus::Rejected || !response.status().is_success() {
+            if status == EventStatus::Delivered {
+                *self.pending.lock().expect("mutex poisoned") = Some(Vec::new());
+            }
+            return status;
+        }
+
+        let body = String::from_utf8_lossy(response.body());
+        let Ok(result) = EsResultResponse::parse(&body) else {
+            return status;
+        };
+
+        let mut pending = Vec::new();
+        let mut failures = BTreeMap::<StatusCode, (usize, String)>::new();
+        for (&index, item) in items.iter().zip(&result.items) {
+            let item = item.result();
+            let Some(status) = item.status_code().filter(|status| !status.is_success()) else {
+                continue;
+            };
+            let reason = match &item.error {
+                Some(error) => format!("error type: {}, reason: {}", error.err_type, error.reason),
+                None => format!("status: {}", status),
+            };
+
+            if is_retriable_status(status) {
+                pending.push(index);
+            } else {
+                self.finalizers[index]
+                    .reject(&Rejection::new(reason.as_str()).with_http_status(status.as_u16()));
+            }
+            failures.entry(status).or_insert((0, reason)).0 += 1;
+        }
+
+        for (status, (count, reason)) in failures {
+            emit!(ElasticsearchBulkItemsFailed {
+                status,
+                count,
+                reason: &reason,
+                retriable: is_retriable_status(status),
+            });
+        }
+
+        let status = if pending.is_empty() {
+            EventStatus::Delivered
+        } else {
+            EventStatus::Errored
+        };
+        *self.pending.lock().expect("mutex poisoned") = Some(pending);
+        status
+    }
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
@@ -73,6 +178,7 @@ pub struct ElasticsearchService {
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    http_request_builder: Arc<HttpRequestBuilder>,
 }
 
 impl ElasticsearchService {
@@ -81,13 +187,19 @@ impl ElasticsearchService {
         http_request_builder: HttpRequestBuilder,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
-        let batch_service = HttpBatchService::new(http_client, move |req| {
-            let request_builder = Arc::clone(&http_request_builder);
-            let future: BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>> =
-                Box::pin(async move { request_builder.build_request(req).await });
-            future
+        let batch_service = HttpBatchService::new(http_client, {
+            let http_request_builder = Arc::clone(&http_request_builder);
+            move |req| {
+                let request_builder = Arc::clone(&http_request_builder);
+                let future: BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>> =
+                    Box::pin(async move { request_builder.build_request(req).await });
+                future
+            }
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            http_request_builder,
+        }
     }
 }
 
@@ -97,6 +209,8 @@ pub struct HttpRequestBuilder {
     pub auth: Option<Auth>,
     pub compression: Compression,
     pub http_request_config: RequestConfig,
+    /// Encodes the items sent again when retrying partial failures.
+    pub request_builder: ElasticsearchRequestBuilder,
 }
 
 impl HttpRequestBuilder {
@@ -107,6 +221,7 @@ impl HttpRequestBuilder {
             auth: common.auth.clone(),
             query_params: common.query_params.clone(),
             compression: config.compression,
+            request_builder: common.request_builder.clone(),
         }
     }
 
@@ -173,6 +288,13 @@ impl DriverResponse for ElasticsearchResponse {
     fn events_sent(&self) -> &GroupedCountByteSize {
         &self.events_byte_size
     }
+
+    fn rejection(&self) -> Option<Rejection> {
+        Some(http_rejection(
+            self.http_response.status(),
+            self.http_response.body(),
+        ))
+    }
 }
 
 impl Service<ElasticsearchRequest> for ElasticsearchService {
@@ -188,14 +310,31 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, mut req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let http_request_builder = Arc::clone(&self.http_request_builder);
         Box::pin(async move {
             http_service.ready().await?;
             let batch_size = req.batch_size;
             let events_byte_size =
                 std::mem::take(req.metadata_mut()).into_events_estimated_json_encoded_byte_size();
+
+            // Only the items that failed with a retriable status in the previous attempt are sent.
+            let partial_retry = req.partial_retry.take().map(|partial_retry| {
+                let items = partial_retry.pending();
+                (partial_retry, items)
+            });
+            if let Some((partial_retry, items)) = &partial_retry {
+                if items.len() != partial_retry.events.len() {
+                    req.payload =
+                        partial_retry.encode(items, &http_request_builder.request_builder)?;
+                }
+            }
+
             let http_response = http_service.call(req).await?;
 
-            let event_status = get_event_status(&http_response);
+            let event_status = match &partial_retry {
+                Some((partial_retry, items)) => partial_retry.update(items, &http_response),
+                None => get_event_status(&http_response),
+            };
             Ok(ElasticsearchResponse {
                 event_status,
                 http_response,
@@ -4,6 +4,7 @@ use vector_lib::lookup::lookup_v2::ConfigValuePath;
 use vrl::path::PathPrefix;
 
 use crate::{
+    internal_events::ElasticsearchMissingDocumentId,
     sinks::{
         elasticsearch::{
             encoder::ProcessedEvent, request_builder::ElasticsearchRequestBuilder,
@@ -101,8 +102,9 @@ where
     }
 }
 
-/// Any `None` values returned from this function will already result in a `TemplateRenderingError`
-/// being emitted, so no further `EventsDropped` event needs emitting.
+/// Any `None` values returned from this function will already result in a `TemplateRenderingError`,
+/// `ElasticsearchUpsertWithoutScript` or `ElasticsearchMissingDocumentId` being emitted, so no
+/// further `EventsDropped` event needs emitting.
 pub(super) fn process_log(
     mut log: LogEvent,
     mode: &ElasticsearchCommonMode,
@@ -123,6 +125,12 @@ pub(super) fn process_log(
     } else {
         None
     };
+    if id.is_none() && bulk_action.requires_id() {
+        emit!(ElasticsearchMissingDocumentId {
+            action: bulk_action.as_str(),
+        });
+        return None;
+    }
     let log = {
         let mut event = Event::from(log);
         transformer.transform(&mut event);
@@ -1,18 +1,30 @@
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
+use futures::StreamExt;
+use http::Response;
 use vector_lib::lookup::PathPrefix;
 
 use crate::{
     codecs::Transformer,
-    event::{LogEvent, Metric, MetricKind, MetricValue, ObjectMap, Value},
+    config::{SinkConfig, SinkContext},
+    event::{
+        BatchNotifier, BatchStatus, LogEvent, Metric, MetricKind, MetricValue, ObjectMap, Value,
+    },
     sinks::{
         elasticsearch::{
             sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion,
             ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode,
         },
-        util::encoding::Encoder,
+        util::{encoding::Encoder, test::build_test_server_generic},
     },
     template::Template,
+    test_util::next_addr,
 };
 
 // helper to unwrap template strings for tests only
@@ -30,6 +42,7 @@ async fn sets_create_action_when_configured() {
         bulk: BulkConfig {
             action: parse_template("{{ action }}te"),
             index: parse_template("vector"),
+            ..Default::default()
         },
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V6,
@@ -197,6 +210,7 @@ async fn handle_metrics() {
         bulk: BulkConfig {
             action: parse_template("create"),
             index: parse_template("vector"),
+            ..Default::default()
         },
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V6,
@@ -239,6 +253,7 @@ async fn decode_bulk_action_error() {
         bulk: BulkConfig {
             action: parse_template("{{ action }}"),
             index: parse_template("vector"),
+            ..Default::default()
         },
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V7,
@@ -271,6 +286,7 @@ async fn decode_bulk_action() {
         bulk: BulkConfig {
             action: parse_template("create"),
             index: parse_template("vector"),
+            ..Default::default()
         },
         endpoints: vec![String::from("https://example.com")],
         api_version: ElasticsearchApiVersion::V7,
@@ -283,6 +299,110 @@ async fn decode_bulk_action() {
     assert!(matches!(action, BulkAction::Create));
 }
 
+async fn encode_with_action(action: &str, script: Option<&str>) -> String {
+    use crate::config::log_schema;
+
+    let config = ElasticsearchConfig {
+        bulk: BulkConfig {
+            action: parse_template(action),
+            index: parse_template("vector"),
+            script: script.map(Into::into),
+        },
+        id_key: Some("id".into()),
+        endpoints: vec![String::from("https://example.com")],
+        api_version: ElasticsearchApiVersion::V8,
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    let mut log = LogEvent::from("hello there");
+    log.remove((PathPrefix::Event, log_schema().timestamp_key().unwrap()));
+    log.insert("id", "42");
+    let event = process_log(log, &es.mode, config.id_key.as_ref(), &config.encoding).unwrap();
+
+    let mut encoded = vec![];
+    es.request_builder
+        .encoder
+        .encode_input(vec![event], &mut encoded)
+        .unwrap();
+    String::from_utf8(encoded).unwrap()
+}
+
+#[tokio::test]
+async fn encodes_update_upsert_and_delete_actions() {
+    assert_eq!(
+        encode_with_action("update", None).await,
+        r#"{"update":{"_index":"vector","_id":"42"}}
+{"doc":{"message":"hello there"},"doc_as_upsert":true}
+"#
+    );
+    assert_eq!(
+        encode_with_action("upsert", Some("ctx._source.message = params.event.message")).await,
+        r#"{"update":{"_index":"vector","_id":"42"}}
+{"script":{"source":"ctx._source.message = params.event.message","params":{"event":{"message":"hello there"}}},"upsert":{"message":"hello there"}}
+"#
+    );
+    assert_eq!(
+        encode_with_action("delete", None).await,
+        r#"{"delete":{"_index":"vector","_id":"42"}}
+"#
+    );
+}
+
+#[tokio::test]
+async fn drops_events_without_id_for_update_actions() {
+    for action in ["update", "upsert", "delete"] {
+        let config = ElasticsearchConfig {
+            bulk: BulkConfig {
+                action: parse_template(action),
+                index: parse_template("vector"),
+                script: Some("ctx._source.message = params.event.message".into()),
+            },
+            id_key: Some("id".into()),
+            endpoints: vec![String::from("https://example.com")],
+            api_version: ElasticsearchApiVersion::V8,
+            ..Default::default()
+        };
+        let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+        let log = LogEvent::from("hello there");
+        assert!(
+            process_log(log, &es.mode, config.id_key.as_ref(), &config.encoding).is_none(),
+            "{action} should require an id"
+        );
+    }
+}
+
+#[tokio::test]
+async fn upsert_action_requires_script() {
+    let config = ElasticsearchConfig {
+        bulk: BulkConfig {
+            action: parse_template("upsert"),
+            index: parse_template("vector"),
+            ..Default::default()
+        },
+        id_key: Some("id".into()),
+        endpoints: vec![String::from("https://example.com")],
+        api_version: ElasticsearchApiVersion::V8,
+        ..Default::default()
+    };
+    assert!(ElasticsearchCommon::parse_single(&config).await.is_err());
+
+    let config = ElasticsearchConfig {
+        bulk: BulkConfig {
+            action: parse_template("{{ action }}"),
+            ..config.bulk
+        },
+        ..config
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    let mut log = LogEvent::from("hello there");
+    log.insert("action", "upsert");
+    log.insert("id", "42");
+    assert!(process_log(log, &es.mode, config.id_key.as_ref(), &config.encoding).is_none());
+}
+
 #[tokio::test]
 async fn encode_datastream_mode_no_sync() {
     use chrono::{TimeZone, Utc};
@@ -529,3 +649,65 @@ async fn datastream_index_name() {
         assert_eq!(processed_event.index, test_case.want, "{test_case:?}");
     }
 }
+
+#[tokio::test]
+async fn retries_only_failed_bulk_items() {
+    let addr = next_addr();
+    let config = ElasticsearchConfig {
+        endpoints: vec![format!("http://{addr}")],
+        api_version: ElasticsearchApiVersion::V8,
+        request_retry_partial: true,
+        bulk: BulkConfig {
+            index: parse_template("vector"),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    // The first bulk request gets one item delivered, one throttled and one rejected, and the
+    // retry of the throttled item succeeds.
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let (rx, trigger, server) = build_test_server_generic(addr, move || {
+        let body = if attempts.fetch_add(1, Ordering::Relaxed) == 0 {
+            r#"{"errors":true,"items":[{"index":{"status":201}},{"index":{"status":429}},{"index":{"status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}]}"#
+        } else {
+            r#"{"errors":false,"items":[{"index":{"status":201}}]}"#
+        };
+        Response::new(hyper::Body::from(body))
+    });
+    tokio::spawn(server);
+
+    let (events, receivers): (Vec<_>, Vec<_>) = ["delivered", "throttled", "rejected"]
+        .into_iter()
+        .map(|message| {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            let event = LogEvent::from(message).with_batch_notifier(&batch);
+            (event.into(), receiver)
+        })
+        .unzip();
+    sink.run_events(events).await.unwrap();
+    drop(trigger);
+
+    let statuses = receivers
+        .into_iter()
+        .map(|mut receiver| receiver.try_recv().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        [
+            BatchStatus::Delivered,
+            BatchStatus::Delivered,
+            BatchStatus::Rejected
+        ]
+    );
+
+    let bodies = rx
+        .map(|(_, body)| String::from_utf8(body.to_vec()).unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[0].lines().count(), 6);
+    assert_eq!(bodies[1].lines().count(), 2);
+    assert!(bodies[1].contains("throttled"));
+}
@@ -197,6 +197,10 @@ impl SinkConfig for FileSinkConfig {
         Input::new(self.encoding.config().1.input_type() & DataType::Log)
     }
 
+    fn encoding_config(&self) -> Option<(EncodingConfigWithFraming, SinkType)> {
+        Some((self.encoding.clone(), SinkType::StreamBased))
+    }
+
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
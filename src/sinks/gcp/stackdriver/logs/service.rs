@@ -3,7 +3,10 @@
 use bytes::Bytes;
 use http::{Request, Uri};
 
-use crate::{gcp::GcpAuthenticator, sinks::util::http::HttpServiceRequestBuilder};
+use crate::{
+    gcp::GcpAuthenticator,
+    sinks::util::http::{HttpRequest, HttpServiceRequestBuilder},
+};
 
 #[derive(Debug, Clone)]
 pub(super) struct StackdriverLogsServiceRequestBuilder {
@@ -11,15 +14,14 @@ pub(super) struct StackdriverLogsServiceRequestBuilder {
     pub(super) auth: GcpAuthenticator,
 }
 
-impl HttpServiceRequestBuilder for StackdriverLogsServiceRequestBuilder {
-    fn build(&self, body: Bytes) -> Request<Bytes> {
+impl HttpServiceRequestBuilder<()> for StackdriverLogsServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<()>) -> Result<Request<Bytes>, crate::Error> {
         let mut request = Request::post(self.uri.clone())
             .header("Content-Type", "application/json")
-            .body(body)
-            .unwrap();
+            .body(request.take_payload())?;
 
         self.auth.apply(&mut request);
 
-        request
+        Ok(request)
     }
 }
@@ -15,7 +15,7 @@ pub(super) struct StackdriverLogsSink<S> {
 
 impl<S> StackdriverLogsSink<S>
 where
-    S: Service<HttpRequest> + Send + 'static,
+    S: Service<HttpRequest<()>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
     S::Error: std::fmt::Debug + Into<crate::Error> + Send,
@@ -63,7 +63,7 @@ where
 #[async_trait::async_trait]
 impl<S> StreamSink<Event> for StackdriverLogsSink<S>
 where
-    S: Service<HttpRequest> + Send + 'static,
+    S: Service<HttpRequest<()>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
     S::Error: std::fmt::Debug + Into<crate::Error> + Send,
@@ -9,7 +9,9 @@ use crate::{
         gcp,
         prelude::*,
         util::{
-            http::{http_response_retry_logic, HttpService, HttpServiceRequestBuilder},
+            http::{
+                http_response_retry_logic, HttpRequest, HttpService, HttpServiceRequestBuilder,
+            },
             service::TowerRequestConfigDefaults,
         },
     },
@@ -156,16 +158,15 @@ pub(super) struct StackdriverMetricsServiceRequestBuilder {
     pub(super) auth: GcpAuthenticator,
 }
 
-impl HttpServiceRequestBuilder for StackdriverMetricsServiceRequestBuilder {
-    fn build(&self, body: Bytes) -> Request<Bytes> {
+impl HttpServiceRequestBuilder<()> for StackdriverMetricsServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<()>) -> Result<Request<Bytes>, crate::Error> {
         let mut request = Request::post(self.uri.clone())
             .header("Content-Type", "application/json")
-            .body(body)
-            .unwrap();
+            .body(request.take_payload())?;
 
         self.auth.apply(&mut request);
 
-        request
+        Ok(request)
     }
 }
 
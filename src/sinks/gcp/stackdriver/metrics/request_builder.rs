@@ -16,7 +16,7 @@ impl RequestBuilder<Vec<Metric>> for StackdriverMetricsRequestBuilder {
     type Events = Vec<Metric>;
     type Encoder = StackdriverMetricsEncoder;
     type Payload = Bytes;
-    type Request = HttpRequest;
+    type Request = HttpRequest<()>;
     type Error = io::Error;
 
     fn compression(&self) -> Compression {
@@ -42,7 +42,7 @@ impl RequestBuilder<Vec<Metric>> for StackdriverMetricsRequestBuilder {
         request_metadata: RequestMetadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
-        HttpRequest::new(payload.into_payload(), metadata, request_metadata)
+        HttpRequest::new(payload.into_payload(), metadata, request_metadata, ())
     }
 }
 
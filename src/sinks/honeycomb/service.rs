@@ -4,7 +4,7 @@ use bytes::Bytes;
 use http::{Request, Uri};
 use vector_lib::sensitive_string::SensitiveString;
 
-use crate::sinks::util::http::HttpServiceRequestBuilder;
+use crate::sinks::util::http::{HttpRequest, HttpServiceRequestBuilder};
 
 use super::config::HTTP_HEADER_HONEYCOMB;
 
@@ -14,12 +14,10 @@ pub(super) struct HoneycombSvcRequestBuilder {
     pub(super) api_key: SensitiveString,
 }
 
-impl HttpServiceRequestBuilder for HoneycombSvcRequestBuilder {
-    fn build(&self, body: Bytes) -> Request<Bytes> {
-        let request = Request::post(&self.uri).header(HTTP_HEADER_HONEYCOMB, self.api_key.inner());
+impl HttpServiceRequestBuilder<()> for HoneycombSvcRequestBuilder {
+    fn build(&self, mut request: HttpRequest<()>) -> Result<Request<Bytes>, crate::Error> {
+        let builder = Request::post(&self.uri).header(HTTP_HEADER_HONEYCOMB, self.api_key.inner());
 
-        request
-            .body(body)
-            .expect("Failed to assign body to request- builder has errors")
+        builder.body(request.take_payload()).map_err(Into::into)
     }
 }
@@ -1,6 +1,8 @@
 //! Configuration for the `http` sink.
 
-use http::{header::AUTHORIZATION, HeaderName, HeaderValue, Method, Request, StatusCode};
+use std::num::NonZeroUsize;
+
+use http::{header::AUTHORIZATION, HeaderName, Method, Request, StatusCode};
 use hyper::Body;
 use indexmap::IndexMap;
 use vector_lib::codecs::{
@@ -21,8 +23,10 @@ use crate::{
 };
 
 use super::{
-    encoder::HttpEncoder, request_builder::HttpRequestBuilder, service::HttpSinkRequestBuilder,
-    sink::HttpSink,
+    encoder::HttpEncoder,
+    request_builder::HttpRequestBuilder,
+    service::HttpSinkRequestBuilder,
+    sink::{HttpPartitioner, HttpSink},
 };
 
 const CONTENT_TYPE_TEXT: &str = "text/plain";
@@ -37,8 +41,13 @@ pub struct HttpSinkConfig {
     /// The full URI to make HTTP requests to.
     ///
     /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    ///
+    /// Events are batched separately for each rendered URI. Only `{{ field }}` references are
+    /// rendered, so percent-encoded characters such as `%20` are sent as written.
+    #[configurable(metadata(docs::templateable))]
     #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/endpoint"))]
-    pub uri: UriSerde,
+    #[configurable(metadata(docs::examples = "https://{{ tenant }}.example.com/endpoint"))]
+    pub uri: String,
 
     /// The HTTP method to use when making the request.
     #[serde(default)]
@@ -87,6 +96,15 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub request: RequestConfig,
 
+    /// The maximum number of partitions to batch events for at once.
+    ///
+    /// Events are batched separately for each rendered `uri` and set of `request.headers`. When an
+    /// event belongs to a new partition while this limit is reached, the batch of the oldest
+    /// partition is sent to make room for it.
+    #[serde(default = "default_max_partitions")]
+    #[configurable(metadata(docs::advanced))]
+    pub max_partitions: NonZeroUsize,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
@@ -99,6 +117,10 @@ pub struct HttpSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+fn default_max_partitions() -> NonZeroUsize {
+    NonZeroUsize::new(100).expect("static non-zero number")
+}
+
 /// HTTP method.
 ///
 /// A subset of the HTTP methods described in [RFC 9110, section 9.1][rfc9110] are supported.
@@ -192,16 +214,25 @@ async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> c
 pub(super) fn validate_headers(
     headers: &IndexMap<String, String>,
     configures_auth: bool,
-) -> crate::Result<IndexMap<HeaderName, HeaderValue>> {
-    let headers = crate::sinks::util::http::validate_headers(headers)?;
+) -> crate::Result<IndexMap<HeaderName, Template>> {
+    let validated_headers = crate::sinks::util::http::validate_headers(headers)?;
 
-    for name in headers.keys() {
+    for name in validated_headers.keys() {
         if configures_auth && name == AUTHORIZATION {
             return Err("Authorization header can not be used with defined auth options".into());
         }
     }
 
-    Ok(headers)
+    // Header values may be templates, which are only rendered once an event is known. A `%` in
+    // them is kept as is, since values like `100%` aren't time formats.
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = HeaderName::from_bytes(name.as_bytes())?;
+            let value = Template::with_fields_only(value.as_str())?;
+            Ok((name, value))
+        })
+        .collect()
 }
 
 pub(super) fn validate_payload_wrapper(
@@ -275,11 +306,20 @@ impl SinkConfig for HttpSinkConfig {
                 .to_string()
         });
 
+        let uri = Template::with_fields_only(self.uri.as_str())?;
+
+        // A static URI is checked right away, instead of failing every request. Rendered URIs are
+        // checked by the partitioner.
+        if !uri.is_dynamic() {
+            let uri: UriSerde = uri.get_ref().parse()?;
+            self.auth.choose_one(&uri.auth)?;
+        }
+
+        let partitioner = HttpPartitioner::new(uri, headers);
+
         let http_sink_request_builder = HttpSinkRequestBuilder::new(
-            self.uri.with_default_parts(),
             self.method,
-            self.auth.choose_one(&self.uri.auth)?,
-            headers,
+            self.auth.clone(),
             content_type,
             content_encoding,
         );
@@ -292,7 +332,13 @@ impl SinkConfig for HttpSinkConfig {
             .settings(request_limits, http_response_retry_logic())
             .service(service);
 
-        let sink = HttpSink::new(service, batch_settings, request_builder);
+        let sink = HttpSink::new(
+            service,
+            batch_settings,
+            self.max_partitions,
+            partitioner,
+            request_builder,
+        );
 
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
     }
@@ -311,9 +357,9 @@ impl ValidatableComponent for HttpSinkConfig {
         use std::str::FromStr;
         use vector_lib::codecs::{JsonSerializerConfig, MetricTagValues};
 
+        let uri = "http://127.0.0.1:9000/endpoint";
         let config = Self {
-            uri: UriSerde::from_str("http://127.0.0.1:9000/endpoint")
-                .expect("should never fail to parse"),
+            uri: uri.to_owned(),
             method: HttpMethod::Post,
             encoding: EncodingConfigWithFraming::new(
                 None,
@@ -325,6 +371,7 @@ impl ValidatableComponent for HttpSinkConfig {
             compression: Compression::default(),
             batch: BatchConfig::default(),
             request: RequestConfig::default(),
+            max_partitions: default_max_partitions(),
             tls: None,
             acknowledgements: AcknowledgementsConfig::default(),
             payload_prefix: String::new(),
@@ -333,7 +380,10 @@ impl ValidatableComponent for HttpSinkConfig {
 
         let external_resource = ExternalResource::new(
             ResourceDirection::Push,
-            HttpResourceConfig::from_parts(config.uri.uri.clone(), Some(config.method.into())),
+            HttpResourceConfig::from_parts(
+                http::Uri::from_str(uri).expect("should never fail to parse"),
+                Some(config.method.into()),
+            ),
             config.encoding.clone(),
         );
 
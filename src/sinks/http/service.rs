@@ -1,22 +1,22 @@
 //! Service implementation for the `http` sink.
 
 use bytes::Bytes;
-use http::{HeaderName, HeaderValue, Method, Request, Uri};
-use indexmap::IndexMap;
+use http::{HeaderName, HeaderValue, Method, Request};
 
 use crate::{
-    http::Auth,
-    sinks::util::{http::HttpServiceRequestBuilder, UriSerde},
+    http::{Auth, MaybeAuth},
+    sinks::util::{
+        http::{HttpRequest, HttpServiceRequestBuilder},
+        UriSerde,
+    },
 };
 
-use super::config::HttpMethod;
+use super::{config::HttpMethod, sink::PartitionKey};
 
 #[derive(Debug, Clone)]
 pub(super) struct HttpSinkRequestBuilder {
-    uri: UriSerde,
     method: HttpMethod,
     auth: Option<Auth>,
-    headers: IndexMap<HeaderName, HeaderValue>,
     content_type: Option<String>,
     content_encoding: Option<String>,
 }
@@ -24,29 +24,32 @@ pub(super) struct HttpSinkRequestBuilder {
 impl HttpSinkRequestBuilder {
     /// Creates a new `HttpSinkRequestBuilder`
     pub(super) const fn new(
-        uri: UriSerde,
         method: HttpMethod,
         auth: Option<Auth>,
-        headers: IndexMap<HeaderName, HeaderValue>,
         content_type: Option<String>,
         content_encoding: Option<String>,
     ) -> Self {
         Self {
-            uri,
             method,
             auth,
-            headers,
             content_type,
             content_encoding,
         }
     }
 }
 
-impl HttpServiceRequestBuilder for HttpSinkRequestBuilder {
-    fn build(&self, body: Bytes) -> Request<Bytes> {
+impl HttpServiceRequestBuilder<PartitionKey> for HttpSinkRequestBuilder {
+    fn build(
+        &self,
+        mut request: HttpRequest<PartitionKey>,
+    ) -> Result<Request<Bytes>, crate::Error> {
+        // The URI and headers are rendered from templates, so they are only validated here.
+        let key = request.get_additional_metadata();
+        let uri = key.uri.parse::<UriSerde>()?.with_default_parts();
+        let auth = self.auth.choose_one(&uri.auth)?;
+
         let method: Method = self.method.into();
-        let uri: Uri = self.uri.uri.clone();
-        let mut builder = Request::builder().method(method).uri(uri);
+        let mut builder = Request::builder().method(method).uri(uri.uri);
 
         if let Some(content_type) = &self.content_type {
             builder = builder.header("Content-Type", content_type);
@@ -61,19 +64,19 @@ impl HttpServiceRequestBuilder for HttpSinkRequestBuilder {
             // The request building should not have errors at this point, and if it did it would fail in the call to `body()` also.
             .expect("Failed to access headers in http::Request builder- builder has errors.");
 
-        for (header, value) in self.headers.iter() {
-            headers.insert(header, value.clone());
+        for (header, value) in key.headers.iter() {
+            headers.insert(
+                HeaderName::from_bytes(header.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
         }
 
-        // The request building should not have errors at this point
-        let mut request = builder
-            .body(body)
-            .expect("Failed to assign body to request- builder has errors");
+        let mut request = builder.body(request.take_payload())?;
 
-        if let Some(auth) = &self.auth {
+        if let Some(auth) = auth {
             auth.apply(&mut request);
         }
 
-        request
+        Ok(request)
     }
 }
@@ -1,18 +1,98 @@
 //! Implementation of the `http` sink.
 
-use crate::sinks::{prelude::*, util::http::HttpRequest};
+use std::{collections::BTreeMap, num::NonZeroUsize};
+
+use http::HeaderName;
+use indexmap::IndexMap;
+
+use crate::{
+    internal_events::HttpInvalidUriError,
+    sinks::{prelude::*, util::http::HttpRequest, util::UriSerde},
+};
 
 use super::{batch::HttpBatchSizer, request_builder::HttpRequestBuilder};
 
+/// The rendered URI and headers that events are batched by.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(super) struct PartitionKey {
+    pub(super) uri: String,
+    pub(super) headers: BTreeMap<String, String>,
+}
+
+/// Partitions events based on the rendered `uri` and `request.headers` templates.
+pub(super) struct HttpPartitioner {
+    uri: Template,
+    headers: IndexMap<HeaderName, Template>,
+}
+
+impl HttpPartitioner {
+    /// Creates a new `HttpPartitioner`.
+    pub(super) const fn new(uri: Template, headers: IndexMap<HeaderName, Template>) -> Self {
+        Self { uri, headers }
+    }
+}
+
+impl Partitioner for HttpPartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let uri = self
+            .uri
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("uri"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        // Catch invalid URIs here so that they don't fail the whole batch once it is sent.
+        if self.uri.is_dynamic() {
+            if let Err(error) = uri.parse::<UriSerde>() {
+                emit!(HttpInvalidUriError {
+                    uri: &uri,
+                    error: &error,
+                });
+                return None;
+            }
+        }
+
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, template)| {
+                template
+                    .render_string(item)
+                    .map(|value| (name.to_string(), value))
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some(name.as_str()),
+                            drop_event: true,
+                        });
+                    })
+            })
+            .collect::<Result<_, _>>()
+            .ok()?;
+
+        Some(PartitionKey { uri, headers })
+    }
+}
+
 pub(super) struct HttpSink<S> {
     service: S,
     batch_settings: BatcherSettings,
+    max_partitions: NonZeroUsize,
+    partitioner: HttpPartitioner,
     request_builder: HttpRequestBuilder,
 }
 
 impl<S> HttpSink<S>
 where
-    S: Service<HttpRequest> + Send + 'static,
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
     S::Error: std::fmt::Debug + Into<crate::Error> + Send,
@@ -21,21 +101,34 @@ where
     pub(super) const fn new(
         service: S,
         batch_settings: BatcherSettings,
+        max_partitions: NonZeroUsize,
+        partitioner: HttpPartitioner,
         request_builder: HttpRequestBuilder,
     ) -> Self {
         Self {
             service,
             batch_settings,
+            max_partitions,
+            partitioner,
             request_builder,
         }
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let encoder = self.request_builder.encoder.encoder.clone();
+
         input
-            // Batch the input stream with size calculation based on the configured codec
-            .batched(self.batch_settings.as_item_size_config(HttpBatchSizer {
-                encoder: self.request_builder.encoder.encoder.clone(),
-            }))
+            // Batch the input stream per partition, with size calculation based on the configured
+            // codec.
+            .batched_partitioned(self.partitioner, move || {
+                batch_settings.as_item_size_config(HttpBatchSizer {
+                    encoder: encoder.clone(),
+                })
+            })
+            .with_max_partitions(self.max_partitions)
+            // Drop the events whose partition could not be rendered.
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
             // Build requests with default concurrency limit.
             .request_builder(
                 default_request_builder_concurrency_limit(),
@@ -62,7 +155,7 @@ where
 #[async_trait::async_trait]
 impl<S> StreamSink<Event> for HttpSink<S>
 where
-    S: Service<HttpRequest> + Send + 'static,
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
     S::Error: std::fmt::Debug + Into<crate::Error> + Send,
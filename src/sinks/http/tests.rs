@@ -1,7 +1,9 @@
 //! Unit tests for the `http` sink.
 
 use std::{
+    collections::BTreeMap,
     io::{BufRead, BufReader},
+    num::NonZeroUsize,
     sync::{atomic, Arc},
 };
 
@@ -60,6 +62,7 @@ fn default_cfg(encoding: EncodingConfigWithFraming) -> HttpSinkConfig {
         payload_suffix: Default::default(),
         batch: Default::default(),
         request: Default::default(),
+        max_partitions: NonZeroUsize::new(100).unwrap(),
         tls: Default::default(),
         acknowledgements: Default::default(),
     }
@@ -266,6 +269,137 @@ async fn http_passes_custom_headers() {
     .await;
 }
 
+#[tokio::test]
+async fn http_partitions_by_templated_uri_and_headers() {
+    let in_addr = next_addr();
+    let config = format!(
+        r#"
+        uri = "http://{in_addr}/{{{{ tenant }}}}"
+        framing.method = "newline_delimited"
+        encoding.codec = "json"
+        [request.headers]
+        X-Tenant = "{{{{ tenant }}}}"
+    "#
+    );
+    let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server(in_addr);
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = ["foo", "bar", "foo"].into_iter().map(|tenant| {
+        let mut log = LogEvent::from(tenant).with_batch_notifier(&batch);
+        log.insert("tenant", tenant);
+        Event::Log(log)
+    });
+    drop(batch);
+    sink.run_events(events).await.unwrap();
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = rx
+        .map(|(parts, body)| {
+            let tenant = parts.headers["x-tenant"].to_str().unwrap().to_owned();
+            assert_eq!(format!("/{tenant}"), parts.uri.path());
+            (
+                tenant,
+                String::from_utf8(body.to_vec()).unwrap().lines().count(),
+            )
+        })
+        .collect::<BTreeMap<_, _>>()
+        .await;
+
+    assert_eq!(
+        requests,
+        BTreeMap::from([("bar".to_owned(), 1), ("foo".to_owned(), 2)])
+    );
+}
+
+#[tokio::test]
+async fn http_drops_events_failing_to_render_uri() {
+    let in_addr = next_addr();
+    let config = format!(
+        r#"
+        uri = "http://{in_addr}/{{{{ tenant }}}}"
+        encoding.codec = "json"
+    "#
+    );
+    let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server(in_addr);
+    tokio::spawn(server);
+
+    let event = Event::Log(LogEvent::from("no tenant"));
+    sink.run_events([event]).await.unwrap();
+    drop(trigger);
+
+    assert_eq!(rx.count().await, 0);
+}
+
+#[tokio::test]
+async fn http_drops_events_rendering_invalid_uri() {
+    let in_addr = next_addr();
+    let config = format!(
+        r#"
+        uri = "http://{in_addr}/{{{{ tenant }}}}"
+        encoding.codec = "json"
+    "#
+    );
+    let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server(in_addr);
+    tokio::spawn(server);
+
+    let mut log = LogEvent::from("bad tenant");
+    log.insert("tenant", "with space");
+    sink.run_events([Event::Log(log)]).await.unwrap();
+    drop(trigger);
+
+    assert_eq!(rx.count().await, 0);
+}
+
+#[tokio::test]
+async fn http_keeps_percent_signs_in_uri_and_headers() {
+    let in_addr = next_addr();
+    let config = format!(
+        r#"
+        uri = "http://{in_addr}/path%20with%2Fencoded/{{{{ tenant }}}}"
+        encoding.codec = "json"
+        [request.headers]
+        X-Sampled = "100%"
+    "#
+    );
+    let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server(in_addr);
+    tokio::spawn(server);
+
+    let mut log = LogEvent::from("event");
+    log.insert("tenant", "foo");
+    sink.run_events([Event::Log(log)]).await.unwrap();
+    drop(trigger);
+
+    let requests = rx
+        .map(|(parts, _)| {
+            (
+                parts.uri.path().to_owned(),
+                parts.headers["x-sampled"].to_str().unwrap().to_owned(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        requests,
+        vec![("/path%20with%2Fencoded/foo".to_owned(), "100%".to_owned())]
+    );
+}
+
 #[tokio::test]
 async fn retries_on_no_connection() {
     components::assert_sink_compliance(&HTTP_SINK_TAGS, async {
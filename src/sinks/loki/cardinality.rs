@@ -0,0 +1,221 @@
+//! Guard against label sets with a high cardinality.
+//!
+//! Every distinct label set is a separate stream in Loki, so labels built from high-cardinality
+//! fields can create an unbounded number of streams. The guard tracks the label sets sent within a
+//! window, and once their number reaches the limit, removes the labels of events that would
+//! create a new stream.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use super::{
+    config::{LabelCardinalityLimitAction, LabelCardinalityLimitConfig},
+    event::Labels,
+};
+use crate::internal_events::LokiLabelCardinalityLimitReached;
+
+#[derive(Clone, Debug)]
+pub(super) struct CardinalityGuard {
+    max_streams: usize,
+    window: Duration,
+    action: LabelCardinalityLimitAction,
+    window_start: Option<Instant>,
+    streams: HashSet<Labels>,
+    /// The distinct values of each label, which stop being tracked past the limit.
+    values: HashMap<String, HashSet<String>>,
+    /// The labels removed since the start of the window.
+    removed: HashSet<String>,
+}
+
+impl CardinalityGuard {
+    pub(super) fn new(config: &LabelCardinalityLimitConfig) -> Self {
+        Self {
+            max_streams: config.max_streams.get(),
+            window: config.window_secs,
+            action: config.action,
+            window_start: None,
+            streams: HashSet::new(),
+            values: HashMap::new(),
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Accepts a new stream while the limit isn't reached, and past it removes labels until the
+    /// label set matches a known stream, moving them to the structured metadata depending on the
+    /// configured action. Events left without labels are sent without any.
+    pub(super) fn apply(
+        &mut self,
+        labels: &mut Labels,
+        structured_metadata: &mut Labels,
+        now: Instant,
+    ) {
+        match self.window_start {
+            Some(start) if now.duration_since(start) < self.window => {}
+            _ => {
+                self.window_start = Some(now);
+                self.streams.clear();
+                self.values.clear();
+                self.removed.clear();
+            }
+        }
+
+        for (key, value) in labels.iter() {
+            let values = self.values.entry(key.clone()).or_default();
+            if values.len() <= self.max_streams {
+                values.insert(value.clone());
+            }
+        }
+
+        // Labels removed earlier in the window are removed right away, so that the streams of
+        // the events don't depend on the order they come in.
+        let removed = &self.removed;
+        let (kept, mut removed_labels): (Labels, Labels) = std::mem::take(labels)
+            .into_iter()
+            .partition(|(key, _)| !removed.contains(key));
+        *labels = kept;
+        labels.sort();
+
+        while !labels.is_empty() && !self.streams.contains(labels) {
+            if self.streams.len() < self.max_streams {
+                self.streams.insert(labels.clone());
+                break;
+            }
+
+            let (index, _) = labels
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (key, _))| self.values.get(key).map_or(0, HashSet::len))
+                .expect("labels are not empty");
+            let label = labels.remove(index);
+            emit!(LokiLabelCardinalityLimitReached {
+                label: &label.0,
+                action: self.action.as_str(),
+            });
+            self.removed.insert(label.0.clone());
+            removed_labels.push(label);
+        }
+
+        if self.action == LabelCardinalityLimitAction::StructuredMetadata {
+            for (key, value) in removed_labels {
+                if !structured_metadata
+                    .iter()
+                    .any(|(existing, _)| *existing == key)
+                {
+                    structured_metadata.push((key, value));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    fn guard(max_streams: usize, action: LabelCardinalityLimitAction) -> CardinalityGuard {
+        CardinalityGuard::new(&LabelCardinalityLimitConfig {
+            max_streams: NonZeroUsize::new(max_streams).unwrap(),
+            window_secs: Duration::from_secs(60),
+            action,
+        })
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn apply(
+        guard: &mut CardinalityGuard,
+        pairs: &[(&str, &str)],
+        now: Instant,
+    ) -> (Labels, Labels) {
+        let mut labels = labels(pairs);
+        let mut structured_metadata = Vec::new();
+        guard.apply(&mut labels, &mut structured_metadata, now);
+        (labels, structured_metadata)
+    }
+
+    #[test]
+    fn keeps_labels_under_the_limit() {
+        let mut guard = guard(2, LabelCardinalityLimitAction::StructuredMetadata);
+        let now = Instant::now();
+
+        for id in ["1", "2", "1", "2"] {
+            let (labels, metadata) = apply(&mut guard, &[("app", "web"), ("id", id)], now);
+            assert_eq!(labels, self::labels(&[("app", "web"), ("id", id)]));
+            assert!(metadata.is_empty());
+        }
+    }
+
+    #[test]
+    fn moves_the_label_with_most_values_to_structured_metadata() {
+        let mut guard = guard(2, LabelCardinalityLimitAction::StructuredMetadata);
+        let now = Instant::now();
+
+        apply(&mut guard, &[("app", "web")], now);
+        apply(&mut guard, &[("app", "web"), ("id", "1")], now);
+
+        let (labels, metadata) = apply(&mut guard, &[("app", "web"), ("id", "2")], now);
+        assert_eq!(labels, self::labels(&[("app", "web")]));
+        assert_eq!(metadata, self::labels(&[("id", "2")]));
+
+        // The label stays removed for the rest of the window, even for known streams.
+        let (labels, metadata) = apply(&mut guard, &[("app", "web"), ("id", "1")], now);
+        assert_eq!(labels, self::labels(&[("app", "web")]));
+        assert_eq!(metadata, self::labels(&[("id", "1")]));
+    }
+
+    #[test]
+    fn drops_labels() {
+        let mut guard = guard(2, LabelCardinalityLimitAction::Drop);
+        let now = Instant::now();
+
+        apply(&mut guard, &[("app", "web")], now);
+        apply(&mut guard, &[("app", "web"), ("id", "1")], now);
+        let (labels, metadata) = apply(&mut guard, &[("app", "web"), ("id", "2")], now);
+        assert_eq!(labels, self::labels(&[("app", "web")]));
+        assert!(metadata.is_empty());
+
+        let (labels, metadata) = apply(&mut guard, &[("app", "api"), ("id", "3")], now);
+        assert!(labels.is_empty());
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn never_exceeds_the_limit() {
+        let mut guard = guard(1, LabelCardinalityLimitAction::StructuredMetadata);
+        let now = Instant::now();
+
+        apply(&mut guard, &[("app", "web"), ("id", "1")], now);
+
+        // Removing a single label still leaves a new stream, so every label is removed.
+        let (labels, metadata) = apply(&mut guard, &[("app", "db"), ("id", "2")], now);
+        assert!(labels.is_empty());
+        assert_eq!(metadata.len(), 2);
+        assert!(metadata.contains(&("app".to_string(), "db".to_string())));
+        assert!(metadata.contains(&("id".to_string(), "2".to_string())));
+        assert_eq!(guard.streams.len(), 1);
+    }
+
+    #[test]
+    fn resets_after_the_window() {
+        let mut guard = guard(1, LabelCardinalityLimitAction::StructuredMetadata);
+        let now = Instant::now();
+
+        apply(&mut guard, &[("id", "1")], now);
+        let (labels, _) = apply(&mut guard, &[("id", "2")], now);
+        assert!(labels.is_empty());
+
+        let later = now + Duration::from_secs(60);
+        let (labels, metadata) = apply(&mut guard, &[("id", "2")], later);
+        assert_eq!(labels, self::labels(&[("id", "2")]));
+        assert!(metadata.is_empty());
+    }
+}
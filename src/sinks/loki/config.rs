@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
 
+use serde_with::serde_as;
 use vrl::value::Kind;
 
 use super::{healthcheck::healthcheck, sink::LokiSink};
@@ -62,6 +63,25 @@ pub struct LokiConfig {
     #[configurable(metadata(docs::additional_props_description = "A Loki label."))]
     pub labels: HashMap<Template, Template>,
 
+    /// A set of [structured metadata][structured_metadata] that is attached to each event.
+    ///
+    /// Both keys and values are templateable, and keys ending with `*` expand objects into
+    /// multiple entries, as for labels. Structured metadata is not indexed, so unlike labels it
+    /// can hold high-cardinality values such as trace IDs.
+    ///
+    /// Requires Loki 3.0 or newer.
+    ///
+    /// [structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+    #[configurable(metadata(docs::examples = "loki_structured_metadata_examples()"))]
+    #[configurable(metadata(
+        docs::additional_props_description = "A Loki structured metadata entry."
+    ))]
+    #[serde(default)]
+    pub structured_metadata: HashMap<Template, Template>,
+
+    #[configurable(derived)]
+    pub label_cardinality_limit: Option<LabelCardinalityLimitConfig>,
+
     /// Whether or not to delete fields from the event when they are used as labels.
     #[serde(default = "crate::serde::default_false")]
     pub remove_label_fields: bool,
@@ -119,6 +139,16 @@ fn loki_labels_examples() -> HashMap<String, String> {
     examples
 }
 
+fn loki_structured_metadata_examples() -> HashMap<String, String> {
+    let mut examples = HashMap::new();
+    examples.insert("trace_id".to_string(), "{{ trace_id }}".to_string());
+    examples.insert(
+        "\"attributes_*\"".to_string(),
+        "{{ attributes }}".to_string(),
+    );
+    examples
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct LokiDefaultBatchSettings;
 
@@ -158,6 +188,62 @@ pub enum OutOfOrderAction {
     Accept,
 }
 
+/// Label cardinality limit.
+///
+/// Labels built from high-cardinality fields can create a large number of streams, which degrades
+/// the performance of Loki. This limits the number of distinct label sets sent within a time
+/// window. Once the limit is reached, the labels of events that would create a new stream are
+/// removed one at a time, starting with the label that had the most distinct values, until the
+/// event matches an existing stream, so the limit is never exceeded. Labels removed this way keep
+/// being removed until the end of the window.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LabelCardinalityLimitConfig {
+    /// The maximum number of distinct label sets within a window.
+    #[configurable(metadata(docs::examples = 10000))]
+    pub max_streams: NonZeroUsize,
+
+    /// The time window after which the label sets seen are forgotten, in seconds.
+    #[serde(default = "default_cardinality_window")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Time Window"))]
+    pub window_secs: Duration,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: LabelCardinalityLimitAction,
+}
+
+const fn default_cardinality_window() -> Duration {
+    Duration::from_secs(3600)
+}
+
+/// What to do with the labels removed by the cardinality limit.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelCardinalityLimitAction {
+    /// Send the label as structured metadata instead.
+    ///
+    /// Requires Loki 3.0 or newer.
+    #[default]
+    StructuredMetadata,
+
+    /// Drop the label.
+    Drop,
+}
+
+impl LabelCardinalityLimitAction {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::StructuredMetadata => "structured_metadata",
+            Self::Drop => "drop",
+        }
+    }
+}
+
 impl GenerateConfig for LokiConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -194,6 +280,12 @@ impl SinkConfig for LokiConfig {
             }
         }
 
+        for key in self.structured_metadata.keys() {
+            if !valid_label_name(key) {
+                return Err(format!("Invalid structured metadata key {:?}", key.get_ref()).into());
+            }
+        }
+
         let client = self.build_client(cx)?;
 
         let config = LokiConfig {
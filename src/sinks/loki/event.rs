@@ -43,11 +43,12 @@ impl Encoder<Vec<LokiRecord>> for LokiBatchEncoder {
                             let labels = stream.stream;
                             let entries = stream
                                 .values
-                                .iter()
+                                .into_iter()
                                 .map(|event| {
                                     loki_logproto::util::Entry(
                                         event.timestamp,
                                         String::from_utf8_lossy(&event.event).into_owned(),
+                                        event.structured_metadata,
                                     )
                                 })
                                 .collect();
@@ -130,11 +131,14 @@ impl From<Vec<LokiRecord>> for LokiBatch {
 pub struct LokiEvent {
     pub timestamp: i64,
     pub event: Bytes,
+    pub structured_metadata: Labels,
 }
 
 impl ByteSizeOf for LokiEvent {
     fn allocated_bytes(&self) -> usize {
-        self.timestamp.allocated_bytes() + self.event.allocated_bytes()
+        self.timestamp.allocated_bytes()
+            + self.event.allocated_bytes()
+            + labels_allocated_bytes(&self.structured_metadata)
     }
 }
 
@@ -143,10 +147,24 @@ impl Serialize for LokiEvent {
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(2))?;
+        // Structured metadata is an optional third element, which Loki before 3.0 rejects.
+        let len = if self.structured_metadata.is_empty() {
+            2
+        } else {
+            3
+        };
+        let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.timestamp.to_string())?;
         let event = String::from_utf8_lossy(&self.event);
         seq.serialize_element(&event)?;
+        if !self.structured_metadata.is_empty() {
+            let metadata: HashMap<&str, &str> = self
+                .structured_metadata
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
+            seq.serialize_element(&metadata)?;
+        }
         seq.end()
     }
 }
@@ -164,13 +182,17 @@ pub struct LokiRecord {
 impl ByteSizeOf for LokiRecord {
     fn allocated_bytes(&self) -> usize {
         self.partition.allocated_bytes()
-            + self.labels.iter().fold(0, |res, item| {
-                res + item.0.allocated_bytes() + item.1.allocated_bytes()
-            })
+            + labels_allocated_bytes(&self.labels)
             + self.event.allocated_bytes()
     }
 }
 
+fn labels_allocated_bytes(labels: &Labels) -> usize {
+    labels.iter().fold(0, |res, item| {
+        res + item.0.allocated_bytes() + item.1.allocated_bytes()
+    })
+}
+
 impl EstimatedJsonEncodedSizeOf for LokiRecord {
     fn estimated_json_encoded_size_of(&self) -> JsonSize {
         self.json_byte_size
@@ -10,6 +10,7 @@
 //!
 //! If an event produces no labels, this can happen if the template
 //! does not match, we will add a default label `{agent="vector"}`.
+mod cardinality;
 mod config;
 mod event;
 mod healthcheck;
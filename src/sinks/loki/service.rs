@@ -1,4 +1,7 @@
-use std::task::{Context, Poll};
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use bytes::Bytes;
 use http::StatusCode;
@@ -7,7 +10,10 @@ use tracing::Instrument;
 
 use crate::{
     http::{Auth, HttpClient},
-    sinks::{prelude::*, util::UriSerde},
+    sinks::{
+        prelude::*,
+        util::{http::parse_retry_after, UriSerde},
+    },
 };
 
 #[derive(Clone)]
@@ -19,7 +25,7 @@ impl RetryLogic for LokiRetryLogic {
 
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
         match error {
-            LokiError::ServerError { code } => match *code {
+            LokiError::ServerError { code, .. } => match *code {
                 StatusCode::TOO_MANY_REQUESTS => true,
                 StatusCode::NOT_IMPLEMENTED => false,
                 _ if code.is_server_error() => true,
@@ -28,12 +34,22 @@ impl RetryLogic for LokiRetryLogic {
             LokiError::HttpError { .. } => true,
         }
     }
+
+    fn retry_after_error(&self, error: &Self::Error) -> Option<Duration> {
+        match error {
+            LokiError::ServerError { retry_after, .. } => *retry_after,
+            LokiError::HttpError { .. } => None,
+        }
+    }
 }
 
 #[derive(Debug, Snafu)]
 pub enum LokiError {
     #[snafu(display("Server responded with an error: {}", code))]
-    ServerError { code: StatusCode },
+    ServerError {
+        code: StatusCode,
+        retry_after: Option<Duration>,
+    },
     #[snafu(display("Failed to make HTTP(S) request: {}", error))]
     HttpError { error: crate::http::HttpError },
 }
@@ -144,7 +160,10 @@ impl Service<LokiRequest> for LokiService {
                     if status.is_success() {
                         Ok(LokiResponse { metadata })
                     } else {
-                        Err(LokiError::ServerError { code: status })
+                        Err(LokiError::ServerError {
+                            code: status,
+                            retry_after: parse_retry_after(response.headers()),
+                        })
                     }
                 }
                 Err(error) => Err(LokiError::HttpError { error }),
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{collections::HashMap, num::NonZeroUsize, time::Instant};
 
 use bytes::{Bytes, BytesMut};
 use once_cell::sync::Lazy;
@@ -8,6 +8,7 @@ use tokio_util::codec::Encoder as _;
 use vrl::path::parse_target_path;
 
 use super::{
+    cardinality::CardinalityGuard,
     config::{LokiConfig, OutOfOrderAction},
     event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
     service::{LokiRequest, LokiRetryLogic, LokiService},
@@ -135,16 +136,32 @@ pub(super) struct EventEncoder {
     transformer: Transformer,
     encoder: Encoder<()>,
     labels: HashMap<Template, Template>,
+    structured_metadata: HashMap<Template, Template>,
+    cardinality_guard: Option<CardinalityGuard>,
     remove_label_fields: bool,
     remove_timestamp: bool,
 }
 
 impl EventEncoder {
     fn build_labels(&self, event: &Event) -> Vec<(String, String)> {
+        Self::render_key_values(&self.labels, event, "label")
+    }
+
+    fn build_structured_metadata(&self, event: &Event) -> Vec<(String, String)> {
+        Self::render_key_values(&self.structured_metadata, event, "structured_metadata")
+    }
+
+    /// Renders templated keys and values, where keys ending with `*` expand objects into multiple
+    /// pairs. The `kind` of the pairs is used in the errors and warnings.
+    fn render_key_values(
+        templates: &HashMap<Template, Template>,
+        event: &Event,
+        kind: &str,
+    ) -> Vec<(String, String)> {
         let mut static_labels: HashMap<String, String> = HashMap::new();
         let mut dynamic_labels: HashMap<String, String> = HashMap::new();
 
-        for (key_template, value_template) in self.labels.iter() {
+        for (key_template, value_template) in templates.iter() {
             let key = key_template.render_string(event);
             let value = value_template.render_string(event);
 
@@ -153,8 +170,8 @@ impl EventEncoder {
                     emit!(TemplateRenderingError {
                         field: Some(
                             format!(
-                                "label_key \"{}\" with label_value \"{}\"",
-                                key_template, value_template
+                                "{}_key \"{}\" with {}_value \"{}\"",
+                                kind, key_template, kind, value_template
                             )
                             .as_str()
                         ),
@@ -166,8 +183,8 @@ impl EventEncoder {
                     emit!(TemplateRenderingError {
                         field: Some(
                             format!(
-                                "label_value \"{}\" with label_key \"{}\"",
-                                value_template, key_template
+                                "{}_value \"{}\" with {}_key \"{}\"",
+                                kind, value_template, kind, key_template
                             )
                             .as_str()
                         ),
@@ -189,7 +206,8 @@ impl EventEncoder {
 
                 if output.is_err() {
                     warn!(
-                        "Failed to expand dynamic label. value: {}, err: {}",
+                        "Failed to expand dynamic {}. value: {}, err: {}",
+                        kind,
                         value_s,
                         output.err().unwrap()
                     );
@@ -202,7 +220,10 @@ impl EventEncoder {
                     let key = slugify_text(format!("{}{}", opening_prefix, k));
                     let val = Value::from(v).to_string_lossy().into_owned();
                     if val == "<null>" {
-                        warn!("Encountered \"null\" value for dynamic label. key: {}", key);
+                        warn!(
+                            "Encountered \"null\" value for dynamic {}. key: {}",
+                            kind, key
+                        );
                         continue;
                     }
                     if let Some(prev) = dynamic_labels.insert(key.clone(), val.clone()) {
@@ -250,6 +271,7 @@ impl EventEncoder {
         let finalizers = event.take_finalizers();
         let json_byte_size = event.estimated_json_encoded_size_of();
         let mut labels = self.build_labels(&event);
+        let mut structured_metadata = self.build_structured_metadata(&event);
         self.remove_label_fields(&mut event);
 
         let timestamp = match event.as_log().get_timestamp() {
@@ -277,6 +299,14 @@ impl EventEncoder {
             labels = vec![("agent".to_string(), "vector".to_string())]
         }
 
+        if let Some(guard) = self.cardinality_guard.as_mut() {
+            guard.apply(&mut labels, &mut structured_metadata, Instant::now());
+            // The guard removes all the labels when none of them fit in the limit.
+            if labels.is_empty() {
+                labels = vec![("agent".to_string(), "vector".to_string())]
+            }
+        }
+
         let partition = PartitionKey { tenant_id };
 
         Some(LokiRecord {
@@ -284,6 +314,7 @@ impl EventEncoder {
             event: LokiEvent {
                 timestamp,
                 event: bytes.freeze(),
+                structured_metadata,
             },
             partition,
             finalizers,
@@ -425,6 +456,11 @@ impl LokiSink {
                 transformer,
                 encoder,
                 labels: config.labels,
+                structured_metadata: config.structured_metadata,
+                cardinality_guard: config
+                    .label_cardinality_limit
+                    .as_ref()
+                    .map(CardinalityGuard::new),
                 remove_label_fields: config.remove_label_fields,
                 remove_timestamp: config.remove_timestamp,
             },
@@ -529,6 +565,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -572,6 +610,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -622,6 +662,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -672,6 +714,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -710,6 +754,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
@@ -731,6 +777,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: false,
             remove_timestamp: true,
         };
@@ -761,6 +809,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: true,
             remove_timestamp: false,
         };
@@ -783,6 +833,8 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            cardinality_guard: None,
             remove_label_fields: false,
             remove_timestamp: false,
         };
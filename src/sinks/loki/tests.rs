@@ -1,6 +1,14 @@
+use std::time::Duration;
+
+use http::StatusCode;
 use vector_lib::config::proxy::ProxyConfig;
 
-use super::{config::LokiConfig, healthcheck::healthcheck, sink::LokiSink};
+use super::{
+    config::LokiConfig,
+    healthcheck::healthcheck,
+    service::{LokiError, LokiRetryLogic},
+    sink::LokiSink,
+};
 use crate::{
     http::HttpClient,
     sinks::prelude::*,
@@ -13,6 +21,19 @@ fn generate_config() {
     test_util::test_generate_config::<LokiConfig>();
 }
 
+#[test]
+fn retry_after_server_errors() {
+    let error = LokiError::ServerError {
+        code: StatusCode::TOO_MANY_REQUESTS,
+        retry_after: Some(Duration::from_secs(30)),
+    };
+    assert!(LokiRetryLogic.is_retriable_error(&error));
+    assert_eq!(
+        LokiRetryLogic.retry_after_error(&error),
+        Some(Duration::from_secs(30))
+    );
+}
+
 #[tokio::test]
 async fn interpolate_labels() {
     let (config, cx) = load_sink::<LokiConfig>(
@@ -84,6 +105,83 @@ async fn use_label_from_dropped_fields() {
     assert_eq!(record.labels[0], ("bar".to_string(), "bar".to_string()));
 }
 
+#[tokio::test]
+async fn interpolate_structured_metadata() {
+    let (config, cx) = load_sink::<LokiConfig>(
+        r#"
+            endpoint = "http://localhost:3100"
+            labels.app = "web"
+            structured_metadata = {trace_id = "{{ trace_id }}", "attr_*" = "{{ attributes }}"}
+            encoding.codec = "json"
+        "#,
+    )
+    .unwrap();
+    let client = config.build_client(cx).unwrap();
+    let mut sink = LokiSink::new(config, client).unwrap();
+
+    let mut e1 = Event::Log(LogEvent::from("hello world"));
+    e1.as_mut_log().insert("trace_id", "abc");
+    e1.as_mut_log().insert("attributes.user", "alice");
+
+    let mut record = sink.encoder.encode_event(e1).unwrap();
+    record.event.structured_metadata.sort();
+
+    assert_eq!(record.labels, vec![("app".to_string(), "web".to_string())]);
+    assert_eq!(
+        record.event.structured_metadata,
+        vec![
+            ("attr_user".to_string(), "alice".to_string()),
+            ("trace_id".to_string(), "abc".to_string()),
+        ]
+    );
+
+    // Structured metadata is the third element of the values in JSON.
+    let encoded = serde_json::to_value(&record.event).unwrap();
+    assert_eq!(
+        encoded[2],
+        serde_json::json!({"attr_user": "alice", "trace_id": "abc"})
+    );
+}
+
+#[tokio::test]
+async fn cardinality_limit_moves_labels_to_structured_metadata() {
+    let (config, cx) = load_sink::<LokiConfig>(
+        r#"
+            endpoint = "http://localhost:3100"
+            labels = {app = "web", id = "{{ id }}"}
+            label_cardinality_limit.max_streams = 1
+            encoding.codec = "json"
+        "#,
+    )
+    .unwrap();
+    let client = config.build_client(cx).unwrap();
+    let mut sink = LokiSink::new(config, client).unwrap();
+
+    let mut records = ["1", "2"].map(|id| {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("id", id);
+        sink.encoder.encode_event(event).unwrap()
+    });
+    records[0].labels.sort();
+
+    assert_eq!(
+        records[0].labels,
+        vec![
+            ("app".to_string(), "web".to_string()),
+            ("id".to_string(), "1".to_string()),
+        ]
+    );
+    assert!(records[0].event.structured_metadata.is_empty());
+    assert_eq!(
+        records[1].labels,
+        vec![("app".to_string(), "web".to_string())]
+    );
+    assert_eq!(
+        records[1].event.structured_metadata,
+        vec![("id".to_string(), "2".to_string())]
+    );
+}
+
 #[tokio::test]
 async fn healthcheck_includes_auth() {
     let (mut config, _cx) = load_sink::<LokiConfig>(
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslConnector, SslMethod},
+};
+use postgres_openssl::MakeTlsConnector;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::{Mutex, OwnedMappedMutexGuard, OwnedMutexGuard};
+use tokio_postgres::{Client, Config, NoTls};
+
+use super::config::PostgresTlsConfig;
+
+#[derive(Debug, Snafu)]
+pub(super) enum ConnectError {
+    #[snafu(display("Failed to create TLS connector: {}", source))]
+    TlsFailed { source: ErrorStack },
+    #[snafu(display("Failed to connect: {}", source))]
+    ConnectionFailed { source: tokio_postgres::Error },
+}
+
+/// A connection to PostgreSQL, shared by the sink and its service.
+///
+/// The connection is established lazily, and re-established once it has been closed.
+#[derive(Clone)]
+pub(super) struct PostgresClient {
+    config: Config,
+    tls: Option<PostgresTlsConfig>,
+    client: Arc<Mutex<Option<Client>>>,
+}
+
+impl PostgresClient {
+    pub(super) fn new(config: Config, tls: Option<PostgresTlsConfig>) -> Self {
+        Self {
+            config,
+            tls,
+            client: Arc::default(),
+        }
+    }
+
+    /// Takes exclusive use of the connection, connecting first if needed.
+    pub(super) async fn connection(
+        &self,
+    ) -> Result<OwnedMappedMutexGuard<Option<Client>, Client>, ConnectError> {
+        let mut guard = Arc::clone(&self.client).lock_owned().await;
+        if guard.as_ref().map_or(true, Client::is_closed) {
+            *guard = Some(self.connect().await?);
+        }
+        Ok(OwnedMutexGuard::map(guard, |client| {
+            client.as_mut().expect("client is connected")
+        }))
+    }
+
+    async fn connect(&self) -> Result<Client, ConnectError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let mut builder =
+                    SslConnector::builder(SslMethod::tls_client()).context(TlsFailedSnafu)?;
+                builder.set_ca_file(&tls.ca_file).context(TlsFailedSnafu)?;
+                let connector = MakeTlsConnector::new(builder.build());
+                let (client, connection) = self
+                    .config
+                    .connect(connector)
+                    .await
+                    .context(ConnectionFailedSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self
+                    .config
+                    .connect(NoTls)
+                    .await
+                    .context(ConnectionFailedSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        debug!(message = "Connected to PostgreSQL.");
+        Ok(client)
+    }
+}
@@ -0,0 +1,352 @@
+use chrono::{DateTime, Utc};
+use futures::stream;
+use tokio_postgres::{Client, NoTls};
+use vector_lib::event::{
+    BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent, Metric, MetricKind,
+    MetricValue,
+};
+use vector_lib::metric_tags;
+
+use super::{
+    client::PostgresClient,
+    config::{CopyFormat, UpsertConfig},
+    schema::SchemaFetcher,
+    *,
+};
+use crate::{
+    config::{SinkConfig, SinkContext},
+    sinks::util::{schema_cache::FetchSchema, BatchConfig},
+    test_util::{
+        components::{
+            run_and_assert_sink_compliance, run_and_assert_sink_error, COMPONENT_ERROR_TAGS,
+            SINK_TAGS,
+        },
+        random_string, trace_init,
+    },
+};
+
+fn pg_host() -> String {
+    std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn pg_url() -> String {
+    std::env::var("PG_URL")
+        .unwrap_or_else(|_| format!("postgres://vector:vector@{}/postgres", pg_host()))
+}
+
+async fn connect() -> Client {
+    let (client, connection) = tokio_postgres::connect(&pg_url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+async fn create_table(client: &Client, columns: &str) -> String {
+    let table = format!("test_{}", random_string(10).to_lowercase());
+    client
+        .batch_execute(&format!("CREATE TABLE {table} ({columns})"))
+        .await
+        .unwrap();
+    table
+}
+
+fn config(table: &str, format: CopyFormat, upsert: Option<&[&str]>) -> PostgresConfig {
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(10);
+
+    PostgresConfig {
+        endpoint: pg_url(),
+        table: table.try_into().unwrap(),
+        format,
+        upsert: upsert.map(|keys| UpsertConfig {
+            keys: keys.iter().map(ToString::to_string).collect(),
+        }),
+        tls: None,
+        metrics: None,
+        encoding: Default::default(),
+        batch,
+        request: Default::default(),
+        acknowledgements: Default::default(),
+    }
+}
+
+fn make_event(fields: &[(&str, vrl::value::Value)]) -> (Event, BatchStatusReceiver) {
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let mut log = LogEvent::default().with_batch_notifier(&batch);
+    for (field, value) in fields {
+        log.insert(*field, value.clone());
+    }
+    (log.into(), receiver)
+}
+
+async fn insert_events(format: CopyFormat) {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(
+        &client,
+        "id BIGINT NOT NULL, message TEXT, tags TEXT[], payload JSONB, \
+         timestamp TIMESTAMPTZ, inserted_at TIMESTAMPTZ DEFAULT now()",
+    )
+    .await;
+
+    let (sink, healthcheck) = config(&table, format, None)
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+    healthcheck.await.unwrap();
+
+    let timestamp = DateTime::parse_from_rfc3339("2024-01-02T03:04:05.678Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let (event, mut receiver) = make_event(&[
+        ("id", 1.into()),
+        ("message", "tab\tand\nnewline".into()),
+        ("tags", vec!["a", "b"].into()),
+        ("payload.nested", true.into()),
+        ("timestamp", timestamp.into()),
+        ("unknown", "ignored".into()),
+    ]);
+
+    run_and_assert_sink_compliance(sink, stream::once(async { event }), &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!(
+                "SELECT id, message, tags, payload::text, timestamp, inserted_at IS NOT NULL \
+                 FROM {table}"
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, i64>(0), 1);
+    assert_eq!(rows[0].get::<_, String>(1), "tab\tand\nnewline");
+    assert_eq!(rows[0].get::<_, Vec<String>>(2), vec!["a", "b"]);
+    assert_eq!(rows[0].get::<_, String>(3), r#"{"nested": true}"#);
+    assert_eq!(rows[0].get::<_, DateTime<Utc>>(4), timestamp);
+    assert!(rows[0].get::<_, bool>(5));
+}
+
+#[tokio::test]
+async fn insert_events_binary() {
+    insert_events(CopyFormat::Binary).await;
+}
+
+#[tokio::test]
+async fn insert_events_text() {
+    insert_events(CopyFormat::Text).await;
+}
+
+#[tokio::test]
+async fn rejects_invalid_events() {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(&client, "id BIGINT NOT NULL, message TEXT").await;
+
+    let (sink, _) = config(&table, CopyFormat::Binary, None)
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+
+    let (valid, mut valid_receiver) = make_event(&[("id", 1.into()), ("message", "a".into())]);
+    let (missing, mut missing_receiver) = make_event(&[("message", "b".into())]);
+    let (invalid, mut invalid_receiver) = make_event(&[("id", "one".into())]);
+
+    run_and_assert_sink_error(
+        sink,
+        stream::iter(vec![valid, missing, invalid]),
+        &COMPONENT_ERROR_TAGS,
+    )
+    .await;
+
+    assert_eq!(valid_receiver.try_recv(), Ok(BatchStatus::Delivered));
+    assert_eq!(missing_receiver.try_recv(), Ok(BatchStatus::Rejected));
+    assert_eq!(invalid_receiver.try_recv(), Ok(BatchStatus::Rejected));
+
+    let rows = client
+        .query(&format!("SELECT id FROM {table}"), &[])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, i64>(0), 1);
+}
+
+#[tokio::test]
+async fn upserts_events() {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(&client, "id BIGINT PRIMARY KEY, message TEXT, count INT").await;
+    client
+        .batch_execute(&format!(
+            "INSERT INTO {table} VALUES (1, 'old', 1), (2, 'kept', 2)"
+        ))
+        .await
+        .unwrap();
+
+    let (sink, _) = config(&table, CopyFormat::Binary, Some(&["id"]))
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+
+    let (first, mut receiver) = make_event(&[("id", 1.into()), ("message", "first".into())]);
+    let (last, _) = make_event(&[("id", 1.into()), ("message", "last".into())]);
+    let (new, _) = make_event(&[("id", 3.into()), ("message", "new".into())]);
+
+    run_and_assert_sink_compliance(sink, stream::iter(vec![first, last, new]), &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!("SELECT id, message, count FROM {table} ORDER BY id"),
+            &[],
+        )
+        .await
+        .unwrap();
+    let rows = rows
+        .iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2)))
+        .collect::<Vec<(i64, String, Option<i32>)>>();
+    assert_eq!(
+        rows,
+        vec![
+            (1, "last".into(), Some(1)),
+            (2, "kept".into(), Some(2)),
+            (3, "new".into(), None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn insert_metrics() {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(
+        &client,
+        "name TEXT NOT NULL, kind TEXT, counter JSONB, tags JSONB, timestamp TIMESTAMPTZ",
+    )
+    .await;
+
+    let (sink, _) = config(&table, CopyFormat::Binary, None)
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let metric = Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 2.0 },
+    )
+    .with_tags(Some(metric_tags!("code" => "200")))
+    .with_timestamp(Some(Utc::now()))
+    .with_batch_notifier(&batch);
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, stream::once(async { metric.into() }), &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!("SELECT name, kind, counter::text, tags->>'code' FROM {table}"),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, String>(0), "requests");
+    assert_eq!(rows[0].get::<_, String>(1), "incremental");
+    assert_eq!(rows[0].get::<_, String>(2), r#"{"value": 2.0}"#);
+    assert_eq!(rows[0].get::<_, String>(3), "200");
+}
+
+#[tokio::test]
+async fn introspects_qualified_tables_with_defaults() {
+    trace_init();
+
+    let client = connect().await;
+    let schema = format!("test_{}", random_string(10).to_lowercase());
+    client
+        .batch_execute(&format!(
+            r#"CREATE SCHEMA {schema};
+            CREATE TABLE {schema}."Mixed Case" (
+                id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                sequence BIGSERIAL,
+                message TEXT NOT NULL,
+                inserted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                doubled BIGINT GENERATED ALWAYS AS (sequence * 2) STORED
+            )"#
+        ))
+        .await
+        .unwrap();
+    let table = format!("{schema}.Mixed Case");
+
+    let fetcher = SchemaFetcher::new(
+        PostgresClient::new(pg_url().parse().unwrap(), None),
+        CopyFormat::Binary,
+        None,
+    );
+    let statements = fetcher.fetch(&table).await.unwrap().statements();
+    assert_eq!(
+        statements.copy,
+        r#"COPY "vector_staging" ("id", "sequence", "message", "inserted_at") FROM STDIN (FORMAT binary)"#
+    );
+
+    let (sink, healthcheck) = config(&table, CopyFormat::Binary, None)
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+    healthcheck.await.unwrap();
+
+    let (first, mut receiver) = make_event(&[("message", "first".into())]);
+    let (second, _) = make_event(&[("message", "second".into()), ("id", 10.into())]);
+    run_and_assert_sink_compliance(sink, stream::iter(vec![first, second]), &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!(
+                r#"SELECT id, sequence, message, doubled FROM {schema}."Mixed Case" ORDER BY id"#
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    let rows = rows
+        .iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2), row.get(3)))
+        .collect::<Vec<(i64, i64, String, i64)>>();
+    assert_eq!(
+        rows,
+        vec![(1, 1, "first".into(), 2), (10, 2, "second".into(), 4)]
+    );
+}
+
+#[tokio::test]
+async fn healthcheck_fails_for_unknown_upsert_keys() {
+    trace_init();
+
+    let client = connect().await;
+    let table = create_table(&client, "id BIGINT PRIMARY KEY, message TEXT").await;
+
+    let (_, healthcheck) = config(&table, CopyFormat::Binary, Some(&["missing"]))
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+    assert!(healthcheck.await.is_err());
+}
+
+#[tokio::test]
+async fn healthcheck_fails_for_missing_table() {
+    trace_init();
+
+    let (_, healthcheck) = config("missing_table", CopyFormat::Binary, None)
+        .build(SinkContext::default())
+        .await
+        .unwrap();
+    assert!(healthcheck.await.is_err());
+}
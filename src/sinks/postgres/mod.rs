@@ -0,0 +1,27 @@
+//! The PostgreSQL [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`] instances and inserting them into PostgreSQL
+//! tables.
+//!
+//! The columns of each table are introspected, the fields of each event are mapped to the columns
+//! by name, and batches are written with `COPY ... FROM STDIN` in the binary or text format. When
+//! the table has column defaults or upserts are enabled, batches are copied into a temporary table
+//! first and then inserted into the target table, with `INSERT ... ON CONFLICT` for upserts.
+//!
+//! Metrics are converted to logs with the `metric_to_log` transform before being inserted.
+
+mod client;
+mod config;
+mod schema;
+mod service;
+mod sink;
+mod value;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests;
+
+pub use self::config::PostgresConfig;
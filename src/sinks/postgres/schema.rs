@@ -0,0 +1,490 @@
+//! Introspection of the tables that events are inserted into, and the encoding of their rows for
+//! `COPY`.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::{BufMut, BytesMut};
+use snafu::{ResultExt, Snafu};
+use tokio_postgres::{
+    types::{IsNull, ToSql, Type},
+    Client,
+};
+use vector_lib::lookup::{event_path, OwnedTargetPath};
+use vrl::{path::parse_target_path, value::Value};
+
+use super::{
+    client::{ConnectError, PostgresClient},
+    config::CopyFormat,
+    service::is_retriable_code,
+    value::{self, ValueError},
+};
+use crate::{
+    event::LogEvent,
+    sinks::util::schema_cache::{self, FetchSchema},
+};
+
+/// Only the columns that can be written to are listed. Generated columns and columns that are
+/// always generated as identities are skipped.
+///
+/// The table name is bound as `text` and cast, since a string can't be bound to a `regclass`
+/// parameter. The default of identity columns is the next value of their sequence.
+const COLUMNS_QUERY: &str = "SELECT a.attname, a.atttypid, t.typcategory = 'A', \
+    pg_catalog.format_type(a.atttypid, a.atttypmod), a.attnotnull, \
+    CASE WHEN a.attidentity = 'd' \
+    THEN pg_catalog.format('nextval(%L::regclass)', \
+    pg_catalog.pg_get_serial_sequence($1::text, a.attname)) \
+    ELSE pg_catalog.pg_get_expr(d.adbin, d.adrelid) END \
+    FROM pg_catalog.pg_attribute a JOIN pg_catalog.pg_type t ON t.oid = a.atttypid \
+    LEFT JOIN pg_catalog.pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+    WHERE a.attrelid = $1::text::regclass AND a.attnum > 0 AND NOT a.attisdropped \
+    AND a.attgenerated = '' AND a.attidentity <> 'a' \
+    ORDER BY a.attnum";
+
+/// The temporary table that batches are copied into, when they can't be copied into the table
+/// directly.
+const STAGING_TABLE: &str = "\"vector_staging\"";
+
+/// The signature that starts the binary `COPY` format, followed by the flags and the length of
+/// the header extension.
+const BINARY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+#[derive(Debug, Snafu)]
+pub(super) enum SchemaError {
+    #[snafu(display("Failed to introspect table {}: {}", table, source))]
+    Connect { table: String, source: ConnectError },
+    #[snafu(display("Failed to introspect table {}: {}", table, source))]
+    Query {
+        table: String,
+        source: tokio_postgres::Error,
+    },
+    #[snafu(display("Table {} has no columns that can be written to", table))]
+    NoColumns { table: String },
+    #[snafu(display("Upsert key {:?} is not a column of table {}", key, table))]
+    MissingKey { table: String, key: String },
+    #[snafu(display(
+        "Column {:?} of table {} has type {}, which the binary format does not support",
+        column,
+        table,
+        type_name
+    ))]
+    UnsupportedColumn {
+        table: String,
+        column: String,
+        type_name: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub(super) enum ConversionError {
+    #[snafu(display("Failed to convert field {:?}: {}", column, source))]
+    Field { column: String, source: ValueError },
+    #[snafu(display("Missing value for column {:?}, which is not nullable", column))]
+    MissingValue { column: String },
+    #[snafu(display("Failed to encode field {:?}: {}", column, message))]
+    Encode { column: String, message: String },
+    #[snafu(display("Event has no field matching a column of the table"))]
+    NoMatchingField,
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    path: Option<OwnedTargetPath>,
+    ty: Type,
+    not_null: bool,
+    /// The expression of the column's default value.
+    default: Option<String>,
+}
+
+impl Column {
+    /// A column named `a.b` takes the nested `b` field of `a`, if any, or else the field named
+    /// `a.b`. A `null` field counts as missing, so that it can be checked against `NOT NULL`.
+    fn lookup<'a>(&self, log: &'a LogEvent) -> Option<&'a Value> {
+        self.path
+            .as_ref()
+            .and_then(|path| log.get(path))
+            .or_else(|| log.get(event_path!(self.name.as_str())))
+            .filter(|value| !matches!(value, Value::Null))
+    }
+}
+
+/// The statements that write a batch into its table, which are run in order in a transaction.
+#[derive(Debug)]
+pub(super) struct Statements {
+    /// Creates the staging table that rows are copied into first.
+    pub(super) create_staging: Option<String>,
+    pub(super) copy: String,
+    /// Inserts the rows of the staging table into the table.
+    pub(super) insert: Option<String>,
+}
+
+/// The columns of a table, in order, and the statements that insert rows of them.
+#[derive(Debug)]
+pub(super) struct TableSchema {
+    columns: Vec<Column>,
+    statements: Arc<Statements>,
+}
+
+impl TableSchema {
+    async fn fetch(
+        client: &Client,
+        table: &str,
+        format: CopyFormat,
+        upsert_keys: Option<&[String]>,
+    ) -> Result<Self, SchemaError> {
+        let quoted_table = quote_table(table);
+        let rows = client
+            .query(COLUMNS_QUERY, &[&quoted_table])
+            .await
+            .context(QuerySnafu { table })?;
+
+        let columns = rows
+            .iter()
+            .map(|row| {
+                let name: String = row.get(0);
+                let oid: u32 = row.get(1);
+                let is_array: bool = row.get(2);
+                let type_name: String = row.get(3);
+                let ty = match (Type::from_oid(oid), format) {
+                    (Some(ty), _) if value::is_supported(&ty) => ty,
+                    (_, CopyFormat::Binary) => {
+                        return Err(SchemaError::UnsupportedColumn {
+                            table: table.to_string(),
+                            column: name,
+                            type_name,
+                        })
+                    }
+                    // The text format leaves conversions to PostgreSQL, so only arrays are told
+                    // apart from other types.
+                    (ty, CopyFormat::Text) => ty.unwrap_or(if is_array {
+                        Type::TEXT_ARRAY
+                    } else {
+                        Type::TEXT
+                    }),
+                };
+                Ok(Column {
+                    path: parse_target_path(&name).ok(),
+                    name,
+                    ty,
+                    not_null: row.get(4),
+                    default: row.get(5),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if columns.is_empty() {
+            return Err(SchemaError::NoColumns {
+                table: table.to_string(),
+            });
+        }
+        if let Some(key) = upsert_keys
+            .into_iter()
+            .flatten()
+            .find(|key| !columns.iter().any(|column| &column.name == *key))
+        {
+            return Err(SchemaError::MissingKey {
+                table: table.to_string(),
+                key: key.clone(),
+            });
+        }
+
+        let statements = Statements::new(&quoted_table, &columns, format, upsert_keys);
+        Ok(Self {
+            columns,
+            statements: Arc::new(statements),
+        })
+    }
+
+    pub(super) fn statements(&self) -> Arc<Statements> {
+        Arc::clone(&self.statements)
+    }
+
+    /// Writes an event as a row, leaving the buffer unchanged when its fields cannot be converted.
+    pub(super) fn write_row(
+        &self,
+        format: CopyFormat,
+        log: &LogEvent,
+        buffer: &mut BytesMut,
+    ) -> Result<(), ConversionError> {
+        let start = buffer.len();
+        let result = self.try_write_row(format, log, buffer);
+        if result.is_err() {
+            buffer.truncate(start);
+        }
+        result
+    }
+
+    fn try_write_row(
+        &self,
+        format: CopyFormat,
+        log: &LogEvent,
+        buffer: &mut BytesMut,
+    ) -> Result<(), ConversionError> {
+        let values = self
+            .columns
+            .iter()
+            .map(|column| match column.lookup(log) {
+                None if column.not_null && column.default.is_none() => {
+                    Err(ConversionError::MissingValue {
+                        column: column.name.clone(),
+                    })
+                }
+                value => Ok((column, value)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.iter().all(|(_, value)| value.is_none()) {
+            return Err(ConversionError::NoMatchingField);
+        }
+
+        match format {
+            CopyFormat::Binary => {
+                buffer.put_i16(values.len() as i16);
+                for (column, value) in values {
+                    let value = match value {
+                        Some(value) => value::convert(&column.ty, value).context(FieldSnafu {
+                            column: &column.name,
+                        })?,
+                        None => value::PgValue::Null,
+                    };
+                    write_binary_field(&column.ty, &value, buffer).map_err(|error| {
+                        ConversionError::Encode {
+                            column: column.name.clone(),
+                            message: error.to_string(),
+                        }
+                    })?;
+                }
+            }
+            CopyFormat::Text => {
+                for (index, (column, value)) in values.into_iter().enumerate() {
+                    if index > 0 {
+                        buffer.put_u8(b'\t');
+                    }
+                    value::write_text(&column.ty, value, buffer);
+                }
+                buffer.put_u8(b'\n');
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Statements {
+    /// Rows are copied into the table directly, unless it has defaults to fill in or rows are
+    /// upserted. They are then copied into a staging table, which has the types of the columns but
+    /// neither their constraints nor their defaults, and inserted from there.
+    fn new(
+        table: &str,
+        columns: &[Column],
+        format: CopyFormat,
+        upsert_keys: Option<&[String]>,
+    ) -> Self {
+        let names = columns
+            .iter()
+            .map(|column| quote_identifier(&column.name))
+            .collect::<Vec<_>>();
+        let column_list = names.join(", ");
+        if upsert_keys.is_none() && columns.iter().all(|column| column.default.is_none()) {
+            return Self {
+                create_staging: None,
+                copy: copy_statement(table, &column_list, format),
+                insert: None,
+            };
+        }
+
+        // Missing fields and `null`s are copied as `NULL`, which is replaced by the default.
+        let values = columns
+            .iter()
+            .zip(&names)
+            .map(|(column, name)| match &column.default {
+                Some(default) => format!("COALESCE({name}, {default})"),
+                None => name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut insert = format!("INSERT INTO {table} AS \"target\" ({column_list}) SELECT ");
+        match upsert_keys {
+            None => insert.push_str(&format!("{values} FROM {STAGING_TABLE}")),
+            Some(keys) => {
+                // `ON CONFLICT` fails when a statement affects the same row twice, so only the
+                // last row copied for each key is kept.
+                let keys = keys
+                    .iter()
+                    .map(|key| quote_identifier(key))
+                    .collect::<Vec<_>>();
+                insert.push_str(&format!(
+                    "DISTINCT ON ({keys}) {values} FROM {STAGING_TABLE} ORDER BY {keys}, ctid DESC \
+                     ON CONFLICT ({keys}) ",
+                    keys = keys.join(", ")
+                ));
+
+                // Columns without a value keep the one of the existing row, unless they have a
+                // default to be reset to.
+                let updates = columns
+                    .iter()
+                    .zip(&names)
+                    .filter(|(_, name)| !keys.contains(name))
+                    .map(|(column, name)| match column.default {
+                        Some(_) => format!("{name} = EXCLUDED.{name}"),
+                        None => format!("{name} = COALESCE(EXCLUDED.{name}, \"target\".{name})"),
+                    })
+                    .collect::<Vec<_>>();
+                if updates.is_empty() {
+                    insert.push_str("DO NOTHING");
+                } else {
+                    insert.push_str(&format!("DO UPDATE SET {}", updates.join(", ")));
+                }
+            }
+        }
+
+        Self {
+            create_staging: Some(format!(
+                "CREATE TEMPORARY TABLE {STAGING_TABLE} ON COMMIT DROP AS \
+                 SELECT {column_list} FROM {table} WITH NO DATA"
+            )),
+            copy: copy_statement(STAGING_TABLE, &column_list, format),
+            insert: Some(insert),
+        }
+    }
+}
+
+fn copy_statement(table: &str, columns: &str, format: CopyFormat) -> String {
+    let format = match format {
+        CopyFormat::Binary => "binary",
+        CopyFormat::Text => "text",
+    };
+    format!("COPY {table} ({columns}) FROM STDIN (FORMAT {format})")
+}
+
+/// Writes what precedes the rows of a `COPY`.
+pub(super) fn write_header(format: CopyFormat, buffer: &mut BytesMut) {
+    if format == CopyFormat::Binary {
+        buffer.put_slice(BINARY_HEADER);
+    }
+}
+
+/// Writes what follows the rows of a `COPY`.
+pub(super) fn write_trailer(format: CopyFormat, buffer: &mut BytesMut) {
+    if format == CopyFormat::Binary {
+        buffer.put_i16(-1);
+    }
+}
+
+/// Writes a field prefixed with its length, which is `-1` for `NULL`.
+fn write_binary_field(
+    ty: &Type,
+    value: &value::PgValue,
+    buffer: &mut BytesMut,
+) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+    let length_at = buffer.len();
+    buffer.put_i32(0);
+    let length = match value.to_sql(ty, buffer)? {
+        IsNull::Yes => -1,
+        IsNull::No => (buffer.len() - length_at - 4) as i32,
+    };
+    buffer[length_at..length_at + 4].copy_from_slice(&length.to_be_bytes());
+    Ok(())
+}
+
+/// Quotes a table name, which may be qualified with its schema.
+pub(super) fn quote_table(table: &str) -> String {
+    match table.split_once('.') {
+        Some((schema, table)) => {
+            format!("{}.{}", quote_identifier(schema), quote_identifier(table))
+        }
+        None => quote_identifier(table),
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// The schemas of the tables, keyed by their possibly schema-qualified name.
+pub(super) type SchemaCache = schema_cache::SchemaCache<SchemaFetcher>;
+
+/// Fetches the columns of a table from `pg_attribute`.
+pub(super) struct SchemaFetcher {
+    client: PostgresClient,
+    format: CopyFormat,
+    upsert_keys: Option<Vec<String>>,
+}
+
+impl SchemaFetcher {
+    pub(super) const fn new(
+        client: PostgresClient,
+        format: CopyFormat,
+        upsert_keys: Option<Vec<String>>,
+    ) -> Self {
+        Self {
+            client,
+            format,
+            upsert_keys,
+        }
+    }
+}
+
+#[async_trait]
+impl FetchSchema for SchemaFetcher {
+    type Key = String;
+    type Schema = TableSchema;
+    type Error = SchemaError;
+
+    async fn fetch(&self, table: &String) -> Result<TableSchema, SchemaError> {
+        let connection = self
+            .client
+            .connection()
+            .await
+            .context(ConnectSnafu { table })?;
+        let schema =
+            TableSchema::fetch(&connection, table, self.format, self.upsert_keys.as_deref())
+                .await?;
+        debug!(
+            message = "Introspected table columns.",
+            table,
+            columns = schema.columns.len(),
+        );
+        Ok(schema)
+    }
+
+    fn is_retriable_error(&self, error: &SchemaError) -> bool {
+        match error {
+            SchemaError::Connect { .. } => true,
+            // Errors without a code come from the connection rather than the query.
+            SchemaError::Query { source, .. } => source
+                .code()
+                .map_or(true, |code| is_retriable_code(code.code())),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+impl TableSchema {
+    pub(super) fn new_for_tests(
+        table: &str,
+        columns: &[(&str, Type, bool, Option<&str>)],
+        upsert_keys: Option<&[String]>,
+    ) -> Self {
+        let columns = columns
+            .iter()
+            .map(|(name, ty, not_null, default)| Column {
+                name: name.to_string(),
+                path: parse_target_path(name).ok(),
+                ty: ty.clone(),
+                not_null: *not_null,
+                default: default.map(ToString::to_string),
+            })
+            .collect::<Vec<_>>();
+        let statements = Statements::new(
+            &quote_table(table),
+            &columns,
+            CopyFormat::Binary,
+            upsert_keys,
+        );
+        Self {
+            columns,
+            statements: Arc::new(statements),
+        }
+    }
+}
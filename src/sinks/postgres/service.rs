@@ -0,0 +1,172 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::SinkExt;
+use snafu::{ResultExt, Snafu};
+use tokio_postgres::Transaction;
+
+use super::{
+    client::{ConnectError, PostgresClient},
+    schema::Statements,
+};
+use crate::sinks::{prelude::*, util::retries::RetryAction};
+
+#[derive(Debug, Snafu)]
+pub(super) enum PostgresServiceError {
+    #[snafu(display("{}", source))]
+    Connect { source: ConnectError },
+    #[snafu(display("Failed to insert rows: {}", source))]
+    Insert { source: tokio_postgres::Error },
+}
+
+/// A batch of rows encoded for `COPY`.
+#[derive(Clone)]
+pub(super) struct PostgresRequest {
+    /// The statements of the table, which copy rows of all of its columns.
+    pub(super) statements: Arc<Statements>,
+    pub(super) payload: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for PostgresRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct PostgresResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresService {
+    client: PostgresClient,
+}
+
+impl PostgresService {
+    pub(super) const fn new(client: PostgresClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresServiceError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let client = self.client.clone();
+
+        Box::pin(async move {
+            let events_byte_size = request
+                .metadata
+                .events_estimated_json_encoded_byte_size()
+                .clone();
+            let byte_size = request.payload.len();
+
+            let mut connection = client.connection().await.context(ConnectSnafu)?;
+            let transaction = connection.transaction().await.context(InsertSnafu)?;
+            let statements = &request.statements;
+            if let Some(statement) = &statements.create_staging {
+                transaction
+                    .batch_execute(statement)
+                    .await
+                    .context(InsertSnafu)?;
+            }
+            copy(&transaction, &statements.copy, request.payload.clone()).await?;
+            if let Some(statement) = &statements.insert {
+                transaction
+                    .batch_execute(statement)
+                    .await
+                    .context(InsertSnafu)?;
+            }
+            transaction.commit().await.context(InsertSnafu)?;
+
+            Ok(PostgresResponse {
+                events_byte_size,
+                byte_size,
+            })
+        })
+    }
+}
+
+async fn copy(
+    transaction: &Transaction<'_>,
+    statement: &str,
+    payload: Bytes,
+) -> Result<(), PostgresServiceError> {
+    let mut sink = Box::pin(
+        transaction
+            .copy_in::<_, Bytes>(statement)
+            .await
+            .context(InsertSnafu)?,
+    );
+    sink.send(payload).await.context(InsertSnafu)?;
+    sink.as_mut().finish().await.context(InsertSnafu)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresServiceError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            PostgresServiceError::Connect { .. } => true,
+            // Errors without a code come from the connection rather than the statements.
+            PostgresServiceError::Insert { source } => source
+                .code()
+                .map_or(true, |code| is_retriable_code(code.code())),
+        }
+    }
+
+    fn should_retry_response(&self, _response: &Self::Response) -> RetryAction {
+        RetryAction::Successful
+    }
+}
+
+/// Connection exceptions, transaction rollbacks such as serialization failures and deadlocks,
+/// insufficient resources, and operator interventions such as shutdowns can succeed when retried.
+pub(super) fn is_retriable_code(code: &str) -> bool {
+    ["08", "40", "53", "57"]
+        .iter()
+        .any(|class| code.starts_with(class))
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use vector_lib::config::telemetry;
+
+use super::{
+    config::CopyFormat,
+    schema::{self, SchemaCache, TableSchema},
+    service::{PostgresRequest, PostgresRetryLogic, PostgresService},
+};
+use crate::{
+    internal_events::{TableRowConversionError, TableSchemaError},
+    sinks::{prelude::*, util::partitioner::KeyPartitioner},
+    transforms::metric_to_log::MetricToLog,
+};
+
+pub(super) struct PostgresSink {
+    batch_settings: BatcherSettings,
+    transformer: Transformer,
+    metric_to_log: MetricToLog,
+    service: Svc<PostgresService, PostgresRetryLogic>,
+    table: Template,
+    format: CopyFormat,
+    schemas: Arc<SchemaCache>,
+}
+
+impl PostgresSink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        transformer: Transformer,
+        metric_to_log: MetricToLog,
+        service: Svc<PostgresService, PostgresRetryLogic>,
+        table: Template,
+        format: CopyFormat,
+        schemas: Arc<SchemaCache>,
+    ) -> Self {
+        Self {
+            batch_settings,
+            transformer,
+            metric_to_log,
+            service,
+            table,
+            format,
+            schemas,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let transformer = self.transformer;
+        let format = self.format;
+        let schemas = self.schemas;
+
+        input
+            .scan(self.metric_to_log, |metric_to_log, event| {
+                future::ready(Some(match event {
+                    Event::Metric(metric) => metric_to_log.transform_one(metric).map(Event::Log),
+                    // Traces are not accepted by this sink, so this case never occurs.
+                    event => Some(event),
+                }))
+            })
+            .filter_map(|event| async move { event })
+            .batched_partitioned(KeyPartitioner::new(self.table), || {
+                batch_settings.as_byte_size_config()
+            })
+            .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
+            .filter_map(move |(table, mut batch)| {
+                let schemas = Arc::clone(&schemas);
+                async move {
+                    match schemas.get(&table).await {
+                        Ok(schema) => Some((schema, batch)),
+                        Err(error) => {
+                            emit!(TableSchemaError {
+                                error: &error,
+                                count: batch.len(),
+                            });
+                            batch.take_finalizers().update_status(EventStatus::Errored);
+                            None
+                        }
+                    }
+                }
+            })
+            .filter_map(move |(schema, events)| {
+                future::ready(build_request(&schema, format, &transformer, events))
+            })
+            .into_driver(self.service)
+            .protocol("postgres")
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for PostgresSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Builds the request that copies a batch into its table.
+///
+/// The `[RequestBuilder]` trait doesn't fit, since the events are converted to rows against the
+/// schema of their table. Events that cannot be converted are rejected individually, and no
+/// request is built when none of them can be.
+fn build_request(
+    schema: &TableSchema,
+    format: CopyFormat,
+    transformer: &Transformer,
+    mut events: Vec<Event>,
+) -> Option<PostgresRequest> {
+    for event in &mut events {
+        transformer.transform(event);
+    }
+    let mut payload = BytesMut::new();
+    schema::write_header(format, &mut payload);
+
+    let mut finalizers = EventFinalizers::default();
+    let mut count = 0;
+    let mut events_byte_size = 0;
+    let mut byte_size = telemetry().create_request_count_byte_size();
+    for mut event in events {
+        match schema.write_row(format, event.as_log(), &mut payload) {
+            Ok(()) => {
+                count += 1;
+                events_byte_size += event.size_of();
+                byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+                finalizers.merge(event.take_finalizers());
+            }
+            Err(error) => {
+                emit!(TableRowConversionError { error: &error });
+                event.take_finalizers().update_status(EventStatus::Rejected);
+            }
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+
+    schema::write_trailer(format, &mut payload);
+    let encoded = EncodeResult::uncompressed(payload.freeze(), byte_size.clone());
+    let metadata = RequestMetadataBuilder::new(count, events_byte_size, byte_size).build(&encoded);
+
+    Some(PostgresRequest {
+        statements: schema.statements(),
+        payload: encoded.into_payload(),
+        finalizers,
+        metadata,
+    })
+}
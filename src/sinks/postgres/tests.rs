@@ -0,0 +1,343 @@
+use bytes::BytesMut;
+use chrono::{TimeZone, Utc};
+use tokio_postgres::types::Type;
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    config::CopyFormat,
+    schema::{quote_table, ConversionError, TableSchema},
+    service::is_retriable_code,
+    value::{convert, write_text, PgValue, ValueError},
+    PostgresConfig,
+};
+use crate::event::LogEvent;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<PostgresConfig>();
+}
+
+fn text(ty: &Type, value: impl Into<Value>) -> String {
+    let mut buffer = BytesMut::new();
+    write_text(ty, Some(&value.into()), &mut buffer);
+    String::from_utf8(buffer.to_vec()).unwrap()
+}
+
+#[test]
+fn write_text_escapes_special_characters() {
+    assert_eq!(text(&Type::TEXT, "a\tb\nc\\d\re"), "a\\tb\\nc\\\\d\\re");
+
+    let mut buffer = BytesMut::new();
+    write_text(&Type::TEXT, None, &mut buffer);
+    write_text(&Type::TEXT, Some(&Value::Null), &mut buffer);
+    assert_eq!(&buffer[..], b"\\N\\N");
+}
+
+#[test]
+fn write_text_formats_fields() {
+    assert_eq!(text(&Type::INT8, 42), "42");
+    assert_eq!(text(&Type::BOOL, true), "true");
+    assert_eq!(text(&Type::BYTEA, "\x01\x02"), "\\\\x0102");
+    assert_eq!(
+        text(&Type::TIMESTAMPTZ, Utc.timestamp_opt(1, 0).unwrap()),
+        "1970-01-01T00:00:01Z"
+    );
+    assert_eq!(
+        text(
+            &Type::JSONB,
+            Value::Object(ObjectMap::from([("a".into(), Value::from(1))]))
+        ),
+        r#"{"a":1}"#
+    );
+}
+
+#[test]
+fn write_text_formats_array_literals() {
+    let value = Value::Array(vec![
+        Value::from("a\"b"),
+        Value::Null,
+        Value::from("c\\d"),
+        Value::from(1),
+    ]);
+    assert_eq!(
+        text(&Type::TEXT_ARRAY, value),
+        r#"{"a\\"b",NULL,"c\\\\d","1"}"#
+    );
+
+    // Arrays are only formatted as literals for array columns.
+    assert_eq!(
+        text(&Type::JSONB, Value::Array(vec![Value::from(1)])),
+        "[1]"
+    );
+}
+
+#[test]
+fn convert_integers() {
+    assert_eq!(convert(&Type::INT4, &Value::from(7)), Ok(PgValue::Int4(7)));
+    assert_eq!(
+        convert(&Type::INT8, &Value::from("12")),
+        Ok(PgValue::Int8(12))
+    );
+    assert_eq!(
+        convert(&Type::INT2, &Value::from(2.0)),
+        Ok(PgValue::Int2(2))
+    );
+    assert_eq!(
+        convert(&Type::INT2, &Value::from(70000)),
+        Err(ValueError::OutOfRange {
+            value: "70000".into(),
+            type_name: "int2".into(),
+        })
+    );
+    assert_eq!(
+        convert(&Type::INT4, &Value::from("seven")),
+        Err(ValueError::Unparsable {
+            value: "seven".into(),
+            type_name: "int4".into(),
+        })
+    );
+}
+
+#[test]
+fn convert_other_scalars() {
+    assert_eq!(
+        convert(&Type::FLOAT8, &Value::from(3)),
+        Ok(PgValue::Float8(3.0))
+    );
+    assert_eq!(
+        convert(&Type::BOOL, &Value::from("t")),
+        Ok(PgValue::Bool(true))
+    );
+    assert_eq!(
+        convert(&Type::TEXT, &Value::from(1.5)),
+        Ok(PgValue::Text("1.5".into()))
+    );
+    assert_eq!(
+        convert(&Type::JSONB, &Value::from("x")),
+        Ok(PgValue::Json(br#""x""#.to_vec()))
+    );
+    assert_eq!(
+        convert(
+            &Type::UUID,
+            &Value::from("67e55044-10b1-426f-9247-bb680e5fe0c8")
+        ),
+        Ok(PgValue::Uuid([
+            0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8
+        ]))
+    );
+    assert!(convert(&Type::BYTEA, &Value::from(1)).is_err());
+}
+
+#[test]
+fn convert_timestamps() {
+    let expected = Utc.timestamp_opt(1_700_000_000, 500_000_000).unwrap();
+    assert_eq!(
+        convert(&Type::TIMESTAMPTZ, &Value::from("2023-11-14T22:13:20.5Z")),
+        Ok(PgValue::TimestampTz(expected))
+    );
+    assert_eq!(
+        convert(&Type::TIMESTAMP, &Value::from(expected)),
+        Ok(PgValue::Timestamp(expected.naive_utc()))
+    );
+    assert_eq!(
+        convert(&Type::DATE, &Value::from("2023-11-14")),
+        Ok(PgValue::Date(expected.date_naive()))
+    );
+}
+
+#[test]
+fn convert_arrays() {
+    assert_eq!(
+        convert(
+            &Type::INT8_ARRAY,
+            &Value::Array(vec![Value::from(1), Value::Null])
+        ),
+        Ok(PgValue::Array(vec![PgValue::Int8(1), PgValue::Null]))
+    );
+    assert!(convert(&Type::INT8_ARRAY, &Value::from(1)).is_err());
+}
+
+#[test]
+fn quotes_tables() {
+    assert_eq!(quote_table("logs"), r#""logs""#);
+    assert_eq!(quote_table("public.logs"), r#""public"."logs""#);
+    assert_eq!(quote_table(r#"my"logs"#), r#""my""logs""#);
+}
+
+fn schema() -> TableSchema {
+    TableSchema::new_for_tests(
+        "public.logs",
+        &[
+            ("id", Type::INT8, true, None),
+            ("message", Type::TEXT, false, None),
+            ("tags", Type::TEXT_ARRAY, false, None),
+        ],
+        None,
+    )
+}
+
+fn log(fields: &[(&str, Value)]) -> LogEvent {
+    let mut log = LogEvent::default();
+    for (field, value) in fields {
+        log.insert(*field, value.clone());
+    }
+    log
+}
+
+#[test]
+fn writes_text_rows() {
+    let schema = schema();
+    let mut buffer = BytesMut::new();
+    let row = log(&[
+        ("id", 1.into()),
+        ("tags", Value::Array(vec!["a".into(), "b".into()])),
+    ]);
+    schema
+        .write_row(CopyFormat::Text, &row, &mut buffer)
+        .unwrap();
+    assert_eq!(&buffer[..], b"1\t\\N\t{\"a\",\"b\"}\n");
+}
+
+#[test]
+fn writes_binary_rows() {
+    let schema = schema();
+    let mut buffer = BytesMut::new();
+    let row = log(&[("id", 1.into())]);
+    schema
+        .write_row(CopyFormat::Binary, &row, &mut buffer)
+        .unwrap();
+    assert_eq!(
+        &buffer[..],
+        &[
+            0, 3, // field count
+            0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 1, // id
+            0xff, 0xff, 0xff, 0xff, // message
+            0xff, 0xff, 0xff, 0xff, // tags
+        ]
+    );
+}
+
+#[test]
+fn rejects_rows_leaving_buffer_unchanged() {
+    let schema = schema();
+    let mut buffer = BytesMut::from(&b"existing"[..]);
+
+    let missing = log(&[("message", "a".into())]);
+    assert_eq!(
+        schema.write_row(CopyFormat::Binary, &missing, &mut buffer),
+        Err(ConversionError::MissingValue {
+            column: "id".into()
+        })
+    );
+
+    let invalid = log(&[("id", "one".into())]);
+    assert!(matches!(
+        schema.write_row(CopyFormat::Binary, &invalid, &mut buffer),
+        Err(ConversionError::Field { .. })
+    ));
+
+    let unrelated =
+        TableSchema::new_for_tests("logs", &[("message", Type::TEXT, false, None)], None);
+    assert_eq!(
+        unrelated.write_row(CopyFormat::Text, &log(&[("other", 1.into())]), &mut buffer),
+        Err(ConversionError::NoMatchingField)
+    );
+
+    assert_eq!(&buffer[..], b"existing");
+}
+
+#[test]
+fn copies_directly_without_defaults() {
+    let statements = schema().statements();
+    assert_eq!(statements.create_staging, None);
+    assert_eq!(
+        statements.copy,
+        r#"COPY "public"."logs" ("id", "message", "tags") FROM STDIN (FORMAT binary)"#
+    );
+    assert_eq!(statements.insert, None);
+}
+
+#[test]
+fn fills_in_defaults_from_staging_table() {
+    let schema = TableSchema::new_for_tests(
+        "logs",
+        &[
+            (
+                "id",
+                Type::INT8,
+                true,
+                Some("nextval('logs_id_seq'::regclass)"),
+            ),
+            ("message", Type::TEXT, false, None),
+        ],
+        None,
+    );
+    let statements = schema.statements();
+    assert_eq!(
+        statements.create_staging.as_deref(),
+        Some(
+            r#"CREATE TEMPORARY TABLE "vector_staging" ON COMMIT DROP AS SELECT "id", "message" FROM "logs" WITH NO DATA"#
+        )
+    );
+    assert_eq!(
+        statements.copy,
+        r#"COPY "vector_staging" ("id", "message") FROM STDIN (FORMAT binary)"#
+    );
+    assert_eq!(
+        statements.insert.as_deref(),
+        Some(
+            r#"INSERT INTO "logs" AS "target" ("id", "message") SELECT COALESCE("id", nextval('logs_id_seq'::regclass)), "message" FROM "vector_staging""#
+        )
+    );
+
+    // A missing value is left to the default, even for `NOT NULL` columns.
+    let mut buffer = BytesMut::new();
+    assert!(schema
+        .write_row(
+            CopyFormat::Text,
+            &log(&[("message", "a".into())]),
+            &mut buffer
+        )
+        .is_ok());
+    assert_eq!(&buffer[..], b"\\N\ta\n");
+}
+
+#[test]
+fn upserts_from_staging_table() {
+    let columns = [
+        ("id", Type::INT8, true, None),
+        ("message", Type::TEXT, false, None),
+        ("updated_at", Type::TIMESTAMPTZ, false, Some("now()")),
+    ];
+    let statements =
+        TableSchema::new_for_tests("logs", &columns, Some(&["id".into()])).statements();
+    assert_eq!(
+        statements.copy,
+        r#"COPY "vector_staging" ("id", "message", "updated_at") FROM STDIN (FORMAT binary)"#
+    );
+    assert_eq!(
+        statements.insert.as_deref(),
+        Some(
+            r#"INSERT INTO "logs" AS "target" ("id", "message", "updated_at") SELECT DISTINCT ON ("id") "id", "message", COALESCE("updated_at", now()) FROM "vector_staging" ORDER BY "id", ctid DESC ON CONFLICT ("id") DO UPDATE SET "message" = COALESCE(EXCLUDED."message", "target"."message"), "updated_at" = EXCLUDED."updated_at""#
+        )
+    );
+
+    let statements =
+        TableSchema::new_for_tests("logs", &columns[..1], Some(&["id".into()])).statements();
+    assert_eq!(
+        statements.insert.as_deref(),
+        Some(
+            r#"INSERT INTO "logs" AS "target" ("id") SELECT DISTINCT ON ("id") "id" FROM "vector_staging" ORDER BY "id", ctid DESC ON CONFLICT ("id") DO NOTHING"#
+        )
+    );
+}
+
+#[test]
+fn retries_transient_errors() {
+    assert!(is_retriable_code("08006"));
+    assert!(is_retriable_code("40001"));
+    assert!(is_retriable_code("57P01"));
+    assert!(!is_retriable_code("23505"));
+    assert!(!is_retriable_code("42P01"));
+}
@@ -0,0 +1,327 @@
+//! Conversion of event fields to the types of PostgreSQL columns.
+
+use std::{borrow::Cow, error::Error, fmt};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use snafu::Snafu;
+use tokio_postgres::types::{to_sql_checked, IsNull, Kind, ToSql, Type};
+use vrl::value::Value;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub(super) enum ValueError {
+    #[snafu(display("cannot convert a value of type {} to {}", kind, type_name))]
+    Mismatch {
+        kind: &'static str,
+        type_name: String,
+    },
+    #[snafu(display("value {} is out of range for {}", value, type_name))]
+    OutOfRange { value: String, type_name: String },
+    #[snafu(display("cannot parse {:?} as {}", value, type_name))]
+    Unparsable { value: String, type_name: String },
+}
+
+/// A value converted to the type of its column, for the binary `COPY` format.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum PgValue {
+    Null,
+    Bool(bool),
+    Int2(i16),
+    Int4(i32),
+    Int8(i64),
+    Float4(f32),
+    Float8(f64),
+    Text(String),
+    Bytea(Vec<u8>),
+    Json(Vec<u8>),
+    Uuid([u8; 16]),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<Utc>),
+    Date(NaiveDate),
+    Array(Vec<PgValue>),
+}
+
+/// Whether values can be converted to this type for the binary `COPY` format.
+pub(super) fn is_supported(ty: &Type) -> bool {
+    match ty.kind() {
+        Kind::Array(member) => !matches!(member.kind(), Kind::Array(_)) && is_supported(member),
+        _ => matches!(
+            *ty,
+            Type::BOOL
+                | Type::INT2
+                | Type::INT4
+                | Type::INT8
+                | Type::FLOAT4
+                | Type::FLOAT8
+                | Type::TEXT
+                | Type::VARCHAR
+                | Type::BPCHAR
+                | Type::NAME
+                | Type::BYTEA
+                | Type::JSON
+                | Type::JSONB
+                | Type::UUID
+                | Type::TIMESTAMP
+                | Type::TIMESTAMPTZ
+                | Type::DATE
+        ),
+    }
+}
+
+/// Converts a field to a column type accepted by [`is_supported`].
+pub(super) fn convert(ty: &Type, value: &Value) -> Result<PgValue, ValueError> {
+    if let Kind::Array(member) = ty.kind() {
+        return match value {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::Null => Ok(PgValue::Null),
+                    item => convert(member, item),
+                })
+                .collect::<Result<_, _>>()
+                .map(PgValue::Array),
+            _ => Err(mismatch(ty, value)),
+        };
+    }
+
+    match *ty {
+        Type::BOOL => match value {
+            Value::Boolean(b) => Ok(PgValue::Bool(*b)),
+            Value::Bytes(bytes) => match bytes.as_ref() {
+                b"true" | b"t" | b"1" => Ok(PgValue::Bool(true)),
+                b"false" | b"f" | b"0" => Ok(PgValue::Bool(false)),
+                _ => Err(unparsable(ty, value)),
+            },
+            _ => Err(mismatch(ty, value)),
+        },
+        Type::INT2 => {
+            let integer = integer(ty, value)?;
+            i16::try_from(integer)
+                .map(PgValue::Int2)
+                .map_err(|_| out_of_range(ty, integer))
+        }
+        Type::INT4 => {
+            let integer = integer(ty, value)?;
+            i32::try_from(integer)
+                .map(PgValue::Int4)
+                .map_err(|_| out_of_range(ty, integer))
+        }
+        Type::INT8 => integer(ty, value).map(PgValue::Int8),
+        Type::FLOAT4 => float(ty, value).map(|float| PgValue::Float4(float as f32)),
+        Type::FLOAT8 => float(ty, value).map(PgValue::Float8),
+        Type::BYTEA => match value {
+            Value::Bytes(bytes) => Ok(PgValue::Bytea(bytes.to_vec())),
+            _ => Err(mismatch(ty, value)),
+        },
+        Type::JSON | Type::JSONB => serde_json::to_vec(value)
+            .map(PgValue::Json)
+            .map_err(|_| mismatch(ty, value)),
+        Type::UUID => value
+            .as_bytes()
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .and_then(|uuid| uuid::Uuid::parse_str(uuid).ok())
+            .map(|uuid| PgValue::Uuid(*uuid.as_bytes()))
+            .ok_or_else(|| unparsable(ty, value)),
+        Type::TIMESTAMP => timestamp(ty, value).map(|ts| PgValue::Timestamp(ts.naive_utc())),
+        Type::TIMESTAMPTZ => timestamp(ty, value).map(PgValue::TimestampTz),
+        Type::DATE => {
+            let date = value
+                .as_bytes()
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+            match date {
+                Some(date) => Ok(PgValue::Date(date)),
+                None => timestamp(ty, value).map(|ts| PgValue::Date(ts.date_naive())),
+            }
+        }
+        // The remaining supported types are textual.
+        _ => Ok(PgValue::Text(
+            String::from_utf8_lossy(&text(value)).into_owned(),
+        )),
+    }
+}
+
+/// Writes a field in the text `COPY` format, which leaves its conversion to PostgreSQL.
+pub(super) fn write_text(ty: &Type, value: Option<&Value>, out: &mut BytesMut) {
+    let text = match (ty.kind(), value) {
+        (_, None | Some(Value::Null)) => {
+            out.put_slice(b"\\N");
+            return;
+        }
+        (Kind::Array(_), Some(Value::Array(items))) => Cow::Owned(array_literal(items)),
+        (_, Some(Value::Bytes(bytes))) if *ty == Type::BYTEA => {
+            Cow::Owned(format!("\\x{}", hex::encode(bytes)).into_bytes())
+        }
+        (_, Some(value)) => text(value),
+    };
+
+    for byte in text.iter() {
+        match byte {
+            b'\\' => out.put_slice(b"\\\\"),
+            b'\n' => out.put_slice(b"\\n"),
+            b'\r' => out.put_slice(b"\\r"),
+            b'\t' => out.put_slice(b"\\t"),
+            byte => out.put_u8(*byte),
+        }
+    }
+}
+
+/// Formats an array as an array literal, such as `{"a",NULL}`.
+fn array_literal(items: &[Value]) -> Vec<u8> {
+    let mut literal = vec![b'{'];
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            literal.push(b',');
+        }
+        match item {
+            Value::Null => literal.extend_from_slice(b"NULL"),
+            Value::Array(items) => literal.extend(array_literal(items)),
+            item => {
+                literal.push(b'"');
+                for byte in text(item).iter() {
+                    if matches!(byte, b'"' | b'\\') {
+                        literal.push(b'\\');
+                    }
+                    literal.push(*byte);
+                }
+                literal.push(b'"');
+            }
+        }
+    }
+    literal.push(b'}');
+    literal
+}
+
+/// The textual representation of a field: strings as-is, timestamps as RFC 3339, and objects
+/// and arrays as JSON.
+fn text(value: &Value) -> Cow<'_, [u8]> {
+    match value {
+        Value::Bytes(bytes) => Cow::Borrowed(bytes.as_ref()),
+        Value::Timestamp(timestamp) => Cow::Owned(
+            timestamp
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into_bytes(),
+        ),
+        Value::Object(_) | Value::Array(_) => {
+            Cow::Owned(serde_json::to_vec(value).unwrap_or_default())
+        }
+        value => Cow::Owned(value.to_string_lossy().into_owned().into_bytes()),
+    }
+}
+
+fn integer(ty: &Type, value: &Value) -> Result<i64, ValueError> {
+    match value {
+        Value::Integer(integer) => Ok(*integer),
+        Value::Boolean(b) => Ok(i64::from(*b)),
+        Value::Float(float)
+            if float.fract() == 0.0 && (i64::MIN as f64..=i64::MAX as f64).contains(&**float) =>
+        {
+            Ok(float.into_inner() as i64)
+        }
+        Value::Float(float) => Err(out_of_range(ty, float)),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|integer| integer.trim().parse().ok())
+            .ok_or_else(|| unparsable(ty, value)),
+        _ => Err(mismatch(ty, value)),
+    }
+}
+
+fn float(ty: &Type, value: &Value) -> Result<f64, ValueError> {
+    match value {
+        Value::Float(float) => Ok(float.into_inner()),
+        Value::Integer(integer) => Ok(*integer as f64),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|float| float.trim().parse().ok())
+            .ok_or_else(|| unparsable(ty, value)),
+        _ => Err(mismatch(ty, value)),
+    }
+}
+
+/// Timestamps are taken as-is, numbers as seconds since the Unix epoch and strings as RFC 3339.
+fn timestamp(ty: &Type, value: &Value) -> Result<DateTime<Utc>, ValueError> {
+    match value {
+        Value::Timestamp(timestamp) => Ok(*timestamp),
+        Value::Integer(seconds) => Utc
+            .timestamp_opt(*seconds, 0)
+            .single()
+            .ok_or_else(|| out_of_range(ty, seconds)),
+        Value::Float(seconds) => {
+            let nanos = (seconds.fract() * 1e9) as u32;
+            Utc.timestamp_opt(seconds.floor() as i64, nanos)
+                .single()
+                .ok_or_else(|| out_of_range(ty, seconds))
+        }
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .ok_or_else(|| unparsable(ty, value)),
+        _ => Err(mismatch(ty, value)),
+    }
+}
+
+fn mismatch(ty: &Type, value: &Value) -> ValueError {
+    ValueError::Mismatch {
+        kind: value.kind_str(),
+        type_name: ty.name().to_string(),
+    }
+}
+
+fn out_of_range(ty: &Type, value: impl fmt::Display) -> ValueError {
+    ValueError::OutOfRange {
+        value: value.to_string(),
+        type_name: ty.name().to_string(),
+    }
+}
+
+fn unparsable(ty: &Type, value: &Value) -> ValueError {
+    ValueError::Unparsable {
+        value: value.to_string_lossy().into_owned(),
+        type_name: ty.name().to_string(),
+    }
+}
+
+impl ToSql for PgValue {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Self::Null => Ok(IsNull::Yes),
+            Self::Bool(value) => value.to_sql(ty, out),
+            Self::Int2(value) => value.to_sql(ty, out),
+            Self::Int4(value) => value.to_sql(ty, out),
+            Self::Int8(value) => value.to_sql(ty, out),
+            Self::Float4(value) => value.to_sql(ty, out),
+            Self::Float8(value) => value.to_sql(ty, out),
+            Self::Text(value) => value.to_sql(ty, out),
+            Self::Bytea(value) => value.as_slice().to_sql(ty, out),
+            Self::Json(value) => {
+                // `jsonb` is prefixed with the version of its binary format.
+                if *ty == Type::JSONB {
+                    out.put_u8(1);
+                }
+                out.put_slice(value);
+                Ok(IsNull::No)
+            }
+            Self::Uuid(value) => {
+                out.put_slice(value);
+                Ok(IsNull::No)
+            }
+            Self::Timestamp(value) => value.to_sql(ty, out),
+            Self::TimestampTz(value) => value.to_sql(ty, out),
+            Self::Date(value) => value.to_sql(ty, out),
+            Self::Array(values) => values.to_sql(ty, out),
+        }
+    }
+
+    // Values are converted from the type of their column, so they always match it.
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
@@ -1,21 +1,33 @@
-use std::{collections::BTreeMap, fmt::Write as _};
+use std::{borrow::Cow, collections::BTreeMap, fmt::Write as _};
 
 use chrono::Utc;
 use indexmap::map::IndexMap;
-use vector_lib::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile};
-use vector_lib::prometheus::parser::{proto, METRIC_NAME_LABEL};
+use prost::Message;
+use vector_lib::event::metric::{samples_to_buckets, Bucket, MetricSketch, MetricTags, Quantile};
+use vector_lib::lookup::path;
+use vector_lib::prometheus::parser::{exposition, proto, Exemplar, METRIC_NAME_LABEL};
 
+use super::native_histogram::{encode_spans, NativeHistogram, NativeHistogramConfig};
 use crate::{
     event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
     sinks::util::{encode_namespace, statistic::DistributionStatistic},
 };
 
+/// The data tracked for a series besides its metric.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct SeriesInfo<'a> {
+    /// When the series was created, in milliseconds.
+    pub(super) created: Option<i64>,
+    /// The latest exemplar of the series.
+    pub(super) exemplar: Option<&'a Exemplar>,
+}
+
 pub(super) trait MetricCollector {
     type Output;
 
     fn new() -> Self;
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue);
+    fn emit_metadata(&mut self, fullname: &str, metric: &Metric);
 
     fn emit_value(
         &mut self,
@@ -27,6 +39,13 @@ pub(super) trait MetricCollector {
         extra: Option<(&str, String)>,
     );
 
+    /// Emits the creation time of a counter, histogram or summary, for the formats that expose
+    /// it.
+    fn emit_created(&mut self, _name: &str, _created_millis: i64, _tags: Option<&MetricTags>) {}
+
+    /// Attaches an exemplar to the last emitted value, for the formats that expose them.
+    fn emit_exemplar(&mut self, _name: &str, _exemplar: &Exemplar) {}
+
     fn finish(self) -> Self::Output;
 
     fn encode_metric(
@@ -35,6 +54,23 @@ pub(super) trait MetricCollector {
         buckets: &[f64],
         quantiles: &[f64],
         metric: &Metric,
+    ) {
+        self.encode_series(
+            default_namespace,
+            buckets,
+            quantiles,
+            metric,
+            SeriesInfo::default(),
+        );
+    }
+
+    fn encode_series(
+        &mut self,
+        default_namespace: Option<&str>,
+        buckets: &[f64],
+        quantiles: &[f64],
+        metric: &Metric,
+        info: SeriesInfo<'_>,
     ) {
         let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
         let name = &name;
@@ -42,11 +78,14 @@ pub(super) trait MetricCollector {
 
         if metric.kind() == MetricKind::Absolute {
             let tags = metric.tags();
-            self.emit_metadata(metric.name(), name, metric.value());
+            self.emit_metadata(name, metric);
 
             match metric.value() {
                 MetricValue::Counter { value } => {
                     self.emit_value(timestamp, name, "", *value, tags, None);
+                    if let Some(exemplar) = info.exemplar {
+                        self.emit_exemplar(name, exemplar);
+                    }
                 }
                 MetricValue::Gauge { value } => {
                     self.emit_value(timestamp, name, "", *value, tags, None);
@@ -60,6 +99,7 @@ pub(super) trait MetricCollector {
                 } => {
                     // convert distributions into aggregated histograms
                     let (buckets, count, sum) = samples_to_buckets(samples, buckets);
+                    let mut exemplar = info.exemplar;
                     let mut bucket_count = 0.0;
                     for bucket in buckets {
                         bucket_count += bucket.count as f64;
@@ -71,6 +111,7 @@ pub(super) trait MetricCollector {
                             tags,
                             Some(("le", bucket.upper_limit.to_string())),
                         );
+                        self.emit_bucket_exemplar(name, &mut exemplar, bucket.upper_limit);
                     }
                     self.emit_value(
                         timestamp,
@@ -80,6 +121,7 @@ pub(super) trait MetricCollector {
                         tags,
                         Some(("le", "+Inf".to_string())),
                     );
+                    self.emit_bucket_exemplar(name, &mut exemplar, f64::INFINITY);
                     self.emit_value(timestamp, name, "_sum", sum, tags, None);
                     self.emit_value(timestamp, name, "_count", count as f64, tags, None);
                 }
@@ -121,6 +163,7 @@ pub(super) trait MetricCollector {
                     count,
                     sum,
                 } => {
+                    let mut exemplar = info.exemplar;
                     let mut bucket_count = 0.0;
                     for bucket in buckets {
                         // Aggregated histograms are cumulative in Prometheus.  This means that the
@@ -149,6 +192,7 @@ pub(super) trait MetricCollector {
                             tags,
                             Some(("le", bucket.upper_limit.to_string())),
                         );
+                        self.emit_bucket_exemplar(name, &mut exemplar, bucket.upper_limit);
                     }
                     self.emit_value(
                         timestamp,
@@ -158,6 +202,7 @@ pub(super) trait MetricCollector {
                         tags,
                         Some(("le", "+Inf".to_string())),
                     );
+                    self.emit_bucket_exemplar(name, &mut exemplar, f64::INFINITY);
                     self.emit_value(timestamp, name, "_sum", *sum, tags, None);
                     self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
                 }
@@ -214,6 +259,27 @@ pub(super) trait MetricCollector {
                     }
                 },
             }
+
+            let has_created = !matches!(
+                metric.value(),
+                MetricValue::Gauge { .. } | MetricValue::Set { .. }
+            );
+            if let Some(created) = info.created.filter(|_| has_created) {
+                self.emit_created(name, created, tags);
+            }
+        }
+    }
+
+    /// Attaches the exemplar to the first bucket that contains its value.
+    fn emit_bucket_exemplar(
+        &mut self,
+        name: &str,
+        exemplar: &mut Option<&Exemplar>,
+        upper_limit: f64,
+    ) {
+        if let Some(value) = exemplar.filter(|exemplar| exemplar.value <= upper_limit) {
+            self.emit_exemplar(name, value);
+            *exemplar = None;
         }
     }
 }
@@ -231,9 +297,9 @@ impl MetricCollector for StringCollector {
         Self { processed }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
+    fn emit_metadata(&mut self, fullname: &str, metric: &Metric) {
         if !self.processed.contains_key(fullname) {
-            let header = Self::encode_header(name, fullname, value);
+            let header = Self::encode_header(&metric_help(metric), fullname, metric.value());
             self.processed.insert(fullname.into(), header);
         }
     }
@@ -288,11 +354,11 @@ impl StringCollector {
         .ok();
     }
 
-    fn encode_header(name: &str, fullname: &str, value: &MetricValue) -> String {
+    fn encode_header(help: &str, fullname: &str, value: &MetricValue) -> String {
         let r#type = prometheus_metric_type(value).as_str();
         format!(
             "# HELP {} {}\n# TYPE {} {}\n",
-            fullname, name, fullname, r#type
+            fullname, help, fullname, r#type
         )
     }
 
@@ -314,15 +380,210 @@ impl StringCollector {
     }
 }
 
+/// Collects metrics in the OpenMetrics text format.
+pub(super) struct OpenMetricsCollector {
+    families: BTreeMap<String, Family>,
+}
+
+struct Family {
+    /// The family name, which leaves out the `_total` suffix of counters.
+    name: String,
+    r#type: proto::MetricType,
+    text: String,
+}
+
+impl MetricCollector for OpenMetricsCollector {
+    type Output = String;
+
+    fn new() -> Self {
+        let families = BTreeMap::new();
+        Self { families }
+    }
+
+    fn emit_metadata(&mut self, fullname: &str, metric: &Metric) {
+        if !self.families.contains_key(fullname) {
+            let r#type = prometheus_metric_type(metric.value());
+            let family = match r#type {
+                proto::MetricType::Counter => fullname.strip_suffix("_total").unwrap_or(fullname),
+                _ => fullname,
+            };
+            let text = format!(
+                "# HELP {} {}\n# TYPE {} {}\n",
+                family,
+                metric_help(metric),
+                family,
+                r#type.as_str()
+            );
+            let family = Family {
+                name: family.into(),
+                r#type,
+                text,
+            };
+            self.families.insert(fullname.into(), family);
+        }
+    }
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+    ) {
+        let family = self
+            .families
+            .get_mut(name)
+            .expect("metric metadata not encoded");
+
+        let result = &mut family.text;
+        match (family.r#type, suffix) {
+            (proto::MetricType::Counter, "") => {
+                result.push_str(&family.name);
+                result.push_str("_total");
+            }
+            // Summaries only have quantiles, a sum and a count in OpenMetrics.
+            (proto::MetricType::Summary, "" | "_sum" | "_count") => {
+                result.push_str(name);
+                result.push_str(suffix);
+            }
+            (proto::MetricType::Summary, _) => return,
+            _ => {
+                result.push_str(name);
+                result.push_str(suffix);
+            }
+        }
+        StringCollector::encode_tags(result, tags, extra);
+        _ = match timestamp_millis {
+            None => writeln!(result, " {}", value),
+            Some(timestamp) => writeln!(result, " {} {}", value, millis_to_seconds(timestamp)),
+        };
+    }
+
+    fn emit_created(&mut self, name: &str, created_millis: i64, tags: Option<&MetricTags>) {
+        let family = self
+            .families
+            .get_mut(name)
+            .expect("metric metadata not encoded");
+
+        family.text.push_str(&family.name);
+        family.text.push_str("_created");
+        StringCollector::encode_tags(&mut family.text, tags, None);
+        _ = writeln!(family.text, " {}", millis_to_seconds(created_millis));
+    }
+
+    fn emit_exemplar(&mut self, name: &str, exemplar: &Exemplar) {
+        let result = &mut self
+            .families
+            .get_mut(name)
+            .expect("metric metadata not encoded")
+            .text;
+
+        result.pop();
+        let labels = exemplar
+            .labels
+            .iter()
+            .map(|(key, value)| StringCollector::format_tag(key, value))
+            .collect::<Vec<_>>();
+        _ = write!(result, " # {{{}}} {}", labels.join(","), exemplar.value);
+        if let Some(timestamp) = exemplar.timestamp {
+            _ = write!(result, " {}", millis_to_seconds(timestamp));
+        }
+        result.push('\n');
+    }
+
+    fn finish(self) -> String {
+        let mut result = self
+            .families
+            .into_values()
+            .map(|family| family.text)
+            .collect::<String>();
+        result.push_str("# EOF\n");
+        result
+    }
+}
+
+fn millis_to_seconds(millis: i64) -> f64 {
+    millis as f64 / 1000.0
+}
+
 type Labels = Vec<proto::Label>;
 
 pub(super) struct TimeSeries {
-    buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    buffer: IndexMap<Labels, Series>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
 }
 
+#[derive(Default)]
+struct Series {
+    samples: Vec<proto::Sample>,
+    histograms: Vec<proto::Histogram>,
+}
+
 impl TimeSeries {
+    /// Encodes a native histogram, which is sent as a single series rather than as the series of
+    /// its buckets, sum and count.
+    pub(super) fn encode_native_histogram(
+        &mut self,
+        default_namespace: Option<&str>,
+        metric: &Metric,
+        histogram: &NativeHistogram,
+    ) {
+        if metric.kind() != MetricKind::Absolute {
+            return;
+        }
+
+        let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+        self.insert_metadata(&name, proto::MetricType::Histogram, metric);
+
+        let spans = |spans: Vec<(i32, u32)>| {
+            spans
+                .into_iter()
+                .map(|(offset, length)| proto::BucketSpan { offset, length })
+                .collect()
+        };
+        let (negative_spans, negative_deltas) = encode_spans(&histogram.negative);
+        let (positive_spans, positive_deltas) = encode_spans(&histogram.positive);
+        let histogram = proto::Histogram {
+            count: Some(proto::histogram::Count::CountInt(histogram.count)),
+            sum: histogram.sum,
+            schema: histogram.schema,
+            zero_threshold: histogram.zero_threshold,
+            zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(
+                histogram.zero_count,
+            )),
+            negative_spans: spans(negative_spans),
+            negative_deltas,
+            positive_spans: spans(positive_spans),
+            positive_deltas,
+            timestamp: metric
+                .timestamp()
+                .map(|t| t.timestamp_millis())
+                .unwrap_or_else(|| self.default_timestamp()),
+            ..Default::default()
+        };
+
+        self.buffer
+            .entry(Self::make_labels(metric.tags(), &name, "", None))
+            .or_default()
+            .histograms
+            .push(histogram);
+    }
+
+    fn insert_metadata(&mut self, fullname: &str, r#type: proto::MetricType, metric: &Metric) {
+        if !self.metadata.contains_key(fullname) {
+            let metadata = proto::MetricMetadata {
+                r#type: r#type as i32,
+                metric_family_name: fullname.into(),
+                help: metric_help(metric).into_owned(),
+                unit: metric_unit(metric).map(Cow::into_owned).unwrap_or_default(),
+            };
+            self.metadata.insert(fullname.into(), metadata);
+        }
+    }
+
     fn make_labels(
         tags: Option<&MetricTags>,
         name: &str,
@@ -367,17 +628,8 @@ impl MetricCollector for TimeSeries {
         }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
-        if !self.metadata.contains_key(name) {
-            let r#type = prometheus_metric_type(value);
-            let metadata = proto::MetricMetadata {
-                r#type: r#type as i32,
-                metric_family_name: fullname.into(),
-                help: name.into(),
-                unit: String::new(),
-            };
-            self.metadata.insert(name.into(), metadata);
-        }
+    fn emit_metadata(&mut self, fullname: &str, metric: &Metric) {
+        self.insert_metadata(fullname, prometheus_metric_type(metric.value()), metric);
     }
 
     fn emit_value(
@@ -393,6 +645,7 @@ impl MetricCollector for TimeSeries {
         self.buffer
             .entry(Self::make_labels(tags, name, suffix, extra))
             .or_default()
+            .samples
             .push(proto::Sample { value, timestamp });
     }
 
@@ -400,7 +653,12 @@ impl MetricCollector for TimeSeries {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, series)| proto::TimeSeries {
+                labels,
+                samples: series.samples,
+                histograms: series.histograms,
+                exemplars: Vec::new(),
+            })
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -414,6 +672,328 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// Collects metrics as Prometheus protobuf metric families.
+///
+/// This is not a [`MetricCollector`], as each metric is encoded as a single message rather than
+/// as separate values.
+pub(super) struct MetricFamilies {
+    families: BTreeMap<String, exposition::MetricFamily>,
+}
+
+impl MetricFamilies {
+    pub(super) const fn new() -> Self {
+        Self {
+            families: BTreeMap::new(),
+        }
+    }
+
+    /// Encodes a metric, converting sketches to native histograms when they are configured.
+    pub(super) fn encode_metric(
+        &mut self,
+        default_namespace: Option<&str>,
+        buckets: &[f64],
+        quantiles: &[f64],
+        native_histograms: Option<&NativeHistogramConfig>,
+        metric: &Metric,
+        info: SeriesInfo<'_>,
+    ) {
+        if metric.kind() != MetricKind::Absolute {
+            return;
+        }
+
+        let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+        let created_timestamp = info.created.map(millis_to_timestamp);
+        let mut message = exposition::Metric {
+            label: label_pairs(metric.tags().into_iter().flat_map(MetricTags::iter_single)),
+            timestamp_ms: metric.timestamp().map(|t| t.timestamp_millis()),
+            ..Default::default()
+        };
+
+        let r#type = match metric.value() {
+            MetricValue::Counter { value } => {
+                message.counter = Some(exposition::Counter {
+                    value: Some(*value),
+                    exemplar: info.exemplar.map(encode_exemplar),
+                    created_timestamp,
+                });
+                exposition::MetricType::Counter
+            }
+            MetricValue::Gauge { value } => {
+                message.gauge = Some(exposition::Gauge {
+                    value: Some(*value),
+                });
+                exposition::MetricType::Gauge
+            }
+            MetricValue::Set { values } => {
+                message.gauge = Some(exposition::Gauge {
+                    value: Some(values.len() as f64),
+                });
+                exposition::MetricType::Gauge
+            }
+            MetricValue::Distribution {
+                samples,
+                statistic: StatisticKind::Histogram,
+            } => {
+                let (buckets, count, sum) = samples_to_buckets(samples, buckets);
+                message.histogram = Some(encode_histogram(
+                    &buckets,
+                    count,
+                    sum,
+                    created_timestamp,
+                    info.exemplar,
+                ));
+                exposition::MetricType::Histogram
+            }
+            MetricValue::Distribution {
+                samples,
+                statistic: StatisticKind::Summary,
+            } => {
+                let summary = match DistributionStatistic::from_samples(samples, quantiles) {
+                    Some(statistic) => exposition::Summary {
+                        sample_count: Some(statistic.count),
+                        sample_sum: Some(statistic.sum),
+                        quantile: statistic
+                            .quantiles
+                            .iter()
+                            .map(|(quantile, value)| encode_quantile(*quantile, *value))
+                            .collect(),
+                        created_timestamp,
+                    },
+                    None => exposition::Summary {
+                        sample_count: Some(0),
+                        sample_sum: Some(0.0),
+                        quantile: Vec::new(),
+                        created_timestamp,
+                    },
+                };
+                message.summary = Some(summary);
+                exposition::MetricType::Summary
+            }
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => {
+                message.histogram = Some(encode_histogram(
+                    buckets,
+                    *count,
+                    *sum,
+                    created_timestamp,
+                    info.exemplar,
+                ));
+                exposition::MetricType::Histogram
+            }
+            MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => {
+                message.summary = Some(exposition::Summary {
+                    sample_count: Some(*count),
+                    sample_sum: Some(*sum),
+                    quantile: quantiles
+                        .iter()
+                        .map(|quantile| encode_quantile(quantile.quantile, quantile.value))
+                        .collect(),
+                    created_timestamp,
+                });
+                exposition::MetricType::Summary
+            }
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(ddsketch),
+            } => match native_histograms {
+                Some(config) => {
+                    let histogram = NativeHistogram::from_sketch(ddsketch, config);
+                    message.histogram = Some(encode_native_histogram(
+                        &histogram,
+                        created_timestamp,
+                        info.exemplar,
+                    ));
+                    exposition::MetricType::Histogram
+                }
+                None => {
+                    message.summary = Some(exposition::Summary {
+                        sample_count: Some(u64::from(ddsketch.count())),
+                        sample_sum: Some(ddsketch.sum().unwrap_or(0.0)),
+                        quantile: quantiles
+                            .iter()
+                            .map(|q| encode_quantile(*q, ddsketch.quantile(*q).unwrap_or(0.0)))
+                            .collect(),
+                        created_timestamp,
+                    });
+                    exposition::MetricType::Summary
+                }
+            },
+        };
+
+        self.families
+            .entry(name.clone())
+            .or_insert_with(|| exposition::MetricFamily {
+                name: Some(name),
+                help: Some(metric_help(metric).into_owned()),
+                r#type: Some(r#type as i32),
+                metric: Vec::new(),
+            })
+            .metric
+            .push(message);
+    }
+
+    /// Encodes the metric families as length-delimited messages.
+    pub(super) fn finish(self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for family in self.families.into_values() {
+            family
+                .encode_length_delimited(&mut buffer)
+                .expect("buffer has no capacity limit");
+        }
+        buffer
+    }
+}
+
+fn label_pairs<'a>(pairs: impl Iterator<Item = (&'a str, &'a str)>) -> Vec<exposition::LabelPair> {
+    let mut labels = pairs
+        .map(|(name, value)| exposition::LabelPair {
+            name: Some(name.into()),
+            value: Some(value.into()),
+        })
+        .collect::<Vec<_>>();
+    labels.sort_by(|a, b| a.name.cmp(&b.name));
+    labels
+}
+
+fn encode_exemplar(exemplar: &Exemplar) -> exposition::Exemplar {
+    exposition::Exemplar {
+        label: label_pairs(
+            exemplar
+                .labels
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        ),
+        value: Some(exemplar.value),
+        timestamp: exemplar.timestamp.map(millis_to_timestamp),
+    }
+}
+
+const fn encode_quantile(quantile: f64, value: f64) -> exposition::Quantile {
+    exposition::Quantile {
+        quantile: Some(quantile),
+        value: Some(value),
+    }
+}
+
+fn encode_histogram(
+    buckets: &[Bucket],
+    count: u64,
+    sum: f64,
+    created_timestamp: Option<prost_types::Timestamp>,
+    mut exemplar: Option<&Exemplar>,
+) -> exposition::Histogram {
+    let mut cumulative_count = 0;
+    let mut encoded = Vec::with_capacity(buckets.len() + 1);
+    let upper_limits = buckets
+        .iter()
+        // As in the text formats, the +Inf bucket is always computed from the total count.
+        .filter(|bucket| !bucket.upper_limit.is_infinite())
+        .map(|bucket| {
+            cumulative_count += bucket.count;
+            (bucket.upper_limit, cumulative_count)
+        })
+        .chain(std::iter::once((f64::INFINITY, count)));
+    for (upper_limit, cumulative_count) in upper_limits {
+        let bucket_exemplar = exemplar.filter(|exemplar| exemplar.value <= upper_limit);
+        if bucket_exemplar.is_some() {
+            exemplar = None;
+        }
+        encoded.push(exposition::Bucket {
+            cumulative_count: Some(cumulative_count),
+            upper_bound: Some(upper_limit),
+            exemplar: bucket_exemplar.map(encode_exemplar),
+            ..Default::default()
+        });
+    }
+
+    exposition::Histogram {
+        sample_count: Some(count),
+        sample_sum: Some(sum),
+        bucket: encoded,
+        created_timestamp,
+        ..Default::default()
+    }
+}
+
+fn encode_native_histogram(
+    histogram: &NativeHistogram,
+    created_timestamp: Option<prost_types::Timestamp>,
+    exemplar: Option<&Exemplar>,
+) -> exposition::Histogram {
+    let encode = |buckets: &BTreeMap<i32, u64>| {
+        let (spans, deltas) = encode_spans(buckets);
+        let spans = spans
+            .into_iter()
+            .map(|(offset, length)| exposition::BucketSpan {
+                offset: Some(offset),
+                length: Some(length),
+            })
+            .collect::<Vec<_>>();
+        (spans, deltas)
+    };
+    let (negative_span, negative_delta) = encode(&histogram.negative);
+    let (mut positive_span, positive_delta) = encode(&histogram.positive);
+    if positive_span.is_empty() && negative_span.is_empty() {
+        // An empty span tells native histograms without observations apart from classic ones.
+        positive_span.push(exposition::BucketSpan {
+            offset: Some(0),
+            length: Some(0),
+        });
+    }
+
+    exposition::Histogram {
+        sample_count: Some(histogram.count),
+        sample_sum: Some(histogram.sum),
+        created_timestamp,
+        schema: Some(histogram.schema),
+        zero_threshold: Some(histogram.zero_threshold),
+        zero_count: Some(histogram.zero_count),
+        negative_span,
+        negative_delta,
+        positive_span,
+        positive_delta,
+        // Exemplars of native histograms must have a timestamp.
+        exemplars: exemplar
+            .filter(|exemplar| exemplar.timestamp.is_some())
+            .map(encode_exemplar)
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }
+}
+
+const fn millis_to_timestamp(millis: i64) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: millis.div_euclid(1000),
+        nanos: (millis.rem_euclid(1000) * 1_000_000) as i32,
+    }
+}
+
+/// The help text of a metric, as received by the Prometheus sources, which defaults to its name.
+fn metric_help(metric: &Metric) -> Cow<'_, str> {
+    metric
+        .metadata()
+        .value()
+        .get(path!("prometheus", "help"))
+        .and_then(|help| help.as_str())
+        .unwrap_or(Cow::Borrowed(metric.name()))
+}
+
+/// The unit of a metric, as received by the Prometheus sources.
+fn metric_unit(metric: &Metric) -> Option<Cow<'_, str>> {
+    metric
+        .metadata()
+        .value()
+        .get(path!("prometheus", "unit"))
+        .and_then(|unit| unit.as_str())
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -440,7 +1020,7 @@ mod tests {
     use chrono::{DateTime, TimeZone, Timelike};
     use indoc::indoc;
     use similar_asserts::assert_eq;
-    use vector_lib::metric_tags;
+    use vector_lib::{metric_tags, metrics::AgentDDSketch};
 
     use super::{super::default_summary_quantiles, *};
     use crate::{
@@ -490,6 +1070,7 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            ..Default::default()
                         },
                     )*
                 ],
@@ -968,4 +1549,307 @@ mod tests {
             "#}
         );
     }
+
+    #[test]
+    fn encodes_distribution_summary_openmetrics() {
+        assert_eq!(
+            encode_distribution_summary::<OpenMetricsCollector>(),
+            indoc! {r#"
+                # HELP ns_requests requests
+                # TYPE ns_requests summary
+                ns_requests{code="200",quantile="0.5"} 2 1612325106.789
+                ns_requests{code="200",quantile="0.75"} 2 1612325106.789
+                ns_requests{code="200",quantile="0.9"} 3 1612325106.789
+                ns_requests{code="200",quantile="0.95"} 3 1612325106.789
+                ns_requests{code="200",quantile="0.99"} 3 1612325106.789
+                ns_requests_sum{code="200"} 15 1612325106.789
+                ns_requests_count{code="200"} 8 1612325106.789
+                # EOF
+            "#}
+        );
+    }
+
+    fn exemplar(value: f64) -> Exemplar {
+        Exemplar {
+            labels: [("trace_id".to_owned(), "abc".to_owned())].into(),
+            value,
+            timestamp: Some(1612325106789),
+        }
+    }
+
+    fn encode_series<T: MetricCollector>(metric: &Metric, info: SeriesInfo<'_>) -> T::Output {
+        let mut s = T::new();
+        s.encode_series(Some("vector"), &[], &[], metric, info);
+        s.finish()
+    }
+
+    #[test]
+    fn encodes_counter_openmetrics() {
+        let metric = Metric::new(
+            "hits_total".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 10.0 },
+        )
+        .with_tags(Some(tags()));
+        let exemplar = exemplar(1.0);
+        let info = SeriesInfo {
+            created: Some(1612325100000),
+            exemplar: Some(&exemplar),
+        };
+
+        assert_eq!(
+            encode_series::<OpenMetricsCollector>(&metric, info),
+            indoc! {r#"
+                # HELP vector_hits hits_total
+                # TYPE vector_hits counter
+                vector_hits_total{code="200"} 10 # {trace_id="abc"} 1 1612325106.789
+                vector_hits_created{code="200"} 1612325100
+                # EOF
+            "#}
+        );
+        // The text format has neither creation times nor exemplars.
+        assert_eq!(
+            encode_series::<StringCollector>(&metric, info),
+            indoc! {r#"
+                # HELP vector_hits_total hits_total
+                # TYPE vector_hits_total counter
+                vector_hits_total{code="200"} 10
+            "#}
+        );
+    }
+
+    #[test]
+    fn encodes_histogram_openmetrics() {
+        let mut histogram = VariableHistogram::new(&[1.0, 2.1, 3.0]);
+        histogram.record_many(&[0.4, 2.0, 1.75, 2.6, 2.25, 2.5][..]);
+        let metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: histogram.buckets(),
+                count: histogram.count(),
+                sum: histogram.sum(),
+            },
+        );
+        let exemplar = exemplar(2.0);
+        let info = SeriesInfo {
+            created: Some(1612325100500),
+            exemplar: Some(&exemplar),
+        };
+
+        assert_eq!(
+            encode_series::<OpenMetricsCollector>(&metric, info),
+            indoc! {r#"
+                # HELP vector_requests requests
+                # TYPE vector_requests histogram
+                vector_requests_bucket{le="1"} 1
+                vector_requests_bucket{le="2.1"} 3 # {trace_id="abc"} 2 1612325106.789
+                vector_requests_bucket{le="3"} 6
+                vector_requests_bucket{le="+Inf"} 6
+                vector_requests_sum 11.5
+                vector_requests_count 6
+                vector_requests_created 1612325100.5
+                # EOF
+            "#}
+        );
+    }
+
+    fn decode_families(bytes: &[u8]) -> Vec<exposition::MetricFamily> {
+        let mut bytes = bytes;
+        let mut families = Vec::new();
+        while !bytes.is_empty() {
+            families.push(exposition::MetricFamily::decode_length_delimited(&mut bytes).unwrap());
+        }
+        families
+    }
+
+    #[test]
+    fn encodes_counter_protobuf() {
+        let metric = Metric::new(
+            "hits".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 10.0 },
+        )
+        .with_tags(Some(tags()))
+        .with_timestamp(Some(timestamp()));
+        let exemplar = exemplar(1.0);
+        let mut families = MetricFamilies::new();
+        families.encode_metric(
+            Some("vector"),
+            &[],
+            &[],
+            None,
+            &metric,
+            SeriesInfo {
+                created: Some(1612325100500),
+                exemplar: Some(&exemplar),
+            },
+        );
+
+        assert_eq!(
+            decode_families(&families.finish()),
+            vec![exposition::MetricFamily {
+                name: Some("vector_hits".into()),
+                help: Some("hits".into()),
+                r#type: Some(exposition::MetricType::Counter as i32),
+                metric: vec![exposition::Metric {
+                    label: vec![exposition::LabelPair {
+                        name: Some("code".into()),
+                        value: Some("200".into()),
+                    }],
+                    counter: Some(exposition::Counter {
+                        value: Some(10.0),
+                        exemplar: Some(exposition::Exemplar {
+                            label: vec![exposition::LabelPair {
+                                name: Some("trace_id".into()),
+                                value: Some("abc".into()),
+                            }],
+                            value: Some(1.0),
+                            timestamp: Some(prost_types::Timestamp {
+                                seconds: 1612325106,
+                                nanos: 789_000_000,
+                            }),
+                        }),
+                        created_timestamp: Some(prost_types::Timestamp {
+                            seconds: 1612325100,
+                            nanos: 500_000_000,
+                        }),
+                    }),
+                    timestamp_ms: Some(1612325106789),
+                    ..Default::default()
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn encodes_native_histogram_protobuf() {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[0.0, 1.0, 3.0, 3.0, -3.0, 100.0]);
+        let metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            },
+        );
+        let config = NativeHistogramConfig {
+            schema: 0,
+            zero_threshold: 0.5,
+        };
+        let mut families = MetricFamilies::new();
+        families.encode_metric(
+            None,
+            &[],
+            &[],
+            Some(&config),
+            &metric,
+            SeriesInfo::default(),
+        );
+
+        let families = decode_families(&families.finish());
+        assert_eq!(families.len(), 1);
+        assert_eq!(
+            families[0].r#type,
+            Some(exposition::MetricType::Histogram as i32)
+        );
+        let histogram = families[0].metric[0].histogram.clone().unwrap();
+        assert_eq!(
+            histogram,
+            exposition::Histogram {
+                sample_count: Some(6),
+                sample_sum: Some(104.0),
+                schema: Some(0),
+                zero_threshold: Some(0.5),
+                zero_count: Some(1),
+                negative_span: vec![exposition::BucketSpan {
+                    offset: Some(2),
+                    length: Some(1),
+                }],
+                negative_delta: vec![1],
+                positive_span: vec![
+                    exposition::BucketSpan {
+                        offset: Some(0),
+                        length: Some(1),
+                    },
+                    exposition::BucketSpan {
+                        offset: Some(1),
+                        length: Some(1),
+                    },
+                    exposition::BucketSpan {
+                        offset: Some(4),
+                        length: Some(1),
+                    },
+                ],
+                positive_delta: vec![1, 1, -1],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn encodes_native_histogram_time_series() {
+        let metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_lib::samples![0.25 => 1, 3.0 => 2],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        let config = NativeHistogramConfig {
+            schema: 0,
+            zero_threshold: 0.5,
+        };
+        let histogram = NativeHistogram::from_metric(&metric, &config).unwrap();
+        let mut time_series = TimeSeries::new();
+        time_series.encode_native_histogram(Some("vector"), &metric, &histogram);
+
+        let request = time_series.finish();
+        assert_eq!(request.metadata.len(), 1);
+        assert_eq!(request.metadata[0].metric_family_name, "vector_latency");
+        assert_eq!(
+            request.metadata[0].r#type,
+            proto::MetricType::Histogram as i32
+        );
+        assert_eq!(request.timeseries.len(), 1);
+        assert!(request.timeseries[0].samples.is_empty());
+        assert_eq!(
+            request.timeseries[0].histograms,
+            vec![proto::Histogram {
+                count: Some(proto::histogram::Count::CountInt(3)),
+                sum: 6.25,
+                schema: 0,
+                zero_threshold: 0.5,
+                zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                positive_spans: vec![proto::BucketSpan {
+                    offset: 2,
+                    length: 1,
+                }],
+                positive_deltas: vec![2],
+                timestamp: 1612325106789,
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn encodes_help_and_unit_from_metadata() {
+        let mut metric = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        );
+        let metadata = metric.metadata_mut().value_mut();
+        metadata.insert(path!("prometheus", "help"), "Requests served.");
+        metadata.insert(path!("prometheus", "unit"), "requests");
+
+        let request = encode_one::<TimeSeries>(None, &[], &[], &metric);
+        assert_eq!(request.metadata[0].help, "Requests served.");
+        assert_eq!(request.metadata[0].unit, "requests");
+
+        let text = encode_one::<StringCollector>(None, &[], &[], &metric);
+        assert!(text.starts_with("# HELP requests Requests served.\n"));
+    }
 }
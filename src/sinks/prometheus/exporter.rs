@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     convert::Infallible,
     hash::Hash,
     mem::{discriminant, Discriminant},
@@ -9,6 +10,7 @@ use std::{
 
 use async_trait::async_trait;
 use base64::prelude::{Engine as _, BASE64_STANDARD};
+use chrono::Utc;
 use futures::{future, stream::BoxStream, FutureExt, StreamExt};
 use hyper::{
     header::HeaderValue,
@@ -22,19 +24,25 @@ use stream_cancel::{Trigger, Tripwire};
 use tower::ServiceBuilder;
 use tracing::{Instrument, Span};
 use vector_lib::configurable::configurable_component;
+use vector_lib::prometheus::parser::Exemplar;
 use vector_lib::{
     internal_event::{
         ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
         Registered,
     },
-    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+    EstimatedJsonEncodedSizeOf,
 };
 
-use super::collector::{MetricCollector, StringCollector};
+use super::{
+    collector::{
+        MetricCollector, MetricFamilies, OpenMetricsCollector, SeriesInfo, StringCollector,
+    },
+    native_histogram::{NativeHistogram, NativeHistogramConfig},
+};
 use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, Resource, SinkConfig, SinkContext},
     event::{
-        metric::{Metric, MetricData, MetricKind, MetricSeries, MetricValue},
+        metric::{Metric, MetricData, MetricKind, MetricSeries, MetricSketch, MetricValue},
         Event, EventStatus, Finalizable,
     },
     http::{build_http_trace_layer, Auth},
@@ -58,6 +66,10 @@ const LOCK_FAILED: &str = "Prometheus exporter data lock is poisoned";
 enum BuildError {
     #[snafu(display("Flush period for sets must be greater or equal to {} secs", min))]
     FlushPeriodTooShort { min: u64 },
+    #[snafu(display(
+        "Native histograms cannot be enabled together with `distributions_as_summaries`"
+    ))]
+    NativeHistogramsWithSummaries,
 }
 
 /// Configuration for the `prometheus_exporter` sink.
@@ -121,6 +133,31 @@ pub struct PrometheusExporterConfig {
     #[configurable(metadata(docs::advanced))]
     pub distributions_as_summaries: bool,
 
+    /// Renders [distributions][dist_metric_docs] and sketches as Prometheus [native histograms][prom_native_hist_docs].
+    ///
+    /// Native histograms have sparse exponential buckets, which keep a high resolution over any
+    /// range of values. They are only exposed when the client requests the protobuf format, and
+    /// are exposed as histograms with the same buckets in the text formats.
+    ///
+    /// This cannot be used together with `distributions_as_summaries`.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    /// [prom_native_hist_docs]: https://prometheus.io/docs/concepts/metric_types/#histogram
+    #[configurable(metadata(docs::advanced))]
+    pub native_histograms: Option<NativeHistogramConfig>,
+
+    /// Tags to expose as exemplars instead of labels.
+    ///
+    /// These tags are removed from all metrics. The ones found on a counter or a distribution
+    /// become the labels of an exemplar of its series, along with the value and timestamp of the
+    /// metric. Only the latest exemplar of each series is kept.
+    ///
+    /// Exemplars are only exposed by the OpenMetrics and protobuf formats.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "trace_id"))]
+    pub exemplar_tags: Vec<String>,
+
     /// The interval, in seconds, on which metrics are flushed.
     ///
     /// On the flush interval, if a metric has not been seen since the last flush interval, it is
@@ -161,6 +198,8 @@ impl Default for PrometheusExporterConfig {
             buckets: super::default_histogram_buckets(),
             quantiles: super::default_summary_quantiles(),
             distributions_as_summaries: default_distributions_as_summaries(),
+            native_histograms: None,
+            exemplar_tags: Vec::new(),
             flush_period_secs: default_flush_period_secs(),
             suppress_timestamp: default_suppress_timestamp(),
             acknowledgements: Default::default(),
@@ -202,6 +241,13 @@ impl SinkConfig for PrometheusExporterConfig {
 
         validate_quantiles(&self.quantiles)?;
 
+        if let Some(native_histograms) = &self.native_histograms {
+            if self.distributions_as_summaries {
+                return Err(Box::new(BuildError::NativeHistogramsWithSummaries));
+            }
+            native_histograms.validate()?;
+        }
+
         let sink = PrometheusExporter::new(self.clone());
         let healthcheck = future::ok(()).boxed();
 
@@ -227,18 +273,32 @@ struct PrometheusExporter {
     metrics: Arc<RwLock<IndexMap<MetricRef, (Metric, MetricMetadata)>>>,
 }
 
-/// Expiration metadata for a metric.
-#[derive(Clone, Copy, Debug)]
+/// Expiration and exposition metadata for a metric.
+#[derive(Clone, Debug)]
 struct MetricMetadata {
     expiration_window: Duration,
     expires_at: Instant,
+    /// When the series was created, in milliseconds, if it is accumulated by this sink.
+    created: Option<i64>,
+    exemplar: Option<Exemplar>,
 }
 
 impl MetricMetadata {
-    pub fn new(expiration_window: Duration) -> Self {
+    pub fn new(expiration_window: Duration, accumulated: bool) -> Self {
         Self {
             expiration_window,
             expires_at: Instant::now() + expiration_window,
+            // The creation time of absolute metrics is unknown, since they may have been counting
+            // for a long time before this sink received them.
+            created: accumulated.then(|| Utc::now().timestamp_millis()),
+            exemplar: None,
+        }
+    }
+
+    fn series_info(&self) -> SeriesInfo<'_> {
+        SeriesInfo {
+            created: self.created,
+            exemplar: self.exemplar.as_ref(),
         }
     }
 
@@ -318,6 +378,7 @@ impl Hash for MetricRef {
 
 struct PrometheusExporterMetricNormalizer {
     distributions_as_summaries: bool,
+    native_histograms: bool,
     buckets: Vec<f64>,
 }
 
@@ -329,11 +390,13 @@ impl MetricNormalize for PrometheusExporterMetricNormalizer {
                 let (series, data, metadata) = metric.into_parts();
                 let (time, kind, value) = data.into_parts();
 
-                let new_value = if self.distributions_as_summaries {
+                let new_value = if self.distributions_as_summaries || self.native_histograms {
                     // We use a sketch when in summary mode because they're actually able to be
                     // merged and provide correct output, unlike the aggregated summaries that
                     // we handle from _sources_ like Prometheus.  The collector code itself
                     // will render sketches as aggregated summaries, so we have continuity there.
+                    //
+                    // Native histograms are built from sketches when rendered, for the same reason.
                     value
                         .distribution_to_sketch()
                         .expect("value should be distribution already")
@@ -353,6 +416,87 @@ impl MetricNormalize for PrometheusExporterMetricNormalizer {
     }
 }
 
+/// Removes the exemplar tags from a metric, and returns the exemplar they make up when the metric
+/// has an observed value.
+fn take_exemplar(metric: &mut Metric, exemplar_tags: &[String]) -> Option<Exemplar> {
+    let labels = exemplar_tags
+        .iter()
+        .filter_map(|tag| metric.remove_tag(tag).map(|value| (tag.clone(), value)))
+        .collect::<BTreeMap<_, _>>();
+    if labels.is_empty() {
+        return None;
+    }
+
+    let value = match metric.value() {
+        MetricValue::Counter { value } => *value,
+        MetricValue::Distribution { samples, .. } => samples.last()?.value,
+        _ => return None,
+    };
+    Some(Exemplar {
+        labels,
+        value,
+        timestamp: Some(
+            metric
+                .timestamp()
+                .unwrap_or_else(Utc::now)
+                .timestamp_millis(),
+        ),
+    })
+}
+
+/// The formats in which metrics are exposed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExpositionFormat {
+    Text,
+    OpenMetrics,
+    Protobuf,
+}
+
+impl ExpositionFormat {
+    /// Picks the format the client prefers from its `Accept` header, falling back to the text
+    /// format.
+    fn negotiate(accept: Option<&HeaderValue>) -> Self {
+        let Some(accept) = accept.and_then(|accept| accept.to_str().ok()) else {
+            return Self::Text;
+        };
+
+        let mut best: Option<(Self, f64)> = None;
+        for media_range in accept.split(',') {
+            let mut parts = media_range.split(';').map(str::trim);
+            let format = match parts
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase()
+                .as_str()
+            {
+                "application/vnd.google.protobuf" => Self::Protobuf,
+                "application/openmetrics-text" => Self::OpenMetrics,
+                "text/plain" | "text/*" | "*/*" => Self::Text,
+                _ => continue,
+            };
+            let quality = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .and_then(|quality| quality.parse::<f64>().ok())
+                .unwrap_or(1.0);
+            // The first of the formats with the highest quality wins.
+            if quality > 0.0 && best.map_or(true, |(_, best)| quality > best) {
+                best = Some((format, quality));
+            }
+        }
+        best.map_or(Self::Text, |(format, _)| format)
+    }
+
+    const fn content_type(self) -> &'static str {
+        match self {
+            Self::Text => "text/plain; version=0.0.4",
+            Self::OpenMetrics => "application/openmetrics-text; version=1.0.0; charset=utf-8",
+            Self::Protobuf => {
+                "application/vnd.google.protobuf; proto=io.prometheus.client.MetricFamily; encoding=delimited"
+            }
+        }
+    }
+}
+
 fn authorized(req: &Request<Body>, auth: &Option<Auth>) -> bool {
     if let Some(auth) = auth {
         let headers = req.headers();
@@ -389,6 +533,7 @@ struct Handler {
     default_namespace: Option<String>,
     buckets: Box<[f64]>,
     quantiles: Box<[f64]>,
+    native_histograms: Option<NativeHistogramConfig>,
     bytes_sent: Registered<BytesSent>,
     events_sent: Registered<EventsSent>,
 }
@@ -411,6 +556,7 @@ impl Handler {
             }
 
             (true, &Method::GET, "/metrics") => {
+                let format = ExpositionFormat::negotiate(req.headers().get(http::header::ACCEPT));
                 let metrics = metrics.read().expect(LOCK_FAILED);
 
                 let count = metrics.len();
@@ -419,27 +565,23 @@ impl Handler {
                     .map(|(_, (metric, _))| metric.estimated_json_encoded_size_of())
                     .sum();
 
-                let mut collector = StringCollector::new();
-
-                for (_, (metric, _)) in metrics.iter() {
-                    collector.encode_metric(
-                        self.default_namespace.as_deref(),
-                        &self.buckets,
-                        &self.quantiles,
-                        metric,
-                    );
-                }
+                let body = match format {
+                    ExpositionFormat::Text => self.encode_text::<StringCollector>(&metrics),
+                    ExpositionFormat::OpenMetrics => {
+                        self.encode_text::<OpenMetricsCollector>(&metrics)
+                    }
+                    ExpositionFormat::Protobuf => self.encode_protobuf(&metrics),
+                };
 
                 drop(metrics);
 
-                let body = collector.finish();
-                let body_size = body.size_of();
+                let body_size = body.len();
 
                 *response.body_mut() = body.into();
 
                 response.headers_mut().insert(
                     "Content-Type",
-                    HeaderValue::from_static("text/plain; version=0.0.4"),
+                    HeaderValue::from_static(format.content_type()),
                 );
 
                 self.events_sent.emit(CountByteSize(count, byte_size));
@@ -453,6 +595,64 @@ impl Handler {
 
         response
     }
+
+    fn encode_text<T: MetricCollector<Output = String>>(
+        &self,
+        metrics: &IndexMap<MetricRef, (Metric, MetricMetadata)>,
+    ) -> Vec<u8> {
+        let mut collector = T::new();
+
+        for (metric, metadata) in metrics.values() {
+            let native_histogram = self
+                .native_histograms
+                .as_ref()
+                .and_then(|config| native_histogram_buckets(metric, config));
+            collector.encode_series(
+                self.default_namespace.as_deref(),
+                &self.buckets,
+                &self.quantiles,
+                native_histogram.as_ref().unwrap_or(metric),
+                metadata.series_info(),
+            );
+        }
+
+        collector.finish().into_bytes()
+    }
+
+    fn encode_protobuf(&self, metrics: &IndexMap<MetricRef, (Metric, MetricMetadata)>) -> Vec<u8> {
+        let mut families = MetricFamilies::new();
+
+        for (metric, metadata) in metrics.values() {
+            families.encode_metric(
+                self.default_namespace.as_deref(),
+                &self.buckets,
+                &self.quantiles,
+                self.native_histograms.as_ref(),
+                metric,
+                metadata.series_info(),
+            );
+        }
+
+        families.finish()
+    }
+}
+
+/// Converts a sketch to a histogram with the buckets of its native histogram, for the formats that
+/// cannot represent native histograms.
+fn native_histogram_buckets(metric: &Metric, config: &NativeHistogramConfig) -> Option<Metric> {
+    match metric.value() {
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } => {
+            let histogram = NativeHistogram::from_sketch(sketch, config);
+            Some(metric.clone().with_value(MetricValue::AggregatedHistogram {
+                buckets: histogram.to_buckets(),
+                count: histogram.count,
+                sum: histogram.sum,
+            }))
+        }
+        _ => None,
+    }
 }
 
 impl PrometheusExporter {
@@ -475,6 +675,7 @@ impl PrometheusExporter {
             default_namespace: self.config.default_namespace.clone(),
             buckets: self.config.buckets.clone().into(),
             quantiles: self.config.quantiles.clone().into(),
+            native_histograms: self.config.native_histograms,
             auth: self.config.auth.clone(),
         };
 
@@ -536,6 +737,7 @@ impl StreamSink<Event> for PrometheusExporter {
         let flush_period = self.config.flush_period_secs;
         let mut normalizer = MetricNormalizer::from(PrometheusExporterMetricNormalizer {
             distributions_as_summaries: self.config.distributions_as_summaries,
+            native_histograms: self.config.native_histograms.is_some(),
             buckets: self.config.buckets.clone(),
         });
 
@@ -567,6 +769,8 @@ impl StreamSink<Event> for PrometheusExporter {
             // Now process the metric we got.
             let mut metric = event.into_metric();
             let finalizers = metric.take_finalizers();
+            let exemplar = take_exemplar(&mut metric, &self.config.exemplar_tags);
+            let accumulated = metric.kind() == MetricKind::Incremental;
 
             if let Some(normalized) = normalizer.normalize(metric) {
                 let normalized = if self.config.suppress_timestamp {
@@ -584,9 +788,14 @@ impl StreamSink<Event> for PrometheusExporter {
                         let (data, metadata) = entry.get_mut();
                         *data = normalized;
                         metadata.refresh();
+                        if exemplar.is_some() {
+                            metadata.exemplar = exemplar;
+                        }
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert((normalized, MetricMetadata::new(flush_period)));
+                        let mut metadata = MetricMetadata::new(flush_period, accumulated);
+                        metadata.exemplar = exemplar;
+                        entry.insert((normalized, metadata));
                     }
                 }
                 finalizers.update_status(EventStatus::Delivered);
@@ -605,6 +814,7 @@ mod tests {
     use chrono::{Duration, Utc};
     use futures::stream;
     use indoc::indoc;
+    use prost::Message;
     use similar_asserts::assert_eq;
     use tokio::{sync::oneshot::error::TryRecvError, time};
     use vector_lib::{
@@ -613,6 +823,7 @@ mod tests {
     };
     use vector_lib::{
         finalization::{BatchNotifier, BatchStatus},
+        prometheus::parser::exposition,
         sensitive_string::SensitiveString,
     };
 
@@ -1330,6 +1541,187 @@ mod tests {
             .expect("histogram metric should exist");
         assert_eq!(actual_histogram.0.value(), expected_histogram.value());
     }
+
+    #[test]
+    fn negotiates_exposition_format() {
+        let negotiate = |accept: &str| {
+            ExpositionFormat::negotiate(Some(&HeaderValue::from_str(accept).unwrap()))
+        };
+
+        assert_eq!(ExpositionFormat::negotiate(None), ExpositionFormat::Text);
+        assert_eq!(negotiate("*/*"), ExpositionFormat::Text);
+        assert_eq!(negotiate("application/json"), ExpositionFormat::Text);
+        assert_eq!(
+            negotiate("application/openmetrics-text;version=1.0.0,text/plain;q=0.5"),
+            ExpositionFormat::OpenMetrics
+        );
+        assert_eq!(
+            negotiate(
+                "application/vnd.google.protobuf;proto=io.prometheus.client.MetricFamily;encoding=delimited;q=0.7,text/plain;version=0.0.4;q=0.3,*/*;q=0.2"
+            ),
+            ExpositionFormat::Protobuf
+        );
+        assert_eq!(
+            negotiate("application/openmetrics-text;q=0,text/plain"),
+            ExpositionFormat::Text
+        );
+    }
+
+    async fn run_and_fetch(
+        config: PrometheusExporterConfig,
+        events: Vec<Event>,
+        accept: &str,
+    ) -> (String, Vec<u8>) {
+        let sink = PrometheusExporter::new(config.clone());
+        let metrics = Arc::clone(&sink.metrics);
+        VectorSink::from_event_streamsink(sink)
+            .run(stream::iter(events).map(Into::into))
+            .await
+            .unwrap();
+
+        let handler = Handler {
+            auth: None,
+            default_namespace: None,
+            buckets: config.buckets.into(),
+            quantiles: config.quantiles.into(),
+            native_histograms: config.native_histograms,
+            bytes_sent: register!(BytesSent::from(Protocol::HTTP)),
+            events_sent: register!(EventsSent::from(Output(None))),
+        };
+        let request = Request::get("/metrics")
+            .header(http::header::ACCEPT, accept)
+            .body(Body::empty())
+            .unwrap();
+        let response = handler.handle(request, &metrics);
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let content_type = response.headers()["Content-Type"]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (content_type, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn exposes_openmetrics_with_exemplars() {
+        let config = PrometheusExporterConfig {
+            exemplar_tags: vec!["trace_id".into()],
+            ..Default::default()
+        };
+        let timestamp = Utc::now();
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_timestamp(Some(timestamp));
+        let events = vec![
+            Event::Metric(
+                counter
+                    .clone()
+                    .with_tags(Some(metric_tags!("trace_id" => "abc"))),
+            ),
+            Event::Metric(
+                counter
+                    .with_value(MetricValue::Counter { value: 2.0 })
+                    .with_tags(Some(metric_tags!("trace_id" => "def"))),
+            ),
+        ];
+
+        let (content_type, body) =
+            run_and_fetch(config, events, "application/openmetrics-text").await;
+        assert_eq!(
+            content_type,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8"
+        );
+
+        let body = String::from_utf8(body).unwrap();
+        let seconds = timestamp.timestamp_millis() as f64 / 1000.0;
+        let mut lines = body.lines();
+        assert_eq!(lines.next(), Some("# HELP requests requests"));
+        assert_eq!(lines.next(), Some("# TYPE requests counter"));
+        assert_eq!(
+            lines.next(),
+            Some(
+                format!(r#"requests_total 3 {seconds} # {{trace_id="def"}} 2 {seconds}"#).as_str()
+            )
+        );
+        assert!(lines.next().unwrap().starts_with("requests_created "));
+        assert_eq!(lines.next(), Some("# EOF"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[tokio::test]
+    async fn exposes_native_histograms() {
+        let config = PrometheusExporterConfig {
+            native_histograms: Some(NativeHistogramConfig {
+                schema: 0,
+                zero_threshold: 0.5,
+            }),
+            ..Default::default()
+        };
+        let events = vec![Event::Metric(Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                statistic: StatisticKind::Histogram,
+                samples: samples!(1.5 => 1, 3.0 => 2),
+            },
+        ))];
+
+        let (_, body) = run_and_fetch(
+            config.clone(),
+            events.clone(),
+            "application/vnd.google.protobuf;proto=io.prometheus.client.MetricFamily;encoding=delimited",
+        )
+        .await;
+        let family = exposition::MetricFamily::decode_length_delimited(&body[..]).unwrap();
+        assert_eq!(family.name.as_deref(), Some("latency"));
+        assert_eq!(
+            family.r#type,
+            Some(exposition::MetricType::Histogram as i32)
+        );
+        let histogram = family.metric[0].histogram.as_ref().unwrap();
+        assert_eq!(histogram.sample_count, Some(3));
+        assert_eq!(histogram.sample_sum, Some(7.5));
+        assert_eq!(histogram.schema, Some(0));
+        assert_eq!(histogram.zero_count, Some(0));
+        assert_eq!(
+            histogram.positive_span,
+            vec![exposition::BucketSpan {
+                offset: Some(1),
+                length: Some(2),
+            }]
+        );
+        assert_eq!(histogram.positive_delta, vec![1, 1]);
+
+        // The text format exposes the buckets of the native histogram.
+        let (_, body) = run_and_fetch(config, events, "text/plain").await;
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            indoc! {r#"
+                # HELP latency latency
+                # TYPE latency histogram
+                latency_bucket{le="0.5"} 0
+                latency_bucket{le="2"} 1
+                latency_bucket{le="4"} 3
+                latency_bucket{le="+Inf"} 3
+                latency_sum 7.5
+                latency_count 3
+            "#}
+        );
+    }
+
+    #[tokio::test]
+    async fn native_histograms_conflict_with_summaries() {
+        let config = PrometheusExporterConfig {
+            distributions_as_summaries: true,
+            native_histograms: Some(NativeHistogramConfig::default()),
+            ..Default::default()
+        };
+        assert!(config.build(SinkContext::default()).await.is_err());
+    }
 }
 
 #[cfg(all(test, feature = "prometheus-integration-tests"))]
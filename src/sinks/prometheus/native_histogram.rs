@@ -0,0 +1,347 @@
+//! Prometheus native histograms, which have sparse exponential buckets.
+//!
+//! The bucket with index `i` covers `(base^(i - 1), base^i]`, where `base = 2^(2^-schema)`, and
+//! the negative buckets mirror the positive ones. Values whose absolute value is at most the zero
+//! threshold are counted in a separate zero bucket.
+
+use std::collections::BTreeMap;
+
+use snafu::Snafu;
+use vector_lib::{configurable::configurable_component, metrics::AgentDDSketch};
+
+use crate::event::metric::{Bucket, Metric, MetricSketch, MetricValue, Sample};
+
+const MIN_SCHEMA: i32 = -4;
+const MAX_SCHEMA: i32 = 8;
+
+#[derive(Debug, Snafu)]
+pub(super) enum NativeHistogramError {
+    #[snafu(display(
+        "Native histogram schema must be between {} and {}, found: {}",
+        MIN_SCHEMA,
+        MAX_SCHEMA,
+        schema
+    ))]
+    InvalidSchema { schema: i32 },
+    #[snafu(display("Native histogram zero threshold must be a non-negative number"))]
+    InvalidZeroThreshold,
+}
+
+/// Configuration of native histograms.
+///
+/// Native histograms are only exposed by the protobuf format. Other formats expose them as
+/// histograms with the bounds of their exponential buckets.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NativeHistogramConfig {
+    /// The resolution of the buckets.
+    ///
+    /// Each power of two is divided into `2^schema` buckets, so that higher values give finer
+    /// buckets. Must be between `-4` and `8`.
+    #[serde(default = "default_schema")]
+    pub schema: i32,
+
+    /// The width of the zero bucket.
+    ///
+    /// Values whose absolute value is at most this threshold are counted in the zero bucket.
+    #[serde(default = "default_zero_threshold")]
+    pub zero_threshold: f64,
+}
+
+impl Default for NativeHistogramConfig {
+    fn default() -> Self {
+        Self {
+            schema: default_schema(),
+            zero_threshold: default_zero_threshold(),
+        }
+    }
+}
+
+const fn default_schema() -> i32 {
+    3
+}
+
+const fn default_zero_threshold() -> f64 {
+    // The default of the Prometheus client libraries.
+    2.938735877055719e-39
+}
+
+impl NativeHistogramConfig {
+    pub(super) fn validate(&self) -> Result<(), NativeHistogramError> {
+        if !(MIN_SCHEMA..=MAX_SCHEMA).contains(&self.schema) {
+            return Err(NativeHistogramError::InvalidSchema {
+                schema: self.schema,
+            });
+        }
+        if !(self.zero_threshold >= 0.0 && self.zero_threshold.is_finite()) {
+            return Err(NativeHistogramError::InvalidZeroThreshold);
+        }
+        Ok(())
+    }
+}
+
+/// The index of the bucket that contains the given positive value.
+pub(super) fn bucket_index(value: f64, schema: i32) -> i32 {
+    // Values that are too large for the buckets end up in the last one.
+    (value.log2() * 2f64.powi(schema)).ceil() as i32
+}
+
+/// The upper bound of the positive bucket with the given index.
+pub(super) fn bucket_upper_bound(index: i32, schema: i32) -> f64 {
+    2f64.powf(f64::from(index) * 2f64.powi(-schema))
+}
+
+/// A native histogram, with the counts of its buckets keyed by their index.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct NativeHistogram {
+    pub(super) schema: i32,
+    pub(super) zero_threshold: f64,
+    pub(super) zero_count: u64,
+    pub(super) count: u64,
+    pub(super) sum: f64,
+    pub(super) positive: BTreeMap<i32, u64>,
+    pub(super) negative: BTreeMap<i32, u64>,
+}
+
+impl NativeHistogram {
+    const fn new(config: &NativeHistogramConfig) -> Self {
+        Self {
+            schema: config.schema,
+            zero_threshold: config.zero_threshold,
+            zero_count: 0,
+            count: 0,
+            sum: 0.0,
+            positive: BTreeMap::new(),
+            negative: BTreeMap::new(),
+        }
+    }
+
+    /// Converts distributions and sketches to native histograms, and leaves out other metrics.
+    pub(super) fn from_metric(metric: &Metric, config: &NativeHistogramConfig) -> Option<Self> {
+        match metric.value() {
+            MetricValue::Distribution { samples, .. } => Some(Self::from_samples(samples, config)),
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            } => Some(Self::from_sketch(sketch, config)),
+            _ => None,
+        }
+    }
+
+    /// Converts the samples of a distribution to a native histogram.
+    pub(super) fn from_samples(samples: &[Sample], config: &NativeHistogramConfig) -> Self {
+        let mut histogram = Self::new(config);
+        for sample in samples {
+            histogram.count += u64::from(sample.rate);
+            histogram.sum += sample.value * f64::from(sample.rate);
+            histogram.insert(sample.value, u64::from(sample.rate));
+        }
+        histogram
+    }
+
+    /// Converts a sketch to a native histogram.
+    ///
+    /// Each bin of the sketch is counted in the bucket that contains its geometric midpoint, so the
+    /// relative error of the sketch adds up to the one of the buckets. Since sketches round the
+    /// logarithm of values to the nearest key, that midpoint is the value of the key itself, which
+    /// `bin_lower_bound` returns despite its name: the bin spans half a step of `gamma` on either
+    /// side of it.
+    pub(super) fn from_sketch(sketch: &AgentDDSketch, config: &NativeHistogramConfig) -> Self {
+        let mut histogram = Self::new(config);
+        histogram.count = u64::from(sketch.count());
+        histogram.sum = sketch.sum().unwrap_or(0.0);
+
+        let (keys, counts) = sketch.bin_map().into_parts();
+        for (key, count) in keys.into_iter().zip(counts) {
+            let midpoint = sketch.config().bin_lower_bound(key);
+            histogram.insert(midpoint, u64::from(count));
+        }
+
+        histogram
+    }
+
+    /// Counts values in the bucket that contains them, without updating the count and sum.
+    fn insert(&mut self, value: f64, count: u64) {
+        if value.abs() <= self.zero_threshold {
+            self.zero_count += count;
+        } else if value > 0.0 {
+            *self
+                .positive
+                .entry(bucket_index(value, self.schema))
+                .or_default() += count;
+        } else {
+            *self
+                .negative
+                .entry(bucket_index(-value, self.schema))
+                .or_default() += count;
+        }
+    }
+
+    /// Converts the buckets to explicit buckets with the same bounds, for the formats that cannot
+    /// represent native histograms.
+    pub(super) fn to_buckets(&self) -> Vec<Bucket> {
+        let mut buckets = Vec::with_capacity(self.negative.len() + self.positive.len() + 1);
+        for (&index, &count) in self.negative.iter().rev() {
+            buckets.push(Bucket {
+                upper_limit: -bucket_upper_bound(index - 1, self.schema),
+                count,
+            });
+        }
+        buckets.push(Bucket {
+            upper_limit: self.zero_threshold,
+            count: self.zero_count,
+        });
+        for (&index, &count) in &self.positive {
+            buckets.push(Bucket {
+                upper_limit: bucket_upper_bound(index, self.schema),
+                count,
+            });
+        }
+        buckets
+    }
+}
+
+/// Encodes buckets as spans of consecutive indexes, given as an offset and a length, and the
+/// deltas between the counts of consecutive buckets.
+///
+/// The offset of the first span is the index of its first bucket, and the offsets of the other
+/// spans are the gaps since the end of the previous span.
+pub(super) fn encode_spans(buckets: &BTreeMap<i32, u64>) -> (Vec<(i32, u32)>, Vec<i64>) {
+    let mut spans: Vec<(i32, u32)> = Vec::new();
+    let mut deltas = Vec::with_capacity(buckets.len());
+    let mut next_index = 0;
+    let mut previous_count = 0;
+
+    for (&index, &count) in buckets {
+        match spans.last_mut() {
+            Some((_, length)) if index == next_index => *length += 1,
+            _ => spans.push((index - next_index, 1)),
+        }
+        next_index = index + 1;
+
+        let count = count as i64;
+        deltas.push(count - previous_count);
+        previous_count = count;
+    }
+
+    (spans, deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn computes_bucket_indexes() {
+        assert_eq!(bucket_index(1.0, 0), 0);
+        assert_eq!(bucket_index(1.5, 0), 1);
+        assert_eq!(bucket_index(2.0, 0), 1);
+        assert_eq!(bucket_index(2.0, 3), 8);
+        assert_eq!(bucket_index(0.25, 3), -16);
+        assert_eq!(bucket_index(4.0, -1), 1);
+        assert_eq!(bucket_index(5.0, -1), 2);
+    }
+
+    #[test]
+    fn computes_bucket_bounds() {
+        assert_eq!(bucket_upper_bound(1, 0), 2.0);
+        assert_eq!(bucket_upper_bound(8, 3), 2.0);
+        assert_eq!(bucket_upper_bound(-16, 3), 0.25);
+        assert_eq!(bucket_upper_bound(2, -1), 16.0);
+    }
+
+    #[test]
+    fn validates_config() {
+        assert!(NativeHistogramConfig::default().validate().is_ok());
+        assert!(NativeHistogramConfig {
+            schema: 9,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(NativeHistogramConfig {
+            zero_threshold: -1.0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn converts_sketches() {
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[0.0, 1.0, 3.0, 3.0, -3.0, 100.0]);
+        let config = NativeHistogramConfig {
+            schema: 0,
+            zero_threshold: 0.5,
+        };
+
+        // The bins are centered on the values, so that 1.0, the upper bound of the bucket with
+        // index 0, stays in that bucket.
+        for value in [1.0, 3.0, 100.0] {
+            let key = sketch.config().key(value);
+            assert!((sketch.config().bin_lower_bound(key) / value - 1.0).abs() < 0.01);
+        }
+
+        let histogram = NativeHistogram::from_sketch(&sketch, &config);
+        assert_eq!(histogram.count, 6);
+        assert_eq!(histogram.sum, 104.0);
+        assert_eq!(histogram.zero_count, 1);
+        assert_eq!(histogram.positive, BTreeMap::from([(0, 1), (2, 2), (7, 1)]));
+        assert_eq!(histogram.negative, BTreeMap::from([(2, 1)]));
+
+        assert_eq!(
+            histogram.to_buckets(),
+            vec![
+                Bucket {
+                    upper_limit: -2.0,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 0.5,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 1.0,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 4.0,
+                    count: 2
+                },
+                Bucket {
+                    upper_limit: 128.0,
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_samples() {
+        let samples = vector_lib::samples![0.25 => 2, 3.0 => 1, -5.0 => 1];
+        let config = NativeHistogramConfig {
+            schema: 0,
+            zero_threshold: 0.5,
+        };
+
+        let histogram = NativeHistogram::from_samples(&samples, &config);
+        assert_eq!(histogram.count, 4);
+        assert_eq!(histogram.sum, -1.5);
+        assert_eq!(histogram.zero_count, 2);
+        assert_eq!(histogram.positive, BTreeMap::from([(2, 1)]));
+        assert_eq!(histogram.negative, BTreeMap::from([(3, 1)]));
+    }
+
+    #[test]
+    fn encodes_spans() {
+        let buckets = BTreeMap::from([(-2, 3), (-1, 1), (2, 4), (3, 4), (10, 1)]);
+        assert_eq!(
+            encode_spans(&buckets),
+            (vec![(-2, 2), (2, 2), (6, 1)], vec![3, -2, 3, 0, -3])
+        );
+        assert_eq!(encode_spans(&BTreeMap::new()), (vec![], vec![]));
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     http::HttpClient,
     sinks::{
         prelude::*,
-        prometheus::PrometheusRemoteWriteAuth,
+        prometheus::{native_histogram::NativeHistogramConfig, PrometheusRemoteWriteAuth},
         util::{auth::Auth, http::http_response_retry_logic},
         UriParseSnafu,
     },
@@ -34,6 +34,24 @@ pub struct RemoteWriteBatchConfig {
     pub aggregate: bool,
 }
 
+/// The version of the remote write protocol.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolVersion {
+    /// [Remote-Write 1.0][rw_spec], which all receivers accept.
+    ///
+    /// [rw_spec]: https://prometheus.io/docs/specs/remote_write_spec/
+    #[default]
+    V1,
+
+    /// [Remote-Write 2.0][rw2_spec], which sends the metadata of every series and lowers the size
+    /// of requests by interning their strings in a symbol table.
+    ///
+    /// [rw2_spec]: https://prometheus.io/docs/specs/remote_write_spec_2_0/
+    V2,
+}
+
 /// Configuration for the `prometheus_remote_write` sink.
 #[configurable_component(sink(
     "prometheus_remote_write",
@@ -75,6 +93,21 @@ pub struct RemoteWriteConfig {
     #[configurable(metadata(docs::advanced))]
     pub quantiles: Vec<f64>,
 
+    /// Sends [distributions][dist_metric_docs] and sketches as Prometheus [native histograms][prom_native_hist_docs].
+    ///
+    /// Native histograms have sparse exponential buckets, which keep a high resolution over any
+    /// range of values. The receiver must accept native histograms.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    /// [prom_native_hist_docs]: https://prometheus.io/docs/concepts/metric_types/#histogram
+    #[configurable(metadata(docs::advanced))]
+    pub native_histograms: Option<NativeHistogramConfig>,
+
+    /// The version of the remote write protocol to send requests with.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub protocol_version: ProtocolVersion,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: RemoteWriteBatchConfig,
@@ -134,6 +167,9 @@ impl SinkConfig for RemoteWriteConfig {
 
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let endpoint = self.endpoint.parse::<Uri>().context(UriParseSnafu)?;
+        if let Some(native_histograms) = &self.native_histograms {
+            native_histograms.validate()?;
+        }
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let request_settings = self.request.into_settings();
         let buckets = self.buckets.clone();
@@ -176,6 +212,7 @@ impl SinkConfig for RemoteWriteConfig {
             client.clone(),
             endpoint.clone(),
             self.compression,
+            self.protocol_version,
             auth.clone(),
         )
         .boxed();
@@ -185,6 +222,7 @@ impl SinkConfig for RemoteWriteConfig {
             client,
             auth,
             compression: self.compression,
+            protocol_version: self.protocol_version,
         };
         let service = ServiceBuilder::new()
             .settings(request_settings, http_response_retry_logic())
@@ -202,6 +240,8 @@ impl SinkConfig for RemoteWriteConfig {
             buckets,
             quantiles,
             default_namespace,
+            native_histograms: self.native_histograms,
+            protocol_version: self.protocol_version,
             service,
         };
 
@@ -217,11 +257,20 @@ async fn healthcheck(
     client: HttpClient,
     endpoint: Uri,
     compression: Compression,
+    protocol_version: ProtocolVersion,
     auth: Option<Auth>,
 ) -> crate::Result<()> {
     let body = bytes::Bytes::new();
-    let request =
-        build_request(http::Method::GET, &endpoint, compression, body, None, auth).await?;
+    let request = build_request(
+        http::Method::GET,
+        &endpoint,
+        compression,
+        protocol_version,
+        body,
+        None,
+        auth,
+    )
+    .await?;
     let response = client.send(request).await?;
 
     match response.status() {
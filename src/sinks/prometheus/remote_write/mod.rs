@@ -25,7 +25,7 @@ mod tests;
 mod integration_tests;
 
 #[cfg(all(test, feature = "sources-prometheus-remote-write"))]
-pub use config::RemoteWriteConfig;
+pub use config::{ProtocolVersion, RemoteWriteConfig};
 
 #[derive(Debug, Snafu)]
 enum Errors {
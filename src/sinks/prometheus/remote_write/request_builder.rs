@@ -1,20 +1,27 @@
-use std::io;
+use std::{collections::HashMap, io};
 
 use bytes::{Bytes, BytesMut};
 use prost::Message;
+use vector_lib::prometheus::parser::{proto, proto_v2, METRIC_NAME_LABEL};
 use vector_lib::{config::telemetry, event::Metric};
 
 use crate::sinks::{
     prelude::*,
-    prometheus::{collector, collector::MetricCollector as _},
+    prometheus::{
+        collector,
+        collector::MetricCollector as _,
+        native_histogram::{NativeHistogram, NativeHistogramConfig},
+    },
 };
 
-use super::{sink::EventCollection, PartitionKey};
+use super::{config::ProtocolVersion, sink::EventCollection, PartitionKey};
 
 pub(crate) struct RemoteWriteEncoder {
     pub(super) default_namespace: Option<String>,
     pub(super) buckets: Vec<f64>,
     pub(super) quantiles: Vec<f64>,
+    pub(super) native_histograms: Option<NativeHistogramConfig>,
+    pub(super) protocol_version: ProtocolVersion,
 }
 
 impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
@@ -30,18 +37,30 @@ impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
         for metric in input {
             byte_size.add_event(&metric, metric.estimated_json_encoded_size_of());
 
-            time_series.encode_metric(
-                self.default_namespace.as_deref(),
-                &self.buckets,
-                &self.quantiles,
-                &metric,
-            );
+            let native_histogram = self
+                .native_histograms
+                .as_ref()
+                .and_then(|config| NativeHistogram::from_metric(&metric, config));
+            match native_histogram {
+                Some(histogram) => time_series.encode_native_histogram(
+                    self.default_namespace.as_deref(),
+                    &metric,
+                    &histogram,
+                ),
+                None => time_series.encode_metric(
+                    self.default_namespace.as_deref(),
+                    &self.buckets,
+                    &self.quantiles,
+                    &metric,
+                ),
+            }
         }
         let request = time_series.finish();
 
-        let mut out = BytesMut::with_capacity(request.encoded_len());
-        request.encode(&mut out).expect("Out of memory");
-        let body = out.freeze();
+        let body = match self.protocol_version {
+            ProtocolVersion::V1 => encode_message(&request),
+            ProtocolVersion::V2 => encode_message(&into_v2_request(request)),
+        };
 
         write_all(writer, len, body.as_ref())?;
 
@@ -49,6 +68,145 @@ impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
     }
 }
 
+fn encode_message(message: &impl Message) -> Bytes {
+    let mut out = BytesMut::with_capacity(message.encoded_len());
+    message.encode(&mut out).expect("Out of memory");
+    out.freeze()
+}
+
+/// Converts a request to the Remote-Write 2.0 format, which attaches the metadata of each family
+/// to its series and references strings by their index in the symbol table of the request.
+fn into_v2_request(request: proto::WriteRequest) -> proto_v2::Request {
+    let metadata = request
+        .metadata
+        .into_iter()
+        .map(|metadata| (metadata.metric_family_name.clone(), metadata))
+        .collect::<HashMap<_, _>>();
+    let mut symbols = SymbolTable::new();
+
+    let timeseries = request
+        .timeseries
+        .into_iter()
+        .map(|series| {
+            let name = series
+                .labels
+                .iter()
+                .find(|label| label.name == METRIC_NAME_LABEL)
+                .map_or("", |label| label.value.as_str());
+            let metadata = family_metadata(&metadata, name).map(|metadata| proto_v2::Metadata {
+                // Both versions number the metric types the same way.
+                r#type: metadata.r#type,
+                help_ref: symbols.intern(&metadata.help),
+                unit_ref: symbols.intern(&metadata.unit),
+            });
+            let labels_refs = series
+                .labels
+                .iter()
+                .flat_map(|label| [symbols.intern(&label.name), symbols.intern(&label.value)])
+                .collect();
+
+            proto_v2::TimeSeries {
+                labels_refs,
+                samples: series
+                    .samples
+                    .into_iter()
+                    .map(|sample| proto_v2::Sample {
+                        value: sample.value,
+                        timestamp: sample.timestamp,
+                    })
+                    .collect(),
+                histograms: series.histograms.into_iter().map(v2_histogram).collect(),
+                exemplars: Vec::new(),
+                metadata,
+                created_timestamp: 0,
+            }
+        })
+        .collect();
+
+    proto_v2::Request {
+        symbols: symbols.symbols,
+        timeseries,
+    }
+}
+
+/// Finds the metadata of the family of a series, whose name may have the suffix of the series of
+/// a histogram or summary.
+fn family_metadata<'a>(
+    metadata: &'a HashMap<String, proto::MetricMetadata>,
+    name: &str,
+) -> Option<&'a proto::MetricMetadata> {
+    metadata.get(name).or_else(|| {
+        ["_bucket", "_sum", "_count"]
+            .iter()
+            .find_map(|suffix| metadata.get(name.strip_suffix(suffix)?))
+    })
+}
+
+fn v2_histogram(histogram: proto::Histogram) -> proto_v2::Histogram {
+    use proto::histogram::{Count, ZeroCount};
+    use proto_v2::histogram as v2;
+
+    let spans = |spans: Vec<proto::BucketSpan>| {
+        spans
+            .into_iter()
+            .map(|span| proto_v2::BucketSpan {
+                offset: span.offset,
+                length: span.length,
+            })
+            .collect()
+    };
+
+    proto_v2::Histogram {
+        count: histogram.count.map(|count| match count {
+            Count::CountInt(count) => v2::Count::CountInt(count),
+            Count::CountFloat(count) => v2::Count::CountFloat(count),
+        }),
+        sum: histogram.sum,
+        schema: histogram.schema,
+        zero_threshold: histogram.zero_threshold,
+        zero_count: histogram.zero_count.map(|count| match count {
+            ZeroCount::ZeroCountInt(count) => v2::ZeroCount::ZeroCountInt(count),
+            ZeroCount::ZeroCountFloat(count) => v2::ZeroCount::ZeroCountFloat(count),
+        }),
+        negative_spans: spans(histogram.negative_spans),
+        negative_deltas: histogram.negative_deltas,
+        negative_counts: histogram.negative_counts,
+        positive_spans: spans(histogram.positive_spans),
+        positive_deltas: histogram.positive_deltas,
+        positive_counts: histogram.positive_counts,
+        // Both versions number the reset hints the same way.
+        reset_hint: histogram.reset_hint,
+        timestamp: histogram.timestamp,
+        custom_values: Vec::new(),
+    }
+}
+
+/// Interns the strings of a Remote-Write 2.0 request, whose symbol table must start with the
+/// empty string.
+struct SymbolTable {
+    symbols: Vec<String>,
+    references: HashMap<String, u32>,
+}
+
+impl SymbolTable {
+    fn new() -> Self {
+        Self {
+            symbols: vec![String::new()],
+            references: HashMap::from([(String::new(), 0)]),
+        }
+    }
+
+    fn intern(&mut self, symbol: &str) -> u32 {
+        if let Some(&reference) = self.references.get(symbol) {
+            return reference;
+        }
+        let reference = self.symbols.len() as u32;
+        self.symbols.push(symbol.into());
+        self.references.insert(symbol.into(), reference);
+        reference
+    }
+}
+
 #[derive(Clone)]
 pub(super) struct RemoteWriteRequest {
     pub(super) request: Bytes,
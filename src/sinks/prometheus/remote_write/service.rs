@@ -8,7 +8,7 @@ use aws_types::region::Region;
 use bytes::Bytes;
 use http::Uri;
 
-use super::request_builder::RemoteWriteRequest;
+use super::{config::ProtocolVersion, request_builder::RemoteWriteRequest};
 use crate::{
     http::HttpClient,
     internal_events::EndpointBytesSent,
@@ -26,7 +26,10 @@ mod headers {
     pub(super) const X_SCOPE_ORGID: &str = "X-Scope-OrgID";
 
     pub(super) const VERSION: &str = "0.1.0";
+    pub(super) const VERSION_2: &str = "2.0.0";
     pub(super) const APPLICATION_X_PROTOBUF: &str = "application/x-protobuf";
+    pub(super) const APPLICATION_X_PROTOBUF_V2: &str =
+        "application/x-protobuf;proto=io.prometheus.write.v2.Request";
 }
 
 #[derive(Clone)]
@@ -35,6 +38,7 @@ pub(super) struct RemoteWriteService {
     pub(super) auth: Option<Auth>,
     pub(super) client: HttpClient,
     pub(super) compression: super::Compression,
+    pub(super) protocol_version: ProtocolVersion,
 }
 
 impl Service<RemoteWriteRequest> for RemoteWriteService {
@@ -52,6 +56,7 @@ impl Service<RemoteWriteRequest> for RemoteWriteService {
         let endpoint = self.endpoint.clone();
         let auth = self.auth.clone();
         let compression = self.compression;
+        let protocol_version = self.protocol_version;
 
         Box::pin(async move {
             let metadata = std::mem::take(request.metadata_mut());
@@ -62,6 +67,7 @@ impl Service<RemoteWriteRequest> for RemoteWriteService {
                 http::Method::POST,
                 &endpoint,
                 compression,
+                protocol_version,
                 request.request,
                 request.tenant_id.as_ref(),
                 auth,
@@ -104,15 +110,20 @@ pub(super) async fn build_request(
     method: http::Method,
     endpoint: &Uri,
     compression: Compression,
+    protocol_version: ProtocolVersion,
     body: Bytes,
     tenant_id: Option<&String>,
     auth: Option<Auth>,
 ) -> crate::Result<http::Request<hyper::Body>> {
+    let (version, content_type) = match protocol_version {
+        ProtocolVersion::V1 => (headers::VERSION, headers::APPLICATION_X_PROTOBUF),
+        ProtocolVersion::V2 => (headers::VERSION_2, headers::APPLICATION_X_PROTOBUF_V2),
+    };
     let mut builder = http::Request::builder()
         .method(method)
         .uri(endpoint)
-        .header(headers::X_PROMETHEUS_REMOTE_WRITE_VERSION, headers::VERSION)
-        .header(headers::CONTENT_TYPE, headers::APPLICATION_X_PROTOBUF);
+        .header(headers::X_PROMETHEUS_REMOTE_WRITE_VERSION, version)
+        .header(headers::CONTENT_TYPE, content_type);
 
     if let Some(content_encoding) = compression.content_encoding() {
         builder = builder.header(headers::CONTENT_ENCODING, content_encoding);
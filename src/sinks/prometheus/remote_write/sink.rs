@@ -4,9 +4,13 @@ use vector_lib::byte_size_of::ByteSizeOf;
 use vector_lib::event::Metric;
 use vector_lib::stream::batcher::{data::BatchData, limiter::ByteSizeOfItemSize};
 
-use crate::sinks::{prelude::*, util::buffer::metrics::MetricSet};
+use crate::sinks::{
+    prelude::*, prometheus::native_histogram::NativeHistogramConfig,
+    util::buffer::metrics::MetricSet,
+};
 
 use super::{
+    config::ProtocolVersion,
     request_builder::{RemoteWriteEncoder, RemoteWriteRequest, RemoteWriteRequestBuilder},
     PartitionKey, PrometheusMetricNormalize,
 };
@@ -149,6 +153,8 @@ pub(super) struct RemoteWriteSink<S> {
     pub(super) default_namespace: Option<String>,
     pub(super) buckets: Vec<f64>,
     pub(super) quantiles: Vec<f64>,
+    pub(super) native_histograms: Option<NativeHistogramConfig>,
+    pub(super) protocol_version: ProtocolVersion,
     pub(super) service: S,
 }
 
@@ -166,6 +172,8 @@ where
                 default_namespace: self.default_namespace.clone(),
                 buckets: self.buckets.clone(),
                 quantiles: self.quantiles.clone(),
+                native_histograms: self.native_histograms,
+                protocol_version: self.protocol_version,
             },
         };
 
@@ -3,13 +3,14 @@ use futures::StreamExt;
 use http::HeaderMap;
 use indoc::indoc;
 use prost::Message;
+use vector_lib::lookup::path;
 use vector_lib::metric_tags;
-use vector_lib::prometheus::parser::proto;
+use vector_lib::prometheus::parser::{proto, proto_v2};
 
 use super::*;
 use crate::{
     config::SinkContext,
-    event::{MetricKind, MetricValue},
+    event::{MetricKind, MetricValue, StatisticKind},
     sinks::{prometheus::remote_write::config::RemoteWriteConfig, util::test::build_test_server},
     test_util::{
         self,
@@ -229,7 +230,99 @@ async fn doesnt_aggregate_batches() {
     assert_eq!(req.timeseries[0].samples[0].value, 27.0);
 }
 
+#[tokio::test]
+async fn sends_native_histograms() {
+    let outputs = send_request(
+        indoc! {r#"
+            [native_histograms]
+            schema = 0
+            zero_threshold = 0.5
+        "#},
+        vec![Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_lib::samples![0.25 => 1, 3.0 => 2],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .into()],
+    )
+    .await;
+
+    assert_eq!(outputs.len(), 1);
+    let (_, req) = &outputs[0];
+    assert_eq!(req.timeseries.len(), 1);
+    assert_eq!(req.timeseries[0].labels, labels!("__name__" => "latency"));
+    assert!(req.timeseries[0].samples.is_empty());
+    assert_eq!(req.timeseries[0].histograms.len(), 1);
+    let histogram = &req.timeseries[0].histograms[0];
+    assert_eq!(histogram.count, Some(proto::histogram::Count::CountInt(3)));
+    assert_eq!(histogram.positive_deltas, vec![2]);
+    assert_eq!(req.metadata.len(), 1);
+    assert_eq!(req.metadata[0].r#type, proto::MetricType::Histogram as i32);
+}
+
+#[tokio::test]
+async fn sends_v2_request() {
+    let mut event = create_event("gauge-2".into(), 32.0);
+    event
+        .metadata_mut()
+        .value_mut()
+        .insert(path!("prometheus", "help"), "A gauge.");
+
+    let outputs = send_raw_request(r#"protocol_version = "v2""#, vec![event]).await;
+    assert_eq!(outputs.len(), 1);
+    let (headers, body) = &outputs[0];
+    assert_eq!(headers["x-prometheus-remote-write-version"], "2.0.0");
+    assert_eq!(
+        headers["content-type"],
+        "application/x-protobuf;proto=io.prometheus.write.v2.Request"
+    );
+
+    let req = proto_v2::Request::decode(body.clone()).expect("Invalid protobuf");
+    assert_eq!(req.symbols[0], "");
+    assert_eq!(req.timeseries.len(), 1);
+    let series = &req.timeseries[0];
+    let labels = series
+        .labels_refs
+        .iter()
+        .map(|&reference| req.symbols[reference as usize].as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![
+            "__name__",
+            "gauge-2",
+            "production",
+            "true",
+            "region",
+            "us-west-1"
+        ]
+    );
+    assert_eq!(series.samples.len(), 1);
+    assert_eq!(series.samples[0].value, 32.0);
+    let metadata = series.metadata.as_ref().unwrap();
+    assert_eq!(metadata.r#type, proto_v2::MetricType::Gauge as i32);
+    assert_eq!(req.symbols[metadata.help_ref as usize], "A gauge.");
+    assert_eq!(metadata.unit_ref, 0);
+}
+
 async fn send_request(config: &str, events: Vec<Event>) -> Vec<(HeaderMap, proto::WriteRequest)> {
+    send_raw_request(config, events)
+        .await
+        .into_iter()
+        .map(|(headers, body)| {
+            assert_eq!(headers["x-prometheus-remote-write-version"], "0.1.0");
+            assert_eq!(headers["content-type"], "application/x-protobuf");
+            let request = proto::WriteRequest::decode(body).expect("Invalid protobuf");
+            (headers, request)
+        })
+        .collect()
+}
+
+/// Sends the events and returns the headers and decompressed body of each request.
+async fn send_raw_request(config: &str, events: Vec<Event>) -> Vec<(HeaderMap, Bytes)> {
     assert_sink_compliance(&HTTP_SINK_TAGS, async {
         let addr = test_util::next_addr();
         let (rx, trigger, server) = build_test_server(addr);
@@ -248,9 +341,7 @@ async fn send_request(config: &str, events: Vec<Event>) -> Vec<(HeaderMap, proto
             assert_eq!(parts.method, "POST");
             assert_eq!(parts.uri.path(), "/write");
             let headers = parts.headers;
-            assert_eq!(headers["x-prometheus-remote-write-version"], "0.1.0");
             assert_eq!(headers["content-encoding"], "snappy");
-            assert_eq!(headers["content-type"], "application/x-protobuf");
 
             if config.auth.is_some() {
                 assert!(headers.contains_key("authorization"));
@@ -259,9 +350,7 @@ async fn send_request(config: &str, events: Vec<Event>) -> Vec<(HeaderMap, proto
             let decoded = snap::raw::Decoder::new()
                 .decompress_vec(&body)
                 .expect("Invalid snappy compressed data");
-            let request =
-                proto::WriteRequest::decode(Bytes::from(decoded)).expect("Invalid protobuf");
-            (headers, request)
+            (headers, Bytes::from(decoded))
         })
         .collect::<Vec<_>>()
         .await
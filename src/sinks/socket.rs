@@ -152,6 +152,21 @@ impl SinkConfig for SocketSinkConfig {
         Input::new(encoder_input_type & DataType::Log)
     }
 
+    fn encoding_config(&self) -> Option<(EncodingConfigWithFraming, SinkType)> {
+        let encoding = match &self.mode {
+            Mode::Tcp(TcpMode { encoding, .. }) => encoding.clone(),
+            // Each UDP datagram carries a single event, so there is no framing to apply.
+            Mode::Udp(UdpMode { encoding, .. }) => EncodingConfigWithFraming::new(
+                Some(FramingConfig::Bytes),
+                encoding.config().clone(),
+                encoding.transformer(),
+            ),
+            #[cfg(unix)]
+            Mode::Unix(UnixMode { encoding, .. }) => encoding.clone(),
+        };
+        Some((encoding, SinkType::StreamBased))
+    }
+
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
@@ -1,4 +1,4 @@
-use vector_lib::event::EventStatus;
+use vector_lib::event::{EventStatus, Rejection};
 use vector_lib::request_metadata::GroupedCountByteSize;
 use vector_lib::stream::DriverResponse;
 
@@ -6,6 +6,7 @@ pub struct HecResponse {
     pub event_status: EventStatus,
     pub events_count: usize,
     pub events_byte_size: GroupedCountByteSize,
+    pub rejection: Option<Rejection>,
 }
 
 impl AsRef<EventStatus> for HecResponse {
@@ -22,4 +23,8 @@ impl DriverResponse for HecResponse {
     fn events_sent(&self) -> &GroupedCountByteSize {
         &self.events_byte_size
     }
+
+    fn rejection(&self) -> Option<Rejection> {
+        self.rejection.clone()
+    }
 }
@@ -6,7 +6,7 @@ use std::{
 
 use bytes::Bytes;
 use futures_util::future::BoxFuture;
-use http::Request;
+use http::{Request, StatusCode};
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
@@ -25,7 +25,7 @@ use crate::{
     internal_events::{SplunkIndexerAcknowledgementUnavailableError, SplunkResponseParseError},
     sinks::{
         splunk_hec::common::{build_uri, request::HecRequest, response::HecResponse},
-        util::{sink::Response, Compression},
+        util::{http::http_rejection, sink::Response, Compression},
         UriParseSnafu,
     },
 };
@@ -158,11 +158,14 @@ where
             } else {
                 EventStatus::Rejected
             };
+            let rejection = (event_status == EventStatus::Rejected && !response.is_successful())
+                .then(|| http_rejection(response.status(), response.body()));
 
             Ok(HecResponse {
                 event_status,
                 events_count,
                 events_byte_size,
+                rejection,
             })
         })
     }
@@ -170,12 +173,18 @@ where
 
 pub trait ResponseExt {
     fn body(&self) -> &Bytes;
+
+    fn status(&self) -> StatusCode;
 }
 
 impl ResponseExt for http::Response<Bytes> {
     fn body(&self) -> &Bytes {
         self.body()
     }
+
+    fn status(&self) -> StatusCode {
+        self.status()
+    }
 }
 
 pub struct HttpRequestBuilder {
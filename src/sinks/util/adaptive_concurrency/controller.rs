@@ -47,6 +47,7 @@ pub(super) struct Inner {
     next_update: Instant,
     current_rtt: Mean,
     had_back_pressure: bool,
+    throttled: bool,
     reached_limit: bool,
 }
 
@@ -82,6 +83,7 @@ impl<L> Controller<L> {
                 next_update: instant_now(),
                 current_rtt: Default::default(),
                 had_back_pressure: false,
+                throttled: false,
                 reached_limit: false,
             })),
             #[cfg(test)]
@@ -127,9 +129,16 @@ impl<L> Controller<L> {
     }
 
     /// Adjust the controller to a response, based on type of response
-    /// given (backpressure or not) and if it should be used as a valid
-    /// RTT measurement.
-    fn adjust_to_response_inner(&self, start: Instant, is_back_pressure: bool, use_rtt: bool) {
+    /// given (backpressure or not), if the downstream service explicitly
+    /// throttled the request and if it should be used as a valid RTT
+    /// measurement.
+    fn adjust_to_response_inner(
+        &self,
+        start: Instant,
+        is_back_pressure: bool,
+        is_throttled: bool,
+        use_rtt: bool,
+    ) {
         let now = instant_now();
         let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
 
@@ -143,6 +152,14 @@ impl<L> Controller<L> {
             inner.had_back_pressure = true;
         }
 
+        // An explicitly throttled request decreases the concurrency limit right away, instead
+        // of waiting for the end of the current interval, but only once per interval as all
+        // the requests in flight are likely to be throttled as well.
+        if is_throttled && !inner.throttled && self.concurrency.is_none() {
+            inner.throttled = true;
+            self.decrease_limit(&mut inner);
+        }
+
         #[cfg(test)]
         let mut stats = self.stats.lock().expect("Stats mutex is poisoned");
 
@@ -207,6 +224,7 @@ impl<L> Controller<L> {
                     inner.next_update = now + Duration::from_secs_f64(past_rtt.mean);
                     inner.current_rtt = Default::default();
                     inner.had_back_pressure = false;
+                    inner.throttled = false;
                     inner.reached_limit = false;
                 }
             }
@@ -238,15 +256,13 @@ impl<L> Controller<L> {
         }
         // Back pressure responses, either explicit or implicit due
         // to increasing response times, trigger a decrease in the
-        // concurrency limit.
-        else if inner.current_limit > 1
+        // concurrency limit, unless it was already decreased during
+        // this interval because the downstream service explicitly
+        // throttled requests.
+        else if !inner.throttled
             && (inner.had_back_pressure || current_rtt.unwrap_or(0.0) >= past_rtt.mean + threshold)
         {
-            // Decrease (multiplicative) the current concurrency limit
-            let to_forget = inner.current_limit
-                - (inner.current_limit as f64 * self.settings.decrease_ratio) as usize;
-            self.semaphore.forget_permits(to_forget);
-            inner.current_limit -= to_forget;
+            self.decrease_limit(inner);
         }
         self.limit.emit(AdaptiveConcurrencyLimitData {
             concurrency: inner.current_limit as u64,
@@ -257,6 +273,16 @@ impl<L> Controller<L> {
             past_rtt_deviation: Duration::from_secs_f64(past_rtt_deviation),
         });
     }
+
+    /// Decrease (multiplicative) the current concurrency limit.
+    fn decrease_limit(&self, inner: &mut MutexGuard<Inner>) {
+        if inner.current_limit > 1 {
+            let to_forget = inner.current_limit
+                - (inner.current_limit as f64 * self.settings.decrease_ratio) as usize;
+            self.semaphore.forget_permits(to_forget);
+            inner.current_limit -= to_forget;
+        }
+    }
 }
 
 impl<L> Controller<L>
@@ -294,8 +320,17 @@ where
                 }
             }
         };
+        // The downstream service explicitly throttled the request if it asked to wait before
+        // retrying it.
+        let is_throttled = is_back_pressure
+            && match response {
+                Ok(response) => self.logic.retry_after_response(response).is_some(),
+                Err(error) => error
+                    .downcast_ref::<L::Error>()
+                    .is_some_and(|error| self.logic.retry_after_error(error).is_some()),
+            };
         // Only adjust to the RTT when the request was successfully processed.
         let use_rtt = matches!(response_action, Ok(RetryAction::Successful));
-        self.adjust_to_response_inner(start, is_back_pressure, use_rtt)
+        self.adjust_to_response_inner(start, is_back_pressure, is_throttled, use_rtt)
     }
 }
@@ -150,6 +150,7 @@ mod tests {
     #[derive(Clone, Copy, Debug, Snafu)]
     enum TestError {
         Deferral,
+        Throttled,
     }
 
     #[derive(Clone, Copy, Debug)]
@@ -160,6 +161,9 @@ mod tests {
         fn is_retriable_error(&self, _error: &Self::Error) -> bool {
             true
         }
+        fn retry_after_error(&self, error: &Self::Error) -> Option<Duration> {
+            matches!(error, TestError::Throttled).then(|| Duration::from_secs(10))
+        }
     }
 
     type TestInner = AdaptiveConcurrencyLimit<Mock<String, String>, TestRetryLogic>;
@@ -252,6 +256,15 @@ mod tests {
                 TestError::Deferral
             );
         }
+
+        async fn throttle(self) {
+            self.response.send_error(TestError::Throttled);
+            assert_downcast_matches!(
+                self.request.await.unwrap_err(),
+                TestError,
+                TestError::Throttled
+            );
+        }
     }
 
     #[tokio::test]
@@ -315,6 +328,30 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn handles_throttling() {
+        TestService::run(|mut svc| async move {
+            for _ in 0..2 {
+                let req = svc.send(false).await;
+                advance(Duration::from_secs(1)).await;
+                req.respond().await;
+            }
+            assert_eq!(svc.inner().current_limit, 2);
+
+            let req1 = svc.send(true).await;
+            let req2 = svc.send(false).await;
+
+            // The limit is decreased right away, without waiting for the next measurement.
+            req1.throttle().await;
+            assert_eq!(svc.inner().current_limit, 1);
+
+            advance(Duration::from_secs(1)).await;
+            req2.respond().await;
+            assert_eq!(svc.inner().current_limit, 1);
+        })
+        .await;
+    }
+
     #[tokio::test]
     async fn rapid_decrease() {
         TestService::run(|mut svc| async move {
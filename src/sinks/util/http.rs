@@ -20,6 +20,7 @@ use snafu::{ResultExt, Snafu};
 use tower::{Service, ServiceBuilder};
 use tower_http::decompression::DecompressionLayer;
 use vector_lib::configurable::configurable_component;
+use vector_lib::event::Rejection;
 use vector_lib::stream::batcher::limiter::ItemBatchSize;
 use vector_lib::{ByteSizeOf, EstimatedJsonEncodedSizeOf};
 
@@ -451,6 +452,10 @@ impl<T: fmt::Debug> sink::Response for http::Response<T> {
     fn is_transient(&self) -> bool {
         self.status().is_server_error()
     }
+
+    fn rejection(&self) -> Option<Rejection> {
+        Some(http_rejection(self.status(), &[]))
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -479,6 +484,27 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn retry_after_response(&self, response: &Self::Response) -> Option<Duration> {
+        parse_retry_after(response.headers())
+    }
+}
+
+/// Parses the delay requested through the `Retry-After` header of an HTTP response, either as a
+/// number of seconds or as an HTTP date.
+pub fn parse_retry_after(headers: &http::HeaderMap) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means that the request can be retried right away.
+    Some(
+        date.signed_duration_since(chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
 }
 
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
@@ -486,6 +512,7 @@ impl RetryLogic for HttpRetryLogic {
 #[derive(Debug)]
 pub struct HttpStatusRetryLogic<F, T> {
     func: F,
+    retry_after: Option<fn(&T) -> Option<Duration>>,
     request: PhantomData<T>,
 }
 
@@ -497,9 +524,17 @@ where
     pub const fn new(func: F) -> HttpStatusRetryLogic<F, T> {
         HttpStatusRetryLogic {
             func,
+            retry_after: None,
             request: PhantomData,
         }
     }
+
+    /// Sets the function extracting the delay requested by the downstream service before
+    /// retrying a response, for example with [`parse_retry_after`].
+    pub const fn with_retry_after(mut self, retry_after: fn(&T) -> Option<Duration>) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
 }
 
 impl<F, T> RetryLogic for HttpStatusRetryLogic<F, T>
@@ -529,6 +564,11 @@ where
             _ => RetryAction::DontRetry(format!("Http status: {}", status).into()),
         }
     }
+
+    fn retry_after_response(&self, response: &T) -> Option<Duration> {
+        self.retry_after
+            .and_then(|retry_after| retry_after(response))
+    }
 }
 
 impl<F, T> Clone for HttpStatusRetryLogic<F, T>
@@ -538,6 +578,7 @@ where
     fn clone(&self) -> Self {
         Self {
             func: self.func.clone(),
+            retry_after: self.retry_after,
             request: PhantomData,
         }
     }
@@ -607,34 +648,47 @@ pub fn validate_headers(
 
 /// Request type for use in the `Service` implementation of HTTP stream sinks.
 #[derive(Clone)]
-pub struct HttpRequest {
+pub struct HttpRequest<T: Send> {
     payload: Bytes,
     finalizers: EventFinalizers,
     request_metadata: RequestMetadata,
+    additional_metadata: T,
 }
 
-impl HttpRequest {
+impl<T: Send> HttpRequest<T> {
     /// Creates a new `HttpRequest`.
     pub fn new(
         payload: Bytes,
         finalizers: EventFinalizers,
         request_metadata: RequestMetadata,
+        additional_metadata: T,
     ) -> Self {
         Self {
             payload,
             finalizers,
             request_metadata,
+            additional_metadata,
         }
     }
+
+    /// The sink specific metadata of the request, such as its partition key.
+    pub const fn get_additional_metadata(&self) -> &T {
+        &self.additional_metadata
+    }
+
+    /// Takes the payload of the request, leaving it empty.
+    pub fn take_payload(&mut self) -> Bytes {
+        std::mem::take(&mut self.payload)
+    }
 }
 
-impl Finalizable for HttpRequest {
+impl<T: Send> Finalizable for HttpRequest<T> {
     fn take_finalizers(&mut self) -> EventFinalizers {
         self.finalizers.take_finalizers()
     }
 }
 
-impl MetaDescriptive for HttpRequest {
+impl<T: Send> MetaDescriptive for HttpRequest<T> {
     fn get_metadata(&self) -> &RequestMetadata {
         &self.request_metadata
     }
@@ -644,7 +698,7 @@ impl MetaDescriptive for HttpRequest {
     }
 }
 
-impl ByteSizeOf for HttpRequest {
+impl<T: Send> ByteSizeOf for HttpRequest<T> {
     fn allocated_bytes(&self) -> usize {
         self.payload.allocated_bytes() + self.finalizers.allocated_bytes()
     }
@@ -675,6 +729,31 @@ impl DriverResponse for HttpResponse {
     fn bytes_sent(&self) -> Option<usize> {
         Some(self.raw_byte_size)
     }
+
+    fn rejection(&self) -> Option<Rejection> {
+        Some(http_rejection(
+            self.http_response.status(),
+            self.http_response.body(),
+        ))
+    }
+}
+
+/// Maximum number of characters of a response body included in the reason of a `Rejection`.
+const REJECTION_BODY_LIMIT: usize = 1024;
+
+/// Describes why a request was rejected from the downstream service's HTTP response.
+pub fn http_rejection(status: StatusCode, body: &[u8]) -> Rejection {
+    let body = String::from_utf8_lossy(body);
+    let body = match body.char_indices().nth(REJECTION_BODY_LIMIT) {
+        Some((end, _)) => &body[..end],
+        None => &body,
+    };
+    let reason = if body.is_empty() {
+        format!("HTTP status {}", status)
+    } else {
+        format!("HTTP status {}: {}", status, body)
+    };
+    Rejection::new(reason).with_http_status(status.as_u16())
 }
 
 /// Creates a `RetryLogic` for use with `HttpResponse`.
@@ -683,6 +762,7 @@ pub fn http_response_retry_logic() -> HttpStatusRetryLogic<
     HttpResponse,
 > {
     HttpStatusRetryLogic::new(|req: &HttpResponse| req.http_response.status())
+        .with_retry_after(|req: &HttpResponse| parse_retry_after(req.http_response.headers()))
 }
 
 /// Uses the estimated json encoded size to determine batch sizing.
@@ -696,30 +776,31 @@ impl ItemBatchSize<Event> for HttpJsonBatchSizer {
 }
 
 /// HTTP request builder for HTTP stream sinks using the generic `HttpService`
-pub trait HttpServiceRequestBuilder {
-    fn build(&self, body: Bytes) -> Request<Bytes>;
+pub trait HttpServiceRequestBuilder<T: Send> {
+    fn build(&self, request: HttpRequest<T>) -> Result<Request<Bytes>, crate::Error>;
 }
 
 /// Generic 'Service' implementation for HTTP stream sinks.
 #[derive(Clone)]
-pub struct HttpService<B> {
+pub struct HttpService<B, T: Send> {
     batch_service:
-        HttpBatchService<BoxFuture<'static, Result<Request<Bytes>, crate::Error>>, HttpRequest>,
+        HttpBatchService<BoxFuture<'static, Result<Request<Bytes>, crate::Error>>, HttpRequest<T>>,
     _phantom: PhantomData<B>,
 }
 
-impl<B> HttpService<B>
+impl<B, T> HttpService<B, T>
 where
-    B: HttpServiceRequestBuilder + std::marker::Sync + std::marker::Send + 'static,
+    B: HttpServiceRequestBuilder<T> + std::marker::Sync + std::marker::Send + 'static,
+    T: Send + 'static,
 {
     pub fn new(http_client: HttpClient<Body>, http_request_builder: B) -> Self {
         let http_request_builder = Arc::new(http_request_builder);
 
-        let batch_service = HttpBatchService::new(http_client, move |req: HttpRequest| {
+        let batch_service = HttpBatchService::new(http_client, move |req: HttpRequest<T>| {
             let request_builder = Arc::clone(&http_request_builder);
 
             let fut: BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>> =
-                Box::pin(async move { Ok(request_builder.build(req.payload)) });
+                Box::pin(async move { request_builder.build(req) });
 
             fut
         });
@@ -730,9 +811,10 @@ where
     }
 }
 
-impl<B> Service<HttpRequest> for HttpService<B>
+impl<B, T> Service<HttpRequest<T>> for HttpService<B, T>
 where
-    B: HttpServiceRequestBuilder + std::marker::Sync + std::marker::Send + 'static,
+    B: HttpServiceRequestBuilder<T> + std::marker::Sync + std::marker::Send + 'static,
+    T: Send + 'static,
 {
     type Response = HttpResponse;
     type Error = crate::Error;
@@ -742,7 +824,7 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, mut request: HttpRequest) -> Self::Future {
+    fn call(&mut self, mut request: HttpRequest<T>) -> Self::Future {
         let mut http_service = self.batch_service.clone();
 
         // NOTE: By taking the metadata here, when passing the request to `call()` below,
@@ -795,6 +877,40 @@ mod test {
             .is_not_retryable());
     }
 
+    #[test]
+    fn util_http_retry_after() {
+        let logic = HttpRetryLogic;
+
+        let response = Response::builder()
+            .status(429)
+            .header("Retry-After", "120")
+            .body(Bytes::new())
+            .unwrap();
+        assert_eq!(
+            logic.retry_after_response(&response),
+            Some(Duration::from_secs(120))
+        );
+
+        let response = Response::builder()
+            .status(503)
+            .header("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT")
+            .body(Bytes::new())
+            .unwrap();
+        assert_eq!(logic.retry_after_response(&response), Some(Duration::ZERO));
+
+        let date = (chrono::Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+        let response = Response::builder()
+            .status(503)
+            .header("Retry-After", date)
+            .body(Bytes::new())
+            .unwrap();
+        let retry_after = logic.retry_after_response(&response).unwrap();
+        assert!(retry_after > Duration::from_secs(50) && retry_after <= Duration::from_secs(60));
+
+        let response = Response::builder().status(429).body(Bytes::new()).unwrap();
+        assert_eq!(logic.retry_after_response(&response), None);
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
@@ -14,6 +14,8 @@ pub mod partitioner;
 pub mod processed_event;
 pub mod request_builder;
 pub mod retries;
+#[cfg(any(feature = "sinks-clickhouse", feature = "sinks-postgres"))]
+pub mod schema_cache;
 pub mod service;
 pub mod sink;
 pub mod snappy;
@@ -41,6 +41,25 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// When the Service call returns a retriable `Err` response, this function allows
+    /// implementors to specify the delay the downstream service asked to wait for before
+    /// retrying, for example through a `Retry-After` header.
+    ///
+    /// This delay is used instead of the backoff of the retry policy, and signals to the
+    /// adaptive concurrency controller that the downstream service is throttling requests.
+    fn retry_after_error(&self, _error: &Self::Error) -> Option<Duration> {
+        None
+    }
+
+    /// When the Service call returns an `Ok` response that should be retried, this function
+    /// allows implementors to specify the delay the downstream service asked to wait for
+    /// before retrying, for example through a `Retry-After` header.
+    ///
+    /// See [`RetryLogic::retry_after_error`].
+    fn retry_after_response(&self, _response: &Self::Response) -> Option<Duration> {
+        None
+    }
 }
 
 /// The jitter mode to use for retry backoff behavior.
@@ -126,12 +145,24 @@ impl<L: RetryLogic> FibonacciRetryPolicy<L> {
         }
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    /// The delay before the next retry, which is the delay requested by the downstream service
+    /// if any, capped to the maximum backoff, or the current backoff otherwise.
+    fn delay(&self, retry_after: Option<Duration>) -> Duration {
+        retry_after.map_or_else(
+            || self.backoff(),
+            |retry_after| cmp::min(retry_after, self.max_duration),
+        )
+    }
+
+    fn build_retry(&self, retry_after: Option<Duration>) -> RetryPolicyFuture<L> {
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let delay = self.delay(retry_after);
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
-        RetryPolicyFuture { delay, policy }
+        debug!(message = "Retrying request.", delay_ms = %delay.as_millis());
+        RetryPolicyFuture {
+            delay: Box::pin(sleep(delay)),
+            policy,
+        }
     }
 }
 
@@ -158,7 +189,7 @@ where
                     }
 
                     warn!(message = "Retrying after response.", reason = %reason, internal_log_rate_limit = true);
-                    Some(self.build_retry())
+                    Some(self.build_retry(self.logic.retry_after_response(response)))
                 }
 
                 RetryAction::DontRetry(reason) => {
@@ -177,7 +208,7 @@ where
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
                     if self.logic.is_retriable_error(expected) {
                         warn!(message = "Retrying after error.", error = %expected, internal_log_rate_limit = true);
-                        Some(self.build_retry())
+                        Some(self.build_retry(self.logic.retry_after_error(expected)))
                     } else {
                         error!(
                             message = "Non-retriable error; dropping the request.",
@@ -191,7 +222,7 @@ where
                         message = "Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.",
                         internal_log_rate_limit = true
                     );
-                    Some(self.build_retry())
+                    Some(self.build_retry(None))
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -410,6 +441,61 @@ mod tests {
         assert_eq!(fut.await.unwrap(), "world");
     }
 
+    #[tokio::test]
+    async fn retry_after_response() {
+        trace_init();
+
+        time::pause();
+
+        let policy = FibonacciRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(60),
+            SvcRetryLogic,
+            JitterMode::None,
+        );
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_response("throttled");
+        assert_pending!(fut.poll());
+
+        // The delay requested by the service is used instead of the backoff.
+        time::advance(Duration::from_secs(29)).await;
+        assert_pending!(fut.poll());
+        assert_pending!(task::spawn(handle.next_request()).poll());
+
+        time::advance(Duration::from_secs(2)).await;
+        assert_pending!(fut.poll());
+
+        assert_request_eq!(handle, "hello").send_response("world");
+        assert_eq!(fut.await.unwrap(), "world");
+    }
+
+    #[test]
+    fn retry_after_is_capped_to_max_duration() {
+        let policy = FibonacciRetryPolicy::new(
+            10,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+            JitterMode::None,
+        );
+
+        assert_eq!(Duration::from_secs(1), policy.delay(None));
+        assert_eq!(
+            Duration::from_secs(5),
+            policy.delay(Some(Duration::from_secs(5)))
+        );
+        assert_eq!(
+            Duration::from_secs(10),
+            policy.delay(Some(Duration::from_secs(30)))
+        );
+    }
+
     #[test]
     fn backoff_grows_to_max() {
         let mut policy = FibonacciRetryPolicy::new(
@@ -496,6 +582,17 @@ mod tests {
         fn is_retriable_error(&self, error: &Self::Error) -> bool {
             error.0
         }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            match *response {
+                "throttled" => RetryAction::Retry("throttled".into()),
+                _ => RetryAction::Successful,
+            }
+        }
+
+        fn retry_after_response(&self, response: &Self::Response) -> Option<Duration> {
+            (*response == "throttled").then(|| Duration::from_secs(30))
+        }
     }
 
     #[derive(Debug)]
@@ -0,0 +1,181 @@
+//! Caching of the table schemas that sinks convert events with.
+
+use std::{
+    cmp,
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use super::service::TowerRequestSettings;
+
+/// How long a schema is used before it is fetched again, so that changes to a table are picked up
+/// and the tables that stopped receiving events are forgotten.
+const MAX_AGE: Duration = Duration::from_secs(300);
+
+/// Fetches the schema of a table from the database.
+#[async_trait]
+pub trait FetchSchema: Send + Sync {
+    /// Identifies a table.
+    type Key: Clone + Eq + Hash + Send + Sync;
+    type Schema: Send + Sync;
+    type Error: fmt::Display + Send;
+
+    async fn fetch(&self, key: &Self::Key) -> Result<Self::Schema, Self::Error>;
+
+    /// Whether fetching the schema again can succeed, such as after a connection error.
+    fn is_retriable_error(&self, error: &Self::Error) -> bool;
+}
+
+/// Caches the schema of each table, fetching it the first time it is needed.
+///
+/// Schemas are fetched again once they are older than five minutes, or once they are invalidated
+/// because inserting into their table failed. Failed fetches are retried with the retry settings
+/// of the sink's requests.
+pub struct SchemaCache<F: FetchSchema> {
+    fetcher: F,
+    retry_attempts: usize,
+    retry_initial_backoff: Duration,
+    retry_max_duration: Duration,
+    schemas: Mutex<HashMap<F::Key, (Instant, Arc<F::Schema>)>>,
+}
+
+impl<F: FetchSchema> SchemaCache<F> {
+    pub fn new(fetcher: F, request: &TowerRequestSettings) -> Self {
+        Self {
+            fetcher,
+            retry_attempts: request.retry_attempts,
+            retry_initial_backoff: request.retry_initial_backoff,
+            retry_max_duration: request.retry_max_duration,
+            schemas: Mutex::default(),
+        }
+    }
+
+    pub async fn get(&self, key: &F::Key) -> Result<Arc<F::Schema>, F::Error> {
+        let cached = self
+            .schemas
+            .lock()
+            .unwrap()
+            .get(key)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < MAX_AGE)
+            .map(|(_, schema)| Arc::clone(schema));
+        if let Some(schema) = cached {
+            return Ok(schema);
+        }
+
+        let schema = Arc::new(self.fetch(key).await?);
+        let now = Instant::now();
+        let mut schemas = self.schemas.lock().unwrap();
+        schemas.retain(|_, (fetched_at, _)| now.duration_since(*fetched_at) < MAX_AGE);
+        schemas.insert(key.clone(), (now, Arc::clone(&schema)));
+        Ok(schema)
+    }
+
+    /// Forgets the schema of a table, so that it is fetched again the next time it is needed.
+    pub fn invalidate(&self, key: &F::Key) {
+        self.schemas.lock().unwrap().remove(key);
+    }
+
+    async fn fetch(&self, key: &F::Key) -> Result<F::Schema, F::Error> {
+        let mut remaining_attempts = self.retry_attempts;
+        let mut backoff = self.retry_initial_backoff;
+        loop {
+            match self.fetcher.fetch(key).await {
+                Err(error) if remaining_attempts > 0 && self.fetcher.is_retriable_error(&error) => {
+                    warn!(
+                        message = "Retrying after failing to fetch the schema of a table.",
+                        %error,
+                        internal_log_rate_limit = true,
+                    );
+                    remaining_attempts -= 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff = cmp::min(backoff * 2, self.retry_max_duration);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<F: FetchSchema> fmt::Debug for SchemaCache<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemaCache").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::sinks::util::TowerRequestConfig;
+
+    struct CountingFetcher(AtomicUsize);
+
+    #[async_trait]
+    impl FetchSchema for CountingFetcher {
+        type Key = String;
+        type Schema = usize;
+        type Error = &'static str;
+
+        async fn fetch(&self, key: &String) -> Result<usize, &'static str> {
+            let count = self.0.fetch_add(1, Ordering::Relaxed);
+            match key.as_str() {
+                "" => Err("missing"),
+                // Fails on the first attempt only.
+                "flaky" if count == 0 => Err("unavailable"),
+                _ => Ok(count),
+            }
+        }
+
+        fn is_retriable_error(&self, error: &&'static str) -> bool {
+            *error == "unavailable"
+        }
+    }
+
+    fn cache() -> SchemaCache<CountingFetcher> {
+        let request: TowerRequestConfig = Default::default();
+        SchemaCache::new(
+            CountingFetcher(AtomicUsize::new(0)),
+            &request.into_settings(),
+        )
+    }
+
+    #[tokio::test]
+    async fn fetches_each_table_once() {
+        let cache = cache();
+
+        assert_eq!(*cache.get(&"a".to_string()).await.unwrap(), 0);
+        assert_eq!(*cache.get(&"b".to_string()).await.unwrap(), 1);
+        assert_eq!(*cache.get(&"a".to_string()).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn fetches_invalidated_tables_again() {
+        let cache = cache();
+
+        assert_eq!(*cache.get(&"a".to_string()).await.unwrap(), 0);
+        cache.invalidate(&"a".to_string());
+        assert_eq!(*cache.get(&"a".to_string()).await.unwrap(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_retriable_errors() {
+        let cache = cache();
+
+        assert_eq!(*cache.get(&"flaky".to_string()).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn fetches_failed_tables_again() {
+        let cache = cache();
+
+        assert!(cache.get(&String::new()).await.is_err());
+        assert!(cache.schemas.lock().unwrap().is_empty());
+        assert_eq!(cache.fetcher.0.load(Ordering::Relaxed), 1);
+    }
+}
@@ -141,6 +141,9 @@ pub struct TowerRequestConfig<D: TowerRequestConfigDefaults = GlobalTowerRequest
     pub retry_attempts: usize,
 
     /// The maximum amount of time to wait between retries.
+    ///
+    /// This also caps the delay requested by the downstream service before retrying, for example
+    /// through a `Retry-After` header.
     #[configurable(metadata(docs::type_unit = "seconds"))]
     #[configurable(metadata(docs::human_name = "Max Retry Duration"))]
     #[serde(default = "default_retry_max_duration_secs::<D>")]
@@ -60,7 +60,7 @@ use super::{
     service::{Map, ServiceBuilderExt},
     EncodedEvent,
 };
-use crate::event::EventStatus;
+use crate::event::{EventStatus, Rejection};
 
 // === BatchSink ===
 
@@ -447,7 +447,16 @@ where
             .err_into()
             .map(move |result| {
                 let status = result_status(&result);
-                finalizers.update_status(status);
+                match &result {
+                    Ok(response) if status == EventStatus::Rejected => {
+                        finalizers.reject(
+                            &response
+                                .rejection()
+                                .unwrap_or_else(|| Rejection::new("Response failed.")),
+                        );
+                    }
+                    _ => finalizers.update_status(status),
+                }
                 match status {
                     EventStatus::Delivered => {
                         events_sent.emit(CountByteSize(count, json_byte_size));
@@ -560,6 +569,12 @@ pub trait Response: fmt::Debug {
     fn is_transient(&self) -> bool {
         true
     }
+
+    /// Describes why the response was rejected, for responses that are neither successful nor
+    /// transient.
+    fn rejection(&self) -> Option<Rejection> {
+        None
+    }
 }
 
 impl Response for () {}
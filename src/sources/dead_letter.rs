@@ -0,0 +1,142 @@
+use tokio::sync::broadcast::{self, error::RecvError};
+use vector_lib::codecs::NativeDeserializerConfig;
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ComponentEventsDropped, CountByteSize, EventsReceived, InternalEventHandle as _, UNINTENTIONAL,
+};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use crate::{
+    config::{ComponentKey, DataType, SourceConfig, SourceContext, SourceOutput},
+    dead_letter::DeadLetter,
+    internal_events::StreamClosedError,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `dead_letter` source.
+#[configurable_component(source(
+    "dead_letter",
+    "Receive the events rejected by sinks with dead-lettering enabled."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DeadLetterConfig {
+    /// The IDs of the sinks to receive rejected events from.
+    ///
+    /// By default, the events rejected by all sinks with `dead_letter.enabled` set are received.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "elasticsearch_out"))]
+    sinks: Vec<ComponentKey>,
+}
+
+impl_generate_config_from_default!(DeadLetterConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "dead_letter")]
+impl SourceConfig for DeadLetterConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let receiver = cx.dead_letters.subscribe();
+
+        Ok(Box::pin(run(
+            self.sinks.clone(),
+            receiver,
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        // Rejected events are forwarded as they were received by the sink, so nothing is known
+        // about their schema.
+        let schema_definition = NativeDeserializerConfig.schema_definition(global_log_namespace);
+
+        vec![SourceOutput::new_logs(DataType::all(), schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    sinks: Vec<ComponentKey>,
+    mut receiver: broadcast::Receiver<DeadLetter>,
+    mut out: SourceSender,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+
+    loop {
+        let dead_letter = tokio::select! {
+            _ = &mut shutdown => break,
+            dead_letter = receiver.recv() => dead_letter,
+        };
+
+        match dead_letter {
+            Ok(DeadLetter { sink, event }) => {
+                if !sinks.is_empty() && !sinks.contains(&sink) {
+                    continue;
+                }
+
+                events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
+
+                if out.send_event(event).await.is_err() {
+                    emit!(StreamClosedError { count: 1 });
+                    return Err(());
+                }
+            }
+            Err(RecvError::Lagged(count)) => {
+                emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                    count: count as usize,
+                    reason: "Source fell behind the rejected events.",
+                });
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::event::{BatchNotifier, Event, EventArray, LogEvent, Rejection};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DeadLetterConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_events_from_selected_sinks() {
+        let (tx, rx) = SourceSender::new_test();
+        let config = DeadLetterConfig {
+            sinks: vec![ComponentKey::from("selected")],
+        };
+        let cx = SourceContext::new_test(tx, None);
+        let dead_letters = cx.dead_letters.clone();
+        let source = config.build(cx).await.unwrap();
+        tokio::spawn(source);
+
+        for sink in ["ignored", "selected"] {
+            let (batch, _receiver) = BatchNotifier::new_with_receiver();
+            let event = LogEvent::from(sink).with_batch_notifier(&batch);
+            let events = dead_letters.track(&ComponentKey::from(sink), event.into());
+            let EventArray::Logs(mut logs) = events else {
+                panic!("expected logs");
+            };
+            let mut event = Event::from(logs.pop().unwrap());
+            event
+                .take_finalizers()
+                .reject(&Rejection::new("invalid event"));
+        }
+
+        let events = rx.take(1).collect::<Vec<_>>().await;
+        assert_eq!(events[0].as_log()["message"], "selected".into());
+    }
+}
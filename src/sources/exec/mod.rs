@@ -271,6 +271,7 @@ impl SourceConfig for ExecConfig {
                     self.clone(),
                     hostname,
                     exec_interval_secs,
+                    cx.batch,
                     decoder,
                     cx.shutdown,
                     cx.out,
@@ -278,7 +279,8 @@ impl SourceConfig for ExecConfig {
                 )))
             }
             Mode::Streaming => {
-                let respawn_on_exit = self.respawn_on_exit_or_default();
+                // In batch mode, the source stops once the command exits.
+                let respawn_on_exit = self.respawn_on_exit_or_default() && !cx.batch;
                 let respawn_interval_secs = self.respawn_interval_secs_or_default();
 
                 Ok(Box::pin(run_streaming(
@@ -346,10 +348,12 @@ impl SourceConfig for ExecConfig {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_scheduled(
     config: ExecConfig,
     hostname: Option<String>,
     exec_interval_secs: u64,
+    run_once: bool,
     decoder: Decoder,
     shutdown: ShutdownSignal,
     out: SourceSender,
@@ -392,6 +396,10 @@ async fn run_scheduled(
                 });
             }
         }
+
+        if run_once {
+            break;
+        }
     }
 
     debug!("Finished scheduled exec runs.");
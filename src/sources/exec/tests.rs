@@ -379,6 +379,38 @@ async fn test_run_command_linux() {
     }
 }
 
+#[tokio::test]
+#[cfg(unix)]
+async fn test_run_scheduled_once() {
+    let config = standard_scheduled_test_config();
+    let (tx, rx) = SourceSender::new_test();
+
+    // The command only runs once, so the source stops well before the next scheduled run.
+    tokio::time::timeout(
+        time::Duration::from_secs(5),
+        run_scheduled(
+            config,
+            None,
+            60,
+            true,
+            Default::default(),
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ),
+    )
+    .await
+    .expect("source didn't stop after the first run")
+    .unwrap();
+
+    let events = rx.collect::<Vec<_>>().await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        *events[0].as_log().get_message().unwrap(),
+        "Hello World!".into()
+    );
+}
+
 #[tokio::test]
 #[cfg(unix)]
 async fn test_graceful_shutdown() {
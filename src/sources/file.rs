@@ -328,6 +328,29 @@ pub enum FingerprintConfig {
     /// [inode]: https://en.wikipedia.org/wiki/Inode
     #[serde(rename = "device_and_inode")]
     DevInode,
+
+    /// Use the [device and inode][inode] together with a checksum of the first lines of the file as the identifier.
+    ///
+    /// Unlike `checksum`, files sharing the same leading lines (such as a common banner) are kept apart, and unlike
+    /// `device_and_inode`, a reused inode is not mistaken for the file that previously had it. A file that is truncated
+    /// in place is read again from the beginning, and the copy made by a copy-truncate rotation resumes from the
+    /// position reached in the original file.
+    ///
+    /// [inode]: https://en.wikipedia.org/wiki/Inode
+    #[serde(rename = "device_inode_and_checksum")]
+    DevInodeChecksum {
+        /// The number of bytes to skip ahead (or ignore) when reading the data used for generating the checksum.
+        #[serde(default)]
+        #[configurable(metadata(docs::type_unit = "bytes"))]
+        ignored_header_bytes: usize,
+
+        /// The number of lines to read for generating the checksum.
+        ///
+        /// If the file has less than this amount of lines, it won’t be read at all.
+        #[serde(default = "default_lines")]
+        #[configurable(metadata(docs::type_unit = "lines"))]
+        lines: usize,
+    },
 }
 
 impl Default for FingerprintConfig {
@@ -366,6 +389,13 @@ impl From<FingerprintConfig> for FingerprintStrategy {
                 }
             }
             FingerprintConfig::DevInode => FingerprintStrategy::DevInode,
+            FingerprintConfig::DevInodeChecksum {
+                ignored_header_bytes,
+                lines,
+            } => FingerprintStrategy::DevInodeChecksum {
+                ignored_header_bytes,
+                lines,
+            },
         }
     }
 }
@@ -447,6 +477,7 @@ impl SourceConfig for FileConfig {
             cx.out,
             acknowledgements,
             log_namespace,
+            cx.batch,
         ))
     }
 
@@ -500,6 +531,7 @@ pub fn file_source(
     mut out: SourceSender,
     acknowledgements: bool,
     log_namespace: LogNamespace,
+    stop_at_eof: bool,
 ) -> super::Source {
     // the include option must be specified but also must contain at least one entry.
     if config.include.is_empty() {
@@ -554,6 +586,7 @@ pub fn file_source(
         },
         oldest_first: config.oldest_first,
         remove_after: config.remove_after_secs.map(Duration::from_secs),
+        stop_at_eof,
         emitter,
         handle: tokio::runtime::Handle::current(),
     };
@@ -571,6 +604,9 @@ pub fn file_source(
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
 
+    // Signals that all the lines read by the file server have been sent.
+    let (lines_sent, lines_sent_rx) = oneshot::channel::<()>();
+
     let (finalizer, shutdown_checkpointer) = if acknowledgements {
         // The shutdown sent in to the finalizer is the global
         // shutdown handle used to tell it to stop accepting new batch
@@ -592,6 +628,20 @@ pub fn file_source(
             send_shutdown.send(())
         });
         (Some(finalizer), shutdown2.map(|_| ()).boxed())
+    } else if stop_at_eof {
+        // When stopping at the end of the files, the global shutdown only comes after the file
+        // server has stopped, so the checkpoint writer also stops once all the lines are sent.
+        let shutdown = shutdown.clone();
+        (
+            None,
+            async move {
+                tokio::select! {
+                    _ = shutdown => {},
+                    _ = lines_sent_rx => {},
+                }
+            }
+            .boxed(),
+        )
     } else {
         // When not dealing with end-to-end acknowledgements, just
         // clone the global shutdown to stop the checkpoint writer.
@@ -682,6 +732,7 @@ pub fn file_source(
                     emit!(StreamClosedError { count });
                 }
             }
+            drop(lines_sent);
         });
 
         let span = info_span!("file_server");
@@ -907,6 +958,23 @@ mod tests {
         .unwrap();
         assert_eq!(config.fingerprint, FingerprintConfig::DevInode);
 
+        let config: FileConfig = toml::from_str(
+            r#"
+        include = [ "/var/log/**/*.log" ]
+        [fingerprint]
+        strategy = "device_inode_and_checksum"
+        lines = 2
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.fingerprint,
+            FingerprintConfig::DevInodeChecksum {
+                ignored_header_bytes: 0,
+                lines: 2
+            }
+        );
+
         let config: FileConfig = toml::from_str(
             r#"
         include = [ "/var/log/**/*.log" ]
@@ -1195,6 +1263,48 @@ mod tests {
         assert_eq!(goodbye_i, n);
     }
 
+    #[tokio::test]
+    async fn file_stop_at_eof() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        for i in 0..5 {
+            writeln!(&mut file, "line {}", i).unwrap();
+        }
+
+        let received = assert_source_compliance(&FILE_SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+
+            // The source stops by itself once the file is read to its end.
+            let source = file::file_source(
+                &config,
+                dir.path().to_path_buf(),
+                ShutdownSignal::noop(),
+                tx,
+                false,
+                LogNamespace::Legacy,
+                true,
+            );
+            timeout(Duration::from_secs(5), source)
+                .await
+                .expect("Source didn't stop at the end of the file.")
+                .unwrap();
+
+            rx.collect::<Vec<_>>().await
+        })
+        .await;
+
+        assert_eq!(
+            extract_messages_string(received),
+            (0..5).map(|i| format!("line {}", i)).collect::<Vec<_>>()
+        );
+    }
+
     // https://github.com/vectordotdev/vector/issues/8363
     #[tokio::test]
     async fn file_read_empty_lines() {
@@ -2328,6 +2438,7 @@ mod tests {
                 tx,
                 acks,
                 log_namespace,
+                false,
             ));
 
             inner.await;
@@ -8,6 +8,7 @@ use vector_lib::configurable::configurable_component;
 use vector_lib::lookup::lookup_v2::OptionalValuePath;
 
 use crate::{
+    codecs::DecodingConfig,
     config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
     serde::default_decoding,
 };
@@ -90,6 +91,12 @@ impl SourceConfig for FileDescriptorSourceConfig {
         outputs(log_namespace, &self.host_key, &self.decoding, Self::NAME)
     }
 
+    fn decoding_config(&self, global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        Some(self.decoding_with_framing(log_namespace))
+    }
+
     fn resources(&self) -> Vec<Resource> {
         vec![Resource::Fd(self.fd)]
     }
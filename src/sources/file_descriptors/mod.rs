@@ -40,6 +40,16 @@ pub trait FileDescriptorConfig: NamedComponent {
     fn decoding(&self) -> DeserializerConfig;
     fn description(&self) -> String;
 
+    /// Gets the decoding configuration, falling back to the default stream framing for the
+    /// configured codec when no framing is set.
+    fn decoding_with_framing(&self, log_namespace: LogNamespace) -> DecodingConfig {
+        let decoding = self.decoding();
+        let framing = self
+            .framing()
+            .unwrap_or_else(|| decoding.default_stream_framing());
+        DecodingConfig::new(framing, decoding, log_namespace)
+    }
+
     fn source<R>(
         &self,
         reader: R,
@@ -58,11 +68,7 @@ pub trait FileDescriptorConfig: NamedComponent {
 
         let description = self.description();
 
-        let decoding = self.decoding();
-        let framing = self
-            .framing()
-            .unwrap_or_else(|| decoding.default_stream_framing());
-        let decoder = DecodingConfig::new(framing, decoding, log_namespace).build()?;
+        let decoder = self.decoding_with_framing(log_namespace).build()?;
 
         let (sender, receiver) = mpsc::channel(1024);
 
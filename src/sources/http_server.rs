@@ -208,7 +208,10 @@ impl SimpleHttpConfig {
         schema_definition
     }
 
-    fn get_decoding_config(&self) -> crate::Result<DecodingConfig> {
+    fn get_decoding_config(
+        &self,
+        log_namespace: Option<LogNamespace>,
+    ) -> crate::Result<DecodingConfig> {
         if self.encoding.is_some() && (self.framing.is_some() || self.decoding.is_some()) {
             return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
         }
@@ -241,11 +244,10 @@ impl SimpleHttpConfig {
             (framing, decoding)
         };
 
-        Ok(DecodingConfig::new(
-            framing,
-            decoding,
-            self.log_namespace.unwrap_or(false).into(),
-        ))
+        let log_namespace =
+            log_namespace.unwrap_or_else(|| self.log_namespace.unwrap_or(false).into());
+
+        Ok(DecodingConfig::new(framing, decoding, log_namespace))
     }
 }
 
@@ -288,7 +290,7 @@ impl ValidatableComponent for SimpleHttpConfig {
             ResourceDirection::Push,
             HttpResourceConfig::from_parts(uri, Some(config.method.into())),
             config
-                .get_decoding_config()
+                .get_decoding_config(None)
                 .expect("should not fail to get decoding config"),
         );
 
@@ -354,8 +356,8 @@ fn build_param_matcher(list: &[String]) -> crate::Result<Vec<HttpConfigParamKind
 #[typetag::serde(name = "http_server")]
 impl SourceConfig for SimpleHttpConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let decoder = self.get_decoding_config()?.build()?;
         let log_namespace = cx.log_namespace(self.log_namespace);
+        let decoder = self.get_decoding_config(Some(log_namespace))?.build()?;
 
         let source = SimpleHttpSource {
             headers: build_param_matcher(&remove_duplicates(self.headers.clone(), "headers"))?,
@@ -398,6 +400,11 @@ impl SourceConfig for SimpleHttpConfig {
         vec![Resource::tcp(self.address)]
     }
 
+    fn decoding_config(&self, global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        self.get_decoding_config(Some(log_namespace)).ok()
+    }
+
     fn can_acknowledge(&self) -> bool {
         true
     }
@@ -538,6 +545,7 @@ mod tests {
     use std::str::FromStr;
     use std::{io::Write, net::SocketAddr};
 
+    use bytes::BytesMut;
     use flate2::{
         write::{GzEncoder, ZlibEncoder},
         Compression,
@@ -545,6 +553,7 @@ mod tests {
     use futures::Stream;
     use http::{HeaderMap, Method, StatusCode};
     use similar_asserts::assert_eq;
+    use tokio_util::codec::Decoder as _;
     use vector_lib::codecs::{
         decoding::{DeserializerConfig, FramingConfig},
         BytesDecoderConfig, JsonDeserializerConfig,
@@ -574,6 +583,26 @@ mod tests {
         crate::test_util::test_generate_config::<SimpleHttpConfig>();
     }
 
+    #[test]
+    fn decoding_config_honors_global_log_namespace() {
+        let config = SimpleHttpConfig {
+            decoding: Some(DeserializerConfig::Bytes),
+            ..Default::default()
+        };
+
+        let mut decoder = config
+            .decoding_config(LogNamespace::Vector)
+            .unwrap()
+            .build()
+            .unwrap();
+        let (events, _) = decoder
+            .decode_eof(&mut BytesMut::from("hello"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(*events[0].as_log().value(), Value::from("hello"));
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn source<'a>(
         headers: Vec<String>,
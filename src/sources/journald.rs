@@ -1,14 +1,14 @@
 use std::{
     collections::{HashMap, HashSet},
     io::SeekFrom,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Stdio,
     str::FromStr,
     sync::Arc,
     time::Duration,
 };
 
-use bytes::Bytes;
+use bytes::{Buf, Bytes, BytesMut};
 use chrono::{TimeZone, Utc};
 use futures::{poll, stream::BoxStream, task::Poll, StreamExt};
 use nix::{
@@ -23,6 +23,7 @@ use tokio::{
     io::{self, AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
     process::{Child, Command},
     sync::{Mutex, MutexGuard},
+    task::JoinHandle,
     time::sleep,
 };
 use tokio_util::codec::FramedRead;
@@ -90,6 +91,12 @@ enum BuildError {
         value,
     ))]
     DuplicatedMatches { field: String, value: String },
+    #[snafu(display("Cannot use both `journal_directory` and `journal_files`"))]
+    BothDirectoryAndFiles,
+    #[snafu(display(
+        "Cannot use `export_file` together with `journal_directory`, `journal_files` or `journal_namespace`"
+    ))]
+    ExportFileWithJournal,
 }
 
 type Matches = HashMap<String, HashSet<String>>;
@@ -196,6 +203,28 @@ pub struct JournaldConfig {
     #[serde(default)]
     pub journal_namespace: Option<String>,
 
+    /// A list of journal files to read from.
+    ///
+    /// Each path is passed to `journalctl` through the [`--file` option][journalctl-file-option], and may contain
+    /// glob patterns. This is useful for reading `.journal` files shipped from other machines, in which case
+    /// `current_boot_only` should usually be disabled. Cannot be used together with `journal_directory`.
+    ///
+    /// [journalctl-file-option]: https://www.freedesktop.org/software/systemd/man/journalctl.html#--file=GLOB
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/log/remote/*.journal"))]
+    pub journal_files: Vec<PathBuf>,
+
+    /// The path of a file in the [journal export format][export-format] to read instead of running `journalctl`.
+    ///
+    /// Such files are produced by `journalctl -o export`. The source stops once the end of the file is reached,
+    /// and `current_boot_only`, `since_now` and `extra_args` have no effect. Cannot be used together with
+    /// `journal_directory`, `journal_files`, or `journal_namespace`.
+    ///
+    /// [export-format]: https://systemd.io/JOURNAL_EXPORT_FORMATS/#journal-export-format
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector/replay.export"))]
+    pub export_file: Option<PathBuf>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -245,6 +274,38 @@ impl JournaldConfig {
         Self::merge_units(&self.exclude_matches, &self.exclude_units)
     }
 
+    /// The name of the checkpoint file within the data directory.
+    ///
+    /// Each combination of journal directory, namespace, files, and export file keeps its own cursor, since a
+    /// cursor from one journal is meaningless in another.
+    fn checkpoint_filename(&self) -> String {
+        let mut key = String::new();
+        if let Some(dir) = &self.journal_directory {
+            key.push_str(&format!("directory={}\n", dir.display()));
+        }
+        if let Some(namespace) = &self.journal_namespace {
+            key.push_str(&format!("namespace={}\n", namespace));
+        }
+        for file in &self.journal_files {
+            key.push_str(&format!("file={}\n", file.display()));
+        }
+        if let Some(file) = &self.export_file {
+            key.push_str(&format!("export_file={}\n", file.display()));
+        }
+
+        if key.is_empty() {
+            CHECKPOINT_FILENAME.to_string()
+        } else {
+            format!("checkpoint-{:016x}.txt", seahash::hash(key.as_bytes()))
+        }
+    }
+
+    /// Whether a checkpoint written by a version that shared one checkpoint file across all journals can be
+    /// taken over by this configuration.
+    fn can_use_legacy_checkpoint(&self) -> bool {
+        self.journal_files.is_empty() && self.export_file.is_none()
+    }
+
     fn merge_units(matches: &Matches, units: &[String]) -> Matches {
         let mut matches = matches.clone();
         for unit in units {
@@ -315,6 +376,8 @@ impl Default for JournaldConfig {
             journalctl_path: None,
             journal_directory: None,
             journal_namespace: None,
+            journal_files: vec![],
+            export_file: None,
             extra_args: vec![],
             acknowledgements: Default::default(),
             remap_priority: false,
@@ -357,22 +420,42 @@ impl SourceConfig for JournaldConfig {
             return Err(BuildError::DuplicatedMatches { field, value }.into());
         }
 
-        let mut checkpoint_path = data_dir;
-        checkpoint_path.push(CHECKPOINT_FILENAME);
+        if self.journal_directory.is_some() && !self.journal_files.is_empty() {
+            return Err(BuildError::BothDirectoryAndFiles.into());
+        }
 
-        let journalctl_path = self
-            .journalctl_path
-            .clone()
-            .unwrap_or_else(|| JOURNALCTL.clone());
-
-        let starter = StartJournalctl::new(
-            journalctl_path,
-            self.journal_directory.clone(),
-            self.journal_namespace.clone(),
-            self.current_boot_only,
-            self.since_now,
-            self.extra_args.clone(),
-        );
+        if self.export_file.is_some()
+            && (self.journal_directory.is_some()
+                || self.journal_namespace.is_some()
+                || !self.journal_files.is_empty())
+        {
+            return Err(BuildError::ExportFileWithJournal.into());
+        }
+
+        let checkpoint_path = data_dir.join(self.checkpoint_filename());
+        if self.can_use_legacy_checkpoint() {
+            migrate_legacy_checkpoint(&data_dir, &checkpoint_path);
+        }
+
+        let starter = match &self.export_file {
+            Some(path) => StartJournal::ExportFile(path.clone()),
+            None => {
+                let journalctl_path = self
+                    .journalctl_path
+                    .clone()
+                    .unwrap_or_else(|| JOURNALCTL.clone());
+
+                StartJournal::Journalctl(StartJournalctl::new(
+                    journalctl_path,
+                    self.journal_directory.clone(),
+                    self.journal_namespace.clone(),
+                    self.journal_files.clone(),
+                    self.current_boot_only,
+                    self.since_now,
+                    self.extra_args.clone(),
+                ))
+            }
+        };
 
         let batch_size = self.batch_size;
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
@@ -390,6 +473,7 @@ impl SourceConfig for JournaldConfig {
                 starter,
                 log_namespace,
                 emit_cursor: self.emit_cursor,
+                skip_until_cursor: None,
             }
             .run_shutdown(cx.shutdown),
         ))
@@ -415,9 +499,11 @@ struct JournaldSource {
     remap_priority: bool,
     out: SourceSender,
     acknowledgements: bool,
-    starter: StartJournalctl,
+    starter: StartJournal,
     log_namespace: LogNamespace,
     emit_cursor: bool,
+    /// When reading an export file, entries up to and including this cursor were already processed.
+    skip_until_cursor: Option<String>,
 }
 
 impl JournaldSource {
@@ -458,15 +544,30 @@ impl JournaldSource {
                 break;
             }
 
-            info!("Starting journalctl.");
             let cursor = checkpointer.lock().await.cursor.clone();
-            match self.starter.start(cursor.as_deref()) {
+            match self.starter.start(cursor.as_deref()).await {
                 Ok((stream, running)) => {
+                    if matches!(self.starter, StartJournal::ExportFile(_)) {
+                        self.skip_until_cursor = cursor;
+                    }
                     if !self.run_stream(stream, &finalizer, shutdown.clone()).await {
                         return;
                     }
                     // Explicit drop to ensure it isn't dropped earlier.
                     drop(running);
+
+                    // Unlike journalctl, an export file has a definite end.
+                    if let StartJournal::ExportFile(path) = &self.starter {
+                        if self.skip_until_cursor.is_some() {
+                            warn!(
+                                message = "Checkpoint cursor was not found in export file, no entries were read.",
+                                path = ?path,
+                            );
+                        }
+                        info!(message = "Finished reading export file.", path = ?path);
+                        finalizer.flush().await;
+                        return;
+                    }
                 }
                 Err(error) => {
                     emit!(JournaldStartJournalctlError { error });
@@ -557,7 +658,9 @@ impl<'a> Batch<'a> {
     fn handle_next(&mut self, result: Option<Result<Bytes, BoxedFramingError>>) -> bool {
         match result {
             None => {
-                warn!("Journalctl process stopped.");
+                if matches!(self.source.starter, StartJournal::Journalctl(_)) {
+                    warn!("Journalctl process stopped.");
+                }
                 self.exiting = Some(true);
                 false
             }
@@ -566,8 +669,23 @@ impl<'a> Batch<'a> {
                 false
             }
             Some(Ok(bytes)) => {
-                match decode_record(&bytes, self.source.remap_priority) {
+                let record = match self.source.starter {
+                    StartJournal::Journalctl(_) => {
+                        decode_record(&bytes, self.source.remap_priority)
+                    }
+                    StartJournal::ExportFile(_) => {
+                        decode_export_record(&bytes, self.source.remap_priority)
+                    }
+                };
+                match record {
                     Ok(mut record) => {
+                        if let Some(skip_cursor) = &self.source.skip_until_cursor {
+                            if record.get(CURSOR) == Some(skip_cursor) {
+                                self.source.skip_until_cursor = None;
+                            }
+                            return true;
+                        }
+
                         if self.source.emit_cursor {
                             if let Some(tmp) = record.get(CURSOR) {
                                 self.cursor = Some(tmp.clone());
@@ -642,10 +760,40 @@ impl<'a> Batch<'a> {
 
 type JournalStream = BoxStream<'static, Result<Bytes, BoxedFramingError>>;
 
+/// Where journal entries are read from.
+enum StartJournal {
+    /// Follow the output of a `journalctl` process in JSON format.
+    Journalctl(StartJournalctl),
+    /// Read a file in the journal export format once, up to its end.
+    ExportFile(PathBuf),
+}
+
+impl StartJournal {
+    async fn start(
+        &mut self,
+        checkpoint: Option<&str>,
+    ) -> crate::Result<(JournalStream, Option<RunningJournalctl>)> {
+        match self {
+            Self::Journalctl(starter) => {
+                info!("Starting journalctl.");
+                let (stream, running) = starter.start(checkpoint)?;
+                Ok((stream, Some(running)))
+            }
+            Self::ExportFile(path) => {
+                info!(message = "Reading journal export file.", path = ?path);
+                let file = File::open(&path).await?;
+                let stream = FramedRead::new(file, ExportFormatDecoder).boxed();
+                Ok((stream, None))
+            }
+        }
+    }
+}
+
 struct StartJournalctl {
     path: PathBuf,
     journal_dir: Option<PathBuf>,
     journal_namespace: Option<String>,
+    journal_files: Vec<PathBuf>,
     current_boot_only: bool,
     since_now: bool,
     extra_args: Vec<String>,
@@ -656,6 +804,7 @@ impl StartJournalctl {
         path: PathBuf,
         journal_dir: Option<PathBuf>,
         journal_namespace: Option<String>,
+        journal_files: Vec<PathBuf>,
         current_boot_only: bool,
         since_now: bool,
         extra_args: Vec<String>,
@@ -664,6 +813,7 @@ impl StartJournalctl {
             path,
             journal_dir,
             journal_namespace,
+            journal_files,
             current_boot_only,
             since_now,
             extra_args,
@@ -686,6 +836,10 @@ impl StartJournalctl {
             command.arg(format!("--namespace={}", namespace));
         }
 
+        for file in &self.journal_files {
+            command.arg(format!("--file={}", file.display()));
+        }
+
         if self.current_boot_only {
             command.arg("--boot");
         }
@@ -847,6 +1001,133 @@ fn decode_record(line: &[u8], remap: bool) -> Result<Record, JsonError> {
     serde_json::from_value(record)
 }
 
+/// Decode one entry in the [journal export format][export-format], as split by `ExportFormatDecoder`.
+///
+/// [export-format]: https://systemd.io/JOURNAL_EXPORT_FORMATS/#journal-export-format
+fn decode_export_record(entry: &[u8], remap: bool) -> Result<Record, JsonError> {
+    let mut record = serde_json::Map::new();
+    let mut rest = entry;
+    while let Some(nl) = rest.iter().position(|&c| c == b'\n') {
+        let line = &rest[..nl];
+        if line.is_empty() {
+            break;
+        }
+        match line.iter().position(|&c| c == b'=') {
+            Some(eq) => {
+                let field = String::from_utf8_lossy(&line[..eq]).into_owned();
+                let value = String::from_utf8_lossy(&line[eq + 1..]).into_owned();
+                record.insert(field, JsonValue::String(value));
+                rest = &rest[nl + 1..];
+            }
+            None => {
+                // Binary-safe field: the name is followed by a little-endian 64 bit size, the data, and a newline.
+                // The framing has already been validated by the decoder.
+                let field = String::from_utf8_lossy(line).into_owned();
+                let data_start = nl + 1 + 8;
+                let size =
+                    u64::from_le_bytes(rest[nl + 1..data_start].try_into().unwrap()) as usize;
+                let value =
+                    String::from_utf8_lossy(&rest[data_start..data_start + size]).into_owned();
+                record.insert(field, JsonValue::String(value));
+                rest = &rest[data_start + size + 1..];
+            }
+        }
+    }
+
+    let mut record = JsonValue::Object(record);
+    if remap {
+        record.get_mut("PRIORITY").map(remap_priority);
+    }
+    serde_json::from_value(record)
+}
+
+/// Splits a stream in the journal export format into entries, which are separated by an empty line.
+///
+/// Each field is either `NAME=value` on one line, or, for values that are not plain text, the name on its own line
+/// followed by the value's size as a little-endian 64 bit integer, the value, and a newline.
+struct ExportFormatDecoder;
+
+impl ExportFormatDecoder {
+    /// Find the end of the first complete entry in `buf`, including its terminating empty line.
+    fn entry_len(buf: &[u8]) -> Result<Option<usize>, io::Error> {
+        let mut pos = 0;
+        loop {
+            let Some(nl) = buf[pos..]
+                .iter()
+                .position(|&c| c == b'\n')
+                .map(|nl| pos + nl)
+            else {
+                return Ok(None);
+            };
+            let line = &buf[pos..nl];
+            if line.is_empty() {
+                return Ok(Some(nl + 1));
+            }
+            if line.contains(&b'=') {
+                pos = nl + 1;
+                continue;
+            }
+
+            let data_start = nl + 1 + 8;
+            if buf.len() < data_start {
+                return Ok(None);
+            }
+            let size = u64::from_le_bytes(buf[nl + 1..data_start].try_into().unwrap()) as usize;
+            let data_end = data_start.checked_add(size).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "Invalid binary field size.")
+            })?;
+            match buf.get(data_end) {
+                None => return Ok(None),
+                Some(b'\n') => pos = data_end + 1,
+                Some(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Binary field is not terminated by a newline.",
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl tokio_util::codec::Decoder for ExportFormatDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            match Self::entry_len(src)? {
+                // Skip stray empty lines between entries.
+                Some(1) => src.advance(1),
+                Some(len) => return Ok(Some(src.split_to(len).freeze())),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(entry) => Ok(Some(entry)),
+            // The last entry is not required to be followed by an empty line.
+            None if src.iter().all(|&c| c == b'\n') => {
+                src.clear();
+                Ok(None)
+            }
+            None => {
+                src.extend_from_slice(b"\n\n");
+                match Self::entry_len(src)? {
+                    Some(len) => Ok(Some(src.split_to(len).freeze())),
+                    None => Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Export file ends in the middle of an entry.",
+                    )
+                    .into()),
+                }
+            }
+        }
+    }
+}
+
 fn decode_array(array: &[JsonValue]) -> JsonValue {
     decode_array_as_bytes(array).unwrap_or_else(|| {
         let ser = serde_json::to_string(array).expect("already deserialized");
@@ -924,7 +1205,11 @@ fn find_duplicate_match(a_matches: &Matches, b_matches: &Matches) -> Option<(Str
 
 enum Finalizer {
     Sync(SharedCheckpointer),
-    Async(OrderedFinalizer<String>),
+    Async {
+        finalizer: OrderedFinalizer<String>,
+        /// The task updating the checkpoint as batches are acknowledged.
+        acks: JoinHandle<()>,
+    },
 }
 
 impl Finalizer {
@@ -935,14 +1220,14 @@ impl Finalizer {
     ) -> Self {
         if acknowledgements {
             let (finalizer, mut ack_stream) = OrderedFinalizer::new(Some(shutdown));
-            tokio::spawn(async move {
+            let acks = tokio::spawn(async move {
                 while let Some((status, cursor)) = ack_stream.next().await {
                     if status == BatchStatus::Delivered {
                         checkpointer.lock().await.set(cursor).await;
                     }
                 }
             });
-            Self::Async(finalizer)
+            Self::Async { finalizer, acks }
         } else {
             Self::Sync(checkpointer)
         }
@@ -951,12 +1236,38 @@ impl Finalizer {
     async fn finalize(&self, cursor: String, receiver: Option<BatchStatusReceiver>) {
         match (self, receiver) {
             (Self::Sync(checkpointer), None) => checkpointer.lock().await.set(cursor).await,
-            (Self::Async(finalizer), Some(receiver)) => finalizer.add(cursor, receiver),
+            (Self::Async { finalizer, .. }, Some(receiver)) => finalizer.add(cursor, receiver),
             _ => {
                 unreachable!("Cannot have async finalization without a receiver in journald source")
             }
         }
     }
+
+    /// Waits for the pending batches to be acknowledged, so that the checkpoint is up to date
+    /// once the source finishes.
+    async fn flush(self) {
+        if let Self::Async { finalizer, acks } = self {
+            drop(finalizer);
+            _ = acks.await;
+        }
+    }
+}
+
+/// Move the checkpoint file shared by all journals in earlier versions to the location used by this configuration,
+/// so that configurations using `journal_directory` or `journal_namespace` keep their position.
+fn migrate_legacy_checkpoint(data_dir: &Path, checkpoint_path: &Path) {
+    let legacy_path = data_dir.join(CHECKPOINT_FILENAME);
+    if legacy_path == checkpoint_path || checkpoint_path.exists() || !legacy_path.exists() {
+        return;
+    }
+    if let Err(error) = std::fs::rename(&legacy_path, checkpoint_path) {
+        warn!(
+            message = "Could not migrate journald checkpoint file.",
+            from = ?legacy_path,
+            to = ?checkpoint_path,
+            %error,
+        );
+    }
 }
 
 struct Checkpointer {
@@ -1455,6 +1766,7 @@ mod tests {
 
         let journal_dir = None;
         let journal_namespace = None;
+        let journal_files = vec![];
         let current_boot_only = false;
         let cursor = None;
         let since_now = false;
@@ -1464,6 +1776,7 @@ mod tests {
             &path,
             journal_dir,
             journal_namespace,
+            journal_files,
             current_boot_only,
             since_now,
             cursor,
@@ -1472,11 +1785,13 @@ mod tests {
         let cmd_line = format!("{:?}", command);
         assert!(!cmd_line.contains("--directory="));
         assert!(!cmd_line.contains("--namespace="));
+        assert!(!cmd_line.contains("--file="));
         assert!(!cmd_line.contains("--boot"));
         assert!(cmd_line.contains("--since=2000-01-01"));
 
         let journal_dir = None;
         let journal_namespace = None;
+        let journal_files = vec![];
         let since_now = true;
         let extra_args = vec![];
 
@@ -1484,6 +1799,7 @@ mod tests {
             &path,
             journal_dir,
             journal_namespace,
+            journal_files,
             current_boot_only,
             since_now,
             cursor,
@@ -1502,6 +1818,7 @@ mod tests {
             &path,
             journal_dir,
             journal_namespace,
+            vec![],
             current_boot_only,
             since_now,
             cursor,
@@ -1513,12 +1830,25 @@ mod tests {
         assert!(cmd_line.contains("--boot"));
         assert!(cmd_line.contains("--after-cursor="));
         assert!(cmd_line.contains("--merge"));
+
+        let journal_files = vec![
+            PathBuf::from("/tmp/remote/a.journal"),
+            PathBuf::from("/tmp/remote/b.journal"),
+        ];
+
+        let command = create_command(&path, None, None, journal_files, false, false, None, vec![]);
+        let cmd_line = format!("{:?}", command);
+        assert!(cmd_line.contains("--file=/tmp/remote/a.journal"));
+        assert!(cmd_line.contains("--file=/tmp/remote/b.journal"));
+        assert!(!cmd_line.contains("--directory="));
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn create_command(
         path: &Path,
         journal_dir: Option<PathBuf>,
         journal_namespace: Option<String>,
+        journal_files: Vec<PathBuf>,
         current_boot_only: bool,
         since_now: bool,
         cursor: Option<&str>,
@@ -1528,6 +1858,7 @@ mod tests {
             path.into(),
             journal_dir,
             journal_namespace,
+            journal_files,
             current_boot_only,
             since_now,
             extra_args,
@@ -1535,6 +1866,201 @@ mod tests {
         .make_command(cursor)
     }
 
+    #[test]
+    fn checkpoint_filename_per_journal() {
+        let default = JournaldConfig::default();
+        assert_eq!(default.checkpoint_filename(), CHECKPOINT_FILENAME);
+
+        let directory = JournaldConfig {
+            journal_directory: Some("/var/log/remote".into()),
+            ..Default::default()
+        };
+        let namespace = JournaldConfig {
+            journal_namespace: Some("remote".into()),
+            ..Default::default()
+        };
+        let files = JournaldConfig {
+            journal_files: vec!["/var/log/remote/a.journal".into()],
+            ..Default::default()
+        };
+        let export_file = JournaldConfig {
+            export_file: Some("/var/log/remote/a.export".into()),
+            ..Default::default()
+        };
+
+        let filenames: HashSet<String> = [&default, &directory, &namespace, &files, &export_file]
+            .iter()
+            .map(|config| config.checkpoint_filename())
+            .collect();
+        assert_eq!(filenames.len(), 5);
+        assert_eq!(
+            directory.checkpoint_filename(),
+            directory.clone().checkpoint_filename()
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_checkpoint() {
+        let tempdir = tempdir().unwrap();
+        let legacy_path = tempdir.path().join(CHECKPOINT_FILENAME);
+        fs::write(&legacy_path, "cursor\n").unwrap();
+
+        let config = JournaldConfig {
+            journal_namespace: Some("remote".into()),
+            ..Default::default()
+        };
+        let checkpoint_path = tempdir.path().join(config.checkpoint_filename());
+        migrate_legacy_checkpoint(tempdir.path(), &checkpoint_path);
+
+        assert!(!legacy_path.exists());
+        assert_eq!(fs::read_to_string(checkpoint_path).unwrap(), "cursor\n");
+    }
+
+    #[tokio::test]
+    async fn rejects_conflicting_journal_options() {
+        let (tx, _rx) = SourceSender::new_test();
+        let tempdir = tempdir().unwrap();
+
+        let config = JournaldConfig {
+            journal_directory: Some("/var/log/remote".into()),
+            export_file: Some("/var/log/remote/a.export".into()),
+            data_dir: Some(tempdir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let (cx, _shutdown) = SourceContext::new_shutdown(&ComponentKey::from(TEST_COMPONENT), tx);
+        assert!(config.build(cx).await.is_err());
+    }
+
+    fn export_entry(cursor: &str, message: &[u8], binary: bool) -> Vec<u8> {
+        let mut entry = format!(
+            "__CURSOR={}\n__REALTIME_TIMESTAMP=1578529839140001\n_SYSTEMD_UNIT=unit.service\nPRIORITY=6\n",
+            cursor
+        )
+        .into_bytes();
+        if binary {
+            entry.extend_from_slice(b"MESSAGE\n");
+            entry.extend_from_slice(&(message.len() as u64).to_le_bytes());
+            entry.extend_from_slice(message);
+            entry.push(b'\n');
+        } else {
+            entry.extend_from_slice(b"MESSAGE=");
+            entry.extend_from_slice(message);
+            entry.push(b'\n');
+        }
+        entry.push(b'\n');
+        entry
+    }
+
+    #[test]
+    fn decodes_export_format() {
+        use tokio_util::codec::Decoder;
+
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(&export_entry("1", b"plain message", false));
+        buf.extend_from_slice(&export_entry("2", b"multi\nline=message", true));
+        // The last entry is not terminated by an empty line.
+        let last = export_entry("3", b"last", false);
+        buf.extend_from_slice(&last[..last.len() - 1]);
+
+        let mut decoder = ExportFormatDecoder;
+        let mut records = Vec::new();
+        while let Some(entry) = decoder.decode_eof(&mut buf).unwrap() {
+            records.push(decode_export_record(&entry, true).unwrap());
+        }
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0][MESSAGE], "plain message");
+        assert_eq!(records[0]["PRIORITY"], "INFO");
+        assert_eq!(records[1][MESSAGE], "multi\nline=message");
+        assert_eq!(records[1][CURSOR], "2");
+        assert_eq!(records[2][MESSAGE], "last");
+    }
+
+    #[test]
+    fn export_format_waits_for_complete_entries() {
+        use tokio_util::codec::Decoder;
+
+        let entry = export_entry("1", b"binary message", true);
+        let mut decoder = ExportFormatDecoder;
+        for split in 1..entry.len() {
+            let mut buf = BytesMut::from(&entry[..split]);
+            assert_eq!(
+                decoder.decode(&mut buf).unwrap(),
+                None,
+                "split at {}",
+                split
+            );
+        }
+        let mut buf = BytesMut::from(&entry[..]);
+        assert_eq!(
+            decoder.decode(&mut buf).unwrap().as_deref(),
+            Some(&entry[..])
+        );
+    }
+
+    async fn run_export_file(
+        data_dir: &Path,
+        export_file: &Path,
+        acknowledgements: bool,
+    ) -> Vec<Event> {
+        assert_source_compliance(&["protocol"], async move {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let (cx, _shutdown) =
+                SourceContext::new_shutdown(&ComponentKey::from(TEST_COMPONENT), tx);
+            let config = JournaldConfig {
+                export_file: Some(export_file.into()),
+                data_dir: Some(data_dir.into()),
+                acknowledgements: acknowledgements.into(),
+                ..Default::default()
+            };
+            let source = tokio::spawn(config.build(cx).await.unwrap());
+
+            // The source finishes on its own once the end of the file is reached and the events
+            // are acknowledged.
+            let events = timeout(Duration::from_secs(5), rx.collect()).await.unwrap();
+            timeout(Duration::from_secs(1), source)
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            events
+        })
+        .await
+    }
+
+    async fn reads_export_file(acknowledgements: bool) {
+        let tempdir = tempdir().unwrap();
+        let export_file = tempdir.path().join("journal.export");
+        let mut contents = export_entry("1", b"first", false);
+        contents.extend(export_entry("2", b"second", true));
+        fs::write(&export_file, &contents).unwrap();
+
+        let received = run_export_file(tempdir.path(), &export_file, acknowledgements).await;
+        assert_eq!(received.len(), 2);
+        assert_eq!(message(&received[0]), Value::Bytes("first".into()));
+        assert_eq!(message(&received[1]), Value::Bytes("second".into()));
+        assert_eq!(timestamp(&received[0]), value_ts(1578529839, 140001000));
+
+        // A second run resumes after the checkpointed cursor.
+        contents.extend(export_entry("3", b"third", false));
+        fs::write(&export_file, &contents).unwrap();
+
+        let received = run_export_file(tempdir.path(), &export_file, acknowledgements).await;
+        assert_eq!(received.len(), 1);
+        assert_eq!(message(&received[0]), Value::Bytes("third".into()));
+    }
+
+    #[tokio::test]
+    async fn reads_export_file_without_acknowledgements() {
+        reads_export_file(false).await;
+    }
+
+    #[tokio::test]
+    async fn reads_export_file_with_acknowledgements() {
+        // The checkpoint of the acknowledged events is written before the source finishes.
+        reads_export_file(true).await;
+    }
+
     fn message(event: &Event) -> Value {
         event.as_log()[log_schema().message_key().unwrap().to_string()].clone()
     }
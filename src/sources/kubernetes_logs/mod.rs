@@ -786,6 +786,7 @@ impl Source {
             oldest_first,
             // We do not remove the log files, `kubelet` is responsible for it.
             remove_after: None,
+            stop_at_eof: false,
             // The standard emitter.
             emitter: FileSourceInternalEventsEmitter {
                 include_file_metric_tag,
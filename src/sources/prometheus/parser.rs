@@ -1,12 +1,16 @@
 use std::cmp::Ordering;
 
 use chrono::{DateTime, TimeZone, Utc};
+use vector_lib::lookup::path;
+use vector_lib::metrics::AgentDDSketch;
 #[cfg(feature = "sources-prometheus-remote-write")]
-use vector_lib::prometheus::parser::proto;
-use vector_lib::prometheus::parser::{GroupKind, MetricGroup, ParserError};
+use vector_lib::prometheus::parser::{proto, proto_v2};
+use vector_lib::prometheus::parser::{
+    GroupKind, HistogramBucket, MetricGroup, NativeHistogram, ParserError,
+};
 
 use crate::event::{
-    metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
+    metric::{Bucket, Metric, MetricKind, MetricSketch, MetricTags, MetricValue, Quantile},
     Event,
 };
 
@@ -25,6 +29,18 @@ pub(super) fn parse_text(packet: &str) -> Result<Vec<Event>, ParserError> {
         .map(|group| reparse_groups(group, vec![], false))
 }
 
+#[cfg(any(test, feature = "sources-prometheus-scrape"))]
+pub(super) fn parse_openmetrics_text(packet: &str) -> Result<Vec<Event>, ParserError> {
+    vector_lib::prometheus::parser::parse_openmetrics_text(packet)
+        .map(|group| reparse_groups(group, vec![], false))
+}
+
+#[cfg(any(test, feature = "sources-prometheus-scrape"))]
+pub(super) fn parse_protobuf(packet: &[u8]) -> Result<Vec<Event>, ParserError> {
+    vector_lib::prometheus::parser::parse_protobuf(packet)
+        .map(|group| reparse_groups(group, vec![], false))
+}
+
 #[cfg(any(test, feature = "sources-prometheus-pushgateway"))]
 pub(super) fn parse_text_with_overrides(
     packet: &str,
@@ -41,6 +57,12 @@ pub(super) fn parse_request(request: proto::WriteRequest) -> Result<Vec<Event>,
         .map(|group| reparse_groups(group, vec![], false))
 }
 
+#[cfg(feature = "sources-prometheus-remote-write")]
+pub(super) fn parse_request_v2(request: proto_v2::Request) -> Result<Vec<Event>, ParserError> {
+    vector_lib::prometheus::parser::parse_request_v2(request)
+        .map(|group| reparse_groups(group, vec![], false))
+}
+
 fn reparse_groups(
     groups: Vec<MetricGroup>,
     tag_overrides: impl IntoIterator<Item = (String, String)> + Clone,
@@ -56,6 +78,7 @@ fn reparse_groups(
     };
 
     for group in groups {
+        let group_start = result.len();
         match group.metrics {
             GroupKind::Counter(metrics) => {
                 for (key, metric) in metrics {
@@ -96,37 +119,26 @@ fn reparse_groups(
                 for (key, metric) in metrics {
                     let tags = combine_tags(key.labels, tag_overrides.clone());
 
-                    let mut buckets = metric.buckets;
-                    buckets.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-                    for i in (1..buckets.len()).rev() {
-                        buckets[i].count = buckets[i].count.saturating_sub(buckets[i - 1].count);
-                    }
-                    let drop_last = buckets
-                        .last()
-                        .map_or(false, |bucket| bucket.bucket == f64::INFINITY);
-                    if drop_last {
-                        buckets.pop();
-                    }
+                    let value = match metric.native {
+                        Some(native) => MetricValue::Sketch {
+                            sketch: MetricSketch::AgentDDSketch(native_histogram_to_sketch(
+                                &native,
+                                metric.count,
+                                metric.sum,
+                            )),
+                        },
+                        None => MetricValue::AggregatedHistogram {
+                            buckets: histogram_buckets(metric.buckets),
+                            count: metric.count,
+                            sum: metric.sum,
+                        },
+                    };
 
                     result.push(
-                        Metric::new(
-                            group.name.clone(),
-                            metric_kind,
-                            MetricValue::AggregatedHistogram {
-                                buckets: buckets
-                                    .into_iter()
-                                    .map(|b| Bucket {
-                                        upper_limit: b.bucket,
-                                        count: b.count,
-                                    })
-                                    .collect(),
-                                count: metric.count,
-                                sum: metric.sum,
-                            },
-                        )
-                        .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
-                        .with_tags(tags.as_option())
-                        .into(),
+                        Metric::new(group.name.clone(), metric_kind, value)
+                            .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                            .with_tags(tags.as_option())
+                            .into(),
                     );
                 }
             }
@@ -159,11 +171,91 @@ fn reparse_groups(
                 }
             }
         }
+        insert_group_metadata(&mut result[group_start..], group.help, group.unit);
     }
 
     result
 }
 
+/// Converts cumulative buckets to the buckets of an aggregated histogram.
+fn histogram_buckets(mut buckets: Vec<HistogramBucket>) -> Vec<Bucket> {
+    buckets.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    for i in (1..buckets.len()).rev() {
+        buckets[i].count = buckets[i].count.saturating_sub(buckets[i - 1].count);
+    }
+    let drop_last = buckets
+        .last()
+        .map_or(false, |bucket| bucket.bucket == f64::INFINITY);
+    if drop_last {
+        buckets.pop();
+    }
+
+    buckets
+        .into_iter()
+        .map(|b| Bucket {
+            upper_limit: b.bucket,
+            count: b.count,
+        })
+        .collect()
+}
+
+/// Converts a native histogram to a sketch, which is the closest metric value.
+///
+/// The observations of each exponential bucket are inserted at its geometric
+/// midpoint, while the count and sum of the sketch are the exact ones of the
+/// histogram.
+fn native_histogram_to_sketch(histogram: &NativeHistogram, count: u64, sum: f64) -> AgentDDSketch {
+    let mut sketch = AgentDDSketch::with_agent_defaults();
+    let mut insert = |value: f64, count: f64| {
+        // Sketches count observations with 32 bits.
+        let count = count.round().clamp(0.0, f64::from(u32::MAX)) as u32;
+        if count > 0 {
+            sketch.insert_n(value, count);
+        }
+    };
+    let midpoint = |index: i32| {
+        (histogram.bucket_upper_bound(index - 1) * histogram.bucket_upper_bound(index)).sqrt()
+    };
+
+    insert(0.0, histogram.zero_count);
+    for &(index, count) in &histogram.negative {
+        insert(-midpoint(index), count);
+    }
+    for &(index, count) in &histogram.positive {
+        insert(midpoint(index), count);
+    }
+
+    let (Some(min), Some(max)) = (sketch.min(), sketch.max()) else {
+        return sketch;
+    };
+    let count = count.clamp(1, u64::from(u32::MAX)) as u32;
+    let bins = sketch.bin_map();
+    AgentDDSketch::from_raw(
+        count,
+        min,
+        max,
+        sum,
+        sum / f64::from(count),
+        &bins.keys,
+        &bins.counts,
+    )
+    .unwrap_or(sketch)
+}
+
+/// Keeps the help text and unit of a group in the metadata of its events, so
+/// that the Prometheus sinks can send them along.
+fn insert_group_metadata(events: &mut [Event], help: Option<String>, unit: Option<String>) {
+    for event in events {
+        let metadata = event.metadata_mut().value_mut();
+        if let Some(help) = &help {
+            metadata.insert(path!("prometheus", "help"), help.clone());
+        }
+        if let Some(unit) = &unit {
+            metadata.insert(path!("prometheus", "unit"), unit.clone());
+        }
+    }
+}
+
 fn combine_tags(
     base_tags: impl Into<MetricTags>,
     tag_overrides: impl IntoIterator<Item = (String, String)>,
@@ -232,6 +324,27 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_openmetrics_counter() {
+        let exp = r##"
+            # HELP uptime A counter
+            # TYPE uptime counter
+            uptime_total 123.0 1612411506.789 # {trace_id="abc"} 1.0
+            uptime_created 1612411000.0
+            # EOF
+            "##;
+
+        assert_event_data_eq!(
+            events_to_metrics(parse_openmetrics_text(exp)),
+            Ok(vec![Metric::new(
+                "uptime_total",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 123.0 },
+            )
+            .with_timestamp(Some(*TIMESTAMP))]),
+        );
+    }
+
     #[test]
     fn test_counter_empty() {
         let exp = r##"
@@ -1276,4 +1389,24 @@ mod test {
             ]),
         );
     }
+
+    #[test]
+    fn converts_native_histograms_to_sketches() {
+        let histogram = NativeHistogram {
+            schema: 0,
+            zero_threshold: 0.5,
+            zero_count: 1.0,
+            positive: vec![(1, 2.0), (3, 1.0)],
+            negative: vec![(2, 1.0)],
+        };
+
+        let sketch = native_histogram_to_sketch(&histogram, 5, 6.5);
+        assert_eq!(sketch.count(), 5);
+        assert_eq!(sketch.sum(), Some(6.5));
+        assert_eq!(sketch.avg(), Some(1.3));
+        // The buckets are (1, 2], (4, 8] and [-4, -2), whose midpoints are about 1.41, 5.66 and
+        // -2.83.
+        assert!((sketch.quantile(0.0).unwrap() + 8f64.sqrt()).abs() < 0.1);
+        assert!((sketch.quantile(1.0).unwrap() - 32f64.sqrt()).abs() < 0.1);
+    }
 }
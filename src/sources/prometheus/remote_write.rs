@@ -4,8 +4,8 @@ use bytes::Bytes;
 use prost::Message;
 use vector_lib::config::LogNamespace;
 use vector_lib::configurable::configurable_component;
-use vector_lib::prometheus::parser::proto;
-use warp::http::{HeaderMap, StatusCode};
+use vector_lib::prometheus::parser::{proto, proto_v2};
+use warp::http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
 
 use super::parser;
 use crate::{
@@ -110,17 +110,15 @@ impl SourceConfig for PrometheusRemoteWriteConfig {
 struct RemoteWriteSource;
 
 impl RemoteWriteSource {
-    fn decode_body(&self, body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
-        let request = proto::WriteRequest::decode(body).map_err(|error| {
-            emit!(PrometheusRemoteWriteParseError {
-                error: error.clone()
-            });
-            ErrorMessage::new(
-                StatusCode::BAD_REQUEST,
-                format!("Could not decode write request: {}", error),
-            )
-        })?;
-        parser::parse_request(request).map_err(|error| {
+    fn decode_body(&self, body: Bytes, v2: bool) -> Result<Vec<Event>, ErrorMessage> {
+        let events = if v2 {
+            let request = proto_v2::Request::decode(body).map_err(decode_error)?;
+            parser::parse_request_v2(request)
+        } else {
+            let request = proto::WriteRequest::decode(body).map_err(decode_error)?;
+            parser::parse_request(request)
+        };
+        events.map_err(|error| {
             ErrorMessage::new(
                 StatusCode::BAD_REQUEST,
                 format!("Could not decode write request: {}", error),
@@ -129,6 +127,32 @@ impl RemoteWriteSource {
     }
 }
 
+fn decode_error(error: prost::DecodeError) -> ErrorMessage {
+    emit!(PrometheusRemoteWriteParseError {
+        error: error.clone()
+    });
+    ErrorMessage::new(
+        StatusCode::BAD_REQUEST,
+        format!("Could not decode write request: {}", error),
+    )
+}
+
+/// Remote-Write 2.0 requests are told apart by the `proto` parameter of their content type.
+fn is_v2_request(header_map: &HeaderMap) -> bool {
+    header_map
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| {
+            content_type
+                .split(';')
+                .skip(1)
+                .filter_map(|parameter| parameter.split_once('='))
+                .any(|(name, value)| {
+                    name.trim() == "proto" && value.trim() == "io.prometheus.write.v2.Request"
+                })
+        })
+}
+
 impl HttpSource for RemoteWriteSource {
     fn decode(&self, encoding_header: Option<&str>, body: Bytes) -> Result<Bytes, ErrorMessage> {
         // Default to snappy decoding the request body.
@@ -138,11 +162,11 @@ impl HttpSource for RemoteWriteSource {
     fn build_events(
         &self,
         body: Bytes,
-        _header_map: &HeaderMap,
+        header_map: &HeaderMap,
         _query_parameters: &HashMap<String, String>,
         _full_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        let events = self.decode_body(body)?;
+        let events = self.decode_body(body, is_v2_request(header_map))?;
         Ok(events)
     }
 }
@@ -151,14 +175,17 @@ impl HttpSource for RemoteWriteSource {
 mod test {
     use chrono::{SubsecRound as _, Utc};
     use vector_lib::{
-        event::{EventStatus, Metric, MetricKind, MetricValue},
+        event::{
+            metric::MetricSketch, EventStatus, Metric, MetricKind, MetricValue, StatisticKind,
+        },
+        lookup::path,
         metric_tags,
     };
 
     use super::*;
     use crate::{
         config::{SinkConfig, SinkContext},
-        sinks::prometheus::remote_write::RemoteWriteConfig,
+        sinks::prometheus::remote_write::{ProtocolVersion, RemoteWriteConfig},
         test_util::{self, wait_for_tcp},
         tls::MaybeTlsSettings,
         SourceSender,
@@ -179,6 +206,131 @@ mod test {
         receives_metrics(Some(TlsEnableableConfig::test_config())).await;
     }
 
+    #[tokio::test]
+    async fn receives_metrics_over_remote_write_v2() {
+        let address = test_util::next_addr();
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+
+        let source = PrometheusRemoteWriteConfig::from_address(address)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let sink = RemoteWriteConfig {
+            endpoint: format!("http://localhost:{}/", address.port()),
+            protocol_version: ProtocolVersion::V2,
+            ..Default::default()
+        };
+        let (sink, _) = sink
+            .build(SinkContext::default())
+            .await
+            .expect("Error building config.");
+
+        let events = make_events();
+        let events_copy = events.clone();
+        let mut output = test_util::spawn_collect_ready(
+            async move {
+                sink.run_events(events_copy).await.unwrap();
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        output.sort_unstable_by_key(|event| event.as_metric().name().to_owned());
+
+        vector_lib::assert_event_data_eq!(events, output);
+    }
+
+    #[tokio::test]
+    async fn receives_native_histograms_and_metadata() {
+        let address = test_util::next_addr();
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+
+        let source = PrometheusRemoteWriteConfig::from_address(address)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let sink = RemoteWriteConfig {
+            endpoint: format!("http://localhost:{}/", address.port()),
+            native_histograms: Some(Default::default()),
+            ..Default::default()
+        };
+        let (sink, _) = sink
+            .build(SinkContext::default())
+            .await
+            .expect("Error building config.");
+
+        let mut metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_lib::samples![0.0 => 1, 2.0 => 3, -8.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(Utc::now().trunc_subsecs(3)));
+        metric
+            .metadata_mut()
+            .value_mut()
+            .insert(path!("prometheus", "help"), "Latency of requests.");
+
+        let output = test_util::spawn_collect_ready(
+            async move {
+                sink.run_events(vec![metric.into()]).await.unwrap();
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        assert_eq!(output.len(), 1);
+        let metric = output[0].as_metric();
+        assert_eq!(metric.name(), "latency");
+        assert_eq!(
+            metric
+                .metadata()
+                .value()
+                .get(path!("prometheus", "help"))
+                .and_then(|help| help.as_str()),
+            Some("Latency of requests.".into())
+        );
+        match metric.value() {
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            } => {
+                assert_eq!(sketch.count(), 5);
+                assert!((sketch.sum().unwrap() - -2.0).abs() < 0.5);
+            }
+            value => panic!("Expected a sketch, found {value:?}"),
+        }
+    }
+
+    #[test]
+    fn detects_v2_requests() {
+        let headers = |content_type: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+            headers
+        };
+        assert!(is_v2_request(&headers(
+            "application/x-protobuf;proto=io.prometheus.write.v2.Request"
+        )));
+        assert!(is_v2_request(&headers(
+            "application/x-protobuf; proto=io.prometheus.write.v2.Request"
+        )));
+        assert!(!is_v2_request(&headers(
+            "application/x-protobuf;proto=prometheus.WriteRequest"
+        )));
+        assert!(!is_v2_request(&headers("application/x-protobuf")));
+        assert!(!is_v2_request(&HeaderMap::new()));
+    }
+
     async fn receives_metrics(tls: Option<TlsEnableableConfig>) {
         let address = test_util::next_addr();
         let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
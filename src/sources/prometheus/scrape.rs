@@ -35,6 +35,60 @@ static NOT_FOUND_NO_PATH: &str = "No path is set on the endpoint and we got a 40
                                   did you mean to use /metrics?\
                                   This behavior changed in version 0.11.";
 
+/// A format that metrics can be scraped in.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScrapeProtocol {
+    /// The Prometheus text format.
+    PrometheusText,
+
+    /// The OpenMetrics text format.
+    ///
+    /// Exemplars and created timestamps are parsed, but not kept.
+    OpenmetricsText,
+
+    /// The Prometheus protobuf format.
+    ///
+    /// Native histograms are converted to histograms with the bounds of their exponential
+    /// buckets.
+    PrometheusProtobuf,
+}
+
+impl ScrapeProtocol {
+    const fn media_type(self) -> &'static str {
+        match self {
+            Self::PrometheusText => "text/plain",
+            Self::OpenmetricsText => "application/openmetrics-text",
+            Self::PrometheusProtobuf => PROTOBUF_MEDIA_TYPE,
+        }
+    }
+}
+
+const PROTOBUF_MEDIA_TYPE: &str =
+    "application/vnd.google.protobuf;proto=io.prometheus.client.MetricFamily;encoding=delimited";
+
+fn default_scrape_protocols() -> Vec<ScrapeProtocol> {
+    vec![ScrapeProtocol::PrometheusText]
+}
+
+/// Builds the `Accept` header, which lists the protocols in order of preference.
+fn accept_header(protocols: &[ScrapeProtocol]) -> String {
+    protocols
+        .iter()
+        .enumerate()
+        .map(|(index, protocol)| match index {
+            0 => protocol.media_type().to_string(),
+            _ => format!(
+                "{};q={:.1}",
+                protocol.media_type(),
+                (1.0 - 0.1 * index as f64).max(0.1)
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 #[derive(Debug, Snafu)]
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
@@ -102,6 +156,13 @@ pub struct PrometheusScrapeConfig {
     #[configurable(metadata(docs::examples = "query_example()"))]
     query: HashMap<String, Vec<String>>,
 
+    /// The formats that metrics are requested in, in order of preference.
+    ///
+    /// Responses are parsed according to their `Content-Type` header.
+    #[serde(default = "default_scrape_protocols")]
+    #[configurable(metadata(docs::advanced))]
+    scrape_protocols: Vec<ScrapeProtocol>,
+
     #[configurable(derived)]
     tls: Option<TlsConfig>,
 
@@ -129,6 +190,7 @@ impl GenerateConfig for PrometheusScrapeConfig {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            scrape_protocols: default_scrape_protocols(),
             tls: None,
             auth: None,
         })
@@ -161,7 +223,7 @@ impl SourceConfig for PrometheusScrapeConfig {
             interval: self.interval,
             timeout: self.timeout,
             headers: HashMap::new(),
-            content_type: "text/plain".to_string(),
+            content_type: accept_header(&self.scrape_protocols),
             auth: self.auth.clone(),
             tls,
             proxy: cx.proxy.clone(),
@@ -289,10 +351,21 @@ impl HttpClientContext for PrometheusScrapeContext {
     }
 
     /// Parses the Prometheus HTTP response into metric events
-    fn on_response(&mut self, url: &Uri, _header: &Parts, body: &Bytes) -> Option<Vec<Event>> {
-        let body = String::from_utf8_lossy(body);
+    fn on_response(&mut self, url: &Uri, header: &Parts, body: &Bytes) -> Option<Vec<Event>> {
+        let content_type = header
+            .headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let result = if content_type.starts_with("application/vnd.google.protobuf") {
+            parser::parse_protobuf(body)
+        } else if content_type.starts_with("application/openmetrics-text") {
+            parser::parse_openmetrics_text(&String::from_utf8_lossy(body))
+        } else {
+            parser::parse_text(&String::from_utf8_lossy(body))
+        };
 
-        match parser::parse_text(&body) {
+        match result {
             Ok(events) => Some(events),
             Err(error) => {
                 if url.path() == "/" {
@@ -305,7 +378,7 @@ impl HttpClientContext for PrometheusScrapeContext {
                 emit!(PrometheusParseError {
                     error,
                     url: url.clone(),
-                    body,
+                    body: String::from_utf8_lossy(body),
                 });
                 None
             }
@@ -370,6 +443,69 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            scrape_protocols: default_scrape_protocols(),
+            auth: None,
+            tls: None,
+        };
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+        assert!(!events.is_empty());
+    }
+
+    #[test]
+    fn builds_accept_header() {
+        assert_eq!(accept_header(&default_scrape_protocols()), "text/plain");
+        assert_eq!(
+            accept_header(&[
+                ScrapeProtocol::OpenmetricsText,
+                ScrapeProtocol::PrometheusText
+            ]),
+            "application/openmetrics-text,text/plain;q=0.9"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_openmetrics() {
+        let in_addr = next_addr();
+
+        let dummy_endpoint = warp::path!("metrics")
+            .and(warp::header::exact(
+                "Accept",
+                "application/openmetrics-text,text/plain;q=0.9",
+            ))
+            .map(|| {
+                warp::reply::with_header(
+                    r#"
+                    # TYPE promhttp_metric_handler_requests counter
+                    promhttp_metric_handler_requests_total{code="200"} 100 1612411516.789 # {trace_id="abc"} 1
+                    promhttp_metric_handler_requests_created{code="200"} 1612411000
+                    # EOF
+                    "#,
+                    "Content-Type",
+                    "application/openmetrics-text; version=1.0.0; charset=utf-8",
+                )
+            });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+        wait_for_tcp(in_addr).await;
+
+        let config = PrometheusScrapeConfig {
+            endpoints: vec![format!("http://{}/metrics", in_addr)],
+            interval: Duration::from_secs(1),
+            timeout: default_timeout(),
+            instance_tag: None,
+            endpoint_tag: None,
+            honor_labels: false,
+            query: HashMap::new(),
+            scrape_protocols: vec![
+                ScrapeProtocol::OpenmetricsText,
+                ScrapeProtocol::PrometheusText,
+            ],
             auth: None,
             tls: None,
         };
@@ -381,6 +517,15 @@ mod test {
         )
         .await;
         assert!(!events.is_empty());
+
+        for event in events {
+            let metric = event.into_metric();
+            assert_eq!(metric.name(), "promhttp_metric_handler_requests_total");
+            assert_eq!(
+                metric.value(),
+                &vector_lib::event::MetricValue::Counter { value: 100.0 }
+            );
+        }
     }
 
     #[tokio::test]
@@ -404,6 +549,7 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            scrape_protocols: default_scrape_protocols(),
             auth: None,
             tls: None,
         };
@@ -456,6 +602,7 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            scrape_protocols: default_scrape_protocols(),
             auth: None,
             tls: None,
         };
@@ -522,6 +669,7 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            scrape_protocols: default_scrape_protocols(),
             auth: None,
             tls: None,
         };
@@ -584,6 +732,7 @@ mod test {
                     vec!["val1".to_string(), "val2".to_string()],
                 ),
             ]),
+            scrape_protocols: default_scrape_protocols(),
             auth: None,
             tls: None,
         };
@@ -684,6 +833,7 @@ mod test {
                 endpoint_tag: None,
                 honor_labels: false,
                 query: HashMap::new(),
+                scrape_protocols: default_scrape_protocols(),
                 interval: Duration::from_secs(1),
                 timeout: default_timeout(),
                 tls: None,
@@ -776,6 +926,7 @@ mod integration_tests {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            scrape_protocols: default_scrape_protocols(),
             auth: None,
             tls: None,
         };
@@ -0,0 +1,582 @@
+use std::{
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use async_compression::tokio::bufread;
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use tokio::{
+    fs::File,
+    io::{AsyncRead, BufReader},
+    time::{sleep_until, Instant, Interval, MissedTickBehavior},
+};
+use tokio_util::codec::FramedRead;
+use vector_lib::codecs::{
+    decoding::DeserializerConfig, NativeDeserializerConfig, NativeJsonDeserializerConfig,
+    StreamDecodingError,
+};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    internal_events::{ReplayFileFinished, ReplayFileOpenError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    source_sender::CHUNK_SIZE,
+    SourceSender,
+};
+
+/// The codec the replayed events were encoded with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayCodec {
+    /// Events encoded with the `native` codec, as length-delimited Protocol Buffers messages.
+    Native,
+
+    /// Events encoded with the `native_json` codec, as newline-delimited JSON objects.
+    ///
+    /// This encoding doesn't hold the metadata of the events, so the replayed events have none.
+    NativeJson,
+}
+
+impl ReplayCodec {
+    fn deserializer(self) -> DeserializerConfig {
+        match self {
+            Self::Native => NativeDeserializerConfig.into(),
+            Self::NativeJson => NativeJsonDeserializerConfig::default().into(),
+        }
+    }
+}
+
+/// Compression scheme of the replayed files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+pub enum ReplayCompression {
+    /// Automatically determine the compression scheme from the file extension.
+    ///
+    /// Files ending with `.gz` are decompressed with GZIP, and files ending with `.zst` with ZSTD.
+    /// Other files are read uncompressed.
+    #[derivative(Default)]
+    Auto,
+
+    /// Uncompressed.
+    None,
+
+    /// GZIP.
+    Gzip,
+
+    /// ZSTD.
+    Zstd,
+}
+
+impl ReplayCompression {
+    fn for_path(self, path: &Path) -> Self {
+        match self {
+            Self::Auto => match path.extension().and_then(std::ffi::OsStr::to_str) {
+                Some("gz") => Self::Gzip,
+                Some("zst") => Self::Zstd,
+                _ => Self::None,
+            },
+            compression => compression,
+        }
+    }
+}
+
+/// The pace at which the events are replayed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[derivative(Default)]
+#[configurable(metadata(docs::enum_tag_description = "The pacing mode."))]
+pub enum ReplayPacing {
+    /// Replay the events as fast as the downstream components accept them.
+    #[derivative(Default)]
+    AsFastAsPossible,
+
+    /// Replay the events at their original pace.
+    ///
+    /// The events are delayed to keep the intervals between their timestamps. Events without a
+    /// timestamp, or with a timestamp earlier than the previous ones, aren't delayed.
+    Original,
+
+    /// Replay the events at a fixed rate.
+    Rate {
+        /// The number of events to replay per second.
+        #[configurable(metadata(docs::examples = 1000))]
+        events_per_second: NonZeroU32,
+    },
+}
+
+/// Configuration for the `replay` source.
+#[configurable_component(source(
+    "replay",
+    "Replay events archived with the `native` or `native_json` codecs."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayConfig {
+    /// Array of file patterns to replay. [Globbing](https://en.wikipedia.org/wiki/Glob_(programming)) is supported.
+    ///
+    /// The matching files are replayed one after the other, in the lexicographic order of their
+    /// paths. The source stops once all of them are replayed.
+    #[configurable(metadata(docs::examples = "/var/lib/vector/archive/*.log.gz"))]
+    pub include: Vec<PathBuf>,
+
+    #[configurable(derived)]
+    pub codec: ReplayCodec,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: ReplayCompression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub pacing: ReplayPacing,
+}
+
+impl GenerateConfig for ReplayConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            include: vec![PathBuf::from("/var/lib/vector/archive/*.log.gz")],
+            codec: ReplayCodec::Native,
+            compression: ReplayCompression::Auto,
+            pacing: ReplayPacing::AsFastAsPossible,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "replay")]
+impl SourceConfig for ReplayConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let paths = self.paths()?;
+        let deserializer = self.codec.deserializer();
+        let framing = deserializer.default_stream_framing();
+        let decoder = DecodingConfig::new(framing, deserializer, cx.log_namespace(None)).build()?;
+
+        Ok(Box::pin(run(
+            paths,
+            self.compression,
+            self.pacing,
+            decoder,
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        // The events are replayed as they were archived, so nothing is known about their schema.
+        let deserializer = self.codec.deserializer();
+        let schema_definition = deserializer.schema_definition(global_log_namespace);
+
+        vec![SourceOutput::new_logs(
+            deserializer.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl ReplayConfig {
+    /// Expands the `include` patterns into the sorted list of the files to replay.
+    fn paths(&self) -> crate::Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for pattern in &self.include {
+            let pattern = pattern.to_string_lossy();
+            let matches = glob::glob(&pattern)
+                .map_err(|error| format!("Invalid pattern {:?}: {}.", pattern, error))?;
+            for path in matches {
+                let path = path.map_err(|error| format!("Unable to list files: {}.", error))?;
+                if path.is_file() {
+                    paths.push(path);
+                }
+            }
+        }
+
+        if paths.is_empty() {
+            return Err("No files match the `include` patterns.".into());
+        }
+
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+}
+
+/// Delays the events according to the `pacing` option.
+struct Pacer {
+    pacing: ReplayPacing,
+    origin: Option<(Instant, DateTime<Utc>)>,
+    interval: Option<Interval>,
+}
+
+impl Pacer {
+    fn new(pacing: ReplayPacing) -> Self {
+        let interval = match pacing {
+            ReplayPacing::Rate { events_per_second } => {
+                // Rates above one event per nanosecond can't be kept anyway, and the interval
+                // panics on a zero period.
+                let period =
+                    (Duration::from_secs(1) / events_per_second.get()).max(Duration::from_nanos(1));
+                let mut interval = tokio::time::interval(period);
+                // Falling behind, for example because of backpressure, mustn't lead to a burst
+                // of events to catch up.
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                Some(interval)
+            }
+            _ => None,
+        };
+
+        Self {
+            pacing,
+            origin: None,
+            interval,
+        }
+    }
+
+    async fn wait(&mut self, event: &Event) {
+        match self.pacing {
+            ReplayPacing::AsFastAsPossible => {}
+            ReplayPacing::Original => {
+                let Some(timestamp) = event_timestamp(event) else {
+                    return;
+                };
+                let (start, first_timestamp) =
+                    *self.origin.get_or_insert((Instant::now(), timestamp));
+                if let Ok(offset) = (timestamp - first_timestamp).to_std() {
+                    sleep_until(start + offset).await;
+                }
+            }
+            ReplayPacing::Rate { .. } => {
+                if let Some(interval) = &mut self.interval {
+                    interval.tick().await;
+                }
+            }
+        }
+    }
+}
+
+fn event_timestamp(event: &Event) -> Option<DateTime<Utc>> {
+    match event {
+        Event::Log(log) => log.get_timestamp().and_then(|v| v.as_timestamp()).copied(),
+        Event::Metric(metric) => metric.timestamp(),
+        Event::Trace(_) => None,
+    }
+}
+
+fn decompress(compression: ReplayCompression, file: File) -> Box<dyn AsyncRead + Send + Unpin> {
+    let reader = BufReader::new(file);
+    match compression {
+        ReplayCompression::Auto | ReplayCompression::None => Box::new(reader),
+        ReplayCompression::Gzip => Box::new({
+            let mut decoder = bufread::GzipDecoder::new(reader);
+            decoder.multiple_members(true);
+            decoder
+        }),
+        ReplayCompression::Zstd => Box::new({
+            let mut decoder = bufread::ZstdDecoder::new(reader);
+            decoder.multiple_members(true);
+            decoder
+        }),
+    }
+}
+
+async fn run(
+    paths: Vec<PathBuf>,
+    compression: ReplayCompression,
+    pacing: ReplayPacing,
+    decoder: Decoder,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let bytes_received = register!(BytesReceived::from(Protocol::from("file")));
+    let events_received = register!(EventsReceived);
+    let mut pacer = Pacer::new(pacing);
+
+    let mut stream = stream! {
+        for path in paths {
+            let file = match File::open(&path).await {
+                Ok(file) => file,
+                Err(error) => {
+                    emit!(ReplayFileOpenError { path: &path, error });
+                    continue;
+                }
+            };
+
+            let reader = decompress(compression.for_path(&path), file);
+            let mut frames = FramedRead::new(reader, decoder.clone());
+            while let Some(result) = frames.next().await {
+                match result {
+                    Ok((events, byte_size)) => {
+                        bytes_received.emit(ByteSize(byte_size));
+
+                        for event in events {
+                            pacer.wait(&event).await;
+                            events_received.emit(CountByteSize(
+                                1,
+                                event.estimated_json_encoded_size_of(),
+                            ));
+                            yield event;
+                        }
+                    }
+                    Err(error) => {
+                        // Error is logged by `crate::codecs::Decoder`, no further handling is
+                        // needed here.
+                        if !error.can_continue() {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            emit!(ReplayFileFinished { path: &path });
+        }
+    }
+    .take_until(shutdown)
+    .ready_chunks(CHUNK_SIZE)
+    .boxed();
+
+    while let Some(events) = stream.next().await {
+        let count = events.len();
+        if out.send_batch(events).await.is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
+    }
+
+    debug!("Finished replaying.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use bytes::BytesMut;
+    use tokio_util::codec::Encoder as _;
+    use vector_lib::codecs::{
+        encoding::Framer, NativeJsonSerializerConfig, NativeSerializerConfig,
+    };
+    use vrl::path;
+
+    use super::*;
+    use crate::{
+        codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+        event::{LogEvent, Metric, MetricKind, MetricValue},
+        test_util::temp_dir,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ReplayConfig>();
+    }
+
+    fn events() -> Vec<Event> {
+        let start = Utc::now();
+        let mut log = LogEvent::from("first");
+        log.insert("timestamp", start);
+        log.metadata_mut()
+            .value_mut()
+            .insert(path!("tenant"), "acme");
+        let mut metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_timestamp(Some(start + chrono::Duration::milliseconds(100)));
+        metric
+            .metadata_mut()
+            .value_mut()
+            .insert(path!("tenant"), "globex");
+        let mut last = LogEvent::from("last");
+        last.insert("timestamp", start + chrono::Duration::milliseconds(200));
+        vec![log.into(), metric.into(), last.into()]
+    }
+
+    /// Encodes the events the way the `file` sink does with the given codec.
+    fn encode(codec: ReplayCodec, events: Vec<Event>) -> Vec<u8> {
+        let serializer = match codec {
+            ReplayCodec::Native => NativeSerializerConfig.into(),
+            ReplayCodec::NativeJson => NativeJsonSerializerConfig.into(),
+        };
+        let (framer, serializer) =
+            EncodingConfigWithFraming::new(None, serializer, Transformer::default())
+                .build(SinkType::StreamBased)
+                .unwrap();
+        let mut encoder = Encoder::<Framer>::new(framer, serializer);
+        let mut buffer = BytesMut::new();
+        for event in events {
+            encoder.encode(event, &mut buffer).unwrap();
+        }
+        buffer.to_vec()
+    }
+
+    async fn replay(config: ReplayConfig) -> Vec<Event> {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        rx.collect().await
+    }
+
+    fn assert_events_eq(replayed: &[Event], events: &[Event]) {
+        assert_eq!(replayed.len(), events.len());
+        for (replayed, event) in replayed.iter().zip(events) {
+            match (replayed, event) {
+                (Event::Log(replayed), Event::Log(event)) => {
+                    assert_eq!(replayed.value(), event.value())
+                }
+                (Event::Metric(replayed), Event::Metric(event)) => {
+                    assert_eq!(replayed.series(), event.series());
+                    assert_eq!(replayed.data(), event.data());
+                }
+                _ => panic!("unexpected event {:?}", replayed),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_compressed_native_files() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let events = events();
+
+        let mut gzip = flate2::write::GzEncoder::new(
+            std::fs::File::create(dir.join("1.log.gz")).unwrap(),
+            flate2::Compression::default(),
+        );
+        gzip.write_all(&encode(ReplayCodec::Native, events[..2].to_vec()))
+            .unwrap();
+        gzip.finish().unwrap();
+        let zstd =
+            zstd::encode_all(&encode(ReplayCodec::Native, events[2..].to_vec())[..], 0).unwrap();
+        std::fs::write(dir.join("2.log.zst"), zstd).unwrap();
+
+        let replayed = replay(ReplayConfig {
+            include: vec![dir.join("*.log.*")],
+            codec: ReplayCodec::Native,
+            compression: ReplayCompression::Auto,
+            pacing: ReplayPacing::AsFastAsPossible,
+        })
+        .await;
+
+        assert_events_eq(&replayed, &events);
+        // The `native` codec keeps the metadata of the events.
+        for (replayed, event) in replayed.iter().zip(&events) {
+            assert_eq!(replayed.metadata().value(), event.metadata().value());
+        }
+        assert_eq!(
+            replayed[0].metadata().value().get(path!("tenant")),
+            Some(&"acme".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn replays_at_original_pace() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let events = events();
+        std::fs::write(
+            dir.join("archive.json"),
+            encode(ReplayCodec::NativeJson, events.clone()),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        let replayed = replay(ReplayConfig {
+            include: vec![dir.join("archive.json")],
+            codec: ReplayCodec::NativeJson,
+            compression: ReplayCompression::Auto,
+            pacing: ReplayPacing::Original,
+        })
+        .await;
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_events_eq(&replayed, &events);
+    }
+
+    #[tokio::test]
+    async fn replays_at_fixed_rate() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let events = events();
+        std::fs::write(
+            dir.join("archive.json"),
+            encode(ReplayCodec::NativeJson, events.clone()),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        let replayed = replay(ReplayConfig {
+            include: vec![dir.join("archive.json")],
+            codec: ReplayCodec::NativeJson,
+            compression: ReplayCompression::None,
+            pacing: ReplayPacing::Rate {
+                events_per_second: NonZeroU32::new(10).unwrap(),
+            },
+        })
+        .await;
+
+        // The first event is replayed right away, and the next ones 100ms apart.
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_events_eq(&replayed, &events);
+    }
+
+    #[tokio::test]
+    async fn replays_at_rates_above_one_event_per_nanosecond() {
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let events = events();
+        std::fs::write(
+            dir.join("archive.json"),
+            encode(ReplayCodec::NativeJson, events.clone()),
+        )
+        .unwrap();
+
+        let replayed = replay(ReplayConfig {
+            include: vec![dir.join("archive.json")],
+            codec: ReplayCodec::NativeJson,
+            compression: ReplayCompression::None,
+            pacing: ReplayPacing::Rate {
+                events_per_second: NonZeroU32::MAX,
+            },
+        })
+        .await;
+
+        assert_events_eq(&replayed, &events);
+    }
+
+    #[tokio::test]
+    async fn fails_without_matching_files() {
+        let dir = temp_dir();
+        let (tx, _rx) = SourceSender::new_test();
+        let result = ReplayConfig {
+            include: vec![dir.join("*.log")],
+            codec: ReplayCodec::Native,
+            compression: ReplayCompression::Auto,
+            pacing: ReplayPacing::AsFastAsPossible,
+        }
+        .build(SourceContext::new_test(tx, None))
+        .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -3,7 +3,7 @@ pub mod udp;
 #[cfg(unix)]
 mod unix;
 
-use vector_lib::codecs::decoding::DeserializerConfig;
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
 use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
 use vector_lib::configurable::configurable_component;
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
@@ -69,14 +69,36 @@ impl SocketConfig {
         }
     }
 
-    fn log_namespace(&self) -> LogNamespace {
+    /// The configured framing, or the default one for the mode.
+    fn framing(&self) -> FramingConfig {
         match &self.mode {
-            Mode::Tcp(config) => config.log_namespace.unwrap_or(false).into(),
-            Mode::Udp(config) => config.log_namespace.unwrap_or(false).into(),
+            Mode::Tcp(config) => config
+                .framing
+                .clone()
+                .unwrap_or_else(|| config.decoding().default_stream_framing()),
+            Mode::Udp(config) => config.framing().clone(),
             #[cfg(unix)]
-            Mode::UnixDatagram(config) => config.log_namespace.unwrap_or(false).into(),
+            Mode::UnixDatagram(config) => config
+                .framing
+                .clone()
+                .unwrap_or_else(default_framing_message_based),
             #[cfg(unix)]
-            Mode::UnixStream(config) => config.log_namespace.unwrap_or(false).into(),
+            Mode::UnixStream(config) => config
+                .framing
+                .clone()
+                .unwrap_or_else(|| config.decoding().default_stream_framing()),
+        }
+    }
+
+    /// The `log_namespace` set for the mode, which overrides the global one.
+    const fn log_namespace(&self) -> Option<bool> {
+        match &self.mode {
+            Mode::Tcp(config) => config.log_namespace,
+            Mode::Udp(config) => config.log_namespace,
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => config.log_namespace,
+            #[cfg(unix)]
+            Mode::UnixStream(config) => config.log_namespace,
         }
     }
 }
@@ -111,20 +133,12 @@ impl GenerateConfig for SocketConfig {
 #[typetag::serde(name = "socket")]
 impl SourceConfig for SocketConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let framing = self.framing();
+        let decoding = self.decoding();
         match self.mode.clone() {
             Mode::Tcp(config) => {
                 let log_namespace = cx.log_namespace(config.log_namespace);
-
-                let decoding = config.decoding().clone();
-                let decoder = DecodingConfig::new(
-                    config
-                        .framing
-                        .clone()
-                        .unwrap_or_else(|| decoding.default_stream_framing()),
-                    decoding,
-                    log_namespace,
-                )
-                .build()?;
+                let decoder = DecodingConfig::new(framing, decoding, log_namespace).build()?;
 
                 let tcp = tcp::RawTcpSource::new(config.clone(), decoder, log_namespace);
                 let tls_config = config.tls().as_ref().map(|tls| tls.tls_config.clone());
@@ -151,12 +165,7 @@ impl SourceConfig for SocketConfig {
             }
             Mode::Udp(config) => {
                 let log_namespace = cx.log_namespace(config.log_namespace);
-                let decoder = DecodingConfig::new(
-                    config.framing().clone(),
-                    config.decoding().clone(),
-                    log_namespace,
-                )
-                .build()?;
+                let decoder = DecodingConfig::new(framing, decoding, log_namespace).build()?;
                 Ok(udp::udp(
                     config,
                     decoder,
@@ -168,32 +177,14 @@ impl SourceConfig for SocketConfig {
             #[cfg(unix)]
             Mode::UnixDatagram(config) => {
                 let log_namespace = cx.log_namespace(config.log_namespace);
-                let decoder = DecodingConfig::new(
-                    config
-                        .framing
-                        .clone()
-                        .unwrap_or_else(default_framing_message_based),
-                    config.decoding.clone(),
-                    log_namespace,
-                )
-                .build()?;
+                let decoder = DecodingConfig::new(framing, decoding, log_namespace).build()?;
 
                 unix::unix_datagram(config, decoder, cx.shutdown, cx.out, log_namespace)
             }
             #[cfg(unix)]
             Mode::UnixStream(config) => {
                 let log_namespace = cx.log_namespace(config.log_namespace);
-
-                let decoding = config.decoding().clone();
-                let decoder = DecodingConfig::new(
-                    config
-                        .framing
-                        .clone()
-                        .unwrap_or_else(|| decoding.default_stream_framing()),
-                    decoding,
-                    log_namespace,
-                )
-                .build()?;
+                let decoder = DecodingConfig::new(framing, decoding, log_namespace).build()?;
 
                 unix::unix_stream(config, decoder, cx.shutdown, cx.out, log_namespace)
             }
@@ -201,7 +192,7 @@ impl SourceConfig for SocketConfig {
     }
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
-        let log_namespace = global_log_namespace.merge(Some(self.log_namespace()));
+        let log_namespace = global_log_namespace.merge(self.log_namespace());
 
         let schema_definition = self
             .decoding()
@@ -309,6 +300,15 @@ impl SourceConfig for SocketConfig {
         }
     }
 
+    fn decoding_config(&self, global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace());
+        Some(DecodingConfig::new(
+            self.framing(),
+            self.decoding(),
+            log_namespace,
+        ))
+    }
+
     fn can_acknowledge(&self) -> bool {
         false
     }
@@ -339,11 +339,13 @@ mod test {
         task::JoinHandle,
         time::{timeout, Duration, Instant},
     };
+    use tokio_util::codec::Decoder as _;
     use vector_lib::codecs::NewlineDelimitedDecoderConfig;
     #[cfg(unix)]
     use vector_lib::codecs::{
         decoding::CharacterDelimitedDecoderOptions, CharacterDelimitedDecoderConfig,
     };
+    use vector_lib::config::LogNamespace;
     use vector_lib::event::EventContainer;
     use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
     use vrl::value::ObjectMap;
@@ -387,6 +389,26 @@ mod test {
         crate::test_util::test_generate_config::<SocketConfig>();
     }
 
+    #[test]
+    fn decoding_config_honors_global_log_namespace() {
+        let config = SocketConfig::from(TcpConfig::from_address(next_addr().into()));
+
+        let mut decoder = config
+            .decoding_config(LogNamespace::Vector)
+            .unwrap()
+            .build()
+            .unwrap();
+        let (events, _) = decoder
+            .decode_eof(&mut BytesMut::from("hello"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            *events[0].as_log().value(),
+            vrl::value::Value::from("hello")
+        );
+    }
+
     //////// TCP TESTS ////////
     #[tokio::test]
     async fn tcp_it_includes_host() {
@@ -948,6 +970,8 @@ mod test {
                 acknowledgements: false,
                 schema: Default::default(),
                 schema_definitions: HashMap::default(),
+                batch: false,
+                dead_letters: Default::default(),
             })
             .await
             .unwrap();
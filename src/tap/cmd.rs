@@ -1,7 +1,16 @@
-use std::{borrow::Cow, collections::BTreeMap, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
 
+use bytes::BytesMut;
 use colored::{ColoredString, Colorize};
 use tokio_stream::StreamExt;
+use tokio_util::codec::Encoder as _;
 use url::Url;
 use vector_lib::api_client::{
     connect_subscription_client,
@@ -11,8 +20,17 @@ use vector_lib::api_client::{
     },
     Client,
 };
+use vector_lib::codecs::{
+    encoding::Framer, LengthDelimitedEncoder, NativeJsonSerializerConfig, NativeSerializerConfig,
+    NewlineDelimitedEncoder,
+};
 
-use crate::signal::{SignalRx, SignalTo};
+use super::TapOutputFormat;
+use crate::{
+    codecs::Encoder,
+    event::Event,
+    signal::{SignalRx, SignalTo},
+};
 
 /// Delay (in milliseconds) before attempting to reconnect to the Vector API
 const RECONNECT_DELAY: u64 = 5000;
@@ -23,7 +41,9 @@ pub(crate) async fn cmd(opts: &super::Opts, signal_rx: SignalRx) -> exitcode::Ex
     let url = opts.url();
     // Return early with instructions for enabling the API if the endpoint isn't reachable
     // via a healthcheck.
-    let client = Client::new(url.clone());
+    let client = Client::new(url.clone())
+        .with_authorization(opts.connection().authorization())
+        .with_tls(opts.connection().tls().cloned());
     #[allow(clippy::print_stderr)]
     if client.healthcheck().await.is_err() {
         eprintln!(
@@ -50,25 +70,41 @@ pub async fn tap(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode::ExitC
     let formatter = EventFormatter::new(opts.meta, opts.format);
     let outputs_patterns = opts.outputs_patterns();
 
+    let mut file_writer = match opts
+        .output_file
+        .as_deref()
+        .map(|path| EventFileWriter::open(path, opts.output_format))
+        .transpose()
+    {
+        Ok(file_writer) => file_writer,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("[tap] Couldn't open output file: {}", error);
+            }
+            return exitcode::CANTCREAT;
+        }
+    };
+
     loop {
         tokio::select! {
             biased;
-            Ok(SignalTo::Shutdown(_) | SignalTo::Quit) = signal_rx.recv() => break,
-            status = run(subscription_url.clone(), opts, outputs_patterns.clone(), formatter.clone()) => {
+            Ok(SignalTo::Shutdown(_) | SignalTo::Quit) = signal_rx.recv() => break exitcode::OK,
+            status = run(subscription_url.clone(), opts, outputs_patterns.clone(), formatter.clone(), file_writer.as_mut()) => {
                 if status == exitcode::UNAVAILABLE || status == exitcode::TEMPFAIL && !opts.no_reconnect {
                     #[allow(clippy::print_stderr)]
                     {
                         eprintln!("[tap] Connection failed. Reconnecting in {:?} seconds.", RECONNECT_DELAY / 1000);
                     }
                     tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
+                } else if status == exitcode::TEMPFAIL || status == exitcode::UNAVAILABLE {
+                    break exitcode::OK;
                 } else {
-                    break;
+                    break status;
                 }
             }
         }
     }
-
-    exitcode::OK
 }
 
 async fn run(
@@ -76,23 +112,30 @@ async fn run(
     opts: &super::Opts,
     outputs_patterns: Vec<String>,
     formatter: EventFormatter,
+    mut file_writer: Option<&mut EventFileWriter>,
 ) -> exitcode::ExitCode {
-    let subscription_client = match connect_subscription_client(url).await {
-        Ok(c) => c,
-        Err(e) => {
-            #[allow(clippy::print_stderr)]
-            {
-                eprintln!("[tap] Couldn't connect to API via WebSockets: {}", e);
+    let connection = opts.connection();
+    let authorization = connection.authorization();
+    let subscription_client =
+        match connect_subscription_client(url, authorization.as_deref(), connection.tls()).await {
+            Ok(c) => c,
+            Err(e) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("[tap] Couldn't connect to API via WebSockets: {}", e);
+                }
+                return exitcode::UNAVAILABLE;
             }
-            return exitcode::UNAVAILABLE;
-        }
-    };
+        };
 
     tokio::pin! {
         let stream = subscription_client.output_events_by_component_id_patterns_subscription(
             outputs_patterns,
             opts.inputs_of.clone(),
-            opts.format,
+            opts.filter.clone(),
+            // Events written to a file are requested in the native JSON encoding, which can be
+            // decoded back into events regardless of the output format.
+            if file_writer.is_some() { TapEncodingFormat::NativeJson } else { opts.format },
             opts.limit as i64,
             opts.interval as i64,
         );
@@ -104,25 +147,52 @@ async fn run(
     loop {
         let message = stream.next().await;
         if let Some(Some(res)) = message {
+            // Errors are only returned for invalid subscription arguments, such as a filter that
+            // fails to compile, so retrying wouldn't help.
+            if let Some(errors) = res.errors {
+                for error in errors {
+                    eprintln!("[tap] {}", error.message);
+                }
+                return exitcode::USAGE;
+            }
             if let Some(d) = res.data {
                 for tap_event in d.output_events_by_component_id_patterns.iter() {
-                    match tap_event {
+                    let (component_id, component_kind, component_type, event) = match tap_event {
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Log(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            (ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref())
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Metric(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            (ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref())
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Trace(ev) => {
-                            println!("{}", formatter.format(ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref()));
+                            (ev.component_id.as_ref(), ev.component_kind.as_ref(), ev.component_type.as_ref(), ev.string.as_ref())
                         },
                         OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::EventNotification(ev) => {
                             if !opts.quiet {
                                 eprintln!("{}", ev.message);
                             }
+                            continue;
                         },
+                    };
+
+                    match file_writer.as_deref_mut() {
+                        Some(file_writer) => {
+                            if let Err(error) = file_writer.write(event) {
+                                eprintln!("[tap] Couldn't write event to output file: {}", error);
+                                return exitcode::IOERR;
+                            }
+                        }
+                        None => println!(
+                            "{}",
+                            formatter.format(component_id, component_kind, component_type, event)
+                        ),
                     }
                 }
+
+                if let Some(Err(error)) = file_writer.as_deref_mut().map(EventFileWriter::flush) {
+                    eprintln!("[tap] Couldn't write event to output file: {}", error);
+                    return exitcode::IOERR;
+                }
             }
         } else {
             return exitcode::TEMPFAIL;
@@ -130,6 +200,49 @@ async fn run(
     }
 }
 
+/// Writes tapped events to a file in a format that can be decoded back into events, so they
+/// can be replayed later.
+struct EventFileWriter {
+    writer: BufWriter<File>,
+    encoder: Encoder<Framer>,
+    buffer: BytesMut,
+}
+
+impl EventFileWriter {
+    fn open(path: &Path, format: TapOutputFormat) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let encoder = match format {
+            TapOutputFormat::Json => Encoder::<Framer>::new(
+                NewlineDelimitedEncoder::new().into(),
+                NativeJsonSerializerConfig.build().into(),
+            ),
+            TapOutputFormat::Native => Encoder::<Framer>::new(
+                LengthDelimitedEncoder::new().into(),
+                NativeSerializerConfig.build().into(),
+            ),
+        };
+
+        Ok(Self {
+            writer: BufWriter::new(file),
+            encoder,
+            buffer: BytesMut::new(),
+        })
+    }
+
+    /// Writes an event received in the native JSON encoding.
+    fn write(&mut self, event: &str) -> crate::Result<()> {
+        let event: Event = serde_json::from_str(event)?;
+        self.buffer.clear();
+        self.encoder.encode(event, &mut self.buffer)?;
+        self.writer.write_all(&self.buffer)?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
 #[derive(Clone)]
 struct EventFormatter {
     meta: bool,
@@ -159,7 +272,7 @@ impl EventFormatter {
     ) -> Cow<'a, str> {
         if self.meta {
             match self.format {
-                TapEncodingFormat::Json => format!(
+                TapEncodingFormat::Json | TapEncodingFormat::NativeJson => format!(
                     r#"{{"{}":"{}","{}":"{}","{}":"{}","event":{}}}"#,
                     self.component_id_label,
                     component_id.green(),
@@ -1,13 +1,15 @@
 //! Tap subcommand
 mod cmd;
 
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
 pub(crate) use cmd::cmd;
 pub use cmd::tap;
 use url::Url;
 use vector_lib::api_client::gql::TapEncodingFormat;
 
-use crate::config::api::default_graphql_url;
+use crate::{api_client::ConnectionOpts, config::api::default_graphql_url};
 
 /// Tap options
 #[derive(Parser, Debug, Clone)]
@@ -21,6 +23,9 @@ pub struct Opts {
     #[arg(short, long)]
     url: Option<Url>,
 
+    #[command(flatten)]
+    connection: ConnectionOpts,
+
     /// Maximum number of events to sample each interval
     #[arg(default_value = "100", short = 'l', long)]
     limit: u32,
@@ -52,6 +57,28 @@ pub struct Opts {
     /// Whether to reconnect if the underlying API connection drops. By default, tap will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    /// VRL condition used to filter events, such as `.status >= 500`. The condition is evaluated by the Vector instance being tapped, so non-matching events are never sent to the client
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Write tapped events to this file instead of printing them to screen. The file is appended to if it already exists
+    #[arg(short, long)]
+    output_file: Option<PathBuf>,
+
+    /// Encoding format for events written to `--output-file`
+    #[arg(default_value = "json", long, requires = "output_file")]
+    output_format: TapOutputFormat,
+}
+
+/// Encoding formats for events written to a file, both of which can be decoded back into
+/// events for later replay.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapOutputFormat {
+    /// Newline-delimited JSON, as produced by the `native_json` codec
+    Json,
+    /// Length-delimited protobuf, as produced by the `native` codec
+    Native,
 }
 
 impl Opts {
@@ -79,6 +106,11 @@ impl Opts {
         self.url.clone().unwrap_or_else(default_graphql_url)
     }
 
+    /// Options for connecting to the GraphQL API server
+    pub const fn connection(&self) -> &ConnectionOpts {
+        &self.connection
+    }
+
     /// URL with scheme set to WebSockets
     pub fn web_socket_url(&self) -> Url {
         let mut url = self.url();
@@ -96,32 +96,7 @@ impl TryFrom<Cow<'_, str>> for Template {
     type Error = TemplateParseError;
 
     fn try_from(src: Cow<'_, str>) -> Result<Self, Self::Error> {
-        parse_template(&src).map(|parts| {
-            let is_static =
-                parts.is_empty() || (parts.len() == 1 && matches!(parts[0], Part::Literal(..)));
-
-            // Calculate a minimum size to reserve for rendered string. This doesn't have to be
-            // exact, and can't be because of references and time format specifiers. We just want a
-            // better starting number than 0 to avoid the first reallocations if possible.
-            let reserve_size = parts
-                .iter()
-                .map(|part| match part {
-                    Part::Literal(lit) => lit.len(),
-                    // We can't really put a useful number here, assume at least one byte will come
-                    // from the input event.
-                    Part::Reference(_path) => 1,
-                    Part::Strftime(parsed) => parsed.reserve_size(),
-                })
-                .sum();
-
-            Template {
-                parts,
-                src: src.into_owned(),
-                is_static,
-                reserve_size,
-                tz_offset: None,
-            }
-        })
+        parse_template(&src, true).map(|parts| Template::from_parts(src.into_owned(), parts))
     }
 }
 
@@ -141,6 +116,43 @@ impl fmt::Display for Template {
 impl ConfigurableString for Template {}
 
 impl Template {
+    /// Parses a template in which only the `{{ field }}` references are rendered, and the rest of
+    /// the string is used verbatim.
+    ///
+    /// Unlike with [`Template::try_from`], `%` doesn't start a time format specifier, which suits
+    /// values such as percent-encoded URIs.
+    pub fn with_fields_only(src: impl Into<String>) -> Result<Self, TemplateParseError> {
+        let src = src.into();
+        parse_template(&src, false).map(|parts| Template::from_parts(src, parts))
+    }
+
+    fn from_parts(src: String, parts: Vec<Part>) -> Self {
+        let is_static =
+            parts.is_empty() || (parts.len() == 1 && matches!(parts[0], Part::Literal(..)));
+
+        // Calculate a minimum size to reserve for rendered string. This doesn't have to be
+        // exact, and can't be because of references and time format specifiers. We just want a
+        // better starting number than 0 to avoid the first reallocations if possible.
+        let reserve_size = parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(lit) => lit.len(),
+                // We can't really put a useful number here, assume at least one byte will come
+                // from the input event.
+                Part::Reference(_path) => 1,
+                Part::Strftime(parsed) => parsed.reserve_size(),
+            })
+            .sum();
+
+        Template {
+            parts,
+            src,
+            is_static,
+            reserve_size,
+            tz_offset: None,
+        }
+    }
+
     /// set tz offset
     pub const fn with_tz_offset(mut self, tz_offset: Option<FixedOffset>) -> Self {
         self.tz_offset = tz_offset;
@@ -319,13 +331,20 @@ fn parse_literal(src: &str) -> Result<Part, TemplateParseError> {
 }
 
 // Pre-parse the template string into a series of parts to be filled in at render time.
-fn parse_template(src: &str) -> Result<Vec<Part>, TemplateParseError> {
+fn parse_template(src: &str, strftime: bool) -> Result<Vec<Part>, TemplateParseError> {
+    let literal = |src: &str| {
+        if strftime {
+            parse_literal(src)
+        } else {
+            Ok(Part::Literal(src.to_string()))
+        }
+    };
     let mut last_end = 0;
     let mut parts = Vec::new();
     for cap in RE.captures_iter(src) {
         let all = cap.get(0).expect("Capture 0 is always defined");
         if all.start() > last_end {
-            parts.push(parse_literal(&src[last_end..all.start()])?);
+            parts.push(literal(&src[last_end..all.start()])?);
         }
 
         let path = cap[1].trim().to_owned();
@@ -340,7 +359,7 @@ fn parse_template(src: &str) -> Result<Vec<Part>, TemplateParseError> {
         last_end = all.end();
     }
     if src.len() > last_end {
-        parts.push(parse_literal(&src[last_end..])?);
+        parts.push(literal(&src[last_end..])?);
     }
 
     Ok(parts)
@@ -481,6 +500,19 @@ mod tests {
         assert_eq!(Ok(Bytes::from("stream-abcd")), template.render(&event))
     }
 
+    #[test]
+    fn render_log_fields_only() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("log_stream", "stream");
+        let template = Template::with_fields_only("/%F%20{{log_stream}}-100%").unwrap();
+
+        assert_eq!(
+            Ok(Bytes::from("/%F%20stream-100%")),
+            template.render(&event)
+        );
+        assert!(!Template::with_fields_only("/%F").unwrap().is_dynamic());
+    }
+
     #[test]
     fn render_log_dynamic_missing_key() {
         let event = Event::Log(LogEvent::from("hello world"));
@@ -28,7 +28,9 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
 
     let url = opts.url();
     // Create a new API client for connecting to the local/remote Vector instance.
-    let client = Client::new(url.clone());
+    let client = Client::new(url.clone())
+        .with_authorization(opts.connection().authorization())
+        .with_tls(opts.connection().tls().cloned());
     #[allow(clippy::print_stderr)]
     if client.healthcheck().await.is_err() {
         eprintln!(
@@ -93,6 +95,7 @@ async fn subscription(
     shutdown_tx: oneshot::Sender<()>,
 ) {
     let ws_url = opts.web_socket_url();
+    let authorization = opts.connection().authorization();
 
     loop {
         // Initialize state. On future reconnects, we re-initialize state in
@@ -107,7 +110,13 @@ async fn subscription(
         };
         _ = tx.send(EventType::InitializeState(state)).await;
 
-        let subscription_client = match connect_subscription_client(ws_url.clone()).await {
+        let subscription_client = match connect_subscription_client(
+            ws_url.clone(),
+            authorization.as_deref(),
+            opts.connection().tls(),
+        )
+        .await
+        {
             Ok(c) => c,
             Err(_) => {
                 tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
@@ -11,7 +11,7 @@ pub use cmd::top;
 pub use dashboard::is_tty;
 use url::Url;
 
-use crate::config::api::default_graphql_url;
+use crate::{api_client::ConnectionOpts, config::api::default_graphql_url};
 
 /// Top options
 #[derive(Parser, Debug, Clone)]
@@ -25,6 +25,9 @@ pub struct Opts {
     #[arg(short, long)]
     url: Option<Url>,
 
+    #[command(flatten)]
+    connection: ConnectionOpts,
+
     /// Humanize metrics, using numeric suffixes - e.g. 1,100 = 1.10 k, 1,000,000 = 1.00 M
     #[arg(short = 'H', long, default_value_t = true)]
     human_metrics: bool,
@@ -43,6 +46,11 @@ impl Opts {
         self.url.clone().unwrap_or_else(default_graphql_url)
     }
 
+    /// Options for connecting to the GraphQL API server
+    pub const fn connection(&self) -> &ConnectionOpts {
+        &self.connection
+    }
+
     /// URL with scheme set to WebSockets
     pub fn web_socket_url(&self) -> Url {
         let mut url = self.url();
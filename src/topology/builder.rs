@@ -12,7 +12,7 @@ use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
     select,
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{mpsc::UnboundedSender, oneshot, watch},
     time::{timeout, Duration},
 };
 use tracing::Instrument;
@@ -40,10 +40,12 @@ use super::{
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
+    batch_mode::Deliveries,
     config::{
         ComponentKey, Config, DataType, EnrichmentTableConfig, Input, Inputs, OutputId,
         ProxyConfig, SinkContext, SourceContext, TransformContext, TransformOuter, TransformOutput,
     },
+    dead_letter::DeadLetters,
     event::{EventArray, EventContainer},
     extra_context::ExtraContext,
     internal_events::EventsReceived,
@@ -85,7 +87,10 @@ struct Builder<'a> {
     inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
     healthchecks: HashMap<ComponentKey, Task>,
     detach_triggers: HashMap<ComponentKey, Trigger>,
+    pause_controls: HashMap<ComponentKey, watch::Sender<bool>>,
     extra_context: ExtraContext,
+    dead_letters: DeadLetters,
+    deliveries: Deliveries,
 }
 
 impl<'a> Builder<'a> {
@@ -94,6 +99,8 @@ impl<'a> Builder<'a> {
         diff: &'a ConfigDiff,
         buffers: HashMap<ComponentKey, BuiltBuffer>,
         extra_context: ExtraContext,
+        dead_letters: DeadLetters,
+        deliveries: Deliveries,
     ) -> Self {
         Self {
             config,
@@ -106,7 +113,10 @@ impl<'a> Builder<'a> {
             inputs: HashMap::new(),
             healthchecks: HashMap::new(),
             detach_triggers: HashMap::new(),
+            pause_controls: HashMap::new(),
             extra_context,
+            dead_letters,
+            deliveries,
         }
     }
 
@@ -130,6 +140,9 @@ impl<'a> Builder<'a> {
                 healthchecks: self.healthchecks,
                 shutdown_coordinator: self.shutdown_coordinator,
                 detach_triggers: self.detach_triggers,
+                pause_controls: self.pause_controls,
+                dead_letters: self.dead_letters,
+                deliveries: self.deliveries,
             })
         } else {
             Err(self.errors)
@@ -236,6 +249,7 @@ impl<'a> Builder<'a> {
             let mut pumps = Vec::new();
             let mut controls = HashMap::new();
             let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
+            let (pause_tx, pause_rx) = watch::channel(false);
 
             for output in source_outputs.into_iter() {
                 let mut rx = builder.add_source_output(output.clone(), key.clone());
@@ -243,15 +257,31 @@ impl<'a> Builder<'a> {
                 let (mut fanout, control) = Fanout::new();
                 let source_type = source.inner.get_component_name();
                 let source = Arc::new(key.clone());
+                let mut paused = pause_rx.clone();
 
                 let pump = async move {
                     debug!("Source pump starting.");
 
-                    while let Some(SourceSenderItem {
-                        events: mut array,
-                        send_reference,
-                    }) = rx.next().await
-                    {
+                    loop {
+                        // While the source is paused, its events are left in the source sender,
+                        // so that it stops pulling new ones once the buffer is full.
+                        while *paused.borrow_and_update() {
+                            if paused.changed().await.is_err() {
+                                break;
+                            }
+                        }
+
+                        let item = select! {
+                            item = rx.next() => item,
+                            Ok(()) = paused.changed() => continue,
+                        };
+                        let Some(SourceSenderItem {
+                            events: mut array,
+                            send_reference,
+                        }) = item
+                        else {
+                            break;
+                        };
                         array.set_output_id(&source);
                         array.set_source_type(source_type);
                         fanout
@@ -330,6 +360,8 @@ impl<'a> Builder<'a> {
                 acknowledgements: source.sink_acknowledgements,
                 schema_definitions,
                 schema: self.config.schema,
+                batch: self.config.batch,
+                dead_letters: self.dead_letters.clone(),
             };
             let source = source.inner.build(context).await;
             let server = match source {
@@ -393,6 +425,7 @@ impl<'a> Builder<'a> {
             let server = Task::new(key.clone(), typetag, server);
 
             self.outputs.extend(controls);
+            self.pause_controls.insert(key.clone(), pause_tx);
             self.tasks.insert(key.clone(), pump);
             source_tasks.insert(key.clone(), server);
         }
@@ -519,6 +552,11 @@ impl<'a> Builder<'a> {
 
             let typetag = sink.inner.get_component_name();
             let input_type = sink.inner.input().data_type();
+            let dead_letters = sink
+                .dead_letter
+                .enabled
+                .then(|| (key.clone(), self.dead_letters.clone()));
+            let deliveries = self.config.batch.then(|| self.deliveries.clone());
 
             let span = error_span!(
                 "sink",
@@ -612,6 +650,14 @@ impl<'a> Builder<'a> {
                                 events.estimated_json_encoded_size_of(),
                             ))
                         })
+                        .map(|events| match &dead_letters {
+                            Some((key, dead_letters)) => dead_letters.track(key, events),
+                            None => events,
+                        })
+                        .map(move |events| match &deliveries {
+                            Some(deliveries) => deliveries.track(events),
+                            None => events,
+                        })
                         .take_until_if(tripwire),
                 )
                 .await
@@ -682,6 +728,9 @@ pub struct TopologyPieces {
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
+    pub(crate) pause_controls: HashMap<ComponentKey, watch::Sender<bool>>,
+    pub(crate) dead_letters: DeadLetters,
+    pub(crate) deliveries: Deliveries,
 }
 
 impl TopologyPieces {
@@ -690,8 +739,19 @@ impl TopologyPieces {
         diff: &ConfigDiff,
         buffers: HashMap<ComponentKey, BuiltBuffer>,
         extra_context: ExtraContext,
+        dead_letters: DeadLetters,
+        deliveries: Deliveries,
     ) -> Option<Self> {
-        match TopologyPieces::build(config, diff, buffers, extra_context).await {
+        match TopologyPieces::build(
+            config,
+            diff,
+            buffers,
+            extra_context,
+            dead_letters,
+            deliveries,
+        )
+        .await
+        {
             Err(errors) => {
                 for error in errors {
                     error!(message = "Configuration error.", %error);
@@ -708,10 +768,19 @@ impl TopologyPieces {
         diff: &ConfigDiff,
         buffers: HashMap<ComponentKey, BuiltBuffer>,
         extra_context: ExtraContext,
+        dead_letters: DeadLetters,
+        deliveries: Deliveries,
     ) -> Result<Self, Vec<String>> {
-        Builder::new(config, diff, buffers, extra_context)
-            .build()
-            .await
+        Builder::new(
+            config,
+            diff,
+            buffers,
+            extra_context,
+            dead_letters,
+            deliveries,
+        )
+        .build()
+        .await
     }
 }
 
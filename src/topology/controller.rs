@@ -4,7 +4,7 @@ use std::sync::Arc;
 use futures_util::future::BoxFuture;
 use futures_util::FutureExt as _;
 
-use tokio::sync::{Mutex, MutexGuard};
+use tokio::sync::{mpsc, oneshot, Mutex, MutexGuard};
 
 #[cfg(feature = "api")]
 use crate::api;
@@ -17,7 +17,34 @@ use crate::internal_events::{
     VectorConfigLoadError, VectorRecoveryError, VectorReloadError, VectorReloaded,
 };
 
-use crate::{config, signal::ShutdownError, topology::RunningTopology};
+use crate::{
+    config::{self, ComponentKey},
+    signal::ShutdownError,
+    topology::RunningTopology,
+};
+
+/// A command to change the running topology, sent through the API.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ControlCommand {
+    /// Reload the configuration from disk.
+    Reload,
+    /// Stop the source from pulling new events.
+    PauseSource(ComponentKey),
+    /// Let a paused source pull new events again.
+    ResumeSource(ComponentKey),
+    /// Remove the sink once it has processed the events in its buffer.
+    DrainSink(ComponentKey),
+}
+
+/// A [`ControlCommand`], along with a channel to reply with the errors that made it fail.
+#[derive(Debug)]
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub reply: oneshot::Sender<Result<(), Vec<String>>>,
+}
+
+pub type ControlTx = mpsc::UnboundedSender<ControlRequest>;
+pub type ControlRx = mpsc::UnboundedReceiver<ControlRequest>;
 
 #[derive(Clone, Debug)]
 pub struct SharedTopologyController(Arc<Mutex<TopologyController>>);
@@ -44,6 +71,7 @@ pub struct TopologyController {
     pub enterprise_reporter: Option<EnterpriseReporter<BoxFuture<'static, ()>>>,
     #[cfg(feature = "api")]
     pub api_server: Option<api::Server>,
+    pub control_tx: ControlTx,
     pub extra_context: ExtraContext,
 }
 
@@ -76,6 +104,8 @@ impl TopologyController {
         new_config
             .healthchecks
             .set_require_healthy(self.require_healthy);
+        // Batch mode is set on the command line, and can't be changed by reloading.
+        new_config.batch = self.topology.config.batch;
 
         #[cfg(feature = "enterprise")]
         // Augment config to enable observability within Datadog, if applicable.
@@ -116,6 +146,7 @@ impl TopologyController {
                 self.topology.config(),
                 self.topology.watch(),
                 Arc::<AtomicBool>::clone(&self.topology.running),
+                self.control_tx.clone(),
                 &Handle::current(),
             ) {
                 Ok(api_server) => {
@@ -135,11 +166,42 @@ impl TopologyController {
             }
         }
 
-        match self
+        let result = self
             .topology
             .reload_config_and_respawn(new_config, self.extra_context.clone())
-            .await
-        {
+            .await;
+        self.reload_outcome(result)
+    }
+
+    /// Removes a sink from the running topology once it has processed the events in its buffer.
+    ///
+    /// The sink is only removed from the running topology, so it comes back on the next reload
+    /// unless it is also removed from the configuration.
+    ///
+    /// # Errors
+    ///
+    /// If the sink doesn't exist, or is the only one in the topology, an error is returned.
+    pub async fn drain_sink(&mut self, key: &ComponentKey) -> Result<ReloadOutcome, String> {
+        let config = self.topology.config();
+        if config.sink(key).is_none() {
+            return Err(format!("Sink \"{}\" doesn't exist.", key));
+        }
+        if config.sinks().count() == 1 {
+            return Err(format!(
+                "Sink \"{}\" is the only sink, and can't be removed.",
+                key
+            ));
+        }
+
+        let result = self
+            .topology
+            .drain_and_remove_sink(key, self.extra_context.clone())
+            .await;
+        Ok(self.reload_outcome(result))
+    }
+
+    fn reload_outcome(&mut self, result: Result<bool, ()>) -> ReloadOutcome {
+        match result {
             Ok(true) => {
                 #[cfg(feature = "api")]
                 // Pass the new config to the API server.
@@ -30,7 +30,10 @@ use tokio::sync::{mpsc, watch};
 use vector_lib::buffers::topology::channel::{BufferReceiverStream, BufferSender};
 
 pub use self::builder::TopologyPieces;
-pub use self::controller::{ReloadOutcome, SharedTopologyController, TopologyController};
+pub use self::controller::{
+    ControlCommand, ControlRequest, ControlRx, ControlTx, ReloadOutcome, SharedTopologyController,
+    TopologyController,
+};
 pub use self::running::{RunningTopology, ShutdownErrorReceiver};
 
 use self::task::{Task, TaskError, TaskResult};
@@ -24,7 +24,9 @@ use super::{
     BuiltBuffer, TapOutput, TapResource, TaskHandle, WatchRx, WatchTx,
 };
 use crate::{
+    batch_mode::Deliveries,
     config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, Inputs, OutputId, Resource},
+    dead_letter::DeadLetters,
     event::EventArray,
     extra_context::ExtraContext,
     shutdown::SourceShutdownCoordinator,
@@ -44,15 +46,23 @@ pub struct RunningTopology {
     tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<ComponentKey, DisabledTrigger>,
+    pause_controls: HashMap<ComponentKey, watch::Sender<bool>>,
     pub(crate) config: Config,
     pub(crate) abort_tx: mpsc::UnboundedSender<ShutdownError>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
     graceful_shutdown_duration: Option<Duration>,
+    dead_letters: DeadLetters,
+    deliveries: Deliveries,
 }
 
 impl RunningTopology {
-    pub fn new(config: Config, abort_tx: mpsc::UnboundedSender<ShutdownError>) -> Self {
+    pub fn new(
+        config: Config,
+        abort_tx: mpsc::UnboundedSender<ShutdownError>,
+        dead_letters: DeadLetters,
+        deliveries: Deliveries,
+    ) -> Self {
         Self {
             inputs: HashMap::new(),
             inputs_tap_metadata: HashMap::new(),
@@ -60,6 +70,7 @@ impl RunningTopology {
             outputs_tap_metadata: HashMap::new(),
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
+            pause_controls: HashMap::new(),
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
             abort_tx,
@@ -67,6 +78,8 @@ impl RunningTopology {
             running: Arc::new(AtomicBool::new(true)),
             graceful_shutdown_duration: config.graceful_shutdown_duration,
             config,
+            dead_letters,
+            deliveries,
         }
     }
 
@@ -75,6 +88,11 @@ impl RunningTopology {
         &self.config
     }
 
+    /// Gets the counters of the events that sinks failed to deliver in batch mode.
+    pub const fn deliveries(&self) -> &Deliveries {
+        &self.deliveries
+    }
+
     /// Creates a subscription to topology changes.
     ///
     /// This is used by the tap API to observe configuration changes, and re-wire tap sinks.
@@ -109,6 +127,8 @@ impl RunningTopology {
     pub fn stop(self) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
+        // Resume any paused source, so that it can flush its events while shutting down.
+        drop(self.pause_controls);
         // Create handy handles collections of all tasks for the subsequent
         // operations.
         let mut wait_handles = Vec::new();
@@ -224,6 +244,54 @@ impl RunningTopology {
         &mut self,
         new_config: Config,
         extra_context: ExtraContext,
+    ) -> Result<bool, ()> {
+        self.reload(new_config, extra_context, &HashSet::new())
+            .await
+    }
+
+    /// Removes a sink from this running topology, after waiting for it to process all of the
+    /// events in its buffer.
+    ///
+    /// The inputs of the sink are disconnected first, so that no new events are sent to it. The
+    /// result is the same as for [`RunningTopology::reload_config_and_respawn`].
+    ///
+    /// # Errors
+    ///
+    /// If the sink cannot be removed, and the current configuration cannot be fully restored, then
+    /// `Err(())` is returned.
+    pub async fn drain_and_remove_sink(
+        &mut self,
+        key: &ComponentKey,
+        extra_context: ExtraContext,
+    ) -> Result<bool, ()> {
+        let new_config = self.config.without_sink(key);
+        let drain = HashSet::from([key.clone()]);
+        self.reload(new_config, extra_context, &drain).await
+    }
+
+    /// Pauses or resumes a source.
+    ///
+    /// While a source is paused, the events it already produced are held back, and it stops
+    /// pulling new ones once its send buffer is full. Sources that acknowledge or checkpoint what
+    /// they read only do so once the events are delivered, so nothing is lost or skipped.
+    ///
+    /// # Errors
+    ///
+    /// If there is no running source with the given key, an error is returned.
+    pub fn set_source_paused(&self, key: &ComponentKey, paused: bool) -> Result<(), String> {
+        let control = self
+            .pause_controls
+            .get(key)
+            .ok_or_else(|| format!("Source \"{}\" doesn't exist.", key))?;
+        control.send_replace(paused);
+        Ok(())
+    }
+
+    async fn reload(
+        &mut self,
+        new_config: Config,
+        extra_context: ExtraContext,
+        drain: &HashSet<ComponentKey>,
     ) -> Result<bool, ()> {
         info!("Reloading running topology with new configuration.");
 
@@ -241,7 +309,7 @@ impl RunningTopology {
         //
         // We also shutdown any component that is simply being removed entirely.
         let diff = ConfigDiff::new(&self.config, &new_config);
-        let buffers = self.shutdown_diff(&diff, &new_config).await;
+        let buffers = self.shutdown_diff(&diff, &new_config, drain).await;
 
         // Gives windows some time to make available any port
         // released by shutdown components.
@@ -259,6 +327,8 @@ impl RunningTopology {
             &diff,
             buffers.clone(),
             extra_context.clone(),
+            self.dead_letters.clone(),
+            self.deliveries.clone(),
         )
         .await
         {
@@ -285,9 +355,15 @@ impl RunningTopology {
         warn!("Failed to completely load new configuration. Restoring old configuration.");
 
         let diff = diff.flip();
-        if let Some(mut new_pieces) =
-            TopologyPieces::build_or_log_errors(&self.config, &diff, buffers, extra_context.clone())
-                .await
+        if let Some(mut new_pieces) = TopologyPieces::build_or_log_errors(
+            &self.config,
+            &diff,
+            buffers,
+            extra_context.clone(),
+            self.dead_letters.clone(),
+            self.deliveries.clone(),
+        )
+        .await
         {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
@@ -341,11 +417,14 @@ impl RunningTopology {
 
     /// Shuts down any changed/removed component in the given configuration diff.
     ///
+    /// Removed sinks in `drain` are waited on until they have processed their buffered events.
+    ///
     /// If buffers for any of the changed/removed components can be recovered, they'll be returned.
     async fn shutdown_diff(
         &mut self,
         diff: &ConfigDiff,
         new_config: &Config,
+        drain: &HashSet<ComponentKey>,
     ) -> HashMap<ComponentKey, BuiltBuffer> {
         // First, we shutdown any changed/removed sources. This ensures that we can allow downstream
         // components to terminate naturally by virtue of the flow of events stopping.
@@ -354,6 +433,11 @@ impl RunningTopology {
             let mut source_shutdown_handles = Vec::new();
 
             let deadline = Instant::now() + timeout;
+            for key in diff.sources.removed_and_changed() {
+                // A paused source couldn't finish sending its events.
+                self.pause_controls.remove(key);
+            }
+
             for key in &diff.sources.to_remove {
                 debug!(component = %key, "Removing source.");
 
@@ -457,9 +541,11 @@ impl RunningTopology {
 
         // For any existing sink that has a conflicting resource dependency with a changed/added
         // sink, or for any sink that we want to reuse their buffer, we need to explicit wait for
-        // them to finish processing so we can reclaim ownership of those resources/buffers.
+        // them to finish processing so we can reclaim ownership of those resources/buffers. Sinks
+        // being drained are waited on as well.
         let wait_for_sinks = conflicting_sinks
             .chain(reuse_buffers.iter().cloned())
+            .chain(drain.iter().cloned())
             .collect::<HashSet<_>>();
 
         // First, we remove any inputs to removed sinks so they can naturally shut down.
@@ -933,6 +1019,9 @@ impl RunningTopology {
 
     fn spawn_source(&mut self, key: &ComponentKey, new_pieces: &mut builder::TopologyPieces) {
         let task = new_pieces.tasks.remove(key).unwrap();
+        if let Some(control) = new_pieces.pause_controls.remove(key) {
+            self.pause_controls.insert(key.clone(), control);
+        }
         let span = error_span!(
             "source",
             component_kind = "source",
@@ -993,9 +1082,15 @@ impl RunningTopology {
         extra_context: ExtraContext,
     ) -> Option<(Self, ShutdownErrorReceiver)> {
         let diff = ConfigDiff::initial(&config);
-        let pieces =
-            TopologyPieces::build_or_log_errors(&config, &diff, HashMap::new(), extra_context)
-                .await?;
+        let pieces = TopologyPieces::build_or_log_errors(
+            &config,
+            &diff,
+            HashMap::new(),
+            extra_context,
+            DeadLetters::default(),
+            Deliveries::default(),
+        )
+        .await?;
         Self::start_validated(config, diff, pieces).await
     }
 
@@ -1031,7 +1126,9 @@ impl RunningTopology {
             return None;
         }
 
-        let mut running_topology = Self::new(config, abort_tx);
+        let dead_letters = pieces.dead_letters.clone();
+        let deliveries = pieces.deliveries.clone();
+        let mut running_topology = Self::new(config, abort_tx, dead_letters, deliveries);
 
         if !running_topology
             .run_healthchecks(&diff, &mut pieces, running_topology.config.healthchecks)
@@ -103,10 +103,16 @@ uri = "http://{address2}/"
     )
     .unwrap();
     let diff = ConfigDiff::initial(&config);
-    let pieces =
-        TopologyPieces::build_or_log_errors(&config, &diff, HashMap::new(), Default::default())
-            .await
-            .unwrap();
+    let pieces = TopologyPieces::build_or_log_errors(
+        &config,
+        &diff,
+        HashMap::new(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
     let (_topology, _) = RunningTopology::start_validated(config, diff, pieces)
         .await
         .unwrap();
@@ -919,11 +919,19 @@ async fn topology_transform_error_definition() {
 
     let config = config.build().unwrap();
     let diff = ConfigDiff::initial(&config);
-    let errors =
-        match TopologyPieces::build(&config, &diff, HashMap::new(), Default::default()).await {
-            Ok(_) => panic!("build pieces should not succeed"),
-            Err(err) => err,
-        };
+    let errors = match TopologyPieces::build(
+        &config,
+        &diff,
+        HashMap::new(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    )
+    .await
+    {
+        Ok(_) => panic!("build pieces should not succeed"),
+        Err(err) => err,
+    };
 
     assert_eq!(
         r#"Transform "transform": It all went horribly wrong"#,
@@ -971,3 +979,92 @@ async fn source_metadata_reaches_sink() {
         ComponentKey::from("in2")
     );
 }
+
+#[tokio::test]
+async fn topology_pause_and_resume_source() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (mut out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (topology, _) = start_topology(config.build().unwrap(), false).await;
+    let key = ComponentKey::from("in1");
+
+    topology.set_source_paused(&key, true).unwrap();
+    in1.send_event(Event::Log(LogEvent::from("paused")))
+        .await
+        .unwrap();
+
+    // The event is held back while the source is paused.
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), out1.next())
+            .await
+            .is_err()
+    );
+
+    topology.set_source_paused(&key, false).unwrap();
+    let res = tokio::time::timeout(Duration::from_secs(1), out1.next())
+        .await
+        .expect("timeout")
+        .map(|array| into_message(array.into_events().next().unwrap()))
+        .expect("no output");
+    assert_eq!("paused", res);
+
+    assert!(topology
+        .set_source_paused(&ComponentKey::from("in2"), true)
+        .is_err());
+
+    topology.stop().await;
+}
+
+#[tokio::test]
+async fn topology_drain_and_remove_sink() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (mut out1, sink1) = basic_sink(10);
+    let (out2, sink2) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+    config.add_sink("out2", &["in1"], sink2);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    // Once the first sink got the event, it is in the buffer of the second one as well.
+    in1.send_event(Event::Log(LogEvent::from("before")))
+        .await
+        .unwrap();
+    let res = out1
+        .next()
+        .await
+        .map(|array| into_message(array.into_events().next().unwrap()))
+        .expect("no output");
+    assert_eq!("before", res);
+
+    assert!(topology
+        .drain_and_remove_sink(&ComponentKey::from("out2"), Default::default())
+        .await
+        .unwrap());
+    assert!(topology
+        .config()
+        .sink(&ComponentKey::from("out2"))
+        .is_none());
+
+    in1.send_event(Event::Log(LogEvent::from("after")))
+        .await
+        .unwrap();
+
+    topology.stop().await;
+
+    let res1 = out1.flat_map(into_message_stream).collect::<Vec<_>>().await;
+    let res2 = out2.flat_map(into_message_stream).collect::<Vec<_>>().await;
+
+    assert_eq!(vec!["after".to_string()], res1);
+    assert_eq!(vec!["before".to_string()], res2);
+}
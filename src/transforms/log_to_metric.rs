@@ -10,7 +10,7 @@ use vector_lib::{
     event::DatadogMetricOriginMetadata,
     event::{
         metric::Sample,
-        metric::{Bucket, Quantile},
+        metric::{samples_to_buckets, Bucket, Quantile},
     },
 };
 use vrl::path::{parse_target_path, PathParseError};
@@ -19,6 +19,7 @@ use vrl::{event_path, path};
 use crate::config::schema::Definition;
 use crate::transforms::log_to_metric::TransformError::PathNotFound;
 use crate::{
+    conditions::{AnyCondition, Condition},
     config::{
         DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
         TransformOutput,
@@ -112,11 +113,40 @@ pub struct MetricConfig {
     #[configurable(metadata(docs::additional_props_description = "A metric tag."))]
     pub tags: Option<IndexMap<String, TagConfig>>,
 
+    /// A logical condition the event must match for the metric to be generated.
+    ///
+    /// Events that don't match it are skipped for this metric only.
+    pub condition: Option<AnyCondition>,
+
     #[configurable(derived)]
     #[serde(flatten)]
     pub metric: MetricTypeConfig,
 }
 
+/// Specification of a histogram derived from a log event.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct HistogramConfig {
+    /// The upper limits of the buckets to count the value in, in ascending order.
+    ///
+    /// When set, the metric is an aggregated histogram with these buckets, instead of a
+    /// distribution holding the value as a sample. Values above the last upper limit are only
+    /// counted in the `count` and `sum` of the histogram.
+    ///
+    /// This only changes the representation of the metric, not the number of metrics: each event
+    /// still produces its own histogram, with a `count` of 1. To combine the histograms of many
+    /// events into one, send the metrics through an `aggregate` transform.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "example_buckets()"))]
+    pub buckets: Vec<f64>,
+}
+
+fn example_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
 /// Specification of the value of a created tag.
 ///
 /// This may be a single value, a `null` for a bare tag, or an array of either.
@@ -141,7 +171,7 @@ pub enum MetricTypeConfig {
     Counter(CounterConfig),
 
     /// A histogram.
-    Histogram,
+    Histogram(HistogramConfig),
 
     /// A gauge.
     Gauge,
@@ -170,6 +200,8 @@ const fn default_kind() -> MetricKind {
 #[derive(Debug, Clone)]
 pub struct LogToMetric {
     config: LogToMetricConfig,
+    /// The built `condition` of each metric, in the same order as `config.metrics`.
+    conditions: Vec<Option<Condition>>,
 }
 
 impl GenerateConfig for LogToMetricConfig {
@@ -180,6 +212,7 @@ impl GenerateConfig for LogToMetricConfig {
                 name: None,
                 namespace: None,
                 tags: None,
+                condition: None,
                 metric: MetricTypeConfig::Counter(CounterConfig {
                     increment_by_value: false,
                     kind: MetricKind::Incremental,
@@ -194,8 +227,8 @@ impl GenerateConfig for LogToMetricConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "log_to_metric")]
 impl TransformConfig for LogToMetricConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(LogToMetric::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        LogToMetric::new(self.clone(), context).map(Transform::function)
     }
 
     fn input(&self) -> Input {
@@ -218,8 +251,38 @@ impl TransformConfig for LogToMetricConfig {
 }
 
 impl LogToMetric {
-    pub const fn new(config: LogToMetricConfig) -> Self {
-        LogToMetric { config }
+    pub fn new(config: LogToMetricConfig, context: &TransformContext) -> crate::Result<Self> {
+        let mut conditions = Vec::with_capacity(config.metrics.len());
+        for metric in &config.metrics {
+            if let MetricTypeConfig::Histogram(histogram) = &metric.metric {
+                if histogram.buckets.iter().any(|limit| limit.is_nan()) {
+                    return Err(format!(
+                        "The `buckets` of metric \"{}\" must not be NaN.",
+                        metric.field()
+                    )
+                    .into());
+                }
+                let sorted = histogram
+                    .buckets
+                    .windows(2)
+                    .all(|limits| limits[0] < limits[1]);
+                if !sorted {
+                    return Err(format!(
+                        "The `buckets` of metric \"{}\" must be in ascending order.",
+                        metric.field()
+                    )
+                    .into());
+                }
+            }
+            conditions.push(
+                metric
+                    .condition
+                    .as_ref()
+                    .map(|condition| condition.build(&context.enrichment_tables))
+                    .transpose()?,
+            );
+        }
+        Ok(LogToMetric { config, conditions })
     }
 }
 
@@ -379,7 +442,7 @@ fn to_metric_with_config(config: &MetricConfig, event: &Event) -> Result<Metric,
 
             (counter.kind, MetricValue::Counter { value })
         }
-        MetricTypeConfig::Histogram => {
+        MetricTypeConfig::Histogram(histogram) => {
             let value = value.to_string_lossy().parse().map_err(|error| {
                 TransformError::ParseFloatError {
                     path: field.to_string(),
@@ -387,13 +450,22 @@ fn to_metric_with_config(config: &MetricConfig, event: &Event) -> Result<Metric,
                 }
             })?;
 
-            (
-                MetricKind::Incremental,
+            let samples = vector_lib::samples![value => 1];
+            let value = if histogram.buckets.is_empty() {
                 MetricValue::Distribution {
-                    samples: vector_lib::samples![value => 1],
+                    samples,
                     statistic: StatisticKind::Histogram,
-                },
-            )
+                }
+            } else {
+                let (buckets, count, sum) = samples_to_buckets(&samples, &histogram.buckets);
+                MetricValue::AggregatedHistogram {
+                    buckets,
+                    count,
+                    sum,
+                }
+            };
+
+            (MetricKind::Incremental, value)
         }
         MetricTypeConfig::Summary => {
             let value = value.to_string_lossy().parse().map_err(|error| {
@@ -775,7 +847,7 @@ fn to_metrics(event: &Event) -> Result<Metric, TransformError> {
 }
 
 impl FunctionTransform for LogToMetric {
-    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
         // Metrics are "all or none" for a specific log. If a single fails, none are produced.
         let mut buffer = Vec::with_capacity(self.config.metrics.len());
         if self
@@ -814,7 +886,15 @@ impl FunctionTransform for LogToMetric {
                 }
             }
         } else {
-            for config in self.config.metrics.iter() {
+            for (config, condition) in self.config.metrics.iter().zip(&self.conditions) {
+                if let Some(condition) = condition {
+                    let (matched, checked) = condition.check(event);
+                    event = checked;
+                    if !matched {
+                        continue;
+                    }
+                }
+
                 match to_metric_with_config(config, &event) {
                     Ok(metric) => {
                         buffer.push(Event::Metric(metric));
@@ -1535,6 +1615,165 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn response_time_histogram_buckets() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [1.0, 2.5, 5.0]
+            "#,
+        );
+
+        let event = create_event("response_time", "2.5");
+        let metric = do_transform(config, event).await.unwrap().into_metric();
+
+        assert_eq!(metric.name(), "response_time");
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![1.0 => 0, 2.5 => 1, 5.0 => 0],
+                count: 1,
+                sum: 2.5,
+            }
+        );
+    }
+
+    #[cfg(feature = "transforms-aggregate")]
+    #[tokio::test]
+    async fn histogram_buckets_combine_in_aggregate() {
+        use futures::{stream, StreamExt};
+
+        use crate::transforms::{
+            aggregate::{Aggregate, AggregateConfig},
+            TaskTransform,
+        };
+
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [1.0, 2.5, 5.0]
+            "#,
+        );
+
+        // Each event produces its own histogram
+        let mut metrics = Vec::new();
+        for value in ["0.5", "2.5", "4.0"] {
+            let event = create_event("response_time", value);
+            let metric = do_transform(config.clone(), event).await.unwrap();
+            assert!(matches!(
+                metric.as_metric().value(),
+                MetricValue::AggregatedHistogram { count: 1, .. }
+            ));
+            metrics.push(metric);
+        }
+
+        // The `aggregate` transform combines them into one
+        let aggregate = Box::new(Aggregate::new(&AggregateConfig::default()).unwrap());
+        let output: Vec<Event> = aggregate
+            .transform(Box::pin(stream::iter(metrics)))
+            .collect()
+            .await;
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            output[0].as_metric().value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![1.0 => 1, 2.5 => 1, 5.0 => 1],
+                count: 3,
+                sum: 7.0,
+            }
+        );
+    }
+
+    #[test]
+    fn nan_histogram_buckets() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [1.0, nan]
+            "#,
+        );
+
+        let error = LogToMetric::new(config, &TransformContext::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The `buckets` of metric \"response_time\" must not be NaN."
+        );
+    }
+
+    #[test]
+    fn unsorted_histogram_buckets() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [1.0, 5.0, 2.5]
+            "#,
+        );
+
+        let error = LogToMetric::new(config, &TransformContext::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The `buckets` of metric \"response_time\" must be in ascending order."
+        );
+    }
+
+    #[tokio::test]
+    async fn count_with_condition() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            name = "server_errors_total"
+            condition = 'starts_with(string!(.status), "5")'
+            "#,
+        );
+
+        let event = create_event("status", "503");
+        let metric = do_transform(config.clone(), event)
+            .await
+            .unwrap()
+            .into_metric();
+        assert_eq!(metric.name(), "server_errors_total");
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 1.0 });
+
+        let event = create_event("status", "200");
+        assert_eq!(do_transform(config, event).await, None);
+    }
+
+    #[tokio::test]
+    async fn multiple_metrics_with_conditions() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            name = "server_errors_total"
+            condition = 'starts_with(string!(.status), "5")'
+
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            name = "requests_total"
+            "#,
+        );
+
+        // The metric whose condition doesn't match is skipped, without preventing the others from
+        // being generated.
+        let event = create_event("status", "200");
+        let metric = do_transform(config, event).await.unwrap().into_metric();
+        assert_eq!(metric.name(), "requests_total");
+    }
+
     #[tokio::test]
     async fn response_time_summary() {
         let config = parse_config(
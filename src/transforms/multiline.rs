@@ -0,0 +1,315 @@
+use std::{pin::Pin, time::Duration};
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use serde_with::serde_as;
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{discriminant::Discriminant, Event, LogEvent},
+    line_agg::{self, LineAgg, Logic},
+    schema,
+    sources::util::multiline_config,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `multiline` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "multiline",
+    "Merge multi-line messages, such as stack traces, into single events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MultilineConfig {
+    /// Regular expression pattern that is used to match the start of a new message.
+    #[configurable(metadata(docs::examples = "^[\\s]+"))]
+    #[configurable(metadata(docs::examples = "\\\\$"))]
+    #[configurable(metadata(docs::examples = "^(INFO|ERROR) "))]
+    #[configurable(metadata(docs::examples = ";$"))]
+    pub start_pattern: String,
+
+    /// Regular expression pattern that is used to determine whether or not more lines should be read.
+    ///
+    /// This setting must be configured in conjunction with `mode`.
+    #[configurable(metadata(docs::examples = "^[\\s]+"))]
+    #[configurable(metadata(docs::examples = "\\\\$"))]
+    #[configurable(metadata(docs::examples = "^(INFO|ERROR) "))]
+    #[configurable(metadata(docs::examples = ";$"))]
+    pub condition_pattern: String,
+
+    /// Aggregation mode.
+    ///
+    /// This setting must be configured in conjunction with `condition_pattern`.
+    #[configurable(derived)]
+    pub mode: line_agg::Mode,
+
+    /// The maximum amount of time to wait for the next additional line, in milliseconds.
+    ///
+    /// Once this timeout is reached, the buffered message is guaranteed to be flushed, even if incomplete.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::examples = 1000))]
+    #[configurable(metadata(docs::examples = 600000))]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    pub timeout_ms: Duration,
+
+    /// An ordered list of fields identifying the stream an event belongs to.
+    ///
+    /// Only the messages of events with matching values for these fields are merged together, so
+    /// that the lines of concurrent streams, such as the containers of a host, aren't mixed. When
+    /// no fields are specified, all events are considered part of a single stream.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::examples = "host",
+        docs::examples = "container_id",
+        docs::examples = "source_type",
+    ))]
+    pub group_by: Vec<String>,
+}
+
+impl GenerateConfig for MultilineConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"start_pattern = "^[^\\s]"
+condition_pattern = "^[\\s]+at"
+mode = "continue_through"
+timeout_ms = 1000"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "multiline")]
+impl TransformConfig for MultilineConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Multiline::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(
+            DataType::Log,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+pub struct Multiline {
+    config: line_agg::Config,
+    group_by: Vec<String>,
+}
+
+impl Multiline {
+    pub fn new(config: &MultilineConfig) -> crate::Result<Self> {
+        let line_agg_config = line_agg::Config::try_from(&multiline_config::MultilineConfig {
+            start_pattern: config.start_pattern.clone(),
+            condition_pattern: config.condition_pattern.clone(),
+            mode: config.mode,
+            timeout_ms: config.timeout_ms,
+        })?;
+
+        Ok(Self {
+            config: line_agg_config,
+            group_by: config.group_by.clone(),
+        })
+    }
+}
+
+/// Merges the metadata of an event whose message was merged into the first event of its group, so
+/// that the finalizers of all the events are kept.
+fn merge_metadata(first: &mut LogEvent, event: LogEvent) {
+    let (_, metadata) = event.into_parts();
+    first.metadata_mut().merge(metadata);
+}
+
+impl TaskTransform<Event> for Multiline {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let group_by = self.group_by;
+        let lines = task.map(move |event| {
+            let log = event.into_log();
+            let discriminant = Discriminant::from_log_event(&log, &group_by);
+            // Events without a message are handled as empty lines, and are only modified if they
+            // are merged with other lines.
+            let line = log
+                .get_message()
+                .map(|message| match message.as_bytes() {
+                    Some(bytes) => bytes.clone(),
+                    None => Bytes::from(message.to_string_lossy().into_owned()),
+                })
+                .unwrap_or_default();
+            (discriminant, line, log)
+        });
+
+        let logic = Logic::new(self.config).with_context_merge(merge_metadata);
+        Box::pin(
+            LineAgg::new(lines, logic).map(|(_, message, mut log, last_log)| {
+                if let Some(last_log) = last_log {
+                    merge_metadata(&mut log, last_log);
+                    if let Some(message_path) = log.message_path().cloned() {
+                        log.insert(&message_path, message);
+                    }
+                }
+                Event::from(log)
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::Value, test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MultilineConfig>();
+    }
+
+    fn java_config(group_by: &[&str]) -> MultilineConfig {
+        MultilineConfig {
+            start_pattern: "^[^\\s]".to_string(),
+            condition_pattern: "^[\\s]+at".to_string(),
+            mode: line_agg::Mode::ContinueThrough,
+            timeout_ms: Duration::from_secs(60),
+            group_by: group_by.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn log(message: &str, host: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("host", host);
+        log.into()
+    }
+
+    #[tokio::test]
+    async fn merges_lines_per_stream() {
+        assert_transform_compliance(async {
+            let (tx, rx) = mpsc::channel(10);
+            let (topology, mut out) =
+                create_topology(ReceiverStream::new(rx), java_config(&["host"])).await;
+
+            for event in [
+                log("java.lang.Exception", "a"),
+                log("java.lang.IllegalStateException", "b"),
+                log("    at com.foo.bar(bar.java:123)", "a"),
+                log("    at com.foo.baz(baz.java:456)", "b"),
+                log("    at com.foo.qux(qux.java:789)", "a"),
+                log("some usual line", "a"),
+            ] {
+                tx.send(event).await.unwrap();
+            }
+
+            let output = out.recv().await.unwrap().into_log();
+            assert_eq!(
+                output["message"],
+                Value::from(concat!(
+                    "java.lang.Exception\n",
+                    "    at com.foo.bar(bar.java:123)\n",
+                    "    at com.foo.qux(qux.java:789)",
+                ))
+            );
+            assert_eq!(output["host"], "a".into());
+
+            drop(tx);
+            topology.stop().await;
+
+            let mut outputs = vec![
+                out.recv().await.unwrap().into_log(),
+                out.recv().await.unwrap().into_log(),
+            ];
+            outputs.sort_by_key(|output| output["host"].to_string_lossy().into_owned());
+            assert_eq!(outputs[0]["message"], "some usual line".into());
+            assert_eq!(
+                outputs[1]["message"],
+                Value::from(concat!(
+                    "java.lang.IllegalStateException\n",
+                    "    at com.foo.baz(baz.java:456)",
+                ))
+            );
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn passes_through_single_lines() {
+        assert_transform_compliance(async {
+            let (tx, rx) = mpsc::channel(10);
+            let (topology, mut out) =
+                create_topology(ReceiverStream::new(rx), java_config(&[])).await;
+
+            let mut event = LogEvent::default();
+            event.insert("count", 1);
+            tx.send(event.clone().into()).await.unwrap();
+            tx.send(log("    at com.foo.bar(bar.java:123)", "a"))
+                .await
+                .unwrap();
+
+            // The event without a message is handled as an empty line, which doesn't start a
+            // message, so it's passed through unmodified.
+            let output = out.recv().await.unwrap().into_log();
+            assert_eq!(output.value(), event.value());
+            let output = out.recv().await.unwrap().into_log();
+            assert_eq!(output["message"], "    at com.foo.bar(bar.java:123)".into());
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn flushes_after_timeout() {
+        assert_transform_compliance(async {
+            let mut config = java_config(&[]);
+            config.timeout_ms = Duration::from_millis(10);
+
+            let (tx, rx) = mpsc::channel(10);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(log("java.lang.Exception", "a")).await.unwrap();
+            tx.send(log("    at com.foo.bar(bar.java:123)", "a"))
+                .await
+                .unwrap();
+
+            // The stream is still open, so the message can only be flushed by the timeout.
+            let output = out.recv().await.unwrap().into_log();
+            assert_eq!(
+                output["message"],
+                Value::from("java.lang.Exception\n    at com.foo.bar(bar.java:123)")
+            );
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
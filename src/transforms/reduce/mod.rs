@@ -9,15 +9,16 @@ use std::{
     time::{Duration, Instant},
 };
 use vector_lib::configurable::configurable_component;
-use vector_lib::lookup::lookup_v2::parse_target_path;
+use vector_lib::lookup::lookup_v2::{parse_target_path, OptionalTargetPath};
 use vector_lib::lookup::PathPrefix;
+use vector_lib::ByteSizeOf;
 
 use crate::config::OutputId;
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, TransformConfig, TransformContext, TransformOutput},
     event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
-    internal_events::ReduceStaleEventFlushed,
+    internal_events::{ReduceGroups, ReduceIncompleteEventFlushed, ReduceStaleEventFlushed},
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -60,6 +61,30 @@ pub struct ReduceConfig {
     /// The maximum number of events to group together.
     pub max_events: Option<NonZeroUsize>,
 
+    /// The maximum size of a group, in bytes.
+    ///
+    /// The size of a group is the sum of the estimated in-memory sizes of the events merged into
+    /// it. The group is flushed along with the event that makes it reach this size.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: Option<NonZeroUsize>,
+
+    /// The maximum size of all the groups combined, in bytes.
+    ///
+    /// When this budget is exceeded, the oldest groups are flushed early until the remaining
+    /// groups fit in it again. The events of the groups flushed this way are marked as incomplete
+    /// with the `incomplete_key` field.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_total_bytes: Option<NonZeroUsize>,
+
+    /// The field to set to `true` in the events of the groups flushed early because
+    /// `max_total_bytes` was exceeded.
+    ///
+    /// Set to `""` to flush these events without marking them.
+    #[serde(default = "default_incomplete_key")]
+    #[derivative(Default(value = "default_incomplete_key()"))]
+    #[configurable(metadata(docs::examples = "reduce_incomplete"))]
+    pub incomplete_key: OptionalTargetPath,
+
     /// An ordered list of fields by which to group events.
     ///
     /// Each group with matching values for the specified keys is reduced independently, allowing
@@ -114,6 +139,10 @@ const fn default_flush_period_ms() -> Duration {
     Duration::from_millis(1000)
 }
 
+fn default_incomplete_key() -> OptionalTargetPath {
+    OptionalTargetPath::event("reduce_incomplete")
+}
+
 impl_generate_config_from_default!(ReduceConfig);
 
 #[async_trait::async_trait]
@@ -224,6 +253,13 @@ impl TransformConfig for ReduceConfig {
             schema_definition = schema_definition.with_field(&key, new_kind, None);
         }
 
+        if self.max_total_bytes.is_some() {
+            if let Some(key) = &self.incomplete_key.path {
+                schema_definition =
+                    schema_definition.with_field(key, Kind::boolean().or_undefined(), None);
+            }
+        }
+
         // the same schema definition is used for all inputs
         let mut output_definitions = HashMap::new();
         for (output, _input) in input_definitions {
@@ -237,25 +273,34 @@ impl TransformConfig for ReduceConfig {
 #[derive(Debug)]
 struct ReduceState {
     events: usize,
+    bytes: usize,
+    sequence: u64,
     fields: HashMap<KeyString, Box<dyn ReduceValueMerger>>,
     stale_since: Instant,
     metadata: EventMetadata,
 }
 
 impl ReduceState {
-    fn new() -> Self {
+    fn new(sequence: u64) -> Self {
         let fields = HashMap::new();
         let metadata = EventMetadata::default();
 
         Self {
             events: 0,
+            bytes: 0,
+            sequence,
             stale_since: Instant::now(),
             fields,
             metadata,
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<KeyString, MergeStrategy>) {
+    fn add_event(
+        &mut self,
+        e: LogEvent,
+        size: usize,
+        strategies: &IndexMap<KeyString, MergeStrategy>,
+    ) {
         let (value, metadata) = e.into_parts();
         self.metadata.merge(metadata);
 
@@ -290,6 +335,7 @@ impl ReduceState {
             }
         }
         self.events += 1;
+        self.bytes += size;
         self.stale_since = Instant::now();
     }
 
@@ -311,9 +357,16 @@ pub struct Reduce {
     group_by: Vec<String>,
     merge_strategies: IndexMap<KeyString, MergeStrategy>,
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
+    /// The discriminants of the groups by the sequence number of their state, oldest first.
+    groups_by_sequence: BTreeMap<u64, Discriminant>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
     max_events: Option<usize>,
+    max_bytes: Option<usize>,
+    max_total_bytes: Option<usize>,
+    incomplete_key: OptionalTargetPath,
+    total_bytes: usize,
+    next_sequence: u64,
 }
 
 impl Reduce {
@@ -337,6 +390,8 @@ impl Reduce {
             .transpose()?;
         let group_by = config.group_by.clone().into_iter().collect();
         let max_events = config.max_events.map(|max| max.into());
+        let max_bytes = config.max_bytes.map(|max| max.into());
+        let max_total_bytes = config.max_total_bytes.map(|max| max.into());
 
         Ok(Reduce {
             expire_after: config.expire_after_ms,
@@ -344,12 +399,60 @@ impl Reduce {
             group_by,
             merge_strategies: config.merge_strategies.clone(),
             reduce_merge_states: HashMap::new(),
+            groups_by_sequence: BTreeMap::new(),
             ends_when,
             starts_when,
             max_events,
+            max_bytes,
+            max_total_bytes,
+            incomplete_key: config.incomplete_key.clone(),
+            total_bytes: 0,
+            next_sequence: 0,
         })
     }
 
+    fn new_reduce_state(&mut self) -> ReduceState {
+        let state = ReduceState::new(self.next_sequence);
+        self.next_sequence += 1;
+        state
+    }
+
+    fn remove_reduce_state(&mut self, discriminant: &Discriminant) -> Option<ReduceState> {
+        let state = self.reduce_merge_states.remove(discriminant)?;
+        self.groups_by_sequence.remove(&state.sequence);
+        self.total_bytes -= state.bytes;
+        Some(state)
+    }
+
+    fn emit_groups(&self) {
+        emit!(ReduceGroups {
+            count: self.reduce_merge_states.len(),
+            byte_size: self.total_bytes,
+        });
+    }
+
+    /// Flushes the oldest groups until the remaining ones fit in `max_total_bytes`.
+    fn flush_over_budget_into(&mut self, emitter: &mut Emitter<Event>) {
+        let Some(max_total_bytes) = self.max_total_bytes else {
+            return;
+        };
+
+        while self.total_bytes > max_total_bytes {
+            let Some((_, oldest)) = self.groups_by_sequence.pop_first() else {
+                break;
+            };
+
+            if let Some(state) = self.remove_reduce_state(&oldest) {
+                emit!(ReduceIncompleteEventFlushed);
+                let mut event = state.flush();
+                if let Some(key) = &self.incomplete_key.path {
+                    event.insert(key, true);
+                }
+                emitter.emit(Event::from(event));
+            }
+        }
+    }
+
     fn flush_into(&mut self, emitter: &mut Emitter<Event>) {
         let mut flush_discriminants = Vec::new();
         let now = Instant::now();
@@ -359,29 +462,38 @@ impl Reduce {
             }
         }
         for k in &flush_discriminants {
-            if let Some(t) = self.reduce_merge_states.remove(k) {
+            if let Some(t) = self.remove_reduce_state(k) {
                 emit!(ReduceStaleEventFlushed);
                 emitter.emit(Event::from(t.flush()));
             }
         }
+        self.emit_groups();
     }
 
     fn flush_all_into(&mut self, emitter: &mut Emitter<Event>) {
         self.reduce_merge_states
             .drain()
             .for_each(|(_, s)| emitter.emit(Event::from(s.flush())));
+        self.groups_by_sequence.clear();
+        self.total_bytes = 0;
+        self.emit_groups();
     }
 
-    fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
-        match self.reduce_merge_states.entry(discriminant) {
-            hash_map::Entry::Vacant(entry) => {
-                let mut state = ReduceState::new();
-                state.add_event(event, &self.merge_strategies);
-                entry.insert(state);
-            }
-            hash_map::Entry::Occupied(mut entry) => {
-                entry.get_mut().add_event(event, &self.merge_strategies);
-            }
+    fn push_or_new_reduce_state(
+        &mut self,
+        event: LogEvent,
+        size: usize,
+        discriminant: Discriminant,
+    ) {
+        if !self.reduce_merge_states.contains_key(&discriminant) {
+            let state = self.new_reduce_state();
+            self.groups_by_sequence
+                .insert(state.sequence, discriminant.clone());
+            self.reduce_merge_states.insert(discriminant.clone(), state);
+        }
+        if let Some(state) = self.reduce_merge_states.get_mut(&discriminant) {
+            state.add_event(event, size, &self.merge_strategies);
+            self.total_bytes += size;
         }
     }
 
@@ -397,6 +509,7 @@ impl Reduce {
         };
 
         let event = event.into_log();
+        let size = event.size_of();
         let discriminant = Discriminant::from_log_event(&event, &self.group_by);
 
         if let Some(max_events) = self.max_events {
@@ -410,26 +523,34 @@ impl Reduce {
             }
         }
 
+        if let Some(max_bytes) = self.max_bytes {
+            let bytes = self
+                .reduce_merge_states
+                .get(&discriminant)
+                .map_or(0, |entry| entry.bytes);
+            // The current event will make this set reach its maximum size
+            if bytes + size >= max_bytes {
+                ends_here = true;
+            }
+        }
+
         if starts_here {
-            if let Some(state) = self.reduce_merge_states.remove(&discriminant) {
+            if let Some(state) = self.remove_reduce_state(&discriminant) {
                 emitter.emit(state.flush().into());
             }
 
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(event, size, discriminant);
+            self.flush_over_budget_into(emitter);
         } else if ends_here {
-            emitter.emit(match self.reduce_merge_states.remove(&discriminant) {
-                Some(mut state) => {
-                    state.add_event(event, &self.merge_strategies);
-                    state.flush().into()
-                }
-                None => {
-                    let mut state = ReduceState::new();
-                    state.add_event(event, &self.merge_strategies);
-                    state.flush().into()
-                }
-            })
+            let mut state = match self.remove_reduce_state(&discriminant) {
+                Some(state) => state,
+                None => self.new_reduce_state(),
+            };
+            state.add_event(event, size, &self.merge_strategies);
+            emitter.emit(state.flush().into());
         } else {
-            self.push_or_new_reduce_state(event, discriminant)
+            self.push_or_new_reduce_state(event, size, discriminant);
+            self.flush_over_budget_into(emitter);
         }
     }
 }
@@ -873,6 +994,96 @@ max_events = 3
         .await
     }
 
+    #[tokio::test]
+    async fn max_bytes() {
+        let mut reduce_config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "id" ]
+merge_strategies.id = "retain"
+merge_strategies.message = "array"
+            "#,
+        )
+        .unwrap();
+
+        let event = |message: &str| {
+            let mut event = LogEvent::from(message);
+            event.insert("id", "1");
+            event
+        };
+        // The events all have the same size, so each group holds two of them.
+        reduce_config.max_bytes = NonZeroUsize::new(2 * event("test 1").size_of());
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), reduce_config).await;
+
+            for message in ["test 1", "test 2", "test 3"] {
+                tx.send(event(message).into()).await.unwrap();
+            }
+
+            let output_1 = out.recv().await.unwrap().into_log();
+            assert_eq!(output_1["message"], vec!["test 1", "test 2"].into());
+
+            drop(tx);
+            topology.stop().await;
+
+            let output_2 = out.recv().await.unwrap().into_log();
+            assert_eq!(output_2["message"], vec!["test 3"].into());
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn max_total_bytes() {
+        let mut reduce_config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "id" ]
+merge_strategies.id = "retain"
+merge_strategies.message = "array"
+            "#,
+        )
+        .unwrap();
+
+        let event = |id: &str| {
+            let mut event = LogEvent::from(format!("test {}", id));
+            event.insert("id", id);
+            event
+        };
+        // The events all have the same size, so only two groups of one event fit in the budget.
+        reduce_config.max_total_bytes = NonZeroUsize::new(5 * event("1").size_of() / 2);
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), reduce_config).await;
+
+            for id in ["1", "2", "3"] {
+                tx.send(event(id).into()).await.unwrap();
+            }
+
+            // The oldest group is flushed early to make room for the third one.
+            let output_1 = out.recv().await.unwrap().into_log();
+            assert_eq!(output_1["message"], vec!["test 1"].into());
+            assert_eq!(output_1["reduce_incomplete"], true.into());
+
+            drop(tx);
+            topology.stop().await;
+
+            let mut outputs = vec![
+                out.recv().await.unwrap().into_log(),
+                out.recv().await.unwrap().into_log(),
+            ];
+            outputs.sort_by_key(|output| output["id"].to_string_lossy().into_owned());
+            assert_eq!(outputs[0]["message"], vec!["test 2"].into());
+            assert_eq!(outputs[1]["message"], vec!["test 3"].into());
+            assert!(outputs
+                .iter()
+                .all(|output| !output.contains("reduce_incomplete")));
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn arrays() {
         let reduce_config = toml::from_str::<ReduceConfig>(
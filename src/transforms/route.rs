@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use indexmap::IndexMap;
 use vector_lib::config::{clone_input_definitions, LogNamespace};
 use vector_lib::configurable::configurable_component;
@@ -20,24 +22,43 @@ pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 pub struct Route {
     conditions: Vec<(String, Condition)>,
     reroute_unmatched: bool,
+    mode: RouteMode,
 }
 
 impl Route {
     pub fn new(config: &RouteConfig, context: &TransformContext) -> crate::Result<Self> {
-        let mut conditions = Vec::with_capacity(config.route.len());
-        for (output_name, condition) in config.route.iter() {
+        let mut conditions = Vec::with_capacity(config.routes.len() + config.route.len());
+        for (output_name, condition) in config.conditions() {
             let condition = condition.build(&context.enrichment_tables)?;
-            conditions.push((output_name.clone(), condition));
+            conditions.push((output_name.to_owned(), condition));
         }
         Ok(Self {
             conditions,
             reroute_unmatched: config.reroute_unmatched,
+            mode: config.mode,
         })
     }
 }
 
 impl SyncTransform for Route {
     fn transform(&mut self, event: Event, output: &mut vector_lib::transform::TransformOutputsBuf) {
+        if self.mode == RouteMode::FirstMatch {
+            // The event is moved from one check to the next, so it is never cloned.
+            let mut event = event;
+            for (output_name, condition) in &self.conditions {
+                let (result, checked) = condition.check(event);
+                if result {
+                    output.push(Some(output_name), checked);
+                    return;
+                }
+                event = checked;
+            }
+            if self.reroute_unmatched {
+                output.push(Some(UNMATCHED_ROUTE), event);
+            }
+            return;
+        }
+
         let mut check_failed: usize = 0;
         for (output_name, condition) in &self.conditions {
             let (result, event) = condition.check(event.clone());
@@ -83,7 +104,67 @@ pub struct RouteConfig {
     /// Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
     /// as a route name.
     #[configurable(metadata(docs::additional_props_description = "An individual route."))]
+    #[serde(default)]
     route: IndexMap<String, AnyCondition>,
+
+    /// A list of routes, checked in the order they are declared in.
+    ///
+    /// These are referenced as inputs the same way as the routes in `route`, and are checked
+    /// before them. Unlike the keys of `route`, the order of this list is always preserved, which
+    /// is required with the `first_match` mode.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    routes: Vec<OrderedRoute>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    mode: RouteMode,
+}
+
+/// How events matching several routes are routed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteMode {
+    /// Events are sent to every route they match.
+    #[default]
+    All,
+
+    /// Routes are checked in the order of `routes`, and events are sent to the first route they
+    /// match only.
+    ///
+    /// This avoids cloning the events, and delivering them to several routes. The routes must be
+    /// declared in `routes`, as the order of the keys of `route` isn't preserved.
+    FirstMatch,
+}
+
+/// A route checked in the order it is declared in.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OrderedRoute {
+    /// The identifier of the route.
+    ///
+    /// The route is referenced as an input by other components with the name
+    /// `<transform_name>.<name>`.
+    #[configurable(metadata(docs::examples = "critical"))]
+    name: String,
+
+    /// The logical condition the events sent to this route must match.
+    condition: AnyCondition,
+}
+
+impl RouteConfig {
+    /// Returns the routes in the order they are checked in.
+    fn conditions(&self) -> impl Iterator<Item = (&str, &AnyCondition)> {
+        self.routes
+            .iter()
+            .map(|route| (route.name.as_str(), &route.condition))
+            .chain(
+                self.route
+                    .iter()
+                    .map(|(name, condition)| (name.as_str(), condition)),
+            )
+    }
 }
 
 impl GenerateConfig for RouteConfig {
@@ -91,6 +172,8 @@ impl GenerateConfig for RouteConfig {
         toml::Value::try_from(Self {
             reroute_unmatched: true,
             route: IndexMap::new(),
+            routes: Vec::new(),
+            mode: RouteMode::All,
         })
         .unwrap()
     }
@@ -109,12 +192,29 @@ impl TransformConfig for RouteConfig {
     }
 
     fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
-        if self.route.contains_key(UNMATCHED_ROUTE) {
-            Err(vec![format!(
-                "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
-            )])
-        } else {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        for (name, _) in self.conditions() {
+            if name == UNMATCHED_ROUTE {
+                errors.push(format!(
+                    "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
+                ));
+            } else if !names.insert(name) {
+                errors.push(format!("duplicate route name: `{name}`"));
+            }
+        }
+        if self.mode == RouteMode::FirstMatch && !self.route.is_empty() {
+            errors.push(
+                "the `first_match` mode requires the routes to be declared in `routes`, as the \
+                 order of the keys of `route` isn't preserved"
+                    .to_string(),
+            );
+        }
+
+        if errors.is_empty() {
             Ok(())
+        } else {
+            Err(errors)
         }
     }
 
@@ -125,9 +225,8 @@ impl TransformConfig for RouteConfig {
         _: LogNamespace,
     ) -> Vec<TransformOutput> {
         let mut result: Vec<TransformOutput> = self
-            .route
-            .keys()
-            .map(|output_name| {
+            .conditions()
+            .map(|(output_name, _)| {
                 TransformOutput::new(DataType::all(), clone_input_definitions(input_definitions))
                     .with_port(output_name)
             })
@@ -357,6 +456,139 @@ mod test {
         }
     }
 
+    fn first_match_outputs(event: Event) -> HashMap<&'static str, usize> {
+        let output_names = ["first", "second", "third", UNMATCHED_ROUTE];
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            mode = "first_match"
+
+            [[routes]]
+            name = "first"
+            condition.type = "vrl"
+            condition.source = '.message == "hello world"'
+
+            [[routes]]
+            name = "second"
+            condition.type = "vrl"
+            condition.source = '.second == "second"'
+
+            [[routes]]
+            name = "third"
+            condition.type = "vrl"
+            condition.source = '.third == "third"'
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate(&schema::Definition::any()).is_ok());
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    TransformOutput::new(DataType::all(), HashMap::new())
+                        .with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+
+        transform.transform(event.clone(), &mut outputs);
+        output_names
+            .into_iter()
+            .map(|output_name| {
+                let events: Vec<_> = outputs.drain_named(output_name).collect();
+                assert!(events.iter().all(|output| *output == event));
+                (output_name, events.len())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn route_first_match() {
+        let event = Event::from_json_value(
+            serde_json::json!({"message": "NOPE", "second": "second", "third": "third"}),
+            LogNamespace::Legacy,
+        )
+        .unwrap();
+
+        let outputs = first_match_outputs(event);
+        assert_eq!(
+            outputs,
+            HashMap::from([
+                ("first", 0),
+                ("second", 1),
+                ("third", 0),
+                (UNMATCHED_ROUTE, 0)
+            ])
+        );
+    }
+
+    #[test]
+    fn route_first_match_unmatched() {
+        let event =
+            Event::from_json_value(serde_json::json!({"message": "NOPE"}), LogNamespace::Legacy)
+                .unwrap();
+
+        let outputs = first_match_outputs(event);
+        assert_eq!(
+            outputs,
+            HashMap::from([
+                ("first", 0),
+                ("second", 0),
+                ("third", 0),
+                (UNMATCHED_ROUTE, 1)
+            ])
+        );
+    }
+
+    #[test]
+    fn route_first_match_requires_ordered_routes() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            mode = "first_match"
+
+            route.first.type = "vrl"
+            route.first.source = '.message == "hello world"'
+        "#,
+        )
+        .unwrap();
+
+        let errors = config.validate(&schema::Definition::any()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("`first_match` mode requires"));
+    }
+
+    #[test]
+    fn route_duplicate_names() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "vrl"
+            route.first.source = '.message == "hello world"'
+
+            [[routes]]
+            name = "first"
+            condition.type = "vrl"
+            condition.source = '.message == "hello"'
+
+            [[routes]]
+            name = "_unmatched"
+            condition.type = "vrl"
+            condition.source = 'true'
+        "#,
+        )
+        .unwrap();
+
+        let errors = config.validate(&schema::Definition::any()).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "cannot have a named output with reserved name: `_unmatched`".to_string(),
+                "duplicate route name: `first`".to_string(),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use indexmap::IndexMap;
+
 use crate::config::{
     DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext, TransformOutput,
 };
@@ -26,6 +28,78 @@ pub struct TagCardinalityLimitConfig {
 
     #[serde(flatten)]
     pub mode: Mode,
+
+    /// Overrides `value_limit` for the tag keys matching a pattern.
+    ///
+    /// Patterns support glob matching, such as `pod_*`. When several patterns match a tag key, the
+    /// first one applies.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "How many distinct values to accept for the matching tag keys."
+    ))]
+    #[configurable(metadata(docs::examples = "example_tag_limits()"))]
+    pub tag_limits: IndexMap<String, usize>,
+
+    /// Limits for the metrics whose name matches a pattern.
+    ///
+    /// The tags of these metrics are tracked per metric name, instead of across all metrics. When
+    /// several entries match a metric name, the first one applies.
+    ///
+    /// The number of metric names tracked this way isn't capped, so memory usage grows with the
+    /// number of distinct metric names matching these entries. Prefer exact names or narrow
+    /// patterns if metric names are unbounded.
+    #[serde(default)]
+    pub per_metric: Vec<PerMetricConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub internal_metrics: TagCardinalityLimitInternalMetricsConfig,
+}
+
+/// Limits for the metrics whose name matches a pattern.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PerMetricConfig {
+    /// The name of the metrics these limits apply to.
+    ///
+    /// Supports glob matching, such as `http_*`.
+    #[configurable(metadata(docs::examples = "http_requests_total"))]
+    #[configurable(metadata(docs::examples = "internal_*"))]
+    pub name: String,
+
+    /// How many distinct values to accept for any given key of these metrics.
+    ///
+    /// Defaults to the top-level `value_limit`.
+    pub value_limit: Option<usize>,
+
+    /// Overrides `value_limit` for the tag keys of these metrics matching a pattern.
+    ///
+    /// These take precedence over the top-level `tag_limits`.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "How many distinct values to accept for the matching tag keys."
+    ))]
+    #[configurable(metadata(docs::examples = "example_tag_limits()"))]
+    pub tag_limits: IndexMap<String, usize>,
+}
+
+/// Configuration of internal metrics for the `tag_cardinality_limit` transform.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TagCardinalityLimitInternalMetricsConfig {
+    /// Whether or not to add the `metric_name` and `tag_key` tags to the
+    /// `tag_value_limit_exceeded_total` and `value_limit_reached_total` internal metrics.
+    ///
+    /// This tells which metric and tag hit the limit, but the cardinality of these tags grows with
+    /// the number of metric names and tag keys. Only set this to true if they are bounded.
+    #[serde(default)]
+    pub include_key_in_limit_metric: bool,
+}
+
+fn example_tag_limits() -> IndexMap<String, usize> {
+    IndexMap::from([("pod_*".to_string(), 50), ("path".to_string(), 100)])
 }
 
 /// Controls the approach taken for tracking tag cardinality.
@@ -95,6 +169,9 @@ impl GenerateConfig for TagCardinalityLimitConfig {
             mode: Mode::Exact,
             value_limit: default_value_limit(),
             limit_exceeded_action: default_limit_exceeded_action(),
+            tag_limits: IndexMap::new(),
+            per_metric: Vec::new(),
+            internal_metrics: Default::default(),
         })
         .unwrap()
     }
@@ -104,9 +181,7 @@ impl GenerateConfig for TagCardinalityLimitConfig {
 #[typetag::serde(name = "tag_cardinality_limit")]
 impl TransformConfig for TagCardinalityLimitConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(TagCardinalityLimit::new(
-            self.clone(),
-        )))
+        TagCardinalityLimit::new(self.clone()).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -0,0 +1,81 @@
+use glob::Pattern;
+use indexmap::IndexMap;
+
+use crate::transforms::tag_cardinality_limit::config::TagCardinalityLimitConfig;
+
+/// The `value_limit` overrides, with their patterns compiled.
+#[derive(Debug)]
+pub struct Limits {
+    value_limit: usize,
+    tag_limits: Vec<(Pattern, usize)>,
+    per_metric: Vec<MetricLimits>,
+}
+
+#[derive(Debug)]
+struct MetricLimits {
+    name: Pattern,
+    value_limit: Option<usize>,
+    tag_limits: Vec<(Pattern, usize)>,
+}
+
+impl Limits {
+    pub fn new(config: &TagCardinalityLimitConfig) -> crate::Result<Self> {
+        let per_metric = config
+            .per_metric
+            .iter()
+            .map(|metric| {
+                Ok(MetricLimits {
+                    name: compile(&metric.name)?,
+                    value_limit: metric.value_limit,
+                    tag_limits: compile_tag_limits(&metric.tag_limits)?,
+                })
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self {
+            value_limit: config.value_limit,
+            tag_limits: compile_tag_limits(&config.tag_limits)?,
+            per_metric,
+        })
+    }
+
+    /// Returns the index of the `per_metric` entry matching the metric name, if any.
+    pub fn metric(&self, metric_name: &str) -> Option<usize> {
+        self.per_metric
+            .iter()
+            .position(|metric| metric.name.matches(metric_name))
+    }
+
+    /// Returns the limit of the tag key, for the metrics of the given `per_metric` entry.
+    ///
+    /// The limits of the `per_metric` entry take precedence over the top-level ones, and in both
+    /// cases a matching `tag_limits` pattern takes precedence over `value_limit`.
+    pub fn value_limit(&self, metric: Option<usize>, key: &str) -> usize {
+        let metric = metric.map(|index| &self.per_metric[index]);
+        metric
+            .and_then(|metric| find_tag_limit(&metric.tag_limits, key).or(metric.value_limit))
+            .or_else(|| find_tag_limit(&self.tag_limits, key))
+            .unwrap_or(self.value_limit)
+    }
+}
+
+fn compile(pattern: &str) -> crate::Result<Pattern> {
+    Pattern::new(pattern)
+        .map_err(|error| format!("Invalid pattern {:?}: {}.", pattern, error).into())
+}
+
+fn compile_tag_limits(
+    tag_limits: &IndexMap<String, usize>,
+) -> crate::Result<Vec<(Pattern, usize)>> {
+    tag_limits
+        .iter()
+        .map(|(pattern, limit)| Ok((compile(pattern)?, *limit)))
+        .collect()
+}
+
+fn find_tag_limit(tag_limits: &[(Pattern, usize)], key: &str) -> Option<usize> {
+    tag_limits
+        .iter()
+        .find(|(pattern, _)| pattern.matches(key))
+        .map(|(_, limit)| *limit)
+}
@@ -13,6 +13,7 @@ use crate::{
 };
 
 mod config;
+mod limits;
 mod tag_value_set;
 
 #[cfg(test)]
@@ -20,34 +21,68 @@ mod tests;
 
 use crate::event::metric::TagValueSet;
 pub use config::TagCardinalityLimitConfig;
+use limits::Limits;
 use tag_value_set::AcceptedTagValueSet;
 
 #[derive(Debug)]
 pub struct TagCardinalityLimit {
     config: TagCardinalityLimitConfig,
+    limits: Limits,
     accepted_tags: HashMap<String, AcceptedTagValueSet>,
+    /// The accepted tags of the metrics matching a `per_metric` entry, by metric name. Names are
+    /// never evicted, so this grows with the number of distinct names matching an entry.
+    accepted_tags_per_metric: HashMap<String, HashMap<String, AcceptedTagValueSet>>,
 }
 
 impl TagCardinalityLimit {
-    fn new(config: TagCardinalityLimitConfig) -> Self {
-        Self {
+    fn new(config: TagCardinalityLimitConfig) -> crate::Result<Self> {
+        Ok(Self {
+            limits: Limits::new(&config)?,
             config,
             accepted_tags: HashMap::new(),
-        }
+            accepted_tags_per_metric: HashMap::new(),
+        })
+    }
+
+    /// Returns the set of accepted values for a key, which is tracked per metric name for the
+    /// metrics matching a `per_metric` entry, and across all the other metrics otherwise.
+    fn accepted_values(
+        &mut self,
+        metric_name: &str,
+        metric_limits: Option<usize>,
+        key: &str,
+    ) -> &mut AcceptedTagValueSet {
+        let accepted_tags = match metric_limits {
+            Some(_) => self
+                .accepted_tags_per_metric
+                .entry_ref(metric_name)
+                .or_insert_with(HashMap::new),
+            None => &mut self.accepted_tags,
+        };
+        let limits = &self.limits;
+        let mode = &self.config.mode;
+        accepted_tags.entry_ref(key).or_insert_with(|| {
+            AcceptedTagValueSet::new(limits.value_limit(metric_limits, key), mode)
+        })
     }
 
     /// Takes in key and a value corresponding to a tag on an incoming Metric
     /// Event.  If that value is already part of set of accepted values for that
     /// key, then simply returns true.  If that value is not yet part of the
-    /// accepted values for that key, checks whether we have hit the value_limit
+    /// accepted values for that key, checks whether we have hit the value limit
     /// for that key yet and if not adds the value to the set of accepted values
     /// for the key and returns true, otherwise returns false.  A false return
     /// value indicates to the caller that the value is not accepted for this
     /// key, and the configured limit_exceeded_action should be taken.
-    fn try_accept_tag(&mut self, key: &str, value: &TagValueSet) -> bool {
-        let tag_value_set = self.accepted_tags.entry_ref(key).or_insert_with(|| {
-            AcceptedTagValueSet::new(self.config.value_limit, &self.config.mode)
-        });
+    fn try_accept_tag(
+        &mut self,
+        metric_name: &str,
+        metric_limits: Option<usize>,
+        key: &str,
+        value: &TagValueSet,
+    ) -> bool {
+        let include_extended_tags = self.config.internal_metrics.include_key_in_limit_metric;
+        let tag_value_set = self.accepted_values(metric_name, metric_limits, key);
 
         if tag_value_set.contains(value) {
             // Tag value has already been accepted, nothing more to do.
@@ -55,12 +90,17 @@ impl TagCardinalityLimit {
         }
 
         // Tag value not yet part of the accepted set.
-        if tag_value_set.len() < self.config.value_limit {
+        let value_limit = tag_value_set.value_limit();
+        if tag_value_set.len() < value_limit {
             // accept the new value
             tag_value_set.insert(value.clone());
 
-            if tag_value_set.len() == self.config.value_limit {
-                emit!(TagCardinalityValueLimitReached { key });
+            if tag_value_set.len() == value_limit {
+                emit!(TagCardinalityValueLimitReached {
+                    metric_name,
+                    key,
+                    include_extended_tags,
+                });
             }
 
             true
@@ -72,26 +112,42 @@ impl TagCardinalityLimit {
 
     /// Checks if recording a key and value corresponding to a tag on an incoming Metric would
     /// exceed the cardinality limit.
-    fn tag_limit_exceeded(&self, key: &str, value: &TagValueSet) -> bool {
-        self.accepted_tags
-            .get(key)
+    fn tag_limit_exceeded(
+        &self,
+        metric_name: &str,
+        metric_limits: Option<usize>,
+        key: &str,
+        value: &TagValueSet,
+    ) -> bool {
+        let accepted_tags = match metric_limits {
+            Some(_) => self.accepted_tags_per_metric.get(metric_name),
+            None => Some(&self.accepted_tags),
+        };
+        accepted_tags
+            .and_then(|accepted_tags| accepted_tags.get(key))
             .map(|value_set| {
-                !value_set.contains(value) && value_set.len() >= self.config.value_limit
+                !value_set.contains(value) && value_set.len() >= value_set.value_limit()
             })
             .unwrap_or(false)
     }
 
     /// Record a key and value corresponding to a tag on an incoming Metric.
-    fn record_tag_value(&mut self, key: &str, value: &TagValueSet) {
-        self.accepted_tags
-            .entry_ref(key)
-            .or_insert_with(|| AcceptedTagValueSet::new(self.config.value_limit, &self.config.mode))
+    fn record_tag_value(
+        &mut self,
+        metric_name: &str,
+        metric_limits: Option<usize>,
+        key: &str,
+        value: &TagValueSet,
+    ) {
+        self.accepted_values(metric_name, metric_limits, key)
             .insert(value.clone());
     }
 
     fn transform_one(&mut self, mut event: Event) -> Option<Event> {
         let metric = event.as_mut_metric();
         let metric_name = metric.name().to_string();
+        let metric_limits = self.limits.metric(&metric_name);
+        let include_extended_tags = self.config.internal_metrics.include_key_in_limit_metric;
         if let Some(tags_map) = metric.tags_mut() {
             match self.config.limit_exceeded_action {
                 LimitExceededAction::DropEvent => {
@@ -99,28 +155,30 @@ impl TagCardinalityLimit {
                     // doesn't change the behavior of the check.
 
                     for (key, value) in tags_map.iter_sets() {
-                        if self.tag_limit_exceeded(key, value) {
+                        if self.tag_limit_exceeded(&metric_name, metric_limits, key, value) {
                             emit!(TagCardinalityLimitRejectingEvent {
                                 metric_name: &metric_name,
                                 tag_key: key,
                                 tag_value: &value.to_string(),
+                                include_extended_tags,
                             });
                             return None;
                         }
                     }
                     for (key, value) in tags_map.iter_sets() {
-                        self.record_tag_value(key, value);
+                        self.record_tag_value(&metric_name, metric_limits, key, value);
                     }
                 }
                 LimitExceededAction::DropTag => {
                     tags_map.retain(|key, value| {
-                        if self.try_accept_tag(key, value) {
+                        if self.try_accept_tag(&metric_name, metric_limits, key, value) {
                             true
                         } else {
                             emit!(TagCardinalityLimitRejectingTag {
                                 metric_name: &metric_name,
                                 tag_key: key,
                                 tag_value: &value.to_string(),
+                                include_extended_tags,
                             });
                             false
                         }
@@ -8,6 +8,7 @@ use std::fmt;
 #[derive(Debug)]
 pub struct AcceptedTagValueSet {
     storage: TagValueSetStorage,
+    value_limit: usize,
 }
 
 enum TagValueSetStorage {
@@ -66,7 +67,15 @@ impl AcceptedTagValueSet {
                 TagValueSetStorage::Bloom(BloomFilterStorage::new(config.cache_size_per_key))
             }
         };
-        Self { storage }
+        Self {
+            storage,
+            value_limit,
+        }
+    }
+
+    /// Returns how many distinct values are accepted for the tag key.
+    pub const fn value_limit(&self) -> usize {
+        self.value_limit
     }
 
     pub fn contains(&self, value: &TagValueSet) -> bool {
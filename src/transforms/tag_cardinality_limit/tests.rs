@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use indexmap::IndexMap;
+
 use vector_lib::config::ComponentKey;
 use vector_lib::config::OutputId;
 use vector_lib::event::EventMetadata;
@@ -12,8 +14,9 @@ use crate::event::metric::TagValue;
 use crate::event::{metric, Event, Metric, MetricTags};
 use crate::test_util::components::assert_transform_compliance;
 use crate::transforms::tag_cardinality_limit::config::{
-    default_cache_size, BloomFilterConfig, Mode,
+    default_cache_size, BloomFilterConfig, Mode, PerMetricConfig,
 };
+use crate::transforms::tag_cardinality_limit::limits::Limits;
 use crate::transforms::test::create_topology;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
@@ -25,11 +28,15 @@ fn generate_config() {
 }
 
 fn make_metric(tags: MetricTags) -> Event {
+    make_named_metric("event", tags)
+}
+
+fn make_named_metric(name: &str, tags: MetricTags) -> Event {
     let event_metadata = EventMetadata::default().with_source_type("unit_test_stream");
 
     Event::Metric(
         Metric::new_with_metadata(
-            "event",
+            name,
             metric::MetricKind::Incremental,
             metric::MetricValue::Counter { value: 1.0 },
             event_metadata,
@@ -38,7 +45,7 @@ fn make_metric(tags: MetricTags) -> Event {
     )
 }
 
-const fn make_transform_hashset(
+fn make_transform_hashset(
     value_limit: usize,
     limit_exceeded_action: LimitExceededAction,
 ) -> TagCardinalityLimitConfig {
@@ -46,10 +53,13 @@ const fn make_transform_hashset(
         value_limit,
         limit_exceeded_action,
         mode: Mode::Exact,
+        tag_limits: IndexMap::new(),
+        per_metric: Vec::new(),
+        internal_metrics: Default::default(),
     }
 }
 
-const fn make_transform_bloom(
+fn make_transform_bloom(
     value_limit: usize,
     limit_exceeded_action: LimitExceededAction,
 ) -> TagCardinalityLimitConfig {
@@ -59,6 +69,9 @@ const fn make_transform_bloom(
         mode: Mode::Probabilistic(BloomFilterConfig {
             cache_size_per_key: default_cache_size(),
         }),
+        tag_limits: IndexMap::new(),
+        per_metric: Vec::new(),
+        internal_metrics: Default::default(),
     }
 }
 
@@ -340,7 +353,7 @@ fn drop_event_checks_all_tags2() {
 
 fn drop_event_checks_all_tags(make_tags: impl Fn(&str, &str) -> MetricTags) {
     let config = make_transform_hashset(2, LimitExceededAction::DropEvent);
-    let mut transform = TagCardinalityLimit::new(config);
+    let mut transform = TagCardinalityLimit::new(config).unwrap();
 
     let event1 = make_metric(make_tags("val1", "val1"));
     let event2 = make_metric(make_tags("val2", "val1"));
@@ -359,3 +372,84 @@ fn drop_event_checks_all_tags(make_tags: impl Fn(&str, &str) -> MetricTags) {
     assert_eq!(new_event3, None);
     assert_eq!(new_event4, Some(event4));
 }
+
+fn make_transform_per_metric(
+    limit_exceeded_action: LimitExceededAction,
+) -> TagCardinalityLimitConfig {
+    TagCardinalityLimitConfig {
+        tag_limits: IndexMap::from([("loose_*".to_string(), 3)]),
+        per_metric: vec![PerMetricConfig {
+            name: "strict_*".to_string(),
+            value_limit: Some(1),
+            tag_limits: IndexMap::from([("tag2".to_string(), 2)]),
+        }],
+        ..make_transform_hashset(2, limit_exceeded_action)
+    }
+}
+
+#[test]
+fn value_limit_per_metric_and_tag() {
+    let limits = Limits::new(&make_transform_per_metric(LimitExceededAction::DropTag)).unwrap();
+
+    let strict = limits.metric("strict_requests");
+    assert_eq!(strict, Some(0));
+    assert_eq!(limits.metric("requests"), None);
+
+    assert_eq!(limits.value_limit(None, "tag1"), 2);
+    assert_eq!(limits.value_limit(None, "loose_tag"), 3);
+    assert_eq!(limits.value_limit(strict, "tag1"), 1);
+    assert_eq!(limits.value_limit(strict, "tag2"), 2);
+    // The metric's `value_limit` takes precedence over the top-level `tag_limits`.
+    assert_eq!(limits.value_limit(strict, "loose_tag"), 1);
+}
+
+#[test]
+fn drop_tag_per_metric() {
+    let config = make_transform_per_metric(LimitExceededAction::DropTag);
+    let mut transform = TagCardinalityLimit::new(config).unwrap();
+
+    let mut accepted = |name: &str, value: &str| {
+        let event = make_named_metric(name, metric_tags!("tag1" => value));
+        let event = transform.transform_one(event).unwrap();
+        event.as_metric().tag_value("tag1").is_some()
+    };
+
+    assert!(accepted("strict_a", "val1"));
+    assert!(!accepted("strict_a", "val2"));
+    // Each metric matching a `per_metric` entry is tracked separately.
+    assert!(accepted("strict_b", "val2"));
+    assert!(!accepted("strict_b", "val1"));
+
+    // The other metrics are tracked together, with the top-level limits.
+    assert!(accepted("other_a", "val1"));
+    assert!(accepted("other_b", "val2"));
+    assert!(!accepted("other_a", "val3"));
+}
+
+#[test]
+fn drop_event_per_metric() {
+    let config = make_transform_per_metric(LimitExceededAction::DropEvent);
+    let mut transform = TagCardinalityLimit::new(config).unwrap();
+
+    let event1 = make_named_metric("strict_a", metric_tags!("tag2" => "val1"));
+    let event2 = make_named_metric("strict_a", metric_tags!("tag2" => "val2"));
+    let event3 = make_named_metric("strict_a", metric_tags!("tag2" => "val3"));
+    let event4 = make_named_metric("other", metric_tags!("tag2" => "val3"));
+
+    assert_eq!(transform.transform_one(event1.clone()), Some(event1));
+    assert_eq!(transform.transform_one(event2.clone()), Some(event2));
+    assert_eq!(transform.transform_one(event3), None);
+    assert_eq!(transform.transform_one(event4.clone()), Some(event4));
+}
+
+#[test]
+fn invalid_metric_pattern() {
+    let mut config = make_transform_hashset(2, LimitExceededAction::DropTag);
+    config.per_metric.push(PerMetricConfig {
+        name: "[".to_string(),
+        value_limit: None,
+        tag_limits: IndexMap::new(),
+    });
+
+    assert!(TagCardinalityLimit::new(config).is_err());
+}
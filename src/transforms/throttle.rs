@@ -1,23 +1,32 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use async_stream::stream;
-use futures::{Stream, StreamExt};
-use governor::{clock, Quota, RateLimiter};
+use governor::clock;
 use serde_with::serde_as;
 use snafu::Snafu;
 use vector_lib::config::{clone_input_definitions, LogNamespace};
 use vector_lib::configurable::configurable_component;
+use vector_lib::enrichment::{Case, Condition as TableCondition, IndexHandle, TableSearch};
+use vector_lib::EstimatedJsonEncodedSizeOf;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
-    event::Event,
-    internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
+    event::{Event, Value},
+    internal_events::{
+        TemplateRenderingError, ThrottleEventDiscarded, ThrottleInvalidThresholdOverride,
+    },
     schema,
     template::Template,
-    transforms::{TaskTransform, Transform},
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
 };
 
+const DROPPED: &str = "dropped";
+
 /// Configuration of internal metrics for the Throttle transform.
 #[configurable_component]
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -36,6 +45,50 @@ pub struct ThrottleInternalMetricsConfig {
     pub emit_events_discarded_per_key: bool,
 }
 
+/// The unit in which thresholds are expressed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleUnit {
+    /// Each event counts as one towards the threshold.
+    #[default]
+    Events,
+
+    /// Each event counts as its estimated JSON-encoded size, in bytes, towards the threshold.
+    ///
+    /// An event larger than the threshold counts as the whole threshold, so it only passes once
+    /// the quota is full.
+    Bytes,
+}
+
+/// Per-key thresholds loaded from an enrichment table.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdOverridesConfig {
+    /// The name of the enrichment table holding the overrides.
+    #[configurable(metadata(docs::examples = "tenant_quotas"))]
+    pub table: String,
+
+    /// The column of the enrichment table matched against the rendered `key_field`.
+    #[serde(default = "default_key_column")]
+    pub key_column: String,
+
+    /// The column of the enrichment table holding the key's threshold.
+    ///
+    /// The threshold is expressed in the configured `unit` per `window_secs`, like `threshold`.
+    #[serde(default = "default_threshold_column")]
+    pub threshold_column: String,
+}
+
+fn default_key_column() -> String {
+    "key".to_string()
+}
+
+fn default_threshold_column() -> String {
+    "threshold".to_string()
+}
+
 /// Configuration for the `throttle` transform.
 #[serde_as]
 #[configurable_component(transform("throttle", "Rate limit logs passing through a topology."))]
@@ -44,7 +97,8 @@ pub struct ThrottleInternalMetricsConfig {
 pub struct ThrottleConfig {
     /// The number of events allowed for a given bucket per configured `window_secs`.
     ///
-    /// Each unique key has its own `threshold`.
+    /// Each unique key has its own `threshold`. When `unit` is `bytes`, this is the number of
+    /// bytes allowed instead.
     threshold: u32,
 
     /// The time window in which the configured `threshold` is applied, in seconds.
@@ -59,9 +113,33 @@ pub struct ThrottleConfig {
     #[configurable(metadata(docs::examples = "{{ message }}", docs::examples = "{{ hostname }}",))]
     key_field: Option<Template>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    unit: ThrottleUnit,
+
+    /// The threshold shared by all buckets per configured `window_secs`.
+    ///
+    /// An event must fit in this global quota and in the quota of its own bucket to pass through,
+    /// and only uses up either quota once it fits in both, so a bucket over its own quota doesn't
+    /// use up the global one. It is expressed in the configured `unit`, like `threshold`.
+    global_threshold: Option<u32>,
+
+    /// Loads the `threshold` of individual buckets from an enrichment table.
+    ///
+    /// Buckets whose key is missing from the table use the configured `threshold`. Requires
+    /// `key_field` to be set.
+    threshold_overrides: Option<ThresholdOverridesConfig>,
+
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
 
+    /// Reroutes events over their quota to a named output instead of discarding them.
+    ///
+    /// For a transform named `foo`, these events can be consumed by specifying `foo.dropped` as
+    /// the input to another component.
+    #[serde(default)]
+    reroute_dropped: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     internal_metrics: ThrottleInternalMetricsConfig,
@@ -73,7 +151,7 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        Throttle::new(self, context, clock::MonotonicClock).map(Transform::synchronous)
     }
 
     fn input(&self) -> Input {
@@ -87,19 +165,171 @@ impl TransformConfig for ThrottleConfig {
         _: LogNamespace,
     ) -> Vec<TransformOutput> {
         // The event is not modified, so the definition is passed through as-is
-        vec![TransformOutput::new(
+        let mut outputs = vec![TransformOutput::new(
             DataType::Log,
             clone_input_definitions(input_definitions),
-        )]
+        )];
+        if self.reroute_dropped {
+            outputs.push(
+                TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions))
+                    .with_port(DROPPED),
+            );
+        }
+        outputs
+    }
+}
+
+/// The rate limiters, shared by all the copies of the transform.
+///
+/// They follow the generic cell rate algorithm like governor's rate limiters, but keep the state
+/// of all the quotas behind a single lock, so that an event only uses up its quotas once it fits in
+/// all of them.
+struct Limiters<I: clock::Reference> {
+    default: Quota,
+    global: Option<Quota>,
+    /// The instant the theoretical arrival times are relative to.
+    start: I,
+    state: Mutex<LimitersState>,
+}
+
+#[derive(Default)]
+struct LimitersState {
+    /// The theoretical arrival time of each bucket.
+    buckets: HashMap<Option<String>, Duration>,
+    /// The theoretical arrival time of the global quota.
+    global: Duration,
+}
+
+impl<I: clock::Reference> Limiters<I> {
+    fn check(&self, key: &Option<String>, cost: NonZeroU32, quota: Option<Quota>, now: I) -> bool {
+        let now = Duration::from(now.duration_since(self.start));
+        let quota = quota.unwrap_or(self.default);
+
+        let mut state = self.state.lock().expect("poisoned lock");
+        let bucket = state.buckets.get(key).copied().unwrap_or_default();
+        let Some(bucket) = quota.check(bucket, now, cost) else {
+            return false;
+        };
+        if let Some(global) = self.global {
+            match global.check(state.global, now, cost) {
+                Some(global) => state.global = global,
+                None => return false,
+            }
+        }
+        match state.buckets.get_mut(key) {
+            Some(tat) => *tat = bucket,
+            None => {
+                state.buckets.insert(key.clone(), bucket);
+            }
+        }
+        true
+    }
+
+    /// Forgets the buckets whose quota is full again.
+    fn retain_recent(&self, now: I) {
+        let now = Duration::from(now.duration_since(self.start));
+        self.state
+            .lock()
+            .expect("poisoned lock")
+            .buckets
+            .retain(|_, tat| *tat > now);
+    }
+}
+
+/// A threshold spread over a window, allowing bursts of up to the threshold.
+#[derive(Clone, Copy, Debug)]
+struct Quota {
+    /// The time it takes for one unit of the quota to be replenished.
+    period: Duration,
+    burst: NonZeroU32,
+}
+
+impl Quota {
+    fn new(threshold: NonZeroU32, window: Duration) -> Option<Self> {
+        let period = Duration::from_secs_f64(window.as_secs_f64() / f64::from(threshold.get()));
+        (!period.is_zero()).then_some(Self {
+            period,
+            burst: threshold,
+        })
+    }
+
+    /// Returns the theoretical arrival time after `cost` is used up at `now`, if it fits in the
+    /// quota. An event costing more than the whole quota uses it up instead, so that it passes
+    /// once the quota is full rather than never.
+    fn check(&self, tat: Duration, now: Duration, cost: NonZeroU32) -> Option<Duration> {
+        let cost = cost.min(self.burst).get();
+        let tat = tat.max(now) + self.period * cost;
+        (tat <= now + self.period * self.burst.get()).then_some(tat)
     }
 }
 
+#[derive(Clone)]
+struct ThresholdOverrides {
+    tables: TableSearch,
+    table: String,
+    key_column: String,
+    threshold_column: String,
+    index: IndexHandle,
+    /// The thresholds already looked up, cleared along with the inactive buckets so that the
+    /// changes to the table are picked up.
+    cache: HashMap<String, Option<NonZeroU32>>,
+}
+
+impl ThresholdOverrides {
+    fn threshold(&mut self, key: &str) -> Option<NonZeroU32> {
+        if let Some(threshold) = self.cache.get(key) {
+            return *threshold;
+        }
+
+        let condition = [TableCondition::Equals {
+            field: &self.key_column,
+            value: key.into(),
+        }];
+        let threshold = self
+            .tables
+            .find_table_row(
+                &self.table,
+                Case::Sensitive,
+                &condition,
+                None,
+                Some(self.index),
+            )
+            .ok()
+            .and_then(|row| match row.get(self.threshold_column.as_str()) {
+                Some(value) => parse_threshold(value)
+                    .map_err(|error| emit!(ThrottleInvalidThresholdOverride { key, error: &error }))
+                    .ok(),
+                None => None,
+            });
+        self.cache.insert(key.to_string(), threshold);
+        threshold
+    }
+}
+
+fn parse_threshold(value: &Value) -> Result<NonZeroU32, String> {
+    let threshold = match value {
+        Value::Integer(threshold) => u32::try_from(*threshold).ok(),
+        Value::Bytes(threshold) => std::str::from_utf8(threshold)
+            .ok()
+            .and_then(|threshold| threshold.trim().parse().ok()),
+        _ => None,
+    };
+    threshold
+        .and_then(NonZeroU32::new)
+        .ok_or_else(|| format!("expected a positive integer, got {}", value))
+}
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
-    quota: Quota,
+    limiters: Arc<Limiters<I>>,
+    window: Duration,
     flush_keys_interval: Duration,
+    last_flush: I,
     key_field: Option<Template>,
+    unit: ThrottleUnit,
+    threshold_overrides: Option<ThresholdOverrides>,
     exclude: Option<Condition>,
+    reroute_dropped: bool,
     clock: C,
     internal_metrics: ThrottleInternalMetricsConfig,
 }
@@ -114,130 +344,165 @@ where
         context: &TransformContext,
         clock: C,
     ) -> crate::Result<Self> {
-        let flush_keys_interval = config.window_secs;
+        let window = config.window_secs;
+
+        let threshold = NonZeroU32::new(config.threshold).ok_or(ConfigError::NonZero)?;
+        let default = Quota::new(threshold, window).ok_or(ConfigError::NonZero)?;
+        let global = config
+            .global_threshold
+            .map(|threshold| {
+                NonZeroU32::new(threshold)
+                    .and_then(|threshold| Quota::new(threshold, window))
+                    .ok_or(ConfigError::NonZero)
+            })
+            .transpose()?;
 
-        let threshold = match NonZeroU32::new(config.threshold) {
-            Some(threshold) => threshold,
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
+        let threshold_overrides = config
+            .threshold_overrides
+            .as_ref()
+            .map(|overrides| {
+                if config.key_field.is_none() {
+                    return Err(ConfigError::OverridesWithoutKeyField);
+                }
+                let index = context
+                    .enrichment_tables
+                    .clone()
+                    .add_index(&overrides.table, Case::Sensitive, &[&overrides.key_column])
+                    .map_err(|message| ConfigError::InvalidOverridesTable { message })?;
+                Ok(ThresholdOverrides {
+                    tables: context.enrichment_tables.as_readonly(),
+                    table: overrides.table.clone(),
+                    key_column: overrides.key_column.clone(),
+                    threshold_column: overrides.threshold_column.clone(),
+                    index,
+                    cache: HashMap::new(),
+                })
+            })
+            .transpose()?;
 
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            flush_keys_interval.as_secs_f64() / f64::from(threshold.get()),
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
         let exclude = config
             .exclude
             .as_ref()
             .map(|condition| condition.build(&context.enrichment_tables))
             .transpose()?;
 
+        let limiters = Limiters {
+            default,
+            global,
+            start: clock.now(),
+            state: Mutex::default(),
+        };
+
         Ok(Self {
-            quota,
-            clock,
-            flush_keys_interval,
+            limiters: Arc::new(limiters),
+            window,
+            flush_keys_interval: window * 2,
+            last_flush: clock.now(),
             key_field: config.key_field.clone(),
+            unit: config.unit,
+            threshold_overrides,
             exclude,
+            reroute_dropped: config.reroute_dropped,
+            clock,
             internal_metrics: config.internal_metrics.clone(),
         })
     }
+
+    /// Forgets the buckets that haven't been used in a while.
+    fn flush_keys(&mut self) {
+        let now = self.clock.now();
+        if Duration::from(now.duration_since(self.last_flush)) >= self.flush_keys_interval {
+            self.limiters.retain_recent(now);
+            if let Some(overrides) = self.threshold_overrides.as_mut() {
+                overrides.cache.clear();
+            }
+            self.last_flush = now;
+        }
+    }
+
+    fn cost(&self, event: &Event) -> NonZeroU32 {
+        match self.unit {
+            ThrottleUnit::Events => NonZeroU32::MIN,
+            ThrottleUnit::Bytes => {
+                let size = event.estimated_json_encoded_size_of().get();
+                NonZeroU32::new(u32::try_from(size).unwrap_or(u32::MAX)).unwrap_or(NonZeroU32::MIN)
+            }
+        }
+    }
 }
 
-impl<C, I> TaskTransform<Event> for Throttle<C, I>
+impl<C, I> SyncTransform for Throttle<C, I>
 where
-    C: clock::Clock<Instant = I> + Send + 'static,
-    I: clock::Reference + Send + 'static,
+    C: clock::Clock<Instant = I> + Send + Sync + 'static,
+    I: clock::Reference + Send + Sync + 'static,
 {
-    fn transform(
-        self: Box<Self>,
-        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
-
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
-
-        Box::pin(stream! {
-          loop {
-            let done = tokio::select! {
-                biased;
-
-                maybe_event = input_rx.next() => {
-                    match maybe_event {
-                        None => true,
-                        Some(event) => {
-                            let (throttle, event) = match self.exclude.as_ref() {
-                                Some(condition) => {
-                                    let (result, event) = condition.check(event);
-                                    (!result, event)
-                                },
-                                _ => (true, event)
-                            };
-                            let output = if throttle {
-                                let key = self.key_field.as_ref().and_then(|t| {
-                                    t.render_string(&event)
-                                        .map_err(|error| {
-                                            emit!(TemplateRenderingError {
-                                                error,
-                                                field: Some("key_field"),
-                                                drop_event: false,
-                                            })
-                                        })
-                                        .ok()
-                                });
-
-                                match limiter.check_key(&key) {
-                                    Ok(()) => {
-                                        Some(event)
-                                    }
-                                    _ => {
-                                        emit!(ThrottleEventDiscarded{
-                                            key: key.unwrap_or_else(|| "None".to_string()),
-                                            emit_events_discarded_per_key: self.internal_metrics.emit_events_discarded_per_key
-                                        });
-                                        None
-                                    }
-                                }
-                            } else {
-                                Some(event)
-                            };
-                            if let Some(event) = output {
-                                yield event;
-                            }
-                            false
-                        }
-                    }
-                }
-                _ = flush_keys.tick() => {
-                    limiter.retain_recent();
-                    false
-                }
-            };
-            if done { break }
-          }
-        })
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        self.flush_keys();
+
+        let (throttle, event) = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                (!result, event)
+            }
+            _ => (true, event),
+        };
+        if !throttle {
+            output.push(None, event);
+            return;
+        }
+
+        let key = self.key_field.as_ref().and_then(|t| {
+            t.render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+        let quota = match (self.threshold_overrides.as_mut(), key.as_deref()) {
+            (Some(overrides), Some(key)) => overrides
+                .threshold(key)
+                .and_then(|threshold| Quota::new(threshold, self.window)),
+            _ => None,
+        };
+        let cost = self.cost(&event);
+
+        if self.limiters.check(&key, cost, quota, self.clock.now()) {
+            output.push(None, event);
+        } else if self.reroute_dropped {
+            output.push(Some(DROPPED), event);
+        } else {
+            emit!(ThrottleEventDiscarded {
+                key: key.unwrap_or_else(|| "None".to_string()),
+                emit_events_discarded_per_key: self.internal_metrics.emit_events_discarded_per_key
+            });
+        }
     }
 }
 
 #[derive(Debug, Snafu)]
 pub enum ConfigError {
-    #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
+    #[snafu(display("`threshold`, `global_threshold` and `window_secs` must be non-zero"))]
     NonZero,
+    #[snafu(display("`threshold_overrides` requires `key_field` to be set"))]
+    OverridesWithoutKeyField,
+    #[snafu(display("invalid `threshold_overrides` table: {}", message))]
+    InvalidOverridesTable { message: String },
 }
 
 #[cfg(test)]
 mod tests {
-    use std::task::Poll;
+    use std::time::SystemTime;
 
-    use futures::SinkExt;
+    use vector_lib::enrichment::{Table, TableRegistry};
 
     use super::*;
     use crate::{
-        event::LogEvent, test_util::components::assert_transform_compliance,
-        transforms::test::create_topology,
+        enrichment_tables::file::File, event::LogEvent,
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
     };
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
@@ -247,73 +512,70 @@ mod tests {
         crate::test_util::test_generate_config::<ThrottleConfig>();
     }
 
-    #[tokio::test]
-    async fn throttle_events() {
+    fn build(config: &str, clock: &clock::FakeRelativeClock) -> Box<dyn SyncTransform> {
+        let config = toml::from_str::<ThrottleConfig>(config).unwrap();
+        Box::new(Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap())
+    }
+
+    /// Returns the event sent to the default output, and the one sent to the `dropped` output.
+    fn transform_one(
+        throttle: &mut dyn SyncTransform,
+        event: impl Into<Event>,
+    ) -> (Option<Event>, Option<Event>) {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                TransformOutput::new(DataType::all(), HashMap::new()),
+                TransformOutput::new(DataType::all(), HashMap::new()).with_port(DROPPED),
+            ],
+            1,
+        );
+
+        throttle.transform(event.into(), &mut outputs);
+
+        let passed = outputs.drain().next();
+        let dropped = outputs.drain_named(DROPPED).next();
+        (passed, dropped)
+    }
+
+    fn passes(throttle: &mut dyn SyncTransform, event: impl Into<Event>) -> bool {
+        transform_one(throttle, event).0.is_some()
+    }
+
+    fn bucket(name: &str) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("bucket", name);
+        log
+    }
+
+    #[test]
+    fn throttle_events() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let mut throttle = build(
             r#"
 threshold = 2
 window_secs = 5
 "#,
-        )
-        .unwrap();
-
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
-
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+            &clock,
+        );
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
-
-        tx.send(LogEvent::default().into()).await.unwrap();
-        tx.send(LogEvent::default().into()).await.unwrap();
-
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        assert!(passes(throttle.as_mut(), LogEvent::default()));
+        assert!(passes(throttle.as_mut(), LogEvent::default()));
 
         clock.advance(Duration::from_secs(2));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // The bucket is at capacity, so the event is dropped
+        assert!(!passes(throttle.as_mut(), LogEvent::default()));
 
         clock.advance(Duration::from_secs(3));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-
         // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
-
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
-
-        tx.disconnect();
-
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert!(passes(throttle.as_mut(), LogEvent::default()));
     }
 
-    #[tokio::test]
-    async fn throttle_exclude() {
+    #[test]
+    fn throttle_exclude() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let mut throttle = build(
             r#"
 threshold = 2
 window_secs = 5
@@ -321,119 +583,243 @@ exclude = """
 exists(.special)
 """
 "#,
-        )
-        .unwrap();
+            &clock,
+        );
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
+        assert!(passes(throttle.as_mut(), LogEvent::default()));
+        assert!(passes(throttle.as_mut(), LogEvent::default()));
 
-        let throttle = throttle.into_task();
+        clock.advance(Duration::from_secs(2));
 
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        assert!(!passes(throttle.as_mut(), LogEvent::default()));
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // The rate limiter should allow this log through regardless of current limit
+        let mut special_log = LogEvent::default();
+        special_log.insert("special", "true");
+        assert!(passes(throttle.as_mut(), special_log));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-        tx.send(LogEvent::default().into()).await.unwrap();
+        clock.advance(Duration::from_secs(3));
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        // The rate limiter should now be refreshed and allow an additional event through
+        assert!(passes(throttle.as_mut(), LogEvent::default()));
+    }
 
-        clock.advance(Duration::from_secs(2));
+    #[test]
+    fn throttle_buckets() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = build(
+            r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+"#,
+            &clock,
+        );
 
-        tx.send(LogEvent::default().into()).await.unwrap();
+        assert!(passes(throttle.as_mut(), bucket("a")));
+        assert!(passes(throttle.as_mut(), bucket("b")));
+        assert!(!passes(throttle.as_mut(), bucket("a")));
+    }
 
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+    #[test]
+    fn throttle_bytes() {
+        let clock = clock::FakeRelativeClock::default();
+        let event = Event::from(LogEvent::from("hello world"));
+        let size = event.estimated_json_encoded_size_of().get();
+        let mut throttle = build(
+            &format!(
+                r#"
+threshold = {}
+window_secs = 5
+unit = "bytes"
+"#,
+                size * 2
+            ),
+            &clock,
+        );
 
-        let mut special_log = LogEvent::default();
-        special_log.insert("special", "true");
-        tx.send(special_log.into()).await.unwrap();
-        // The rate limiter should allow this log through regardless of current limit
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+        assert!(passes(throttle.as_mut(), event.clone()));
+        assert!(passes(throttle.as_mut(), event.clone()));
+        assert!(!passes(throttle.as_mut(), event.clone()));
 
-        clock.advance(Duration::from_secs(3));
+        clock.advance(Duration::from_secs_f64(2.5));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
+        assert!(passes(throttle.as_mut(), event));
+    }
 
-        // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+    #[test]
+    fn throttle_bytes_over_threshold() {
+        let clock = clock::FakeRelativeClock::default();
+        let small = Event::from(LogEvent::from("hello"));
+        let large = Event::from(LogEvent::from("hello world, hello world"));
+        let mut throttle = build(
+            &format!(
+                r#"
+threshold = {}
+window_secs = 5
+unit = "bytes"
+"#,
+                small.estimated_json_encoded_size_of().get()
+            ),
+            &clock,
+        );
 
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // Larger than the threshold, so it uses up the whole quota
+        assert!(passes(throttle.as_mut(), large.clone()));
+        assert!(!passes(throttle.as_mut(), small));
+        assert!(!passes(throttle.as_mut(), large.clone()));
 
-        tx.disconnect();
+        clock.advance(Duration::from_secs(5));
 
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert!(passes(throttle.as_mut(), large));
     }
 
-    #[tokio::test]
-    async fn throttle_buckets() {
+    #[test]
+    fn throttle_global_threshold() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let mut throttle = build(
             r#"
 threshold = 1
+global_threshold = 2
 window_secs = 5
 key_field = "{{ bucket }}"
 "#,
-        )
-        .unwrap();
+            &clock,
+        );
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
+        assert!(passes(throttle.as_mut(), bucket("a")));
+        assert!(passes(throttle.as_mut(), bucket("b")));
+        // Within the bucket's quota, but over the global one
+        assert!(!passes(throttle.as_mut(), bucket("c")));
 
-        let throttle = throttle.into_task();
+        // The global quota has room again, and the bucket's quota wasn't used up
+        clock.advance(Duration::from_secs_f64(2.5));
+        assert!(passes(throttle.as_mut(), bucket("c")));
+    }
 
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+    #[test]
+    fn throttle_global_threshold_over_quota_bucket() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = build(
+            r#"
+threshold = 1
+global_threshold = 2
+window_secs = 5
+key_field = "{{ bucket }}"
+"#,
+            &clock,
+        );
+
+        assert!(passes(throttle.as_mut(), bucket("a")));
+        // Over the bucket's quota, so the global quota isn't used up
+        assert!(!passes(throttle.as_mut(), bucket("a")));
+        assert!(!passes(throttle.as_mut(), bucket("a")));
+        assert!(passes(throttle.as_mut(), bucket("b")));
+    }
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+    #[test]
+    fn throttle_threshold_overrides() {
+        let clock = clock::FakeRelativeClock::default();
+        let tables = TableRegistry::default();
+        let table = File::new(
+            Default::default(),
+            SystemTime::now(),
+            vec![
+                vec!["a".into(), Value::Integer(2)],
+                vec!["b".into(), "3".into()],
+                vec!["c".into(), "none".into()],
+            ],
+            vec!["key".to_string(), "threshold".to_string()],
+        );
+        tables.load(HashMap::from([(
+            "quotas".to_string(),
+            Box::new(table) as Box<dyn Table + Send + Sync>,
+        )]));
 
-        let mut log_a = LogEvent::default();
-        log_a.insert("bucket", "a");
-        let mut log_b = LogEvent::default();
-        log_b.insert("bucket", "b");
-        tx.send(log_a.into()).await.unwrap();
-        tx.send(log_b.into()).await.unwrap();
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+threshold_overrides.table = "quotas"
+"#,
+        )
+        .unwrap();
+        let context = TransformContext {
+            enrichment_tables: tables.clone(),
+            ..Default::default()
+        };
+        let mut throttle = Throttle::new(&config, &context, clock).unwrap();
+        tables.finish_load();
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
+        // Buckets missing from the table, or with an invalid threshold, use the default one
+        for (key, threshold) in [("a", 2), ("b", 3), ("c", 1), ("d", 1)] {
+            for _ in 0..threshold {
+                assert!(passes(&mut throttle, bucket(key)));
             }
+            assert!(!passes(&mut throttle, bucket(key)));
         }
-        assert_eq!(2, count);
+    }
+
+    #[test]
+    fn throttle_reroute_dropped() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = build(
+            r#"
+threshold = 1
+window_secs = 5
+reroute_dropped = true
+"#,
+            &clock,
+        );
+
+        let (passed, dropped) = transform_one(throttle.as_mut(), LogEvent::from("first"));
+        assert!(passed.is_some());
+        assert!(dropped.is_none());
+
+        let (passed, dropped) = transform_one(throttle.as_mut(), LogEvent::from("second"));
+        assert!(passed.is_none());
+        assert_eq!(dropped, Some(LogEvent::from("second").into()));
+    }
 
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+    #[test]
+    fn threshold_overrides_require_key_field() {
+        let config = toml::from_str::<ThrottleConfig>(
+            r#"
+threshold = 1
+window_secs = 5
+threshold_overrides.table = "quotas"
+"#,
+        )
+        .unwrap();
 
-        tx.disconnect();
+        let error = Throttle::new(
+            &config,
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`threshold_overrides` requires `key_field` to be set"
+        );
+    }
 
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+    #[test]
+    fn parses_threshold_overrides() {
+        assert_eq!(
+            parse_threshold(&Value::Integer(10)),
+            Ok(NonZeroU32::new(10).unwrap())
+        );
+        assert_eq!(
+            parse_threshold(&Value::from(" 25 ")),
+            Ok(NonZeroU32::new(25).unwrap())
+        );
+        assert!(parse_threshold(&Value::Integer(0)).is_err());
+        assert!(parse_threshold(&Value::Integer(-1)).is_err());
+        assert!(parse_threshold(&Value::from("many")).is_err());
+        assert!(parse_threshold(&Value::Boolean(true)).is_err());
     }
 
     #[tokio::test]
@@ -443,7 +829,11 @@ key_field = "{{ bucket }}"
                 threshold: 1,
                 window_secs: Duration::from_secs_f64(1.0),
                 key_field: None,
+                unit: ThrottleUnit::Events,
+                global_threshold: None,
+                threshold_overrides: None,
                 exclude: None,
+                reroute_dropped: false,
                 internal_metrics: Default::default(),
             };
             let (tx, rx) = mpsc::channel(1);
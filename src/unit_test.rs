@@ -1,5 +1,5 @@
 #![allow(missing_docs)]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use colored::*;
@@ -39,6 +39,23 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Record the events produced at each `extract_from` target of every test into
+    /// snapshot files, replacing previous recordings.
+    ///
+    /// Snapshot files are stored in a `__snapshots__` directory next to the first
+    /// configuration file or directory, even when several are given. Once recorded,
+    /// every run compares the events against them, failing tests without one.
+    #[arg(long, default_value_t = false)]
+    update_snapshots: bool,
+
+    /// Paths to remove from events before recording or comparing snapshots, such as
+    /// volatile fields like `.timestamp`.
+    ///
+    /// Paths given while recording are stored in the snapshot files, so that later
+    /// runs ignore them as well.
+    #[arg(long, value_delimiter(','))]
+    snapshot_ignore_paths: Vec<String>,
 }
 
 impl Opts {
@@ -59,6 +76,18 @@ impl Opts {
     }
 }
 
+/// Snapshots are stored next to the first configuration file or directory. Since tests are built
+/// from all the configuration files merged together, there's no single file a test belongs to.
+fn snapshot_dir(paths: &[config::ConfigPath]) -> PathBuf {
+    let dir = match paths.first() {
+        Some(config::ConfigPath::File(path, _)) => path.parent().map(Path::to_path_buf),
+        Some(config::ConfigPath::Dir(dir)) => Some(dir.clone()),
+        None => None,
+    };
+    dir.unwrap_or_default()
+        .join(config::unit_test::SNAPSHOT_DIR_NAME)
+}
+
 pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exitcode::ExitCode {
     let mut aggregated_test_errors: Vec<(String, Vec<String>)> = Vec::new();
 
@@ -68,6 +97,25 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
         None => return exitcode::CONFIG,
     };
 
+    let snapshot_dir = snapshot_dir(&paths);
+    let snapshot_mode = if opts.update_snapshots {
+        Some(config::SnapshotMode::Update)
+    } else if snapshot_dir.is_dir() {
+        Some(config::SnapshotMode::Compare)
+    } else {
+        None
+    };
+    let snapshots = match snapshot_mode
+        .map(|mode| config::Snapshots::new(snapshot_dir, mode, opts.snapshot_ignore_paths.clone()))
+        .transpose()
+    {
+        Ok(snapshots) => snapshots,
+        Err(error) => {
+            error!("Invalid snapshot options: {}.", error);
+            return exitcode::USAGE;
+        }
+    };
+
     #[allow(clippy::print_stdout)]
     {
         println!("Running tests");
@@ -82,7 +130,13 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
             } else {
                 for test in tests {
                     let name = test.name.clone();
-                    let UnitTestResult { errors } = test.run().await;
+                    let UnitTestResult {
+                        mut errors,
+                        outputs,
+                    } = test.run().await;
+                    if let Some(snapshots) = &snapshots {
+                        errors.extend(snapshots.check(&name, &outputs));
+                    }
                     if !errors.is_empty() {
                         #[allow(clippy::print_stdout)]
                         {
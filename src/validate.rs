@@ -7,7 +7,9 @@ use colored::*;
 use exitcode::ExitCode;
 
 use crate::{
+    batch_mode::Deliveries,
     config::{self, Config, ConfigDiff},
+    dead_letter::DeadLetters,
     extra_context::ExtraContext,
     topology::{self, builder::TopologyPieces},
 };
@@ -187,8 +189,15 @@ async fn validate_components(
     diff: &ConfigDiff,
     fmt: &mut Formatter,
 ) -> Option<TopologyPieces> {
-    match topology::TopologyPieces::build(config, diff, HashMap::new(), ExtraContext::default())
-        .await
+    match topology::TopologyPieces::build(
+        config,
+        diff,
+        HashMap::new(),
+        ExtraContext::default(),
+        DeadLetters::default(),
+        Deliveries::default(),
+    )
+    .await
     {
         Ok(pieces) => {
             fmt.success("Component configuration");
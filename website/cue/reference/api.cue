@@ -58,6 +58,111 @@ api: {
 				endpoint of the address set using the `bind` parameter.
 				"""
 		}
+		auth: {
+			common:   false
+			required: false
+			description: """
+				The credentials required to use the `/graphql` endpoint, including the
+				WebSocket subscriptions used by `vector tap` and `vector top`, which take
+				them with the `--token`, or `--user` and `--password` flags. Requests
+				carrying the `mutation_token` are accepted as well. The `/health` endpoint
+				doesn't require credentials.
+				"""
+			type: object: options: {
+				strategy: {
+					required:    true
+					description: "The authentication strategy to use."
+					type: string: enum: {
+						basic:  "Basic authentication, with a username and password."
+						bearer: "Bearer authentication, with a token."
+					}
+				}
+				user: {
+					required:      true
+					relevant_when: "strategy = \"basic\""
+					description:   "The basic authentication username."
+					type: string: examples: ["${USERNAME}", "username"]
+				}
+				password: {
+					required:      true
+					relevant_when: "strategy = \"basic\""
+					description:   "The basic authentication password."
+					type: string: examples: ["${PASSWORD}", "password"]
+				}
+				token: {
+					required:      true
+					relevant_when: "strategy = \"bearer\""
+					description:   "The bearer authentication token."
+					type: string: examples: ["${VECTOR_API_TOKEN}"]
+				}
+			}
+		}
+		mutation_token: {
+			common:   false
+			required: false
+			type: string: {
+				default: null
+				examples: ["${VECTOR_API_MUTATION_TOKEN}"]
+			}
+			description: """
+				The token required to run GraphQL mutations, which reload the
+				configuration, pause and resume sources, and drain sinks. Clients
+				send it in the `Authorization` header as a bearer token. Mutations
+				are disabled when it isn't set.
+				"""
+		}
+		tls: {
+			common:   false
+			required: false
+			description: """
+				Serves the API over TLS. Clients then connect with `https://` URLs, and
+				`vector tap` and `vector top` with `wss://` subscriptions.
+				"""
+			type: object: options: {
+				enabled: {
+					required:    false
+					description: "Whether or not to require TLS for incoming connections."
+					type: bool: default: false
+				}
+				crt_file: {
+					required:    false
+					description: "Absolute path to a certificate file used to identify this server, in DER or PEM format (X.509) or PKCS#12."
+					type: string: {
+						default: null
+						examples: ["/path/to/host_certificate.crt"]
+					}
+				}
+				key_file: {
+					required:    false
+					description: "Absolute path to a private key file used to identify this server, in DER or PEM format (PKCS#8)."
+					type: string: {
+						default: null
+						examples: ["/path/to/host_certificate.key"]
+					}
+				}
+				key_pass: {
+					required:    false
+					description: "Passphrase used to unlock the encrypted key file."
+					type: string: {
+						default: null
+						examples: ["${KEY_PASS_ENV_VAR}"]
+					}
+				}
+				ca_file: {
+					required:    false
+					description: "Absolute path to an additional CA certificate file, used to verify client certificates when `verify_certificate` is enabled."
+					type: string: {
+						default: null
+						examples: ["/path/to/certificate_authority.crt"]
+					}
+				}
+				verify_certificate: {
+					required:    false
+					description: "Whether to require clients to present a valid certificate."
+					type: bool: default: false
+				}
+			}
+		}
 	}
 
 	endpoints: {
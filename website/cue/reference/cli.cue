@@ -125,6 +125,10 @@ cli: {
 			description: env_vars.VECTOR_STRICT_ENV_VARS.description
 			env_var:     "VECTOR_STRICT_ENV_VARS"
 		}
+		"batch": {
+			description: env_vars.VECTOR_BATCH.description
+			env_var:     "VECTOR_BATCH"
+		}
 	}
 
 	_core_config_options: {
@@ -163,6 +167,25 @@ cli: {
 		}
 	}
 
+	_api_connection_options: {
+		"token": {
+			description: "Bearer token for a GraphQL API server that requires authentication. Can also be set with the `VECTOR_API_TOKEN` environment variable."
+			type:        "string"
+		}
+		"user": {
+			description: "Username for a GraphQL API server that requires basic authentication. Can also be set with the `VECTOR_API_USER` environment variable."
+			type:        "string"
+		}
+		"password": {
+			description: "Password for a GraphQL API server that requires basic authentication. Can also be set with the `VECTOR_API_PASSWORD` environment variable."
+			type:        "string"
+		}
+		"ca-file": {
+			description: "PEM file with the certificate authorities to trust for an HTTPS GraphQL API server, on top of the webpki roots"
+			type:        "string"
+		}
+	}
+
 	// Reusable options
 	_core_options: _core_config_options & {
 		"color": {
@@ -263,7 +286,25 @@ cli: {
 				out the [unit testing documentation](\(urls.vector_unit_tests)).
 				"""
 
+			flags: _default_flags & {
+				"update-snapshots": {
+					description: """
+						Record the events produced at each `extract_from` target of every test into
+						snapshot files next to the first configuration file or directory, replacing
+						previous recordings. Once recorded, every run compares the events against them
+						"""
+				}
+			}
+
 			options: {
+				"snapshot-ignore-paths": {
+					description: """
+						Paths to remove from events before recording or comparing snapshots, such as
+						`.timestamp`. Paths given while recording are stored in the snapshot files
+						"""
+					type:    "string"
+					example: ".timestamp,.host"
+				}
 				"config-toml": {
 					description: env_vars.VECTOR_CONFIG_TOML.description
 					type:        "string"
@@ -313,7 +354,7 @@ cli: {
 				}
 			}
 
-			options: {
+			options: _api_connection_options & {
 				"interval": {
 					_short:      "i"
 					description: "Interval to sample events at, in milliseconds"
@@ -337,9 +378,32 @@ cli: {
 					type:        "enum"
 					default:     "json"
 					enum: {
-						yaml:   "Output events as YAML"
-						json:   "Output events as JSON"
-						logfmt: "Output events as logfmt"
+						yaml:        "Output events as YAML"
+						json:        "Output events as JSON"
+						logfmt:      "Output events as logfmt"
+						native_json: "Output events as native JSON, which includes the event type and metadata"
+					}
+				}
+				"filter": {
+					description: """
+						VRL condition used to filter events, such as `.status >= 500`. The
+						condition is evaluated by the Vector instance being tapped, so
+						non-matching events are never sent to the client.
+						"""
+					type: "string"
+				}
+				"output-file": {
+					_short:      "o"
+					description: "Write tapped events to this file instead of printing them to screen. The file is appended to if it already exists."
+					type:        "string"
+				}
+				"output-format": {
+					description: "Encoding format for events written to `--output-file`"
+					type:        "enum"
+					default:     "json"
+					enum: {
+						json:   "Newline-delimited JSON, as produced by the `native_json` codec"
+						native: "Length-delimited protobuf, as produced by the `native` codec"
 					}
 				}
 				"inputs-of": {
@@ -387,7 +451,7 @@ cli: {
 				}
 			}
 
-			options: {
+			options: _api_connection_options & {
 				"refresh-interval": {
 					_short:      "i"
 					description: "How often the screen refreshes (in milliseconds)"
@@ -650,6 +714,14 @@ cli: {
 				"""
 			type: bool: default: false
 		}
+		VECTOR_BATCH: {
+			description: """
+				Run the topology to completion, as a one-shot batch job.
+
+				Sources with a finite input, such as `file`, `stdin`, `exec` and `replay`, stop once they reach its end. When all sources have stopped, Vector waits for all the events to be delivered, without time limit, and exits. The exit status is non-zero if sinks rejected or failed to deliver some of the events, or if a component failed.
+				"""
+			type: bool: default: false
+		}
 		VECTOR_STRICT_ENV_VARS: {
 			description: """
 				Turn on strict mode for environment variable interpolation. When set, interpolation of a missing environment variable in configuration files will cause an error instead of a warning, which will result in a failure to load any such configuration file. This defaults to false, but that default is deprecated and will be changed to strict in future versions.
@@ -76,6 +76,26 @@ base: components: sinks: configuration: {
 			}
 		}
 	}
+	dead_letter: {
+		description: """
+			Dead-letter configuration.
+
+			Events that the sink permanently rejects are sent to the `dead_letter` sources, instead of
+			being dropped, so that another sink can consume them.
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not to send events rejected by the sink to `dead_letter` sources.
+
+				Each rejected event is annotated with the reason of the rejection, the HTTP status
+				returned by the downstream service if any, and the ID of the sink, in the
+				`%vector.dead_letter` metadata field.
+				"""
+			required: false
+			type: bool: default: false
+		}
+	}
 	healthcheck: {
 		description: "Healthcheck configuration."
 		required:    false
@@ -8,6 +8,7 @@ components: sinks: [Name=string]: {
 	configuration: {
 		inputs: base.components.sinks.configuration.inputs
 		buffer: base.components.sinks.configuration.buffer
+		dead_letter: base.components.sinks.configuration.dead_letter
 		healthcheck: {
 			description: base.components.sinks.configuration.healthcheck.description
 			required:    base.components.sinks.configuration.healthcheck.required
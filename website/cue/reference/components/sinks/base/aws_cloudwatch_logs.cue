@@ -664,8 +664,13 @@ base: components: sinks: aws_cloudwatch_logs: configuration: {
 				}
 			}
 			retry_max_duration_secs: {
-				description: "The maximum amount of time to wait between retries."
-				required:    false
+				description: """
+					The maximum amount of time to wait between retries.
+
+					This also caps the delay requested by the downstream service before retrying, for example
+					through a `Retry-After` header.
+					"""
+				required: false
 				type: uint: {
 					default: 30
 					unit:    "seconds"
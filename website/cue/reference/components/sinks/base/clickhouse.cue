@@ -149,8 +149,12 @@ base: components: sinks: clickhouse: configuration: {
 		}
 	}
 	date_time_best_effort: {
-		description: "Sets `date_time_input_format` to `best_effort`, allowing ClickHouse to properly parse RFC3339/ISO 8601."
-		required:    false
+		description: """
+			Sets `date_time_input_format` to `best_effort`, allowing ClickHouse to properly parse RFC3339/ISO 8601.
+
+			Only applies to the `json_each_row` format.
+			"""
+		required: false
 		type: bool: default: false
 	}
 	encoding: {
@@ -186,6 +190,38 @@ base: components: sinks: clickhouse: configuration: {
 		required:    true
 		type: string: examples: ["http://localhost:8123"]
 	}
+	format: {
+		description: """
+			The format used to insert events.
+
+			With `row_binary` and `native`, the columns of the table are introspected, and each event is
+			converted to the column types before being sent. Fields that are not columns of the table are
+			ignored, and missing or `null` fields are set to the default value of their column type. Events
+			that cannot be converted are rejected individually.
+			"""
+		required: false
+		type: string: {
+			default: "json_each_row"
+			enum: {
+				json_each_row: """
+					Events are sent as newline-delimited JSON, and ClickHouse converts their fields to the
+					column types.
+					"""
+				native: """
+					Event fields are converted to the column types of the table and sent column by column.
+
+					Missing fields are written as the zero value of the column type, as the format can't leave
+					them to the column's `DEFAULT` expression.
+					"""
+				row_binary: """
+					Event fields are converted to the column types of the table and sent row by row.
+
+					Rows are sent in the `RowBinaryWithDefaults` format, so that missing fields are left to the
+					column's `DEFAULT` expression.
+					"""
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -350,8 +386,13 @@ base: components: sinks: clickhouse: configuration: {
 				}
 			}
 			retry_max_duration_secs: {
-				description: "The maximum amount of time to wait between retries."
-				required:    false
+				description: """
+					The maximum amount of time to wait between retries.
+
+					This also caps the delay requested by the downstream service before retrying, for example
+					through a `Retry-After` header.
+					"""
+				required: false
 				type: uint: {
 					default: 30
 					unit:    "seconds"
@@ -373,8 +414,12 @@ base: components: sinks: clickhouse: configuration: {
 		}
 	}
 	skip_unknown_fields: {
-		description: "Sets `input_format_skip_unknown_fields`, allowing ClickHouse to discard fields not present in the table schema."
-		required:    false
+		description: """
+			Sets `input_format_skip_unknown_fields`, allowing ClickHouse to discard fields not present in the table schema.
+
+			Only applies to the `json_each_row` format.
+			"""
+		required: false
 		type: bool: default: false
 	}
 	table: {
@@ -241,7 +241,14 @@ base: components: sinks: elasticsearch: configuration: {
 				description: """
 					Action to use when making requests to the [Elasticsearch Bulk API][es_bulk].
 
-					Only `index` and `create` actions are supported.
+					The `index`, `create`, `update`, `upsert` and `delete` actions are supported. The `update`,
+					`upsert` and `delete` actions require the document ID, set through `id_key`, and the
+					events without one are dropped.
+
+					The `update` action sends the event as a partial document, with `doc_as_upsert` so that
+					the document is created from the event if it does not exist. The `upsert` action updates
+					the document with `bulk.script` instead, and requires it: the events rendering `upsert`
+					without a script are dropped.
 
 					[es_bulk]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
 					"""
@@ -261,6 +268,18 @@ base: components: sinks: elasticsearch: configuration: {
 					syntax: "template"
 				}
 			}
+			script: {
+				description: """
+					The [script][es_script] used to update the documents with the `upsert` action.
+
+					The event is available to the script as `params.event`, and is used as the document
+					when it does not exist yet. Required by the `upsert` action.
+
+					[es_script]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update.html#update-api-example
+					"""
+				required: false
+				type: string: examples: ["ctx._source.counter = (ctx._source.counter ?: 0) + params.event.count"]
+			}
 		}
 	}
 	compression: {
@@ -715,8 +734,13 @@ base: components: sinks: elasticsearch: configuration: {
 				}
 			}
 			retry_max_duration_secs: {
-				description: "The maximum amount of time to wait between retries."
-				required:    false
+				description: """
+					The maximum amount of time to wait between retries.
+
+					This also caps the delay requested by the downstream service before retrying, for example
+					through a `Retry-After` header.
+					"""
+				required: false
 				type: uint: {
 					default: 30
 					unit:    "seconds"
@@ -739,9 +763,10 @@ base: components: sinks: elasticsearch: configuration: {
 	}
 	request_retry_partial: {
 		description: """
-			Whether or not to retry successful requests containing partial failures.
+			Whether or not to retry the items of successful requests that failed with a retriable status.
 
-			To avoid duplicates in Elasticsearch, please use option `id_key`.
+			Only the items that failed with a `429` or server error status are sent again, while the
+			items rejected with any other status are marked as such.
 			"""
 		required: false
 		type: bool: default: false
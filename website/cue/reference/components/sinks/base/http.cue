@@ -427,6 +427,17 @@ base: components: sinks: http: configuration: {
 			type: string: {}
 		}
 	}
+	max_partitions: {
+		description: """
+			The maximum number of partitions to batch events for at once.
+
+			Events are batched separately for each rendered `uri` and set of `request.headers`. When an
+			event belongs to a new partition while this limit is reached, the batch of the oldest
+			partition is sent to make room for it.
+			"""
+		required: false
+		type: uint: default: 100
+	}
 	method: {
 		description: """
 			HTTP method.
@@ -651,8 +662,13 @@ base: components: sinks: http: configuration: {
 				}
 			}
 			retry_max_duration_secs: {
-				description: "The maximum amount of time to wait between retries."
-				required:    false
+				description: """
+					The maximum amount of time to wait between retries.
+
+					This also caps the delay requested by the downstream service before retrying, for example
+					through a `Retry-After` header.
+					"""
+				required: false
 				type: uint: {
 					default: 30
 					unit:    "seconds"
@@ -763,8 +779,14 @@ base: components: sinks: http: configuration: {
 			The full URI to make HTTP requests to.
 
 			This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+
+			Events are batched separately for each rendered URI. Only `{{ field }}` references are
+			rendered, so percent-encoded characters such as `%20` are sent as written.
 			"""
 		required: true
-		type: string: examples: ["https://10.22.212.22:9000/endpoint"]
+		type: string: {
+			examples: ["https://10.22.212.22:9000/endpoint", "https://{{ tenant }}.example.com/endpoint"]
+			syntax: "template"
+		}
 	}
 }
@@ -397,6 +397,49 @@ base: components: sinks: loki: configuration: {
 		required: true
 		type: string: examples: ["http://localhost:3100"]
 	}
+	label_cardinality_limit: {
+		description: """
+			Label cardinality limit.
+
+			Labels built from high-cardinality fields can create a large number of streams, which degrades
+			the performance of Loki. This limits the number of distinct label sets sent within a time
+			window. Once the limit is reached, the labels of events that would create a new stream are
+			removed one at a time, starting with the label that had the most distinct values, until the
+			event matches an existing stream, so the limit is never exceeded. Labels removed this way keep
+			being removed until the end of the window.
+			"""
+		required: false
+		type: object: options: {
+			action: {
+				description: "What to do with the labels removed by the cardinality limit."
+				required:    false
+				type: string: {
+					default: "structured_metadata"
+					enum: {
+						drop: "Drop the label."
+						structured_metadata: """
+							Send the label as structured metadata instead.
+
+							Requires Loki 3.0 or newer.
+							"""
+					}
+				}
+			}
+			max_streams: {
+				description: "The maximum number of distinct label sets within a window."
+				required:    true
+				type: uint: examples: [10000]
+			}
+			window_secs: {
+				description: "The time window after which the label sets seen are forgotten, in seconds."
+				required:    false
+				type: float: {
+					default: 3600.0
+					unit:    "seconds"
+				}
+			}
+		}
+	}
 	labels: {
 		description: """
 			A set of labels that are attached to each batch of events.
@@ -639,8 +682,13 @@ base: components: sinks: loki: configuration: {
 				}
 			}
 			retry_max_duration_secs: {
-				description: "The maximum amount of time to wait between retries."
-				required:    false
+				description: """
+					The maximum amount of time to wait between retries.
+
+					This also caps the delay requested by the downstream service before retrying, for example
+					through a `Retry-After` header.
+					"""
+				required: false
 				type: uint: {
 					default: 30
 					unit:    "seconds"
@@ -661,6 +709,31 @@ base: components: sinks: loki: configuration: {
 			}
 		}
 	}
+	structured_metadata: {
+		description: """
+			A set of [structured metadata][structured_metadata] that is attached to each event.
+
+			Both keys and values are templateable, and keys ending with `*` expand objects into
+			multiple entries, as for labels. Structured metadata is not indexed, so unlike labels it
+			can hold high-cardinality values such as trace IDs.
+
+			Requires Loki 3.0 or newer.
+
+			[structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				"\"attributes_*\"": "{{ attributes }}"
+				trace_id:           "{{ trace_id }}"
+			}]
+			options: "*": {
+				description: "A Loki structured metadata entry."
+				required:    true
+				type: string: syntax: "template"
+			}
+		}
+	}
 	tenant_id: {
 		description: """
 			The [tenant ID][tenant_id] to specify in requests to Loki.
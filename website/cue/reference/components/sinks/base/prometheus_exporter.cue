@@ -127,6 +127,22 @@ base: components: sinks: prometheus_exporter: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	exemplar_tags: {
+		description: """
+			Tags to expose as exemplars instead of labels.
+
+			These tags are removed from all metrics. The ones found on a counter or a distribution
+			become the labels of an exemplar of its series, along with the value and timestamp of the
+			metric. Only the latest exemplar of each series is kept.
+
+			Exemplars are only exposed by the OpenMetrics and protobuf formats.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["trace_id"]
+		}
+	}
 	flush_period_secs: {
 		description: """
 			The interval, in seconds, on which metrics are flushed.
@@ -142,6 +158,42 @@ base: components: sinks: prometheus_exporter: configuration: {
 			unit:    "seconds"
 		}
 	}
+	native_histograms: {
+		description: """
+			Renders [distributions][dist_metric_docs] and sketches as Prometheus [native histograms][prom_native_hist_docs].
+
+			Native histograms have sparse exponential buckets, which keep a high resolution over any
+			range of values. They are only exposed when the client requests the protobuf format, and
+			are exposed as histograms with the same buckets in the text formats.
+
+			This cannot be used together with `distributions_as_summaries`.
+
+			[dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+			[prom_native_hist_docs]: https://prometheus.io/docs/concepts/metric_types/#histogram
+			"""
+		required: false
+		type: object: options: {
+			schema: {
+				description: """
+					The resolution of the buckets.
+
+					Each power of two is divided into `2^schema` buckets, so that higher values give finer
+					buckets. Must be between `-4` and `8`.
+					"""
+				required: false
+				type: int: default: 3
+			}
+			zero_threshold: {
+				description: """
+					The width of the zero bucket.
+
+					Values whose absolute value is at most this threshold are counted in the zero bucket.
+					"""
+				required: false
+				type: float: default: 2.938735877055719e-39
+			}
+		}
+	}
 	quantiles: {
 		description: """
 			Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
@@ -295,6 +295,59 @@ base: components: sinks: prometheus_remote_write: configuration: {
 		required: true
 		type: string: examples: ["https://localhost:8087/api/v1/write"]
 	}
+	native_histograms: {
+		description: """
+			Sends [distributions][dist_metric_docs] and sketches as Prometheus [native histograms][prom_native_hist_docs].
+
+			Native histograms have sparse exponential buckets, which keep a high resolution over any
+			range of values. The receiver must accept native histograms.
+
+			[dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+			[prom_native_hist_docs]: https://prometheus.io/docs/concepts/metric_types/#histogram
+			"""
+		required: false
+		type: object: options: {
+			schema: {
+				description: """
+					The resolution of the buckets.
+
+					Each power of two is divided into `2^schema` buckets, so that higher values give finer
+					buckets. Must be between `-4` and `8`.
+					"""
+				required: false
+				type: int: default: 3
+			}
+			zero_threshold: {
+				description: """
+					The width of the zero bucket.
+
+					Values whose absolute value is at most this threshold are counted in the zero bucket.
+					"""
+				required: false
+				type: float: default: 2.938735877055719e-39
+			}
+		}
+	}
+	protocol_version: {
+		description: "The version of the remote write protocol to send requests with."
+		required:    false
+		type: string: {
+			default: "v1"
+			enum: {
+				v1: """
+					[Remote-Write 1.0][rw_spec], which all receivers accept.
+
+					[rw_spec]: https://prometheus.io/docs/specs/remote_write_spec/
+					"""
+				v2: """
+					[Remote-Write 2.0][rw2_spec], which sends the metadata of every series and lowers the size
+					of requests by interning their strings in a symbol table.
+
+					[rw2_spec]: https://prometheus.io/docs/specs/remote_write_spec_2_0/
+					"""
+			}
+		}
+	}
 	quantiles: {
 		description: """
 			Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
@@ -471,8 +524,13 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				}
 			}
 			retry_max_duration_secs: {
-				description: "The maximum amount of time to wait between retries."
-				required:    false
+				description: """
+					The maximum amount of time to wait between retries.
+
+					This also caps the delay requested by the downstream service before retrying, for example
+					through a `Retry-After` header.
+					"""
+				required: false
 				type: uint: {
 					default: 30
 					unit:    "seconds"
@@ -80,4 +80,30 @@ components: sinks: clickhouse: {
 		metrics: null
 		traces:  false
 	}
+
+	how_it_works: {
+		binary_formats: {
+			title: "Binary formats"
+			body: """
+				By default, events are inserted with the `JSONEachRow` format, leaving the conversion of
+				their fields to ClickHouse. With `format` set to `row_binary` or `native`, Vector instead
+				queries `system.columns` for the columns of each table it inserts into, converts the
+				fields of each event to the column types, and sends the batch as `RowBinary` or `Native`.
+
+				The columns of a table are introspected by the healthcheck when the table is not templated
+				and otherwise by the first batch for that table. They are introspected again every five
+				minutes, and after an insert into the table fails, so that changes to the table's columns
+				are picked up. Failed introspection queries are retried like the inserts.
+
+				The following column types are supported, including as `Nullable`, `LowCardinality`,
+				`Array`, and `Map` of them: `Bool`, integers up to 64 bits, `Float32`, `Float64`,
+				`String`, `FixedString`, `UUID`, `Date`, `Date32`, `DateTime`, `DateTime64`, `IPv4`, and
+				`IPv6`. Timestamps are read from timestamp fields, from numbers of seconds since the Unix
+				epoch, and from RFC 3339 strings. `MATERIALIZED` and `ALIAS` columns are skipped.
+
+				An event with a field that cannot be converted, for example a value out of the range of
+				its column, is rejected and dropped on its own, while the rest of the batch is sent.
+				"""
+		}
+	}
 }
@@ -127,11 +127,35 @@ components: sinks: elasticsearch: {
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
 
-				By default, partial failures are not retried. To enable retries, set `request_retry_partial`. Once enabled it will
-				retry whole partially failed requests. As such it is advised to use `id_key` to avoid duplicates.
+				By default, partial failures are not retried. To enable retries, set `request_retry_partial`. Once enabled,
+				only the items that failed with a retriable status, that is `429` or a server error, are sent again. The
+				items rejected with any other status are marked as rejected, without affecting the rest of the request.
+				The events of a request are kept in memory until all of its items are delivered or rejected.
+
+				The failed items are counted by status in the `elasticsearch_bulk_item_errors_total` internal metric.
+				"""
+		}
+
+		bulk_actions: {
+			title: "Bulk actions"
+			body:  """
+				In `bulk` mode, the action of each event is rendered from `bulk.action`:
+
+				* `index` and `create` send the event as the document.
+				* `update` sends the event as a partial document, updating an existing document.
+				* `upsert` updates the document with `bulk.script` if set, or with the event as a partial
+				  document otherwise, and creates it from the event if it does not exist yet.
+				* `delete` deletes the document.
+
+				The `update`, `upsert` and `delete` actions target an existing document, so they require its ID,
+				which is taken from the field set by `id_key`. The events without an ID are dropped.
 				"""
 		}
 
 		aws_authentication: components._aws.how_it_works.aws_authentication
 	}
+
+	telemetry: metrics: {
+		elasticsearch_bulk_item_errors_total: components.sources.internal_metrics.output.metrics.elasticsearch_bulk_item_errors_total
+	}
 }
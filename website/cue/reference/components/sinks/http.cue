@@ -79,6 +79,21 @@ components: sinks: http: {
 
 	configuration: base.components.sinks.http.configuration
 
+	how_it_works: {
+		partitioning: {
+			title: "Partitioning"
+			body: """
+				The `uri` and the values of `request.headers` can be [templates](/docs/reference/configuration/template-syntax/),
+				rendered for each event. Events are batched separately for each rendered URI and set of
+				headers, so that every request is sent with the values of the events it contains.
+
+				Events for which a template fails to render are dropped, and an error is logged. At most
+				`max_partitions` batches are kept at once: when an event belongs to a new partition while
+				that limit is reached, the batch of the oldest partition is sent first.
+				"""
+		}
+	}
+
 	input: {
 		logs: true
 		metrics: {
@@ -159,6 +159,7 @@ components: sinks: loki: {
 	}
 
 	telemetry: metrics: {
-		streams_total: components.sources.internal_metrics.output.metrics.streams_total
+		loki_labels_removed_total: components.sources.internal_metrics.output.metrics.loki_labels_removed_total
+		streams_total:             components.sources.internal_metrics.output.metrics.streams_total
 	}
 }
@@ -0,0 +1,129 @@
+package metadata
+
+components: sinks: postgres: {
+	title: "PostgreSQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled:     true
+				concurrency: 1
+				headers:     false
+			}
+			tls: enabled: false
+			to: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				[PostgreSQL](\(urls.postgresql)) version `>= 12` is required.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.postgres.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		copy: {
+			title: "Inserting with `COPY`"
+			body: """
+				Vector queries the catalog for the columns of each table it inserts into, and maps the
+				fields of each event to the columns by name. A column named `a.b` is matched against the
+				nested field `b` of `a` first, and then against a field literally named `a.b`. Fields that
+				are not columns of the table are ignored. Columns that an event has no field for, or a
+				`null` field, are set to their default value, or to `NULL` if they have none.
+
+				Each batch is written in its own transaction with `COPY ... FROM STDIN`, in the binary
+				format by default. When the table has column defaults, the batch is copied into a temporary
+				table first, and then inserted into the table with the defaults filled in. The columns of a table are introspected by the healthcheck when
+				the table is not templated and otherwise by the first batch for that table, and again every
+				five minutes so that changes to the table's columns are picked up. Failed introspection
+				queries are retried like the inserts. Generated columns and identity columns that
+				are `GENERATED ALWAYS` are skipped.
+
+				Metrics are converted to logs as the [`metric_to_log`][metric_to_log] transform does, with
+				the options under `metrics`, before being inserted.
+
+				[metric_to_log]: /docs/reference/configuration/transforms/metric_to_log/
+				"""
+		}
+		types: {
+			title: "Type conversion"
+			body: """
+				With the binary format, the fields of each event are converted to the column types, and
+				the following types are supported, including as one-dimensional arrays: `boolean`,
+				`smallint`, `integer`, `bigint`, `real`, `double precision`, `text`, `varchar`, `char`,
+				`name`, `bytea`, `json`, `jsonb`, `uuid`, `timestamp`, `timestamptz`, and `date`.
+				Timestamps are read from timestamp fields, from numbers of seconds since the Unix epoch,
+				and from RFC 3339 strings. Objects are written as JSON to `json` and `jsonb` columns.
+
+				With the text format, fields are written as text and PostgreSQL converts them to the
+				column types, so that any column type can be used.
+
+				An event that cannot be converted, for example because a value is out of the range of
+				its column or a `NOT NULL` column has no field, is rejected and dropped on its own, while
+				the rest of the batch is inserted.
+				"""
+		}
+		upsert: {
+			title: "Upserts"
+			body: """
+				With `upsert` set, each batch is copied into a temporary table, and then inserted into the
+				target table with `INSERT ... ON CONFLICT (keys) DO UPDATE`, so that rows that conflict
+				with existing rows update them. Columns that the event has no value for keep their
+				existing value, unless they have a default, which they are reset to. The `keys` columns
+				must be columns of the table and match a unique index or constraint.
+				"""
+		}
+	}
+}
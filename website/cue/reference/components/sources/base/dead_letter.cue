@@ -0,0 +1,14 @@
+package metadata
+
+base: components: sources: dead_letter: configuration: sinks: {
+	description: """
+		The IDs of the sinks to receive rejected events from.
+
+		By default, the events rejected by all sinks with `dead_letter.enabled` set are received.
+		"""
+	required: false
+	type: array: {
+		default: []
+		items: type: string: examples: ["elasticsearch_out"]
+	}
+}
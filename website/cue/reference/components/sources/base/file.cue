@@ -101,7 +101,7 @@ base: components: sources: file: configuration: {
 
 					This can be helpful if all files share a common header that should be skipped.
 					"""
-				relevant_when: "strategy = \"checksum\""
+				relevant_when: "strategy = \"checksum\" or strategy = \"device_inode_and_checksum\""
 				required:      false
 				type: uint: {
 					default: 0
@@ -116,7 +116,7 @@ base: components: sources: file: configuration: {
 
 					If the file has less than this amount of lines, it won’t be read at all.
 					"""
-				relevant_when: "strategy = \"checksum\""
+				relevant_when: "strategy = \"checksum\" or strategy = \"device_inode_and_checksum\""
 				required:      false
 				type: uint: {
 					default: 1
@@ -137,6 +137,16 @@ base: components: sources: file: configuration: {
 						device_and_inode: """
 															Use the [device and inode][inode] as the identifier.
 
+															[inode]: https://en.wikipedia.org/wiki/Inode
+															"""
+						device_inode_and_checksum: """
+															Use the [device and inode][inode] together with a checksum of the first lines of the file as the identifier.
+
+															Unlike `checksum`, files sharing the same leading lines (such as a common banner) are kept apart, and unlike
+															`device_and_inode`, a reused inode is not mistaken for the file that previously had it. A file that is truncated
+															in place is read again from the beginning, and the copy made by a copy-truncate rotation resumes from the
+															position reached in the original file.
+
 															[inode]: https://en.wikipedia.org/wiki/Inode
 															"""
 					}
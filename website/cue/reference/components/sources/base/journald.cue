@@ -96,6 +96,19 @@ base: components: sources: journald: configuration: {
 			items: type: string: examples: ["badservice", "sysinit.target"]
 		}
 	}
+	export_file: {
+		description: """
+			The path of a file in the [journal export format][export-format] to read instead of running `journalctl`.
+
+			Such files are produced by `journalctl -o export`. The source stops once the end of the file is reached,
+			and `current_boot_only`, `since_now` and `extra_args` have no effect. Cannot be used together with
+			`journal_directory`, `journal_files`, or `journal_namespace`.
+
+			[export-format]: https://systemd.io/JOURNAL_EXPORT_FORMATS/#journal-export-format
+			"""
+		required: false
+		type: string: examples: ["/var/lib/vector/replay.export"]
+	}
 	extra_args: {
 		description: """
 			A list of extra command line arguments to pass to `journalctl`.
@@ -152,6 +165,22 @@ base: components: sources: journald: configuration: {
 		required: false
 		type: string: {}
 	}
+	journal_files: {
+		description: """
+			A list of journal files to read from.
+
+			Each path is passed to `journalctl` through the [`--file` option][journalctl-file-option], and may contain
+			glob patterns. This is useful for reading `.journal` files shipped from other machines, in which case
+			`current_boot_only` should usually be disabled. Cannot be used together with `journal_directory`.
+
+			[journalctl-file-option]: https://www.freedesktop.org/software/systemd/man/journalctl.html#--file=GLOB
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["/var/log/remote/*.journal"]
+		}
+	}
 	journal_namespace: {
 		description: """
 			The [journal namespace][journal-namespace].
@@ -115,6 +115,31 @@ base: components: sources: prometheus_scrape: configuration: {
 			unit:    "seconds"
 		}
 	}
+	scrape_protocols: {
+		description: """
+			The formats that metrics are requested in, in order of preference.
+
+			Responses are parsed according to their `Content-Type` header.
+			"""
+		required: false
+		type: array: {
+			default: ["prometheus_text"]
+			items: type: string: enum: {
+				openmetrics_text: """
+					The OpenMetrics text format.
+
+					Exemplars and created timestamps are parsed, but not kept.
+					"""
+				prometheus_protobuf: """
+					The Prometheus protobuf format.
+
+					Native histograms are converted to histograms with the bounds of their exponential
+					buckets.
+					"""
+				prometheus_text: "The Prometheus text format."
+			}
+		}
+	}
 	scrape_timeout_secs: {
 		description: "The timeout for each scrape request."
 		required:    false
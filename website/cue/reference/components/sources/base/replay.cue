@@ -0,0 +1,73 @@
+package metadata
+
+base: components: sources: replay: configuration: {
+	codec: {
+		description: "The codec the replayed events were encoded with."
+		required:    true
+		type: string: enum: {
+			native:      "Events encoded with the `native` codec, as length-delimited Protocol Buffers messages."
+			native_json: """
+				Events encoded with the `native_json` codec, as newline-delimited JSON objects.
+
+				This encoding doesn't hold the metadata of the events, so the replayed events have none.
+				"""
+		}
+	}
+	compression: {
+		description: "Compression scheme of the replayed files."
+		required:    false
+		type: string: {
+			default: "auto"
+			enum: {
+				auto: """
+					Automatically determine the compression scheme from the file extension.
+
+					Files ending with `.gz` are decompressed with GZIP, and files ending with `.zst` with ZSTD.
+					Other files are read uncompressed.
+					"""
+				gzip: "GZIP."
+				none: "Uncompressed."
+				zstd: "ZSTD."
+			}
+		}
+	}
+	include: {
+		description: """
+			Array of file patterns to replay. [Globbing](https://en.wikipedia.org/wiki/Glob_(programming)) is supported.
+
+			The matching files are replayed one after the other, in the lexicographic order of their
+			paths. The source stops once all of them are replayed.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["/var/lib/vector/archive/*.log.gz"]
+	}
+	pacing: {
+		description: "The pace at which the events are replayed."
+		required:    false
+		type: object: options: {
+			events_per_second: {
+				description:   "The number of events to replay per second."
+				relevant_when: "mode = \"rate\""
+				required:      true
+				type: uint: examples: [1000]
+			}
+			mode: {
+				description: "The pacing mode."
+				required:    false
+				type: string: {
+					default: "as_fast_as_possible"
+					enum: {
+						as_fast_as_possible: "Replay the events as fast as the downstream components accept them."
+						original: """
+							Replay the events at their original pace.
+
+							The events are delayed to keep the intervals between their timestamps. Events without a
+							timestamp, or with a timestamp earlier than the previous ones, aren't delayed.
+							"""
+						rate: "Replay the events at a fixed rate."
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,76 @@
+package metadata
+
+components: sources: dead_letter: {
+	title: "Dead Letter"
+
+	description: """
+		Receives the events that sinks with `dead_letter.enabled` set permanently rejected, so that
+		they can be routed to another sink instead of being dropped.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.dead_letter.configuration
+
+	output: logs: event: {
+		description: "An event rejected by a sink."
+		fields: {
+			"*": {
+				description: "The rejected event is forwarded as it was received by the sink."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		rejection_metadata: {
+			title: "Rejection metadata"
+			body: """
+				Each event is annotated with the details of its rejection in the `%vector.dead_letter`
+				metadata field: the `reason` of the rejection, the `http_status` returned by the
+				downstream service if any, and the `component_id` of the sink that rejected it.
+				"""
+		}
+		buffering: {
+			title: "Buffering"
+			body: """
+				Sinks only keep a copy of the events they receive while at least one `dead_letter`
+				source is running. Events rejected while the source falls behind by more than 1000
+				events are dropped.
+				"""
+		}
+	}
+}
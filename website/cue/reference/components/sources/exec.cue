@@ -102,6 +102,14 @@ components: sources: exec: {
 	]
 
 	how_it_works: {
+		batch_mode: {
+			title: "Batch Mode"
+			body: """
+				When Vector runs with the `--batch` flag, this source stops once the command
+				exits: in `scheduled` mode, the command only runs once, and in `streaming` mode, it
+				isn't respawned, regardless of `respawn_on_exit`.
+				"""
+		}
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
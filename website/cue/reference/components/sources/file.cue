@@ -119,6 +119,16 @@ components: sources: file: {
 				"""
 		}
 
+		batch_mode: {
+			title: "Batch Mode"
+			body: """
+				When Vector runs with the `--batch` flag, this source stops once a pass over all
+				the watched files reads nothing from them, instead of tailing the files for new
+				lines. Files are only discovered until then, so this is meant to be used with a
+				finite set of files, for example to backfill archived logs as a one-shot job.
+				"""
+		}
+
 		compressed_files: {
 			title: "Compressed Files"
 			body: """
@@ -498,6 +498,14 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		elasticsearch_bulk_item_errors_total: {
+			description:       "The total number of items of Elasticsearch bulk requests that failed, tagged with their status."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				status: _status
+			}
+		}
 		internal_metrics_cardinality: {
 			description:       "The total number of metrics emitted from the internal metrics registry."
 			type:              "gauge"
@@ -711,12 +719,33 @@ components: sources: internal_metrics: {
 				status: _status
 			}
 		}
+		incomplete_events_flushed_total: {
+			description:       "The number of events that Vector has flushed early because the groups of the `reduce` transform exceeded their memory budget."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		invalid_record_total: {
 			description:       "The total number of invalid records that have been discarded."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		loki_labels_removed_total: {
+			description:       "The total number of labels that the `loki` sink removed from events because of the label cardinality limit."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				action: {
+					description: "What was done with the removed label."
+					required:    true
+					enum: {
+						drop:                "The label was dropped."
+						structured_metadata: "The label was sent as structured metadata."
+					}
+				}
+			}
+		}
 		lua_memory_used_bytes: {
 			description:       "The total memory currently being used by the Lua runtime."
 			type:              "gauge"
@@ -747,6 +776,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		reduce_groups: {
+			description:       "The number of groups currently held by the `reduce` transform."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		reduce_groups_bytes: {
+			description:       "The estimated in-memory size, in bytes, of the groups currently held by the `reduce` transform."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
@@ -829,7 +870,16 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags: _component_tags & {
+				metric_name: {
+					description: "The name of the metric whose tag hit the limit. Only present when `internal_metrics.include_key_in_limit_metric` is set."
+					required:    false
+				}
+				tag_key: {
+					description: "The key of the tag that hit the limit. Only present when `internal_metrics.include_key_in_limit_metric` is set."
+					required:    false
+				}
+			}
 		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
@@ -897,7 +947,16 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags: _component_tags & {
+				metric_name: {
+					description: "The name of the metric whose tag hit the limit. Only present when `internal_metrics.include_key_in_limit_metric` is set."
+					required:    false
+				}
+				tag_key: {
+					description: "The key of the tag that hit the limit. Only present when `internal_metrics.include_key_in_limit_metric` is set."
+					required:    false
+				}
+			}
 		}
 
 		// Windows metrics
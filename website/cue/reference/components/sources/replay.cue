@@ -0,0 +1,81 @@
+package metadata
+
+components: sources: replay: {
+	title: "Replay"
+
+	description: """
+		Replays events archived by the `file` or `aws_s3` sinks with the `native` or
+		`native_json` codecs, with their original timestamps, and stops once all the files
+		are replayed. Events archived with the `native` codec also keep their metadata.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "archived events"
+				thing:    "\(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.replay.configuration
+
+	output: logs: event: {
+		description: "An archived event."
+		fields: {
+			"*": {
+				description: "The archived event is replayed as it was encoded, including its timestamp, and its metadata with the `native` codec."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		backfilling: {
+			title: "Backfilling"
+			body: """
+				The files matching the `include` patterns are read once, one after the other, in
+				the lexicographic order of their paths. The source stops once all the files are
+				replayed, and Vector shuts down once all its sources are stopped, so a
+				configuration with a single `replay` source can be run as a one-shot backfill job.
+				Files that can't be opened are skipped, and a file is abandoned at the first
+				event that can't be decoded.
+				"""
+		}
+		pacing: {
+			title: "Pacing"
+			body: """
+				By default, the events are replayed as fast as the downstream components accept
+				them. With the `original` pacing mode, the events are delayed to keep the
+				intervals between their timestamps, and with the `rate` mode, they are replayed at
+				a fixed number of events per second.
+				"""
+		}
+	}
+}
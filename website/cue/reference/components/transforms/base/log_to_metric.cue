@@ -37,6 +37,34 @@ base: components: transforms: log_to_metric: configuration: {
 		description: "A list of metrics to generate."
 		required:    true
 		type: array: items: type: object: options: {
+			buckets: {
+				description: """
+					The upper limits of the buckets to count the value in, in ascending order.
+
+					When set, the metric is an aggregated histogram with these buckets, instead of a
+					distribution holding the value as a sample. Values above the last upper limit are only
+					counted in the `count` and `sum` of the histogram.
+
+					This only changes the representation of the metric, not the number of metrics: each event
+					still produces its own histogram, with a `count` of 1. To combine the histograms of many
+					events into one, send the metrics through an `aggregate` transform.
+					"""
+				relevant_when: "type = \"histogram\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: float: examples: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+				}
+			}
+			condition: {
+				description: """
+					A logical condition the event must match for the metric to be generated.
+
+					Events that don't match it are skipped for this metric only.
+					"""
+				required: false
+				type: condition: {}
+			}
 			field: {
 				description: "Name of the field in the event to generate the metric."
 				required:    true
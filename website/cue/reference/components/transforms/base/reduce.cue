@@ -47,11 +47,45 @@ base: components: transforms: reduce: configuration: {
 			items: type: string: examples: ["request_id", "user_id", "transaction_id"]
 		}
 	}
+	incomplete_key: {
+		description: """
+			The field to set to `true` in the events of the groups flushed early because
+			`max_total_bytes` was exceeded.
+
+			Set to `""` to flush these events without marking them.
+			"""
+		required: false
+		type: string: {
+			default: ".reduce_incomplete"
+			examples: ["reduce_incomplete"]
+		}
+	}
+	max_bytes: {
+		description: """
+			The maximum size of a group, in bytes.
+
+			The size of a group is the sum of the estimated in-memory sizes of the events merged into
+			it. The group is flushed along with the event that makes it reach this size.
+			"""
+		required: false
+		type: uint: unit: "bytes"
+	}
 	max_events: {
 		description: "The maximum number of events to group together."
 		required:    false
 		type: uint: {}
 	}
+	max_total_bytes: {
+		description: """
+			The maximum size of all the groups combined, in bytes.
+
+			When this budget is exceeded, the oldest groups are flushed early until the remaining
+			groups fit in it again. The events of the groups flushed this way are marked as incomplete
+			with the `incomplete_key` field.
+			"""
+		required: false
+		type: uint: unit: "bytes"
+	}
 	merge_strategies: {
 		description: """
 			A map of field names to custom merge strategies.
@@ -1,6 +1,23 @@
 package metadata
 
 base: components: transforms: route: configuration: {
+	mode: {
+		description: "How events matching several routes are routed."
+		required:    false
+		type: string: {
+			default: "all"
+			enum: {
+				all: "Events are sent to every route they match."
+				first_match: """
+					Routes are checked in the order of `routes`, and events are sent to the first route they
+					match only.
+
+					This avoids cloning the events, and delivering them to several routes. The routes must be
+					declared in `routes`, as the order of the keys of `route` isn't preserved.
+					"""
+			}
+		}
+	}
 	reroute_unmatched: {
 		description: """
 			Reroutes unmatched events to a named output instead of silently discarding them.
@@ -34,4 +51,31 @@ base: components: transforms: route: configuration: {
 			type: condition: {}
 		}
 	}
+	routes: {
+		description: """
+			A list of routes, checked in the order they are declared in.
+
+			These are referenced as inputs the same way as the routes in `route`, and are checked
+			before them. Unlike the keys of `route`, the order of this list is always preserved, which
+			is required with the `first_match` mode.
+			"""
+		required: false
+		type: array: items: type: object: options: {
+			condition: {
+				description: "The logical condition the events sent to this route must match."
+				required:    true
+				type: condition: {}
+			}
+			name: {
+				description: """
+					The identifier of the route.
+
+					The route is referenced as an input by other components with the name
+					`<transform_name>.<name>`.
+					"""
+				required: true
+				type: string: examples: ["critical"]
+			}
+		}
+	}
 }
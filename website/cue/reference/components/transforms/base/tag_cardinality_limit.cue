@@ -12,6 +12,21 @@ base: components: transforms: tag_cardinality_limit: configuration: {
 		required:      false
 		type: uint: default: 5120
 	}
+	internal_metrics: {
+		description: "Configuration of internal metrics for the `tag_cardinality_limit` transform."
+		required:    false
+		type: object: options: include_key_in_limit_metric: {
+			description: """
+				Whether or not to add the `metric_name` and `tag_key` tags to the
+				`tag_value_limit_exceeded_total` and `value_limit_reached_total` internal metrics.
+
+				This tells which metric and tag hit the limit, but the cardinality of these tags grows with
+				the number of metric names and tag keys. Only set this to true if they are bounded.
+				"""
+			required: false
+			type: bool: default: false
+		}
+	}
 	limit_exceeded_action: {
 		description: """
 			Possible actions to take when an event arrives that would exceed the cardinality limit for one
@@ -46,6 +61,81 @@ base: components: transforms: tag_cardinality_limit: configuration: {
 				"""
 		}
 	}
+	per_metric: {
+		description: """
+			Limits for the metrics whose name matches a pattern.
+
+			The tags of these metrics are tracked per metric name, instead of across all metrics. When
+			several entries match a metric name, the first one applies.
+
+			The number of metric names tracked this way isn't capped, so memory usage grows with the
+			number of distinct metric names matching these entries. Prefer exact names or narrow
+			patterns if metric names are unbounded.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				name: {
+					description: """
+						The name of the metrics these limits apply to.
+
+						Supports glob matching, such as `http_*`.
+						"""
+					required: true
+					type: string: examples: ["http_requests_total", "internal_*"]
+				}
+				tag_limits: {
+					description: """
+						Overrides `value_limit` for the tag keys of these metrics matching a pattern.
+
+						These take precedence over the top-level `tag_limits`.
+						"""
+					required: false
+					type: object: {
+						examples: [{
+							path:    100
+							"pod_*": 50
+						}]
+						options: "*": {
+							description: "How many distinct values to accept for the matching tag keys."
+							required:    true
+							type: uint: {}
+						}
+					}
+				}
+				value_limit: {
+					description: """
+						How many distinct values to accept for any given key of these metrics.
+
+						Defaults to the top-level `value_limit`.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	tag_limits: {
+		description: """
+			Overrides `value_limit` for the tag keys matching a pattern.
+
+			Patterns support glob matching, such as `pod_*`. When several patterns match a tag key, the
+			first one applies.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				path:    100
+				"pod_*": 50
+			}]
+			options: "*": {
+				description: "How many distinct values to accept for the matching tag keys."
+				required:    true
+				type: uint: {}
+			}
+		}
+	}
 	value_limit: {
 		description: "How many distinct values to accept for any given key."
 		required:    false
@@ -6,6 +6,17 @@ base: components: transforms: throttle: configuration: {
 		required:    false
 		type: condition: {}
 	}
+	global_threshold: {
+		description: """
+			The threshold shared by all buckets per configured `window_secs`.
+
+			An event must fit in this global quota and in the quota of its own bucket to pass through,
+			and only uses up either quota once it fits in both, so a bucket over its own quota doesn't
+			use up the global one. It is expressed in the configured `unit`, like `threshold`.
+			"""
+		required: false
+		type: uint: {}
+	}
 	internal_metrics: {
 		description: "Configuration of internal metrics for the Throttle transform."
 		required:    false
@@ -38,15 +49,72 @@ base: components: transforms: throttle: configuration: {
 			syntax: "template"
 		}
 	}
+	reroute_dropped: {
+		description: """
+			Reroutes events over their quota to a named output instead of discarding them.
+
+			For a transform named `foo`, these events can be consumed by specifying `foo.dropped` as
+			the input to another component.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	threshold: {
 		description: """
 			The number of events allowed for a given bucket per configured `window_secs`.
 
-			Each unique key has its own `threshold`.
+			Each unique key has its own `threshold`. When `unit` is `bytes`, this is the number of
+			bytes allowed instead.
 			"""
 		required: true
 		type: uint: {}
 	}
+	threshold_overrides: {
+		description: """
+			Loads the `threshold` of individual buckets from an enrichment table.
+
+			Buckets whose key is missing from the table use the configured `threshold`. Requires
+			`key_field` to be set.
+			"""
+		required: false
+		type: object: options: {
+			key_column: {
+				description: "The column of the enrichment table matched against the rendered `key_field`."
+				required:    false
+				type: string: default: "key"
+			}
+			table: {
+				description: "The name of the enrichment table holding the overrides."
+				required:    true
+				type: string: examples: ["tenant_quotas"]
+			}
+			threshold_column: {
+				description: """
+					The column of the enrichment table holding the key's threshold.
+
+					The threshold is expressed in the configured `unit` per `window_secs`, like `threshold`.
+					"""
+				required: false
+				type: string: default: "threshold"
+			}
+		}
+	}
+	unit: {
+		description: "The unit in which thresholds are expressed."
+		required:    false
+		type: string: {
+			default: "events"
+			enum: {
+				bytes: """
+					Each event counts as its estimated JSON-encoded size, in bytes, towards the threshold.
+
+					An event larger than the threshold counts as the whole threshold, so it only passes once
+					the quota is full.
+					"""
+				events: "Each event counts as one towards the threshold."
+			}
+		}
+	}
 	window_secs: {
 		description: "The time window in which the configured `threshold` is applied, in seconds."
 		required:    true
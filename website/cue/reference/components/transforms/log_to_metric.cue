@@ -325,5 +325,27 @@ components: transforms: log_to_metric: {
 				will not be emitted.
 				"""
 		}
+		conditions: {
+			title: "Conditions"
+			body: """
+				Each metric can have its own `condition`. A metric is only generated from the
+				events matching its condition, while the other metrics of the transform are still
+				generated from every event. This avoids splitting the stream with a `route`
+				transform to generate metrics from subsets of the events.
+				"""
+		}
+		histogram_buckets: {
+			title: "Histogram Buckets"
+			body: """
+				By default, a `histogram` metric is emitted as a distribution holding the value of
+				the field as a single sample. When `buckets` are set, the value is counted in the
+				given buckets instead, and the metric is emitted as an aggregated histogram.
+
+				This does not reduce the number of metrics: each event still produces its own
+				histogram, counting only the value of that event. To combine the histograms of many
+				events, route the metrics through an `aggregate` transform, which adds up the
+				buckets of the histograms of the same series over its `interval_ms`.
+				"""
+		}
 	}
 }
@@ -0,0 +1,88 @@
+package metadata
+
+components: transforms: multiline: {
+	title: "Multiline"
+
+	description: """
+		Merges multi-line messages, such as stack traces, that were split into
+		several log events, into single log events.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.multiline.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Merge Java exceptions"
+			input: [
+				{log: {message: "java.lang.Exception", host: "host-1.hostname.com"}},
+				{log: {message: "    at com.foo.bar(bar.java:123)", host: "host-1.hostname.com"}},
+				{log: {message: "    at com.foo.baz(baz.java:456)", host: "host-1.hostname.com"}},
+				{log: {message: "Hello world, I am a new log", host: "host-1.hostname.com"}},
+			]
+
+			configuration: {
+				start_pattern:     #"^[^\s]"#
+				condition_pattern: #"^[\s]+at"#
+				mode:              "continue_through"
+				timeout_ms:        1000
+				group_by: ["host"]
+			}
+
+			output: [
+				{
+					log: {
+						message: """
+							java.lang.Exception
+							    at com.foo.bar(bar.java:123)
+							    at com.foo.baz(baz.java:456)
+							"""
+						host: "host-1.hostname.com"
+					}
+				},
+				{log: {message: "Hello world, I am a new log", host: "host-1.hostname.com"}},
+			]
+		},
+	]
+
+	how_it_works: {
+		aggregation: {
+			title: "Aggregation"
+			body: """
+				This transform applies the same aggregation as the `multiline` option of the
+				`file` source, to the messages of log events coming from any source. Events
+				whose message matches `start_pattern` start a new message, and the following
+				events are merged into it according to `mode` and `condition_pattern`. The merged
+				event keeps the fields of the first event, with the messages of all the events
+				joined with newlines. Events that aren't part of a multi-line message are passed
+				through unmodified.
+
+				Messages are merged separately for each stream, as identified by the `group_by`
+				fields, and a message that doesn't receive any new line for `timeout_ms` is
+				flushed, even if incomplete.
+				"""
+		}
+	}
+}
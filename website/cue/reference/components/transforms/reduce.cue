@@ -144,7 +144,31 @@ components: transforms: reduce: {
 		},
 	]
 
+	how_it_works: {
+		memory_limits: {
+			title: "Memory Limits"
+			body: """
+				Groups are held in memory until they are complete, so a group merging large
+				events, with the `concat` strategy for example, can grow large. The `max_bytes`
+				option bounds the size of each group, which is flushed as soon as it reaches this
+				size, like with `max_events`.
+
+				The `max_total_bytes` option sets a budget for all the groups combined. When it is
+				exceeded, the oldest groups are flushed early, before they are complete, and the
+				field set in `incomplete_key` is set to `true` in their events, so that downstream
+				components can tell them apart.
+
+				The sizes are estimates of the in-memory sizes of the events merged into the groups.
+				The number and the total size of the groups are reported by the `reduce_groups`
+				and `reduce_groups_bytes` internal metrics.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
-		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
+		incomplete_events_flushed_total: components.sources.internal_metrics.output.metrics.incomplete_events_flushed_total
+		reduce_groups:                   components.sources.internal_metrics.output.metrics.reduce_groups
+		reduce_groups_bytes:             components.sources.internal_metrics.output.metrics.reduce_groups_bytes
+		stale_events_flushed_total:      components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
 }
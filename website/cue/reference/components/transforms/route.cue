@@ -87,8 +87,45 @@ components: transforms: route: {
 				namespace: "host"
 			}
 		},
+		{
+			title: "Route to the first matching tier"
+
+			configuration: {
+				mode: "first_match"
+				routes: [
+					{name: "critical", condition: #".level == "error" && .service == "payments""#},
+					{name: "errors", condition: #".level == "error""#},
+				]
+			}
+
+			input: log: {
+				level:   "error"
+				service: "payments"
+			}
+			output: log: {
+				level:   "error"
+				service: "payments"
+			}
+		},
 	]
 
+	how_it_works: {
+		first_match: {
+			title: "First Match"
+			body: """
+				By default, an event is sent to every route whose condition it matches, which
+				clones the event once per matching route. With `mode` set to `first_match`, the
+				routes are checked in the order they are declared in `routes`, and the event is
+				sent to the first matching route only, without being cloned. Events matching no
+				route are still sent to the `_unmatched` output, unless `reroute_unmatched` is
+				set to `false`.
+
+				The routes must be declared in the `routes` list in this mode, because the order
+				of the keys of the `route` table isn't preserved when the configuration is loaded.
+				"""
+		}
+	}
+
 	outputs: [
 		{
 			name:        "<route_id>"
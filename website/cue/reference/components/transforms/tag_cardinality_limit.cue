@@ -125,6 +125,33 @@ components: transforms: tag_cardinality_limit: {
 				"""
 		}
 
+		per_metric_limits: {
+			title: "Per-Metric Limits"
+			body: """
+				By default, the values of each tag key are tracked across all metrics, and
+				limited to `value_limit`. The `tag_limits` option sets a different limit for
+				the tag keys matching a glob pattern, and the `per_metric` option sets limits
+				for the metrics whose name matches a glob pattern. The tags of the metrics
+				matching a `per_metric` entry are tracked per metric name, so a value accepted
+				for one metric doesn't count towards the limit of another one. A catch-all
+				entry such as `name = "*"` tracks the tags of every metric separately.
+
+				The limit of a tag key is the first of these that applies: the `tag_limits` of
+				the matching `per_metric` entry, its `value_limit`, the top-level `tag_limits`,
+				and finally the top-level `value_limit`.
+				"""
+		}
+
+		reporting: {
+			title: "Reporting"
+			body: """
+				When a tag key reaches its limit, a debug message naming the metric and the tag
+				key is logged. Setting `internal_metrics.include_key_in_limit_metric` also adds the
+				`metric_name` and `tag_key` tags to the `tag_value_limit_exceeded_total` and
+				`value_limit_reached_total` internal metrics.
+				"""
+		}
+
 		memory_utilization: {
 			title: "Failed Parsing"
 			body: """
@@ -168,6 +195,12 @@ components: transforms: tag_cardinality_limit: {
 				for you.   Remember when converting from `value_limit` to the 'm' value to plug
 				into the calculator that `value_limit` is in bytes, and 'm' is often presented
 				in bits (1/8 of a byte).
+
+				The tags of the metrics matching a `per_metric` entry are tracked separately for
+				each metric name, so the estimates above apply to each of these metric names. The
+				number of metric names tracked this way isn't capped: with a broad pattern such
+				as `*`, memory usage grows with the number of distinct metric names, so prefer
+				exact names or narrow patterns if metric names are unbounded.
 				"""
 		}
 
@@ -36,6 +36,20 @@ components: transforms: throttle: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
 
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				This transform also implements an additional `dropped` output. When
+				`reroute_dropped` is set to `true`, events over their quota are sent to the
+				`dropped` output instead of being discarded. For a transform component named
+				`foo`, this output can be accessed by specifying `foo.dropped` as the input to
+				another component.
+				"""
+		},
+	]
+
 	examples: [
 		{
 			title: "Rate limiting"
@@ -101,13 +115,34 @@ components: transforms: throttle: {
 						replenishes a cell every 6 seconds and allows a burst of up to 10 events.
 						"""
 				},
+				{
+					title: "Byte Quotas"
+					body: """
+						With `unit` set to `bytes`, each event consumes as many cells as its estimated JSON-encoded size, in
+						bytes, so `threshold` and `window_secs` express a throughput. For example, a `window_secs` of 1 with a
+						`threshold` of 1048576 allows each bucket 1 MiB per second.
+						"""
+				},
+				{
+					title: "Global and Per-Key Quotas"
+					body: """
+						Setting `global_threshold` adds a rate limiter shared by all buckets. An event passes through only if
+						its bucket and the global rate limiter both have capacity for it, and only uses up their capacity then, so
+						events over their bucket's quota don't use up the global quota.
+
+						With `threshold_overrides`, the `threshold` of individual buckets is looked up in an enrichment table
+						by their key. Keys missing from the table use the configured `threshold`. The lookups are cached, and
+						refreshed every two `window_secs`.
+						"""
+				},
 				{
 					title: "Rate Limited Events"
 					body: """
 						The rate limiter will allow up to `threshold` number of events through and drop any further events
 						for that particular bucket when the rate limiter is at capacity. Any event passed when the rate
 						limiter is at capacity will be discarded and tracked by an `events_discarded_total` metric tagged
-						by the bucket's `key`.
+						by the bucket's `key`, unless `reroute_dropped` is set, in which case it is sent to the `dropped`
+						output.
 						"""
 				},
 			]